@@ -0,0 +1,347 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Reads a single `key = value` setting from a `[profile.<name>]` section of the manifest.
+fn profile_setting(manifest: &str, profile: &str, key: &str) -> Option<String> {
+    let header = format!("[profile.{profile}]");
+
+    manifest
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| *line != header)
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .filter_map(|line| line.split_once('='))
+        .find(|(k, _)| k.trim() == key)
+        .map(|(_, v)| v.trim().trim_matches('"').to_string())
+}
+
+/// A day and part that a solution claims, for an event (`year = ...` or the year prefix of the
+/// binary, `None` for the current one) and an author (the suffix of the binary).
+type Claim = (Option<u16>, Option<String>, u8, u8);
+
+/// The contents of `(...)` after `start`, up to the matching parenthesis.
+fn parenthesized(source: &str, start: usize) -> Option<&str> {
+    let mut depth = 0;
+    for (i, c) in source[start..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 1 => return Some(&source[start + 1..start + i]),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits macro arguments at the commas that are not nested in brackets.
+fn split_args(args: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let (mut depth, mut from) = (0, 0);
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(args[from..i].trim());
+                from = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(args[from..].trim());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// The event and author encoded in a binary name like `07`, `07_alice` or `2024_07_alice`.
+fn parse_bin_name(name: &str) -> Option<(Option<u16>, u8, Option<String>)> {
+    let mut segments: Vec<&str> = name.split('_').collect();
+    let year = match segments.first() {
+        Some(year) if year.len() == 4 => Some(year.parse().ok()?),
+        _ => None,
+    };
+    if year.is_some() {
+        segments.remove(0);
+    }
+    let day = segments.first()?.parse().ok()?;
+    let author = (segments.len() > 1).then(|| segments[1..].join("_"));
+    Some((year, day, author))
+}
+
+/// Days and parts claimed by `solution!(...)` invocations and `Solution` impls in `source`.
+fn claims(source: &str, year: Option<u16>, author: Option<&str>) -> Vec<Claim> {
+    // drop comments, so documented examples don't count.
+    let source: String = source
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut claims = vec![];
+    let mut claim = |year: Option<u16>, day: u8, parts: &[u8]| {
+        for &part in parts {
+            claims.push((year, author.map(str::to_string), day, part));
+        }
+    };
+
+    for (start, _) in source.match_indices("solution!") {
+        let Some(args) = parenthesized(&source, start + "solution!".len()) else {
+            continue;
+        };
+        let args = split_args(args);
+        let Some(day) = args.first().and_then(|day| day.parse().ok()) else {
+            continue;
+        };
+
+        let (mut year, mut parts) = (year, vec![]);
+        for arg in &args[1..] {
+            match arg
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim()))
+            {
+                Some(("part_one", _)) => parts.push(1),
+                Some(("part_two", _)) => parts.push(2),
+                Some(("part_three", _)) => parts.push(3),
+                Some(("year", value)) => year = value.parse().ok().or(year),
+                None => parts.extend(arg.parse::<u8>().ok()),
+                Some(_) => {}
+            }
+        }
+        if parts.is_empty() {
+            parts = vec![1, 2, 3];
+        }
+        claim(year, day, &parts);
+    }
+
+    for block in source.split("impl ").skip(1) {
+        let Some((header, body)) = block.split_once('{') else {
+            continue;
+        };
+        if !header.contains("Solution for") {
+            continue;
+        }
+        let Some(day) = body
+            .find("const DAY")
+            .and_then(|i| body[i..].find("day!").map(|j| i + j + "day!".len()))
+            .and_then(|i| parenthesized(body, i))
+            .and_then(|day| day.trim().parse().ok())
+        else {
+            continue;
+        };
+
+        let parts: Vec<u8> = match body.find("const PARTS") {
+            Some(i) => {
+                let line = body[i..].split(';').next().unwrap_or_default();
+                [("Part::One", 1), ("Part::Two", 2), ("Part::Three", 3)]
+                    .into_iter()
+                    .filter(|(name, _)| line.contains(name))
+                    .map(|(_, part)| part)
+                    .collect()
+            }
+            None => vec![1, 2, 3],
+        };
+        claim(year, day, &parts);
+    }
+
+    claims
+}
+
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        if path.is_dir() {
+            rust_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+/// Fails the build if two solutions claim the same part of the same quest, which would leave
+/// it to file order which one `cargo solve` or a `Registry` runs.
+fn check_duplicate_days() {
+    // only the solutions and the module tree, so edits to the template or utilities don't
+    // rerun the script and rebuild the library.
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-changed=src/lib.rs");
+
+    let mut files = vec![];
+    rust_files(Path::new("src"), &mut files);
+    files.sort();
+
+    let mut claimed: BTreeMap<Claim, Vec<String>> = BTreeMap::new();
+    for file in files {
+        // the template documents and tests its own macros.
+        if file.starts_with("src/template") {
+            continue;
+        }
+        let Ok(source) = fs::read_to_string(&file) else {
+            continue;
+        };
+
+        let bin = file
+            .strip_prefix("src/bin")
+            .ok()
+            .and_then(|_| parse_bin_name(file.file_stem()?.to_str()?));
+        let (year, author) = match &bin {
+            Some((year, _, author)) => (*year, author.as_deref()),
+            None => (None, None),
+        };
+
+        for claim in claims(&source, year, author) {
+            let files = claimed.entry(claim).or_default();
+            let file = file.display().to_string();
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+
+    let duplicates: Vec<_> = claimed
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .collect();
+    if duplicates.is_empty() {
+        return;
+    }
+
+    for ((year, author, day, part), files) in duplicates {
+        let year = year.map(|year| format!(" of {year}")).unwrap_or_default();
+        let author = author
+            .map(|author| format!(" by {author}"))
+            .unwrap_or_default();
+        eprintln!(
+            "error: day {day:02} part {part}{year}{author} is solved in more than one place: {}",
+            files.join(", ")
+        );
+    }
+    eprintln!("Check the day passed to `solution!` or `day!` and remove one of the solutions.");
+    process::exit(1);
+}
+
+/// Whether `source` is a plain `solution!` binary that a `Registry` can run: without hooks or a
+/// different event, which are set up by its own `main`, and without `crate::` paths or `mod foo;`
+/// files, which resolve from the registry instead of the binary once it is a module.
+fn registrable(source: &str) -> bool {
+    let source: String = source
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let declares_files = source.lines().any(|line| {
+        let line = line.trim();
+        let line = line.strip_prefix("pub ").unwrap_or(line);
+        line.starts_with("mod ") && line.ends_with(';')
+    });
+    if declares_files || source.contains("crate::") {
+        return false;
+    }
+
+    let invocations: Vec<&str> = source
+        .match_indices("solution!")
+        .filter_map(|(start, _)| parenthesized(&source, start + "solution!".len()))
+        .collect();
+    let [args] = invocations[..] else {
+        return false;
+    };
+
+    split_args(args).iter().skip(1).all(|arg| {
+        match arg.split_once('=').map(|(key, _)| key.trim()) {
+            Some(key) => ["part_one", "part_two", "part_three"].contains(&key),
+            None => arg.parse::<u8>().is_ok(),
+        }
+    })
+}
+
+/// Writes `registry.rs` to `OUT_DIR` for the `registry` binary: a module for every quest binary
+/// of the current event and a `registry()` with all of them.
+fn generate_registry() {
+    // in every profile, so added, renamed and removed binaries never leave a stale registry.
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let mut days: Vec<(u8, PathBuf)> = fs::read_dir("src/bin")
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|path| {
+            let (year, day, author) = parse_bin_name(path.file_stem()?.to_str()?)?;
+            let source = fs::read_to_string(&path).ok()?;
+            (year.is_none() && author.is_none() && registrable(&source)).then_some((day, path))
+        })
+        .collect();
+    days.sort();
+
+    let root = env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    let mut modules = String::new();
+    let mut registry = String::from("fn registry() -> everybody_codes::template::Registry {\n    everybody_codes::template::Registry::new()\n");
+    for (day, path) in &days {
+        modules.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {:?}]\nmod day_{day:02};\n\n",
+            root.join(path)
+        ));
+        registry.push_str(&format!(
+            "        .register::<day_{day:02}::__Solution>()\n"
+        ));
+    }
+    registry.push_str("}\n");
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap_or_default()).join("registry.rs");
+    let generated = modules + &registry;
+    if fs::read_to_string(&out).ok().as_deref() != Some(&generated) {
+        fs::write(out, generated).expect("OUT_DIR should be writable");
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+
+    let profile = env::var("PROFILE").unwrap_or_else(|_| "debug".into());
+
+    // watching the solutions reruns this script and rebuilds the library after every edit,
+    // which is cheap with incremental debug builds but not in release, so release builds
+    // skip the check.
+    if profile == "debug" {
+        check_duplicate_days();
+    }
+    if env::var_os("CARGO_FEATURE_REGISTRY").is_some() {
+        generate_registry();
+    }
+    let (section, default_codegen_units) = if profile == "release" {
+        ("release", "16")
+    } else {
+        ("dev", "256")
+    };
+
+    let manifest = fs::read_to_string("Cargo.toml").unwrap_or_default();
+
+    let setting = |key: &str, env_key: &str, default: &str| {
+        let env_key = format!("CARGO_PROFILE_{}_{env_key}", section.to_uppercase());
+        println!("cargo:rerun-if-env-changed={env_key}");
+        env::var(env_key)
+            .ok()
+            .or_else(|| profile_setting(&manifest, section, key))
+            .unwrap_or_else(|| default.into())
+    };
+
+    let lto = setting("lto", "LTO", "false");
+    let codegen_units = setting("codegen-units", "CODEGEN_UNITS", default_codegen_units);
+    let opt_level = env::var("OPT_LEVEL").unwrap_or_else(|_| "0".into());
+
+    println!("cargo:rustc-env=EC_BUILD_PROFILE={profile}");
+    println!("cargo:rustc-env=EC_BUILD_OPT_LEVEL={opt_level}");
+    println!("cargo:rustc-env=EC_BUILD_LTO={lto}");
+    println!("cargo:rustc-env=EC_BUILD_CODEGEN_UNITS={codegen_units}");
+}
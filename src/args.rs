@@ -0,0 +1,1224 @@
+/// Command line parsing for the template commands.
+///
+/// Every command is described by a [`Usage`], which is printed for `--help` and alongside
+/// usage errors. Options are consumed before positional arguments, so they can appear
+/// anywhere on the command line.
+///
+/// Before parsing, a command name from `[aliases]` in `ec.toml` is expanded, and the options
+/// from `[defaults]` the command line doesn't give are added.
+use std::{
+    env,
+    ffi::OsString,
+    fmt::{self, Display},
+    num::NonZeroUsize,
+    str::FromStr,
+};
+
+use everybody_codes::template::{
+    authors::is_valid_author, config::config, deploy::DEFAULT_REMOTE_DIR, read_only,
+    reduce::Predicate, set_story, set_year, Date, Day, DaySet, Part, StoryId, Year,
+};
+use pico_args::Arguments;
+
+pub enum AppArguments {
+    Help {
+        command: Option<&'static str>,
+    },
+    Download {
+        day: Option<Day>,
+        jobs: usize,
+    },
+    Read {
+        day: Day,
+        part: Option<Part>,
+        pager: bool,
+    },
+    Deploy {
+        year: u16,
+        host: String,
+        target: Option<String>,
+        remote_dir: String,
+        day: Option<Day>,
+        solution_args: Vec<String>,
+    },
+    FixAnswers,
+    Gen {
+        day: Day,
+        size: usize,
+        seed: u64,
+    },
+    HeadInput {
+        day: Day,
+        part: Part,
+        step: Option<usize>,
+        input: Option<String>,
+        run: bool,
+        author: Option<String>,
+        release: bool,
+        timeout: Option<u64>,
+    },
+    Init,
+    Oracle {
+        day: Day,
+        reference: String,
+        part: Option<Part>,
+        runs: u64,
+        size: usize,
+        seed: u64,
+        author: Option<String>,
+        timeout: Option<u64>,
+    },
+    #[cfg(feature = "encrypt")]
+    EncryptAnswers,
+    #[cfg(feature = "encrypt")]
+    EncryptInputs,
+    #[cfg(feature = "http_client")]
+    AuditAnswers,
+    Verify {
+        days: Option<DaySet>,
+        author: Option<String>,
+        timeout: Option<u64>,
+        as_of: Option<Date>,
+    },
+    MigrateLayout {
+        dry_run: bool,
+    },
+    Modgraph {
+        mermaid: bool,
+    },
+    Notes {
+        output: Option<String>,
+    },
+    Reduce {
+        day: Day,
+        part: Part,
+        predicate: Predicate,
+        reference: Option<String>,
+        author: Option<String>,
+        input: Option<String>,
+        release: bool,
+        timeout: Option<u64>,
+    },
+    ScaleBench {
+        day: Day,
+        part: Option<Part>,
+        size: Option<usize>,
+        enforce: bool,
+        author: Option<String>,
+    },
+    Scaffold {
+        day: Day,
+        download: bool,
+        overwrite: bool,
+        author: Option<String>,
+        template: Option<String>,
+    },
+    ScaffoldUtils {
+        preset: String,
+        overwrite: bool,
+    },
+    Scratch {
+        day: Day,
+        overwrite: bool,
+    },
+    Selftest {
+        keep: bool,
+    },
+    Solve {
+        day: Option<Day>,
+        release: bool,
+        submit: Option<Part>,
+        author: Option<String>,
+        record: bool,
+        explain: bool,
+        checks: bool,
+        input: Option<String>,
+        sample: bool,
+        timeout: Option<u64>,
+        json: bool,
+        chrome_trace: Option<String>,
+    },
+    Stats,
+    Submit {
+        day: Day,
+        part: Part,
+        author: Option<String>,
+        yes: bool,
+    },
+    Sync,
+    Compare {
+        day: Day,
+        authors: Vec<String>,
+    },
+    CompareInputs {
+        day: Day,
+        part: Option<Part>,
+    },
+    Explain {
+        day: Day,
+        part: Part,
+        release: bool,
+        author: Option<String>,
+    },
+    Profile {
+        day: Day,
+        part: Part,
+        author: Option<String>,
+    },
+    All {
+        release: bool,
+        days: Option<DaySet>,
+        from: Option<Day>,
+        timeout: Option<u64>,
+        jobs: Option<NonZeroUsize>,
+        json: bool,
+    },
+    Bench {
+        day: Option<Day>,
+    },
+    BuildTimes {
+        debug: bool,
+    },
+    Contest {
+        day: Day,
+        tries: usize,
+        review: bool,
+        reset: bool,
+    },
+    Time {
+        all: bool,
+        day: Option<Day>,
+        store: bool,
+        share: bool,
+        days: Option<DaySet>,
+        json: bool,
+        check: Option<f64>,
+    },
+    Today {
+        day: Option<Day>,
+        date: Option<Date>,
+        edit: bool,
+    },
+    Watch {
+        day: Day,
+        author: Option<String>,
+        timeout: u64,
+    },
+    Web {
+        day: Option<Day>,
+        leaderboard: bool,
+    },
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Description of a command, used to render its help.
+pub struct Usage {
+    pub name: &'static str,
+    /// Positional arguments and options, as shown in the usage line.
+    pub synopsis: &'static str,
+    pub about: &'static str,
+    pub options: &'static [(&'static str, &'static str)],
+}
+
+const AUTHOR_OPTION: (&str, &str) = (
+    "--author <name>",
+    "Use the solution of a teammate, e.g. `src/bin/07_alice.rs`",
+);
+
+/// Accepted by every command.
+const YEAR_OPTION: (&str, &str) = ("--year <year>", "Work on this event instead of `EC_YEAR`");
+const STORY_OPTION: (&str, &str) = ("--story <id>", "Work on this story instead of a main event");
+const READ_ONLY_OPTION: (&str, &str) =
+    ("--read-only", "Leave data, README and meta files untouched");
+const NO_DEFAULTS_OPTION: (&str, &str) = ("--no-defaults", "Ignore the defaults in `ec.toml`");
+const GLOBAL_OPTIONS: [(&str, &str); 4] = [
+    YEAR_OPTION,
+    STORY_OPTION,
+    READ_ONLY_OPTION,
+    NO_DEFAULTS_OPTION,
+];
+
+const DAYS_OPTION: (&str, &str) = (
+    "--days <days>",
+    "Only run these days, e.g. `1-5,7,20-` or `5..=10`",
+);
+const TIMEOUT_OPTION: (&str, &str) = ("--timeout <secs>", "Abort parts running longer than this");
+const JSON_OPTION: (&str, &str) = ("--json", "Print one JSON record per part instead of text");
+
+pub const COMMANDS: &[Usage] = &[
+    Usage {
+        name: "all",
+        synopsis: "[--release] [--days <days>] [--from <day>] [--timeout <secs>] [--jobs <n>] [--json]",
+        about: "Run the solutions of all quests.",
+        options: &[
+            ("--release", "Build with optimizations"),
+            DAYS_OPTION,
+            ("--from <day>", "Only run this day and the ones after it"),
+            TIMEOUT_OPTION,
+            (
+                "--jobs <n>",
+                "Parts to run at once, defaults to the number of cores",
+            ),
+            JSON_OPTION,
+        ],
+    },
+    #[cfg(feature = "http_client")]
+    Usage {
+        name: "audit-answers",
+        synopsis: "",
+        about: "Check the recorded real answers against the quests solved on the site.",
+        options: &[],
+    },
+    Usage {
+        name: "bench",
+        synopsis: "[<day>]",
+        about: "Benchmark quests with criterion, for timings precise enough to optimize.",
+        options: &[],
+    },
+    Usage {
+        name: "build-times",
+        synopsis: "[--debug]",
+        about: "Measure the compile time of each quest binary.",
+        options: &[("--debug", "Build without optimizations")],
+    },
+    Usage {
+        name: "compare",
+        synopsis: "<day> [<author>...]",
+        about: "Compare the answers and timings of the solutions of several authors.",
+        options: &[],
+    },
+    Usage {
+        name: "compare-inputs",
+        synopsis: "<day> [--part <part>]",
+        about: "Summarize how the real input differs from the sample.",
+        options: &[("--part <part>", "Only compare a single part")],
+    },
+    Usage {
+        name: "contest",
+        synopsis: "<day> [--tries <n>] [--review] [--reset]",
+        about: "Practice a quest under contest conditions.",
+        options: &[
+            ("--tries <n>", "Number of tries per part [default: 3]"),
+            ("--review", "Review the recorded session"),
+            ("--reset", "Start over"),
+        ],
+    },
+    Usage {
+        name: "deploy",
+        synopsis: "<year> --host <host> [--target <triple>] [--dir <dir>] [--day <day>] [--part <part>] [--time]",
+        about: "Build solutions locally and run them on a remote machine.",
+        options: &[
+            ("--host <host>", "Host to copy to and run on over ssh"),
+            ("--target <triple>", "Target triple to cross-compile for"),
+            ("--dir <dir>", "Remote directory [default: everybody-codes]"),
+            ("--day <day>", "Only deploy a single quest"),
+            ("--part <part>", "Only run a single part"),
+            ("--time", "Benchmark the solutions"),
+        ],
+    },
+    Usage {
+        name: "download",
+        synopsis: "(<day> | --all [--jobs <n>])",
+        about: "Download the inputs, samples and description of a quest.",
+        options: &[
+            ("--all", "Download every scaffolded quest"),
+            ("--jobs <n>", "Number of parallel downloads [default: 4]"),
+        ],
+    },
+    #[cfg(feature = "encrypt")]
+    Usage {
+        name: "encrypt-answers",
+        synopsis: "",
+        about: "Encrypt the answer files with `EC_ANSWER_KEY`.",
+        options: &[],
+    },
+    #[cfg(feature = "encrypt")]
+    Usage {
+        name: "encrypt-inputs",
+        synopsis: "",
+        about: "Encrypt the input files with `EC_INPUT_KEY`.",
+        options: &[],
+    },
+    Usage {
+        name: "explain",
+        synopsis: "<day> <part> [--release] [--author <name>]",
+        about: "Rerun a part on the real input and describe how its answer came about.",
+        options: &[("--release", "Build with optimizations"), AUTHOR_OPTION],
+    },
+    Usage {
+        name: "fix-answers",
+        synopsis: "",
+        about: "Normalize the answer files.",
+        options: &[],
+    },
+    Usage {
+        name: "gen",
+        synopsis: "<day> [--size <n>] [--seed <n>]",
+        about: "Write a synthetic input from the quest's `gen_input` generator to `data/tests`.",
+        options: &[
+            ("--size <n>", "Size passed to the generator [default: 1000]"),
+            ("--seed <n>", "Seed passed to the generator [default: 0]"),
+        ],
+    },
+    Usage {
+        name: "head-input",
+        synopsis: "<day> --part <part> [--lines <n>] [--input <path>] [--run] [--author <name>] [--release] [--timeout <secs>]",
+        about: "Write the first lines of an input to `data/tests` in growing steps, and run a part on them.",
+        options: &[
+            ("--part <part>", "The part to debug"),
+            (
+                "--lines <n>",
+                "Lines added by each step [default: a tenth of the input]",
+            ),
+            ("--input <path>", "Truncate this input instead of the real one"),
+            ("--run", "Run the part on each truncated input until it fails"),
+            AUTHOR_OPTION,
+            ("--release", "Build with optimizations"),
+            TIMEOUT_OPTION,
+        ],
+    },
+    Usage {
+        name: "init",
+        synopsis: "",
+        about: "Set up the year, features, data layout and session token, and scaffold day 1.",
+        options: &[],
+    },
+    Usage {
+        name: "migrate-layout",
+        synopsis: "[--dry-run]",
+        about: "Move the data and solutions of a single year repository to `data/<year>`.",
+        options: &[("--dry-run", "Only print the files that would be moved")],
+    },
+    Usage {
+        name: "modgraph",
+        synopsis: "[--mermaid]",
+        about: "Print a graph of the utility modules used by each quest.",
+        options: &[("--mermaid", "Print a Mermaid diagram instead of Graphviz")],
+    },
+    Usage {
+        name: "notes",
+        synopsis: "[--output <path>]",
+        about: "Compile the notes of all quests into a single write-up.",
+        options: &[(
+            "--output <path>",
+            "Where to write it, defaults to `notes/writeup.md`",
+        )],
+    },
+    Usage {
+        name: "oracle",
+        synopsis: "<day> --cmd <command> [--part <part>] [--runs <n>] [--size <n>] [--seed <n>] [--author <name>] [--timeout <secs>]",
+        about: "Compare parts with a reference program on generated inputs.",
+        options: &[
+            (
+                "--cmd <command>",
+                "Shell command reading an input on stdin and printing the answer",
+            ),
+            ("--part <part>", "Only compare a single part"),
+            ("--runs <n>", "Number of inputs to compare on [default: 100]"),
+            ("--size <n>", "Size passed to the generator [default: 100]"),
+            ("--seed <n>", "Seed of the first input [default: 0]"),
+            AUTHOR_OPTION,
+            TIMEOUT_OPTION,
+        ],
+    },
+    Usage {
+        name: "profile",
+        synopsis: "<day> <part> [--author <name>]",
+        about: "Record a flamegraph of a part on the real input.",
+        options: &[AUTHOR_OPTION],
+    },
+    Usage {
+        name: "read",
+        synopsis: "<day> [--part <part>] [--no-pager]",
+        about: "Read the description of a quest.",
+        options: &[
+            ("--part <part>", "Only read a single part"),
+            ("--no-pager", "Print the description instead of paging it"),
+        ],
+    },
+    Usage {
+        name: "reduce",
+        synopsis: "<day> --part <part> --predicate <panics|differs> [--reference <author>] [--author <name>] [--input <path>] [--release] [--timeout <secs>]",
+        about: "Shrink an input to the fewest lines that still make a part fail.",
+        options: &[
+            ("--part <part>", "The part to debug"),
+            (
+                "--predicate <predicate>",
+                "`panics` at the same location, or `differs` from the reference",
+            ),
+            ("--reference <author>", "Solution to compare answers with"),
+            AUTHOR_OPTION,
+            ("--input <path>", "Reduce this input instead of the real one"),
+            ("--release", "Build with optimizations"),
+            TIMEOUT_OPTION,
+        ],
+    },
+    Usage {
+        name: "scale-bench",
+        synopsis: "<day> [--part <part>] [--size <n>] [--enforce] [--author <name>]",
+        about: "Measure how the run time of parts grows with generated inputs of growing size.",
+        options: &[
+            ("--part <part>", "Only measure a single part"),
+            (
+                "--size <n>",
+                "Largest input to generate, defaults to the annotated size",
+            ),
+            (
+                "--enforce",
+                "Fail if a part grows faster than its annotated complexity",
+            ),
+            AUTHOR_OPTION,
+        ],
+    },
+    Usage {
+        name: "scaffold",
+        synopsis: "<day> [--download] [--overwrite] [--author <name>] [--template <path>]",
+        about: "Create the solution file and empty input and sample files of a quest.",
+        options: &[
+            ("--download", "Download the inputs afterwards"),
+            ("--overwrite", "Replace an existing solution file"),
+            AUTHOR_OPTION,
+            (
+                "--template <path>",
+                "Solution template, defaults to `templates/day.rs.tpl` if it exists",
+            ),
+        ],
+    },
+    Usage {
+        name: "scaffold-utils",
+        synopsis: "[--preset <name>] [--overwrite]",
+        about: "Generate utility modules with tests into `src/utils/` and declare them.",
+        options: &[
+            (
+                "--preset <name>",
+                "`standard` (grid, point, parse, math, graph) or `minimal`, defaults to `standard`",
+            ),
+            ("--overwrite", "Replace existing modules"),
+        ],
+    },
+    Usage {
+        name: "scratch",
+        synopsis: "<day> [--overwrite]",
+        about: "Create a throwaway binary with the quest's inputs preloaded.",
+        options: &[("--overwrite", "Replace an existing scratchpad")],
+    },
+    Usage {
+        name: "selftest",
+        synopsis: "[--keep]",
+        about: "Scaffold, test and run a synthetic quest to verify the setup.",
+        options: &[("--keep", "Keep the temporary project")],
+    },
+    Usage {
+        name: "solve",
+        synopsis: "[<day>] [--release] [--submit <part>] [--author <name>] [--record] [--explain] [--checks] [--input <path>] [--sample] [--timeout <secs>] [--json] [--chrome-trace <path>]",
+        about: "Run the solution of a quest.",
+        options: &[
+            ("--release", "Build with optimizations"),
+            ("--submit <part>", "Submit the answer of a part"),
+            AUTHOR_OPTION,
+            ("--record", "Save the run as an asciinema cast"),
+            ("--explain", "Compare checkpoints against the sample fixtures"),
+            ("--checks", "Enable `ec_debug_assert!` in release builds"),
+            ("--input <path>", "Run all parts on another input, e.g. from `cargo gen`"),
+            ("--sample", "Run all parts on their samples instead of the real inputs"),
+            TIMEOUT_OPTION,
+            JSON_OPTION,
+            (
+                "--chrome-trace <path>",
+                "Write a timeline of the run for Perfetto or chrome://tracing",
+            ),
+        ],
+    },
+    Usage {
+        name: "stats",
+        synopsis: "",
+        about: "Show which parts are implemented, answered, tested and timed.",
+        options: &[],
+    },
+    Usage {
+        name: "submit",
+        synopsis: "<day> <part> [--author <name>] [--yes]",
+        about: "Run a part of a quest and submit its answer.",
+        options: &[
+            AUTHOR_OPTION,
+            ("--yes", "Submit without asking for confirmation"),
+        ],
+    },
+    Usage {
+        name: "sync",
+        synopsis: "",
+        about: "Retry the downloads that failed while ec-cli or the network was unavailable.",
+        options: &[],
+    },
+    Usage {
+        name: "time",
+        synopsis: "[<day> | --all] [--days <days>] [--store | --check [--threshold <percent>]] [--share] [--json]",
+        about: "Benchmark a quest, or all quests that are not benchmarked yet.",
+        options: &[
+            ("--all", "Benchmark all quests, including benchmarked ones"),
+            DAYS_OPTION,
+            ("--store", "Save the results to the README"),
+            (
+                "--check",
+                "Fail if a part got slower than its stored timing, benchmarks all stored quests",
+            ),
+            (
+                "--threshold <percent>",
+                "Slowdown `--check` tolerates [default: 10]",
+            ),
+            ("--share", "Compare the results with the community"),
+            JSON_OPTION,
+        ],
+    },
+    Usage {
+        name: "today",
+        synopsis: "[--day <day> | --date <date>] [--edit]",
+        about: "Scaffold, download and read today's quest.",
+        options: &[
+            (
+                "--day <day>",
+                "Use this quest instead, e.g. without the `today` feature",
+            ),
+            ("--date <date>", "Use the quest released on this date"),
+            ("--edit", "Open the solution in $VISUAL or $EDITOR afterwards"),
+        ],
+    },
+    Usage {
+        name: "verify",
+        synopsis: "[--days <days>] [--author <name>] [--timeout <secs>] [--as-of <date>]",
+        about: "Run all parts on the real inputs and check them against the recorded real answers.",
+        options: &[
+            DAYS_OPTION,
+            AUTHOR_OPTION,
+            TIMEOUT_OPTION,
+            (
+                "--as-of <date>",
+                "Use the inputs and answers accepted by then, e.g. `2025-11-20`",
+            ),
+        ],
+    },
+    Usage {
+        name: "watch",
+        synopsis: "<day> [--author <name>] [--timeout <secs>]",
+        about: "Rerun a quest on its samples whenever its solution or data change.",
+        options: &[
+            AUTHOR_OPTION,
+            (
+                "--timeout <secs>",
+                "Abort parts running longer than this [default: 10]",
+            ),
+        ],
+    },
+    Usage {
+        name: "web",
+        synopsis: "[<day>] [--leaderboard]",
+        about: "Open the page of a quest, or of the event, in the browser.",
+        options: &[("--leaderboard", "Open the standings of the event instead")],
+    },
+];
+
+fn find_command(name: &str) -> Option<&'static Usage> {
+    COMMANDS.iter().find(|usage| usage.name == name)
+}
+
+impl Usage {
+    /// Whether the command takes `option`, e.g. `--timeout`.
+    fn accepts(&self, option: &str) -> bool {
+        self.options
+            .iter()
+            .chain(&GLOBAL_OPTIONS)
+            .any(|(name, _)| name.split([' ', ',']).next() == Some(option))
+    }
+
+    fn usage_line(&self) -> String {
+        format!("Usage: cargo {} {}", self.name, self.synopsis)
+            .trim_end()
+            .to_string()
+    }
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.about)?;
+        writeln!(f)?;
+        writeln!(f, "{}", self.usage_line())?;
+        writeln!(f)?;
+        writeln!(f, "Options:")?;
+
+        let help = ("-h, --help", "Print this help");
+        let width = self
+            .options
+            .iter()
+            .chain(&GLOBAL_OPTIONS)
+            .chain([&help])
+            .map(|(option, _)| option.len())
+            .max()
+            .unwrap_or(0);
+        for (option, description) in self.options.iter().chain(&GLOBAL_OPTIONS).chain([&help]) {
+            writeln!(f, "  {option:width$}  {description}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+/// Help for a command, or an overview of all commands.
+pub fn help(command: Option<&str>) -> String {
+    if let Some(usage) = command.and_then(find_command) {
+        return usage.to_string();
+    }
+
+    let width = COMMANDS
+        .iter()
+        .map(|usage| usage.name.len())
+        .max()
+        .unwrap_or(0);
+    let mut s = String::from("Usage: cargo <command> [options]\n\nCommands:\n");
+    for usage in COMMANDS {
+        s.push_str(&format!("  {:width$}  {}\n", usage.name, usage.about));
+    }
+    s.push_str("\nRun `cargo <command> --help` for the options of a command.\n");
+    s.push_str(
+        "Every command accepts `--year <year>` or `--story <id>` to work on another event, and\n",
+    );
+    s.push_str("`--read-only` to keep the repository untouched during demos.\n");
+    s.push_str("Aliases and default options can be configured in `ec.toml`.\n");
+    s
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An invalid command line, optionally with the usage of the command it was meant for.
+#[derive(Debug)]
+pub struct UsageError {
+    pub command: Option<&'static Usage>,
+    pub message: String,
+}
+
+impl std::error::Error for UsageError {}
+
+impl Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.message)?;
+        writeln!(f)?;
+        match self.command {
+            Some(usage) => {
+                writeln!(f, "{}", usage.usage_line())?;
+                write!(f, "Run `cargo {} --help` for more information.", usage.name)
+            }
+            None => write!(f, "Run `cargo ec --help` for a list of commands."),
+        }
+    }
+}
+
+/// Arguments of a single command, turning parse failures into [`UsageError`]s.
+struct Parser {
+    args: Arguments,
+    usage: &'static Usage,
+}
+
+impl Parser {
+    fn error(&self, message: impl Into<String>) -> UsageError {
+        UsageError {
+            command: Some(self.usage),
+            message: message.into(),
+        }
+    }
+
+    fn flag(&mut self, key: &'static str) -> bool {
+        self.args.contains(key)
+    }
+
+    fn value<T>(&mut self, key: &'static str) -> Result<Option<T>, UsageError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self
+            .args
+            .opt_value_from_str::<_, String>(key)
+            .map_err(|e| {
+                self.error(match e {
+                    pico_args::Error::OptionWithoutAValue(_) => {
+                        format!("`{key}` requires a value.")
+                    }
+                    e => e.to_string(),
+                })
+            })?;
+
+        value
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|e| self.error(format!("invalid value `{value}` for `{key}`: {e}.")))
+            })
+            .transpose()
+    }
+
+    fn required_value<T>(&mut self, key: &'static str) -> Result<T, UsageError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.value(key)?
+            .ok_or_else(|| self.error(format!("missing required option `{key}`.")))
+    }
+
+    /// A positive count, e.g. of tries or jobs.
+    fn count(&mut self, key: &'static str, default: usize) -> Result<usize, UsageError> {
+        match self.value(key)? {
+            Some(0) => Err(self.error(format!("`{key}` must be at least 1."))),
+            value => Ok(value.unwrap_or(default)),
+        }
+    }
+
+    fn author(&mut self) -> Result<Option<String>, UsageError> {
+        match self.value::<String>("--author")? {
+            Some(author) if !is_valid_author(&author) => Err(self.error(format!(
+                "invalid author name `{author}`, use lowercase letters, digits and `_`."
+            ))),
+            author => Ok(author),
+        }
+    }
+
+    fn opt_free<T>(&mut self, name: &str) -> Result<Option<T>, UsageError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let Some(value) = self
+            .args
+            .opt_free_from_str::<String>()
+            .map_err(|e| self.error(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+
+        if value.starts_with('-') {
+            return Err(self.error(format!("unexpected option `{value}`.")));
+        }
+
+        value
+            .parse()
+            .map(Some)
+            .map_err(|e| self.error(format!("invalid {name} `{value}`: {e}.")))
+    }
+
+    fn free<T>(&mut self, name: &str) -> Result<T, UsageError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.opt_free(name)?
+            .ok_or_else(|| self.error(format!("missing <{name}> argument.")))
+    }
+
+    /// Fail on arguments that were not consumed.
+    fn finish(self) -> Result<(), UsageError> {
+        let usage = self.usage;
+        match self.args.finish().first() {
+            Some(arg) => {
+                let arg = arg.to_string_lossy();
+                Err(UsageError {
+                    command: Some(usage),
+                    message: if arg.starts_with('-') {
+                        format!("unexpected option `{arg}`.")
+                    } else {
+                        format!("unexpected argument `{arg}`.")
+                    },
+                })
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Expand an alias from `[aliases]` in place of the command name, and add the options from
+/// `[defaults]` the command line doesn't already give, unless it has `--no-defaults`. Built-in
+/// commands can't be shadowed by aliases.
+fn apply_config(mut args: Vec<OsString>) -> Result<Vec<OsString>, UsageError> {
+    let config = config();
+    let command = |args: &[OsString]| args.first().and_then(|arg| arg.to_str()).map(String::from);
+
+    if let Some(name) = command(&args).filter(|name| find_command(name).is_none()) {
+        if let Some(alias) = config.alias(&name) {
+            let alias = alias.map_err(|message| UsageError {
+                command: None,
+                message: format!("{message} in ec.toml."),
+            })?;
+            args.splice(0..1, alias.into_iter().map(OsString::from));
+        }
+    }
+
+    if let Some(i) = args.iter().position(|arg| arg == NO_DEFAULTS_OPTION.0) {
+        args.remove(i);
+        return Ok(args);
+    }
+    let Some(usage) = command(&args).as_deref().and_then(find_command) else {
+        return Ok(args);
+    };
+
+    let error = |message: String| UsageError {
+        command: Some(usage),
+        message,
+    };
+    let defaults = config
+        .default_options(usage.name)
+        .map_err(|message| error(format!("{message} in ec.toml.")))?;
+    for (option, value) in defaults {
+        if !usage.accepts(&option) {
+            return Err(error(format!(
+                "unknown option `{option}` in the defaults of ec.toml."
+            )));
+        }
+
+        let given = args.iter().filter_map(|arg| arg.to_str()).any(|arg| {
+            arg == option
+                || arg
+                    .strip_prefix(option.as_str())
+                    .is_some_and(|v| v.starts_with('='))
+        });
+        if !given {
+            args.push(option.into());
+            args.extend(value.map(OsString::from));
+        }
+    }
+
+    Ok(args)
+}
+
+pub fn parse() -> Result<AppArguments, UsageError> {
+    let mut args = apply_config(env::args_os().skip(1).collect())?;
+    // options may follow a `--` like with `cargo run`, e.g. `cargo solve 07 -- --sample`.
+    args.retain(|arg| arg != "--");
+    let mut args = Arguments::from_vec(args);
+
+    let command = args.subcommand().map_err(|e| UsageError {
+        command: None,
+        message: e.to_string(),
+    })?;
+
+    let Some(command) = command else {
+        if args.contains(["-h", "--help"]) {
+            return Ok(AppArguments::Help { command: None });
+        }
+        return Err(UsageError {
+            command: None,
+            message: "no command specified.".into(),
+        });
+    };
+
+    if command == "help" {
+        let command = args.opt_free_from_str::<String>().ok().flatten();
+        return Ok(AppArguments::Help {
+            command: command.as_deref().and_then(find_command).map(|u| u.name),
+        });
+    }
+
+    let usage = find_command(&command).ok_or_else(|| UsageError {
+        command: None,
+        message: format!("unknown command `{command}`."),
+    })?;
+
+    let mut p = Parser { args, usage };
+
+    if p.args.contains(["-h", "--help"]) {
+        return Ok(AppArguments::Help {
+            command: Some(usage.name),
+        });
+    }
+
+    let year = p.value::<Year>("--year")?;
+    let story = p.value::<StoryId>("--story")?;
+    match (year, story) {
+        (Some(_), Some(_)) => {
+            return Err(p.error("`--year` and `--story` can't be combined."));
+        }
+        (Some(year), None) => set_year(year),
+        (None, Some(story)) => set_story(story),
+        (None, None) => {}
+    }
+    if p.flag("--read-only") {
+        read_only::enable();
+    }
+
+    let app_args = match usage.name {
+        "all" => AppArguments::All {
+            release: p.flag("--release"),
+            days: p.value("--days")?,
+            from: p.value("--from")?,
+            timeout: p.value("--timeout")?,
+            jobs: p.value("--jobs")?,
+            json: p.flag("--json"),
+        },
+        "bench" => AppArguments::Bench {
+            day: p.opt_free("day")?,
+        },
+        "build-times" => AppArguments::BuildTimes {
+            debug: p.flag("--debug"),
+        },
+        "contest" => AppArguments::Contest {
+            tries: p.count("--tries", 3)?,
+            review: p.flag("--review"),
+            reset: p.flag("--reset"),
+            day: p.free("day")?,
+        },
+        "time" => {
+            let all = p.flag("--all");
+            let store = p.flag("--store");
+            let share = p.flag("--share");
+            let days = p.value("--days")?;
+            let json = p.flag("--json");
+            let check = p.flag("--check");
+            let threshold = p.value::<f64>("--threshold")?;
+
+            if check && store {
+                return Err(p.error("`--check` and `--store` can't be combined."));
+            }
+            if threshold.is_some() && !check {
+                return Err(p.error("`--threshold` only applies to `--check`."));
+            }
+
+            AppArguments::Time {
+                all,
+                day: p.opt_free("day")?,
+                store,
+                share,
+                days,
+                json,
+                check: check.then(|| threshold.unwrap_or(10.0)),
+            }
+        }
+        "download" => {
+            let all = p.flag("--all");
+            let jobs = p.count("--jobs", 4)?;
+
+            AppArguments::Download {
+                day: if all { None } else { Some(p.free("day")?) },
+                jobs,
+            }
+        }
+        "oracle" => AppArguments::Oracle {
+            reference: p.required_value("--cmd")?,
+            part: p.value("--part")?,
+            runs: p.value("--runs")?.unwrap_or(100),
+            size: p.value("--size")?.unwrap_or(100),
+            seed: p.value("--seed")?.unwrap_or(0),
+            author: p.author()?,
+            timeout: p.value("--timeout")?,
+            day: p.free("day")?,
+        },
+        "profile" => AppArguments::Profile {
+            author: p.author()?,
+            day: p.free("day")?,
+            part: p.free("part")?,
+        },
+        "read" => AppArguments::Read {
+            part: p.value("--part")?,
+            pager: !p.flag("--no-pager"),
+            day: p.free("day")?,
+        },
+        "reduce" => {
+            let reference = p.value::<String>("--reference")?;
+            if let Some(reference) = reference.as_deref().filter(|r| !is_valid_author(r)) {
+                return Err(p.error(format!("invalid author name `{reference}`.")));
+            }
+
+            AppArguments::Reduce {
+                part: p.required_value("--part")?,
+                predicate: p.required_value("--predicate")?,
+                reference,
+                author: p.author()?,
+                input: p.value("--input")?,
+                release: p.flag("--release"),
+                timeout: p.value("--timeout")?,
+                day: p.free("day")?,
+            }
+        }
+        "deploy" => {
+            let host = p.required_value("--host")?;
+            let target = p.value("--target")?;
+            let remote_dir = p
+                .value("--dir")?
+                .unwrap_or_else(|| DEFAULT_REMOTE_DIR.to_string());
+            let day = p.value("--day")?;
+
+            // forwarded to the solution binaries.
+            let mut solution_args = vec![];
+            if let Some(part) = p.value::<Part>("--part")? {
+                solution_args.extend(["--part".to_string(), part.to_string()]);
+            }
+            if p.flag("--time") {
+                solution_args.push("--time".to_string());
+            }
+
+            AppArguments::Deploy {
+                year: p.free("year")?,
+                host,
+                target,
+                remote_dir,
+                day,
+                solution_args,
+            }
+        }
+        "explain" => AppArguments::Explain {
+            release: p.flag("--release"),
+            author: p.author()?,
+            day: p.free("day")?,
+            part: p.free("part")?,
+        },
+        "fix-answers" => AppArguments::FixAnswers,
+        "gen" => AppArguments::Gen {
+            size: p.value("--size")?.unwrap_or(1000),
+            seed: p.value("--seed")?.unwrap_or(0),
+            day: p.free("day")?,
+        },
+        "head-input" => AppArguments::HeadInput {
+            part: p.required_value("--part")?,
+            step: p.value("--lines")?,
+            input: p.value("--input")?,
+            run: p.flag("--run"),
+            author: p.author()?,
+            release: p.flag("--release"),
+            timeout: p.value("--timeout")?,
+            day: p.free("day")?,
+        },
+        "init" => AppArguments::Init,
+        #[cfg(feature = "encrypt")]
+        "encrypt-answers" => AppArguments::EncryptAnswers,
+        #[cfg(feature = "encrypt")]
+        "encrypt-inputs" => AppArguments::EncryptInputs,
+        #[cfg(feature = "http_client")]
+        "audit-answers" => AppArguments::AuditAnswers,
+        "verify" => AppArguments::Verify {
+            days: p.value("--days")?,
+            author: p.author()?,
+            timeout: p.value("--timeout")?,
+            as_of: p.value("--as-of")?,
+        },
+        "migrate-layout" => AppArguments::MigrateLayout {
+            dry_run: p.flag("--dry-run"),
+        },
+        "modgraph" => AppArguments::Modgraph {
+            mermaid: p.flag("--mermaid"),
+        },
+        "notes" => AppArguments::Notes {
+            output: p.value("--output")?,
+        },
+        "scale-bench" => AppArguments::ScaleBench {
+            part: p.value("--part")?,
+            size: p.value("--size")?,
+            enforce: p.flag("--enforce"),
+            author: p.author()?,
+            day: p.free("day")?,
+        },
+        "scaffold" => AppArguments::Scaffold {
+            download: p.flag("--download"),
+            overwrite: p.flag("--overwrite"),
+            author: p.author()?,
+            template: p.value("--template")?,
+            day: p.free("day")?,
+        },
+        "scaffold-utils" => AppArguments::ScaffoldUtils {
+            preset: p
+                .value("--preset")?
+                .unwrap_or_else(|| "standard".to_string()),
+            overwrite: p.flag("--overwrite"),
+        },
+        "scratch" => AppArguments::Scratch {
+            overwrite: p.flag("--overwrite"),
+            day: p.free("day")?,
+        },
+        "selftest" => AppArguments::Selftest {
+            keep: p.flag("--keep"),
+        },
+        "solve" => {
+            let submit = p.value("--submit")?;
+            let input = p.value::<String>("--input")?;
+            let sample = p.flag("--sample");
+
+            if sample && input.is_some() {
+                return Err(p.error("`--sample` and `--input` can't be combined."));
+            }
+            if sample && submit.is_some() {
+                return Err(p.error("`--sample` and `--submit` can't be combined."));
+            }
+
+            AppArguments::Solve {
+                release: p.flag("--release"),
+                submit,
+                author: p.author()?,
+                record: p.flag("--record"),
+                explain: p.flag("--explain"),
+                checks: p.flag("--checks"),
+                input,
+                sample,
+                timeout: p.value("--timeout")?,
+                json: p.flag("--json"),
+                chrome_trace: p.value("--chrome-trace")?,
+                day: p.opt_free("day")?,
+            }
+        }
+        "stats" => AppArguments::Stats,
+        "submit" => {
+            let author = p.author()?;
+            let yes = p.flag("--yes");
+
+            AppArguments::Submit {
+                author,
+                yes,
+                day: p.free("day")?,
+                part: p.free("part")?,
+            }
+        }
+        "sync" => AppArguments::Sync,
+        "compare" => {
+            let day = p.free("day")?;
+            let mut authors = vec![];
+            while let Some(author) = p.opt_free::<String>("author")? {
+                if !is_valid_author(&author) {
+                    return Err(p.error(format!("invalid author name `{author}`.")));
+                }
+                authors.push(author);
+            }
+
+            AppArguments::Compare { day, authors }
+        }
+        "compare-inputs" => AppArguments::CompareInputs {
+            part: p.value("--part")?,
+            day: p.free("day")?,
+        },
+        "today" => {
+            let day = p.value("--day")?;
+            let date = p.value("--date")?;
+            if day.is_some() && date.is_some() {
+                return Err(p.error("`--day` and `--date` can't be combined."));
+            }
+            AppArguments::Today {
+                day,
+                date,
+                edit: p.flag("--edit"),
+            }
+        }
+        "watch" => AppArguments::Watch {
+            author: p.author()?,
+            timeout: p.value("--timeout")?.unwrap_or(10),
+            day: p.free("day")?,
+        },
+        "web" => AppArguments::Web {
+            leaderboard: p.flag("--leaderboard"),
+            day: p.opt_free("day")?,
+        },
+        _ => unreachable!("command `{}` has a usage but no parser", usage.name),
+    };
+
+    p.finish()?;
+    Ok(app_args)
+}
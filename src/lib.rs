@@ -1,3 +1,9 @@
 pub mod template;
+pub mod utils;
+
+use template::plugins::EcPlugin;
+
+/// Plugins notified of downloads, runs, submissions and benchmarks, see [`template::plugins`].
+pub static PLUGINS: &[&dyn EcPlugin] = &[];
 
 // Use this file to add helper functions and additional modules.
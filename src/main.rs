@@ -1,97 +1,17 @@
-use everybody_codes::template::commands::{all, download, read, scaffold, solve, time};
 use args::{parse, AppArguments};
+use everybody_codes::template::commands::{
+    all, bench, build_times, compare, compare_inputs, contest, deploy, download, explain,
+    fix_answers, gen, head_input, init, migrate_layout, modgraph, notes, oracle, profile, read,
+    reduce, scaffold, scaffold_utils, scale_bench, scratch, selftest, solve, stats, submit, sync,
+    time, today, verify, watch, web,
+};
 
-#[cfg(feature = "today")]
-use everybody_codes::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+#[cfg(feature = "http_client")]
+use everybody_codes::template::commands::audit_answers;
+#[cfg(feature = "encrypt")]
+use everybody_codes::template::commands::{encrypt_answers, encrypt_inputs};
 
-mod args {
-    use everybody_codes::template::Day;
-    use std::process;
-
-    pub enum AppArguments {
-        Download {
-            day: Day,
-        },
-        Read {
-            day: Day,
-        },
-        Scaffold {
-            day: Day,
-            download: bool,
-            overwrite: bool,
-        },
-        Solve {
-            day: Day,
-            release: bool,
-            submit: Option<u8>,
-        },
-        All {
-            release: bool,
-        },
-        Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
-        },
-        #[cfg(feature = "today")]
-        Today,
-    }
-
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
-
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
-
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-            },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
-        };
-
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
-
-        Ok(app_args)
-    }
-}
+mod args;
 
 fn main() {
     match parse() {
@@ -100,42 +20,197 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::Help { command } => print!("{}", args::help(command)),
+            AppArguments::All {
+                release,
+                days,
+                from,
+                timeout,
+                jobs,
+                json,
+            } => all::handle(release, days, from, timeout, jobs, json),
+            AppArguments::Bench { day } => bench::handle(day),
+            AppArguments::BuildTimes { debug } => build_times::handle(!debug),
+            AppArguments::Contest {
+                day,
+                tries,
+                review,
+                reset,
+            } => contest::handle(day, tries, review, reset),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                share,
+                days,
+                json,
+                check,
+            } => time::handle(day, all, store, share, days, json, check),
+            AppArguments::Download { day, jobs } => match day {
+                Some(day) => download::handle(day),
+                None => download::handle_all(jobs),
+            },
+            AppArguments::Read { day, part, pager } => read::handle(day, part, pager),
+            AppArguments::Profile { day, part, author } => {
+                profile::handle(day, part, author.as_deref());
+            }
+            AppArguments::ScaleBench {
+                day,
+                part,
+                size,
+                enforce,
+                author,
+            } => scale_bench::handle(day, part, size, enforce, author.as_deref()),
+            AppArguments::Reduce {
+                day,
+                part,
+                predicate,
+                reference,
+                author,
+                input,
+                release,
+                timeout,
+            } => reduce::handle(
+                day,
+                part,
+                predicate,
+                reference.as_deref(),
+                author.as_deref(),
+                input.as_deref(),
+                release,
+                timeout,
+            ),
+            AppArguments::Deploy {
+                year,
+                host,
+                target,
+                remote_dir,
+                day,
+                solution_args,
+            } => deploy::handle(year, host, target, remote_dir, day, &solution_args),
+            AppArguments::FixAnswers => fix_answers::handle(),
+            AppArguments::Gen { day, size, seed } => gen::handle(day, size, seed),
+            AppArguments::HeadInput {
+                day,
+                part,
+                step,
+                input,
+                run,
+                author,
+                release,
+                timeout,
+            } => head_input::handle(
+                day,
+                part,
+                step,
+                input.as_deref(),
+                run,
+                author.as_deref(),
+                release,
+                timeout,
+            ),
+            AppArguments::Init => init::handle(),
+            AppArguments::Oracle {
+                day,
+                reference,
+                part,
+                runs,
+                size,
+                seed,
+                author,
+                timeout,
+            } => oracle::handle(
+                day,
+                &reference,
+                part,
+                runs,
+                size,
+                seed,
+                author.as_deref(),
+                timeout,
+            ),
+            #[cfg(feature = "encrypt")]
+            AppArguments::EncryptAnswers => encrypt_answers::handle(),
+            #[cfg(feature = "encrypt")]
+            AppArguments::EncryptInputs => encrypt_inputs::handle(),
+            #[cfg(feature = "http_client")]
+            AppArguments::AuditAnswers => audit_answers::handle(),
+            AppArguments::Verify {
+                days,
+                author,
+                timeout,
+                as_of,
+            } => verify::handle(days, author.as_deref(), timeout, as_of),
+            AppArguments::MigrateLayout { dry_run } => migrate_layout::handle(dry_run),
+            AppArguments::Modgraph { mermaid } => modgraph::handle(mermaid),
+            AppArguments::Notes { output } => notes::handle(output.as_deref()),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                author,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(day, overwrite, author.as_deref(), template.as_deref());
                 if download {
                     download::handle(day);
                 }
             }
+            AppArguments::ScaffoldUtils { preset, overwrite } => {
+                scaffold_utils::handle(&preset, overwrite);
+            }
+            AppArguments::Scratch { day, overwrite } => scratch::handle(day, overwrite),
+            AppArguments::Selftest { keep } => selftest::handle(keep),
             AppArguments::Solve {
                 day,
                 release,
                 submit,
-            } => solve::handle(day, release, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run during an active Everybody Codes event. \
-                            Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+                author,
+                record,
+                explain,
+                checks,
+                input,
+                sample,
+                timeout,
+                json,
+                chrome_trace,
+            } => solve::handle(
+                day,
+                release,
+                submit,
+                author.as_deref(),
+                record,
+                explain,
+                checks,
+                input.as_deref(),
+                sample,
+                timeout,
+                json,
+                chrome_trace.as_deref(),
+            ),
+            AppArguments::Stats => stats::handle(),
+            AppArguments::Submit {
+                day,
+                part,
+                author,
+                yes,
+            } => submit::handle(day, part, author.as_deref(), yes),
+            AppArguments::Sync => sync::handle(),
+            AppArguments::Compare { day, authors } => compare::handle(day, authors),
+            AppArguments::CompareInputs { day, part } => compare_inputs::handle(day, part),
+            AppArguments::Explain {
+                day,
+                part,
+                release,
+                author,
+            } => explain::handle(day, part, release, author.as_deref()),
+            AppArguments::Today { day, date, edit } => today::handle(day, date, edit),
+            AppArguments::Watch {
+                day,
+                author,
+                timeout,
+            } => watch::handle(day, author.as_deref(), timeout),
+            AppArguments::Web { day, leaderboard } => web::handle(day, leaderboard),
         },
     };
 }
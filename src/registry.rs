@@ -0,0 +1,40 @@
+//! Every `solution!` binary of the current event compiled into one, so `cargo all` and
+//! `cargo time` build once and then run each quest without going through `cargo run`.
+//!
+//! Built with the `registry` feature. The build script lists the quests in `registry()`.
+//! Binaries with hooks, `mod foo;` files or `crate::` paths, for another event or by another
+//! author are left out and still run on their own.
+use std::{env, process};
+
+use everybody_codes::template::Day;
+
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+fn main() {
+    let registry = registry();
+    let args: Vec<String> = env::args().collect();
+
+    if args.iter().any(|arg| arg == "--list") {
+        for day in registry.days() {
+            println!("{day}");
+        }
+        return;
+    }
+
+    let day = args
+        .iter()
+        .position(|arg| arg == "--day")
+        .map(|i| args.get(i + 1).and_then(|day| day.parse::<Day>().ok()));
+    match day {
+        None => registry.run(),
+        Some(Some(day)) if registry.run_day(day, env!("CARGO_CRATE_NAME")) => {}
+        Some(Some(day)) => {
+            eprintln!("Day {day} is not in the registry.");
+            process::exit(1);
+        }
+        Some(None) => {
+            eprintln!("Expected a day after `--day`.");
+            process::exit(1);
+        }
+    }
+}
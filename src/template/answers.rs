@@ -0,0 +1,624 @@
+/// Helpers for reading and normalizing the expected answers stored in `data/answers`.
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+#[cfg(feature = "encrypt")]
+use crate::template::{
+    crypto::{Cipher, CryptoError},
+    dirs::config_dir,
+};
+use crate::template::{data_dir, quest_meta, read_only, sample_paths, snapshots, Day, Part};
+
+/// Returns the path of the answer file for a given day and part.
+#[must_use]
+pub fn get_answer_path(day: Day, part: Part) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join(read_only::quest_file(format!("answers/{day}-{part}.txt")))
+}
+
+/// Returns the path of the known answer to the real input for a given day and part.
+#[must_use]
+pub fn get_real_answer_path(day: Day, part: Part) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join(data_dir())
+        .join("real_answers")
+        .join(format!("{day}-{part}.txt"))
+}
+
+/// Environment variable holding the hex key used to encrypt real answers.
+#[cfg(feature = "encrypt")]
+pub const ANSWER_KEY_ENV: &str = "EC_ANSWER_KEY";
+
+/// File in the global config directory holding the key when `EC_ANSWER_KEY` is not set.
+#[cfg(feature = "encrypt")]
+pub const ANSWER_KEY_FILE: &str = "answer.key";
+
+/// The cipher for real answers, keyed from `EC_ANSWER_KEY` or the global `answer.key` file.
+#[cfg(feature = "encrypt")]
+pub fn answer_cipher() -> Result<Cipher, CryptoError> {
+    if env::var_os(ANSWER_KEY_ENV).is_some() {
+        return Cipher::from_env(ANSWER_KEY_ENV);
+    }
+
+    match config_dir().and_then(|dir| fs::read_to_string(dir.join(ANSWER_KEY_FILE)).ok()) {
+        Some(hex) => Cipher::from_hex(&hex, ANSWER_KEY_FILE),
+        None => Cipher::from_env(ANSWER_KEY_ENV),
+    }
+}
+
+/// Returns the path of the encrypted answer to the real input, e.g. `01-1.txt.enc`.
+#[must_use]
+pub fn get_encrypted_real_answer_path(day: Day, part: Part) -> PathBuf {
+    get_real_answer_path(day, part).with_extension("txt.enc")
+}
+
+/// Reads the known answer to the real input, if one was recorded.
+///
+/// Plaintext answers take precedence. With the `encrypt` feature, encrypted answers are
+/// decrypted transparently when `EC_ANSWER_KEY` or the `answer.key` file is set.
+#[must_use]
+pub fn read_real_answer(day: Day, part: Part) -> Option<String> {
+    let raw = match fs::read_to_string(get_real_answer_path(day, part)) {
+        Ok(raw) => raw,
+        Err(_) => read_encrypted_real_answer(day, part)?,
+    };
+
+    Some(normalize(&raw).to_string()).filter(|answer| !answer.is_empty())
+}
+
+#[cfg(feature = "encrypt")]
+fn read_encrypted_real_answer(day: Day, part: Part) -> Option<String> {
+    let path = get_encrypted_real_answer_path(day, part);
+    let encrypted = fs::read_to_string(&path).ok()?;
+
+    let decrypted = answer_cipher().and_then(|cipher| cipher.decrypt(&encrypted));
+
+    match decrypted {
+        Ok(bytes) => Some(String::from_utf8_lossy(&bytes).to_string()),
+        Err(e) => {
+            eprintln!("Could not decrypt \"{}\": {e}", path.display());
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "encrypt"))]
+fn read_encrypted_real_answer(day: Day, part: Part) -> Option<String> {
+    if get_encrypted_real_answer_path(day, part).exists() {
+        eprintln!("Found an encrypted answer for day {day} part {part}, enable the `encrypt` feature to read it.");
+    }
+    None
+}
+
+/// Records an accepted answer to the real input and returns the path written to. The input is
+/// kept in [`snapshots`] along with it, and the next part is marked as unlocked in
+/// [`quest_meta`].
+///
+/// With the `encrypt` feature and an answer key set, the answer is stored encrypted.
+pub fn store_real_answer(day: Day, part: Part, answer: &str) -> Result<PathBuf, io::Error> {
+    let path = get_real_answer_path(day, part);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    if let Err(e) = snapshots::record(day, part, normalize(answer)) {
+        eprintln!("Failed to snapshot the input of day {day} part {part}: {e}");
+    }
+    quest_meta::record_solved(day, part);
+
+    #[cfg(feature = "encrypt")]
+    if let Ok(cipher) = answer_cipher() {
+        let encrypted_path = get_encrypted_real_answer_path(day, part);
+        fs::write(
+            &encrypted_path,
+            cipher.encrypt(normalize(answer).as_bytes()),
+        )?;
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        return Ok(encrypted_path);
+    }
+
+    fs::write(&path, normalize(answer))?;
+    Ok(path)
+}
+
+/// Encrypts every plaintext answer in `data/real_answers` and removes the plaintext,
+/// returning the paths of the encrypted files.
+#[cfg(feature = "encrypt")]
+pub fn encrypt_all() -> Result<Vec<PathBuf>, String> {
+    let cipher = answer_cipher().map_err(|e| e.to_string())?;
+    let dir = env::current_dir()
+        .map_err(|e| e.to_string())?
+        .join(data_dir())
+        .join("real_answers");
+
+    let mut encrypted = vec![];
+
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+
+        if path.extension().is_none_or(|ext| ext != "txt") {
+            continue;
+        }
+
+        let raw = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let encrypted_path = path.with_extension("txt.enc");
+        fs::write(&encrypted_path, cipher.encrypt(normalize(&raw).as_bytes()))
+            .map_err(|e| e.to_string())?;
+        fs::remove_file(&path).map_err(|e| e.to_string())?;
+        encrypted.push(encrypted_path);
+    }
+
+    encrypted.sort();
+    Ok(encrypted)
+}
+
+/// How a recorded real answer relates to the answer the site accepted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditStatus {
+    Match,
+    /// Recorded locally, but the part is not solved on the site.
+    LocalOnly,
+    /// Solved on the site, but no answer was recorded locally.
+    RemoteOnly,
+    Mismatch,
+}
+
+/// Compare a recorded real answer with the answer the site accepted, `None` if neither exists.
+#[must_use]
+pub fn audit(local: Option<&str>, remote: Option<&str>) -> Option<AuditStatus> {
+    match (local.map(normalize), remote.map(normalize)) {
+        (None, None) => None,
+        (Some(_), None) => Some(AuditStatus::LocalOnly),
+        (None, Some(_)) => Some(AuditStatus::RemoteOnly),
+        (Some(local), Some(remote)) if local == remote => Some(AuditStatus::Match),
+        (Some(_), Some(_)) => Some(AuditStatus::Mismatch),
+    }
+}
+
+/// Strips surrounding whitespace and newlines from a raw answer.
+#[must_use]
+pub fn normalize(raw: &str) -> &str {
+    raw.trim()
+}
+
+/// Returns `true` if a raw answer differs from its normalized form.
+#[must_use]
+pub fn needs_fixing(raw: &str) -> bool {
+    normalize(raw) != raw
+}
+
+/// Reads the expected answer for a given day and part.
+///
+/// Surrounding whitespace is stripped, and a warning pointing at the offending file is printed
+/// so stray newlines or spaces can be cleaned up with `cargo fix-answers`.
+#[must_use]
+pub fn read_answer(day: Day, part: Part) -> String {
+    read_answer_file(&get_answer_path(day, part))
+}
+
+/// Reads the expected answers of every example of a part, in the order of
+/// [`read_samples`](crate::template::read_samples)`("samples", ...)`. Each example is paired
+/// with the answer file of the same name, e.g. `data/samples/07-1-2.txt` with
+/// `data/answers/07-1-2.txt`.
+///
+/// # Panics
+/// If an example has no answer file.
+#[must_use]
+pub fn read_answers(day: Day, part: Part) -> Vec<String> {
+    sample_paths("samples", day, part)
+        .iter()
+        .map(|sample| {
+            let name = sample.file_name().expect("sample paths end in a file name");
+            let path = read_only::quest_file(Path::new("answers").join(name));
+            assert!(
+                path.exists(),
+                "the sample \"{}\" has no answer, add it to \"{}\"",
+                sample.display(),
+                path.display()
+            );
+            read_answer_file(&path)
+        })
+        .collect()
+}
+
+fn read_answer_file(path: &Path) -> String {
+    let raw = fs::read_to_string(path).expect("could not open answer file");
+
+    if needs_fixing(&raw) {
+        eprintln!(
+            "⚠️  Answer file \"{}\" contains surrounding whitespace, run `cargo fix-answers` to clean it up.",
+            path.display()
+        );
+    }
+
+    normalize(&raw).to_string()
+}
+
+/// Rewrites every answer file in `data/answers` to its normalized form,
+/// returning the paths of the files that were changed.
+pub fn fix_all() -> Result<Vec<PathBuf>, io::Error> {
+    let dir = env::current_dir()?.join(data_dir()).join("answers");
+    let mut fixed = vec![];
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.extension().is_none_or(|ext| ext != "txt") {
+            continue;
+        }
+
+        let raw = fs::read_to_string(&path)?;
+        if needs_fixing(&raw) {
+            fs::write(&path, normalize(&raw))?;
+            fixed.push(path);
+        }
+    }
+
+    fixed.sort();
+    Ok(fixed)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Separators recognized when parsing a multi-value answer, in order of precedence.
+///
+/// `-` only separates values that are all non-empty and not padded with whitespace, so
+/// negative numbers like `-5` or `12 -3` keep their sign.
+pub const SEPARATORS: [char; 6] = [',', ';', '|', ':', '-', ' '];
+
+/// Whether `separator` splits `s` into the values of an answer.
+fn separates(s: &str, separator: char) -> bool {
+    match separator {
+        '-' => s.contains('-') && s.split('-').all(|v| !v.is_empty() && v.trim() == v),
+        _ => s.contains(separator),
+    }
+}
+
+/// An answer made up of one or more values joined by a separator, e.g. `12,34`.
+///
+/// Parts returning several numbers can return `Option<Answer>` and the generated tests
+/// compare it against the answer files like any other [`EcAnswer`].
+///
+/// ```
+/// # use everybody_codes::template::Answer;
+/// let answer = Answer::pair(12, 34);
+/// assert_eq!(answer.to_string(), "12,34");
+/// assert_eq!("12,34".parse::<Answer>().unwrap(), answer);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Single(String),
+    Pair(String, String, char),
+    List(Vec<String>, char),
+}
+
+impl Answer {
+    /// Two values joined by a comma.
+    pub fn pair(a: impl Display, b: impl Display) -> Self {
+        Answer::Pair(a.to_string(), b.to_string(), ',')
+    }
+
+    /// Any number of values joined by a comma.
+    /// Lists of one or two values are represented as [`Answer::Single`] and [`Answer::Pair`].
+    pub fn list<T: Display>(values: impl IntoIterator<Item = T>) -> Self {
+        let mut values: Vec<String> = values.into_iter().map(|v| v.to_string()).collect();
+
+        match values.len() {
+            1 => Answer::Single(values.remove(0)),
+            2 => {
+                let b = values.remove(1);
+                Answer::Pair(values.remove(0), b, ',')
+            }
+            _ => Answer::List(values, ','),
+        }
+    }
+
+    /// Replaces the separator used to join the values.
+    #[must_use]
+    pub fn with_separator(self, separator: char) -> Self {
+        match self {
+            Answer::Single(x) => Answer::Single(x),
+            Answer::Pair(a, b, _) => Answer::Pair(a, b, separator),
+            Answer::List(values, _) => Answer::List(values, separator),
+        }
+    }
+
+    /// Parses an answer split on a specific separator.
+    pub fn parse_with(s: &str, separator: char) -> Self {
+        let values: Vec<&str> = s.split(separator).map(str::trim).collect();
+        Answer::list(values).with_separator(separator)
+    }
+
+    /// The individual values of the answer.
+    pub fn values(&self) -> Vec<&str> {
+        match self {
+            Answer::Single(x) => vec![x],
+            Answer::Pair(a, b, _) => vec![a, b],
+            Answer::List(values, _) => values.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// Parses every value of the answer, e.g. into a list of numbers.
+    pub fn parse_values<T: FromStr>(&self) -> Result<Vec<T>, T::Err> {
+        self.values().into_iter().map(str::parse).collect()
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Single(x) => write!(f, "{x}"),
+            Answer::Pair(a, b, separator) => write!(f, "{a}{separator}{b}"),
+            Answer::List(values, separator) => {
+                write!(f, "{}", values.join(&separator.to_string()))
+            }
+        }
+    }
+}
+
+/// Parses an answer, detecting the separator from [`SEPARATORS`].
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = normalize(s);
+
+        Ok(SEPARATORS
+            .iter()
+            .find(|separator| separates(s, **separator))
+            .map_or_else(
+                || Answer::Single(s.to_string()),
+                |separator| Answer::parse_with(s, *separator),
+            ))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A value a part can return: integers, floats, [`bool`], [`char`], strings and [`Answer`].
+///
+/// The answer is run, submitted and compared against `data/answers` in its
+/// [`to_answer`](EcAnswer::to_answer) form, so `part_one(input: &str) -> Option<String>`
+/// works like a number would. Implement it for your own answer types:
+///
+/// ```
+/// # use everybody_codes::template::EcAnswer;
+/// struct Position(u32, u32);
+///
+/// impl EcAnswer for Position {
+///     fn to_answer(&self) -> String {
+///         format!("{},{}", self.0, self.1)
+///     }
+/// }
+/// ```
+///
+/// Types that implement [`Display`] already can use it as their answer with
+/// [`impl_ec_answer!`](crate::impl_ec_answer).
+pub trait EcAnswer {
+    /// The answer as it is submitted.
+    fn to_answer(&self) -> String;
+
+    /// Whether this is the expected answer from an answer file, ignoring surrounding whitespace.
+    fn matches_answer(&self, expected: &str) -> bool {
+        normalize(&self.to_answer()) == normalize(expected)
+    }
+}
+
+/// Implement [`EcAnswer`] for types whose [`Display`] output is the answer, e.g. a part that
+/// returned a `Display` type before parts returned an [`EcAnswer`]:
+///
+/// ```
+/// # use std::fmt;
+/// struct Position(u32, u32);
+///
+/// impl fmt::Display for Position {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write!(f, "{},{}", self.0, self.1)
+///     }
+/// }
+///
+/// everybody_codes::impl_ec_answer!(Position);
+/// ```
+#[macro_export]
+macro_rules! impl_ec_answer {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl $crate::template::EcAnswer for $ty {
+                fn to_answer(&self) -> String {
+                    ::std::string::ToString::to_string(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_ec_answer!(
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    bool,
+    char,
+    str,
+    String,
+    Box<str>,
+    Cow<'_, str>
+);
+
+impl<T: EcAnswer + ?Sized> EcAnswer for &T {
+    fn to_answer(&self) -> String {
+        (**self).to_answer()
+    }
+
+    fn matches_answer(&self, expected: &str) -> bool {
+        (**self).matches_answer(expected)
+    }
+}
+
+/// Multi-value answers match regardless of the whitespace around their separators.
+impl EcAnswer for Answer {
+    fn to_answer(&self) -> String {
+        self.to_string()
+    }
+
+    fn matches_answer(&self, expected: &str) -> bool {
+        expected
+            .parse::<Answer>()
+            .is_ok_and(|expected| expected == *self)
+    }
+}
+
+/// Assert that a part returned the expected answer from an answer file, for the generated tests:
+///
+/// ```
+/// # use everybody_codes::template::assert_answer;
+/// assert_answer(Some(42), "42\n");
+/// assert_answer(Some("ABC"), "ABC");
+/// ```
+#[track_caller]
+pub fn assert_answer<A: EcAnswer>(actual: Option<A>, expected: &str) {
+    match actual {
+        Some(actual) if actual.matches_answer(expected) => {}
+        Some(actual) => panic!(
+            "expected `{}`, got `{}`",
+            normalize(expected),
+            actual.to_answer()
+        ),
+        None => panic!("expected `{}`, got no answer", normalize(expected)),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{assert_answer, audit, needs_fixing, normalize, Answer, AuditStatus, EcAnswer};
+
+    #[test]
+    fn displays_multi_value_answers() {
+        assert_eq!(Answer::pair(12, 34).to_string(), "12,34");
+        assert_eq!(
+            Answer::list([1, 2, 3]).with_separator('-').to_string(),
+            "1-2-3"
+        );
+        assert_eq!(Answer::list(["A"]).to_string(), "A");
+    }
+
+    #[test]
+    fn parses_multi_value_answers() {
+        assert_eq!("12,34".parse::<Answer>().unwrap(), Answer::pair(12, 34));
+        assert_eq!(
+            "1-2-3\n".parse::<Answer>().unwrap(),
+            Answer::list([1, 2, 3]).with_separator('-')
+        );
+        assert_eq!(
+            "1234".parse::<Answer>().unwrap(),
+            Answer::Single("1234".into())
+        );
+        assert_eq!(
+            Answer::parse_with("1, 2", ',')
+                .parse_values::<u64>()
+                .unwrap(),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn keeps_negative_numbers() {
+        let values = |s: &str| s.parse::<Answer>().unwrap().parse_values::<i64>().unwrap();
+
+        assert_eq!(values("-5"), vec![-5]);
+        assert_eq!(values("12 -3"), vec![12, -3]);
+        assert_eq!(values("-1,-2"), vec![-1, -2]);
+        assert_eq!(values("-4 7 -9"), vec![-4, 7, -9]);
+        assert_eq!(values("1-2"), vec![1, 2]);
+    }
+
+    #[test]
+    fn compares_separators() {
+        assert_ne!("12:34".parse::<Answer>().unwrap(), Answer::pair(12, 34));
+        assert_eq!(
+            "12:34".parse::<Answer>().unwrap(),
+            Answer::pair(12, 34).with_separator(':')
+        );
+    }
+
+    #[test]
+    fn matches_answers_of_any_type() {
+        assert_eq!(42u64.to_answer(), "42");
+        assert_eq!((-7i32).to_answer(), "-7");
+        assert_eq!("ABC".to_answer(), "ABC");
+        assert_eq!(String::from("ABC").to_answer(), "ABC");
+        assert_eq!('x'.to_answer(), "x");
+        assert_eq!(1.5f64.to_answer(), "1.5");
+        assert_eq!(Cow::Borrowed("ABC").to_answer(), "ABC");
+
+        assert!(42u64.matches_answer("42\n"));
+        assert!(!42u64.matches_answer("042"));
+        assert!("ABC".matches_answer(" ABC "));
+        assert!(!"ABC".matches_answer("abc"));
+        assert!(Answer::pair(12, 34).matches_answer("12, 34"));
+        assert!(!Answer::pair(12, 34).matches_answer("12:34"));
+
+        assert_answer(Some("ABC"), "ABC\n");
+        assert_answer(Some(String::from("ABC")), "ABC");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected `ABD`, got `ABC`")]
+    fn reports_wrong_answers() {
+        assert_answer(Some("ABC"), "ABD");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected `42`, got no answer")]
+    fn reports_missing_answers() {
+        assert_answer(None::<u64>, "42");
+    }
+
+    #[test]
+    fn normalizes_trailing_newlines() {
+        assert_eq!(normalize("1234\n\n"), "1234");
+        assert_eq!(normalize(" 1234\r\n"), "1234");
+    }
+
+    #[test]
+    fn detects_answers_needing_fixes() {
+        assert_eq!(needs_fixing("1234\n"), true);
+        assert_eq!(needs_fixing("1234 "), true);
+        assert_eq!(needs_fixing("1234"), false);
+        assert_eq!(needs_fixing(""), false);
+    }
+
+    #[test]
+    fn audits_answers() {
+        assert_eq!(audit(None, None), None);
+        assert_eq!(audit(Some("12\n"), Some("12")), Some(AuditStatus::Match));
+        assert_eq!(audit(Some("12"), Some("13")), Some(AuditStatus::Mismatch));
+        assert_eq!(audit(Some("12"), None), Some(AuditStatus::LocalOnly));
+        assert_eq!(audit(None, Some("12")), Some(AuditStatus::RemoteOnly));
+    }
+}
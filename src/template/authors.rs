@@ -0,0 +1,160 @@
+/// Team mode: several people's solutions for the same quest, e.g. `src/bin/07_alice.rs` and
+/// `src/bin/07_bob.rs`, sharing the quest's inputs, samples and answers.
+use std::{fs, path::Path};
+
+use crate::template::{
+    is_single_year_layout,
+    run_multi::child_commands::{parse_answer, parse_duration, parse_exec_time},
+    Day, Event, Part,
+};
+
+static BIN_DIR: &str = "./src/bin";
+
+/// Author names become part of binary names, so only lowercase letters, digits and `_` are
+/// allowed.
+pub fn is_valid_author(author: &str) -> bool {
+    !author.is_empty()
+        && author
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// The binary name of a solution, e.g. `07` or `07_alice`. Outside a single year layout,
+/// names start with the event, e.g. `2024_07` or `story1_02`.
+pub fn bin_name(day: Day, author: Option<&str>) -> String {
+    let name = match author {
+        Some(author) => format!("{day}_{author}"),
+        None => day.to_string(),
+    };
+
+    if is_single_year_layout() {
+        name
+    } else {
+        format!("{}_{name}", Event::current().slug())
+    }
+}
+
+pub fn get_path_for_author_bin(day: Day, author: Option<&str>) -> String {
+    format!("{BIN_DIR}/{}.rs", bin_name(day, author))
+}
+
+fn author_from_file_name(day: Day, file_name: &str) -> Option<String> {
+    let author = file_name
+        .strip_suffix(".rs")?
+        .strip_prefix(&format!("{}_", bin_name(day, None)))?;
+    is_valid_author(author).then(|| author.to_string())
+}
+
+/// Authors with a solution for `day`, sorted by name.
+pub fn list_authors(day: Day) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(Path::new(BIN_DIR)) else {
+        return vec![];
+    };
+
+    let mut authors: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| author_from_file_name(day, entry.file_name().to_str()?))
+        .collect();
+    authors.sort();
+    authors
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Answers and timings of one author's solution, parsed from its output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthorRun {
+    pub author: String,
+    pub answers: [Option<String>; 3],
+    pub times: [Option<String>; 3],
+}
+
+impl AuthorRun {
+    pub fn from_output(author: &str, day: Day, output: &[String]) -> Self {
+        let timing = parse_exec_time(output, day);
+
+        AuthorRun {
+            author: author.to_string(),
+            answers: Part::ALL.map(|part| parse_answer(output, part)),
+            times: [timing.part_1, timing.part_2, timing.part_3],
+        }
+    }
+
+    fn nanos(&self, part: Part) -> Option<f64> {
+        self.times[part.index()].as_deref().and_then(parse_duration)
+    }
+}
+
+/// Whether all authors that solved `part` agree, or `None` if fewer than two solved it.
+pub fn answers_match(runs: &[AuthorRun], part: Part) -> Option<bool> {
+    let mut answers = runs
+        .iter()
+        .filter_map(|run| run.answers[part.index()].as_ref());
+
+    let first = answers.next()?;
+    let mut rest = answers.peekable();
+    rest.peek()?;
+    Some(rest.all(|answer| answer == first))
+}
+
+/// The author with the fastest timing for `part`.
+pub fn fastest(runs: &[AuthorRun], part: Part) -> Option<&str> {
+    runs.iter()
+        .filter_map(|run| Some((run.author.as_str(), run.nanos(part)?)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(author, _)| author)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        answers_match, author_from_file_name, bin_name, fastest, is_valid_author, AuthorRun,
+    };
+    use crate::{day, template::Part};
+
+    fn get_mock_run(author: &str, answer: &str, time: &str) -> AuthorRun {
+        AuthorRun::from_output(
+            author,
+            day!(7),
+            &[
+                format!("Part 1: {answer} ({time} @ 10 samples)"),
+                "Part 2: ✖".into(),
+                format!("Part 3: 5 ({time} @ 10 samples)"),
+            ],
+        )
+    }
+
+    #[test]
+    fn names_author_bins() {
+        assert_eq!(bin_name(day!(7), None), "07");
+        assert_eq!(bin_name(day!(7), Some("alice")), "07_alice");
+        assert_eq!(is_valid_author("bob_2"), true);
+        assert_eq!(is_valid_author("Bob"), false);
+        assert_eq!(is_valid_author(""), false);
+        assert_eq!(
+            author_from_file_name(day!(7), "07_alice.rs"),
+            Some("alice".into())
+        );
+        assert_eq!(author_from_file_name(day!(7), "07.rs"), None);
+        assert_eq!(author_from_file_name(day!(7), "17_alice.rs"), None);
+        assert_eq!(author_from_file_name(day!(7), "scratch_07.rs"), None);
+    }
+
+    #[test]
+    fn compares_runs() {
+        let runs = [
+            get_mock_run("alice", "12", "1.5ms"),
+            get_mock_run("bob", "13", "900µs"),
+        ];
+        assert_eq!(runs[0].answers[0], Some("12".into()));
+        assert_eq!(runs[0].times[0], Some("1.5ms".into()));
+
+        assert_eq!(answers_match(&runs, Part::One), Some(false));
+        assert_eq!(answers_match(&runs, Part::Two), None);
+        assert_eq!(answers_match(&runs, Part::Three), Some(true));
+        assert_eq!(fastest(&runs, Part::One), Some("bob"));
+        assert_eq!(fastest(&runs, Part::Two), None);
+    }
+}
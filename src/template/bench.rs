@@ -0,0 +1,140 @@
+/// The criterion harness behind `cargo ec bench`.
+///
+/// The harness in `benches/quests.rs` includes the solution binaries as modules and benchmarks
+/// the [`Solution`](crate::template::Solution) that `solution!` implements for each of them, on
+/// the real inputs. It is regenerated on every run, so it always covers all scaffolded quests.
+use std::fmt::Write;
+
+use crate::template::{run_multi::get_path_for_bin, Day};
+
+pub const BENCH_NAME: &str = "quests";
+pub const HARNESS_PATH: &str = "benches/quests.rs";
+
+const CRITERION: &str = "criterion = \"0.5\"";
+
+/// The manifest with criterion and the harness added, or `None` if both are set up already.
+pub fn with_bench_target(manifest: &str) -> Option<String> {
+    let table: toml::Table = manifest.parse().ok()?;
+
+    let has_criterion = table
+        .get("dev-dependencies")
+        .and_then(|deps| deps.get("criterion"))
+        .is_some();
+    let has_bench = table
+        .get("bench")
+        .and_then(|benches| benches.as_array())
+        .is_some_and(|benches| {
+            benches
+                .iter()
+                .any(|bench| bench.get("name").and_then(|n| n.as_str()) == Some(BENCH_NAME))
+        });
+
+    if has_criterion && has_bench {
+        return None;
+    }
+
+    let mut manifest = manifest.trim_end().to_string();
+    if !has_criterion {
+        if let Some(i) = manifest.find("[dev-dependencies]") {
+            manifest.insert_str(i + "[dev-dependencies]".len(), &format!("\n{CRITERION}"));
+        } else {
+            manifest.push_str(&format!("\n\n[dev-dependencies]\n{CRITERION}"));
+        }
+    }
+    if !has_bench {
+        manifest.push_str(&format!(
+            "\n\n[[bench]]\nname = \"{BENCH_NAME}\"\nharness = false"
+        ));
+    }
+    manifest.push('\n');
+
+    Some(manifest)
+}
+
+/// Whether a solution binary is written with `solution!`, which the harness relies on.
+pub fn uses_solution_macro(source: &str) -> bool {
+    source.lines().any(|line| {
+        let line = line.trim_start();
+        !line.starts_with("//") && line.contains("solution!(")
+    })
+}
+
+/// The source of the harness, benchmarking each part of `days` that has an input.
+pub fn harness(days: &[Day]) -> String {
+    let mut modules = String::new();
+    let mut calls = String::new();
+
+    for day in days {
+        let path = get_path_for_bin(*day);
+        let path = path.trim_start_matches("./");
+        let _ = writeln!(modules, "#[allow(dead_code)]");
+        let _ = writeln!(modules, "#[path = \"../{path}\"]");
+        let _ = writeln!(modules, "mod day{day};\n");
+        let _ = writeln!(calls, "    bench::<day{day}::__Solution>(c);");
+    }
+
+    format!(
+        r#"//! Generated by `cargo ec bench`, which overwrites this file on every run.
+
+use std::{{fs, hint::black_box}};
+
+use criterion::{{criterion_group, criterion_main, Criterion}};
+use everybody_codes::template::{{read_only, runner, Solution}};
+
+{modules}fn bench<S: Solution>(c: &mut Criterion) {{
+    let mut group = c.benchmark_group(format!("day {{}}", S::DAY));
+    for &part in S::PARTS {{
+        let file = format!("{{}}/{{}}-{{part}}.txt", runner::input_folder(), S::DAY);
+        let Ok(input) = fs::read(read_only::quest_file(file)) else {{
+            continue;
+        }};
+
+        let id = format!("part {{part}}");
+        match std::str::from_utf8(&input) {{
+            Ok(text) => group.bench_function(id, |b| {{
+                b.iter(|| S::part(part, S::parse(black_box(text))))
+            }}),
+            Err(_) => group.bench_function(id, |b| {{
+                b.iter(|| S::part(part, S::parse_bytes(black_box(&input))))
+            }}),
+        }};
+    }}
+    group.finish();
+}}
+
+fn quests(c: &mut Criterion) {{
+{calls}}}
+
+criterion_group!(benches, quests);
+criterion_main!(benches);
+"#
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{uses_solution_macro, with_bench_target};
+
+    #[test]
+    fn adds_bench_target() {
+        let manifest = "[package]\nname = \"ec\"\n\n[dependencies]\ntoml = \"1\"\n";
+        let updated = with_bench_target(manifest).unwrap();
+        assert!(updated.contains("[dev-dependencies]\ncriterion = \"0.5\""));
+        assert!(updated.ends_with("[[bench]]\nname = \"quests\"\nharness = false\n"));
+        assert_eq!(with_bench_target(&updated), None);
+
+        let manifest = "[dev-dependencies]\nproptest = \"1\"\n";
+        let updated = with_bench_target(manifest).unwrap();
+        assert!(updated.starts_with("[dev-dependencies]\ncriterion = \"0.5\"\nproptest"));
+    }
+
+    #[test]
+    fn detects_solution_macro() {
+        assert!(uses_solution_macro("everybody_codes::solution!(7);"));
+        assert!(!uses_solution_macro(
+            "// everybody_codes::solution!(7);\nfn main() {}"
+        ));
+    }
+}
@@ -0,0 +1,110 @@
+/// Compile time of each quest binary, to find the quests that drag down build times, e.g.
+/// through heavy dependencies or generic bloat.
+///
+/// Dependencies and the library are built first, then every quest binary is rebuilt on its
+/// own, so each measurement is the time spent compiling that quest.
+use std::time::Duration;
+
+use crate::template::Day;
+
+/// Quests that take this many times the median to build are flagged as slow.
+const SLOW_FACTOR: u32 = 2;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BuildTime {
+    pub day: Day,
+    pub duration: Duration,
+}
+
+/// Sort build times slowest first.
+pub fn rank(times: &mut [BuildTime]) {
+    times.sort_by(|a, b| b.duration.cmp(&a.duration).then(a.day.cmp(&b.day)));
+}
+
+pub fn median(times: &[BuildTime]) -> Option<Duration> {
+    let mut durations: Vec<Duration> = times.iter().map(|t| t.duration).collect();
+    durations.sort_unstable();
+
+    match durations.len() {
+        0 => None,
+        n if n % 2 == 0 => Some((durations[n / 2 - 1] + durations[n / 2]) / 2),
+        n => Some(durations[n / 2]),
+    }
+}
+
+pub fn is_slow(time: &BuildTime, median: Duration) -> bool {
+    time.duration > median * SLOW_FACTOR
+}
+
+/// Render build times as a table, slowest first, with each quest's share of the total.
+pub fn render(times: &[BuildTime]) -> String {
+    let mut times = times.to_vec();
+    rank(&mut times);
+
+    let total: Duration = times.iter().map(|t| t.duration).sum();
+    let median = median(&times).unwrap_or_default();
+
+    let mut s = String::from("| Day | Build time | Share |\n| :---: | ---: | ---: |\n");
+    for time in &times {
+        let share = if total.is_zero() {
+            0.0
+        } else {
+            time.duration.as_secs_f64() / total.as_secs_f64() * 100.0
+        };
+        let flag = if is_slow(time, median) { " 🐢" } else { "" };
+        s.push_str(&format!(
+            "| {} | {:.2}s{flag} | {share:.1}% |\n",
+            time.day,
+            time.duration.as_secs_f64()
+        ));
+    }
+    s.push_str(&format!("\n**Total: {:.2}s**\n", total.as_secs_f64()));
+    s
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{is_slow, median, rank, render, BuildTime};
+    use crate::template::Day;
+
+    fn get_mock_times() -> Vec<BuildTime> {
+        [(1, 1_000), (2, 5_000), (3, 1_200), (4, 900)]
+            .into_iter()
+            .map(|(d, ms)| BuildTime {
+                day: Day::new(d).unwrap(),
+                duration: Duration::from_millis(ms),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ranks_slowest_first() {
+        let mut times = get_mock_times();
+        rank(&mut times);
+        let days: Vec<String> = times.iter().map(|t| t.day.to_string()).collect();
+        assert_eq!(days, vec!["02", "03", "01", "04"]);
+    }
+
+    #[test]
+    fn flags_slow_quests() {
+        let times = get_mock_times();
+        let median = median(&times).unwrap();
+        assert_eq!(median, Duration::from_millis(1_100));
+
+        let slow: Vec<bool> = times.iter().map(|t| is_slow(t, median)).collect();
+        assert_eq!(slow, vec![false, true, false, false]);
+        assert_eq!(super::median(&[]), None);
+    }
+
+    #[test]
+    fn renders_table() {
+        let table = render(&get_mock_times());
+        assert!(table.contains("| 02 | 5.00s 🐢 | 61.7% |"));
+        assert!(table.contains("| 04 | 0.90s | 11.1% |"));
+        assert!(table.contains("**Total: 8.10s**"));
+    }
+}
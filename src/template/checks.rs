@@ -0,0 +1,208 @@
+/// Sanity checks for answers, declared in the quest module and run as soon as a part
+/// returns, to catch overflows or answers that are off by orders of magnitude before they
+/// are submitted:
+///
+/// ```ignore
+/// use everybody_codes::template::{
+///     checks::{Check, Rule},
+///     Part,
+/// };
+///
+/// everybody_codes::solution!(7, checks = checks);
+///
+/// fn checks() -> Vec<Check> {
+///     vec![
+///         Check::all(Rule::LessThan(10i128.pow(15))),
+///         Check::part(Part::Three, Rule::GreaterThanPart(Part::Two)),
+///     ]
+/// }
+/// ```
+///
+/// A part that fails a check is reported and not submitted.
+use std::sync::{Mutex, OnceLock};
+
+use crate::template::Part;
+
+/// A rule an answer has to satisfy. Numeric rules fail for answers that are not integers.
+#[derive(Clone, Debug)]
+pub enum Rule {
+    LessThan(i128),
+    GreaterThan(i128),
+    /// Greater than the answer of another part of the same run.
+    GreaterThanPart(Part),
+    /// Less than the answer of another part of the same run.
+    LessThanPart(Part),
+    /// A named predicate on the answer.
+    Custom(&'static str, fn(&str) -> bool),
+}
+
+#[derive(Clone, Debug)]
+pub struct Check {
+    /// The part the rule applies to, or all parts.
+    part: Option<Part>,
+    rule: Rule,
+}
+
+impl Check {
+    pub fn part(part: Part, rule: Rule) -> Self {
+        Check {
+            part: Some(part),
+            rule,
+        }
+    }
+
+    pub fn all(rule: Rule) -> Self {
+        Check { part: None, rule }
+    }
+}
+
+pub type Checks = fn() -> Vec<Check>;
+
+static CHECKS: OnceLock<Checks> = OnceLock::new();
+static ANSWERS: Mutex<[Option<String>; 3]> = Mutex::new([None, None, None]);
+
+/// Register the checks of the quest. Only the first registration takes effect.
+pub fn register(checks: Checks) {
+    let _ = CHECKS.set(checks);
+}
+
+fn parse(answer: &str) -> Result<i128, String> {
+    answer
+        .trim()
+        .parse()
+        .map_err(|_| format!("answer `{answer}` is not an integer"))
+}
+
+impl Rule {
+    /// Check `answer`, given the answers of the parts that ran before. Rules comparing with
+    /// a part that did not run pass.
+    fn verify(&self, answer: &str, answers: &[Option<String>; 3]) -> Result<(), String> {
+        let other = |part: Part| answers[part.index()].as_deref();
+
+        match self {
+            Rule::LessThan(max) => {
+                let value = parse(answer)?;
+                if value < *max {
+                    return Ok(());
+                }
+                Err(format!("{value} is not less than {max}"))
+            }
+            Rule::GreaterThan(min) => {
+                let value = parse(answer)?;
+                if value > *min {
+                    return Ok(());
+                }
+                Err(format!("{value} is not greater than {min}"))
+            }
+            Rule::GreaterThanPart(part) => {
+                let Some(other) = other(*part) else {
+                    return Ok(());
+                };
+                let (value, other) = (parse(answer)?, parse(other)?);
+                if value > other {
+                    return Ok(());
+                }
+                Err(format!(
+                    "{value} is not greater than the answer of part {part} ({other})"
+                ))
+            }
+            Rule::LessThanPart(part) => {
+                let Some(other) = other(*part) else {
+                    return Ok(());
+                };
+                let (value, other) = (parse(answer)?, parse(other)?);
+                if value < other {
+                    return Ok(());
+                }
+                Err(format!(
+                    "{value} is not less than the answer of part {part} ({other})"
+                ))
+            }
+            Rule::Custom(name, predicate) => {
+                if predicate(answer) {
+                    return Ok(());
+                }
+                Err(format!("answer `{answer}` fails `{name}`"))
+            }
+        }
+    }
+}
+
+/// All failures of the checks applying to `part`.
+pub fn evaluate(
+    checks: &[Check],
+    part: Part,
+    answer: &str,
+    answers: &[Option<String>; 3],
+) -> Vec<String> {
+    checks
+        .iter()
+        .filter(|check| check.part.is_none_or(|p| p == part))
+        .filter_map(|check| check.rule.verify(answer, answers).err())
+        .collect()
+}
+
+/// Run the registered checks for the answer of `part` and remember the answer for checks
+/// of later parts.
+pub(crate) fn verify(part: Part, answer: &str) -> Vec<String> {
+    let mut answers = ANSWERS.lock().unwrap();
+
+    let failures = match CHECKS.get() {
+        Some(checks) => evaluate(&checks(), part, answer, &answers),
+        None => vec![],
+    };
+
+    answers[part.index()] = Some(answer.to_string());
+
+    failures
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{evaluate, Check, Rule};
+    use crate::template::Part;
+
+    fn get_mock_checks() -> Vec<Check> {
+        vec![
+            Check::all(Rule::LessThan(1_000)),
+            Check::part(Part::Two, Rule::GreaterThanPart(Part::One)),
+            Check::part(
+                Part::Three,
+                Rule::Custom("even", |a| a.ends_with(['0', '2', '4', '6', '8'])),
+            ),
+        ]
+    }
+
+    #[test]
+    fn passes_sane_answers() {
+        let answers = [Some("10".to_string()), None, None];
+        assert!(evaluate(&get_mock_checks(), Part::One, "10", &answers).is_empty());
+        assert!(evaluate(&get_mock_checks(), Part::Two, "20", &answers).is_empty());
+        assert!(evaluate(&get_mock_checks(), Part::Three, "42", &answers).is_empty());
+    }
+
+    #[test]
+    fn reports_failures() {
+        let answers = [Some("10".to_string()), None, None];
+        assert_eq!(
+            evaluate(&get_mock_checks(), Part::Two, "5", &answers),
+            vec!["5 is not greater than the answer of part 1 (10)"]
+        );
+        assert_eq!(
+            evaluate(&get_mock_checks(), Part::Three, "1001", &answers),
+            vec!["1001 is not less than 1000", "answer `1001` fails `even`"]
+        );
+        assert_eq!(
+            evaluate(&get_mock_checks(), Part::One, "-", &answers),
+            vec!["answer `-` is not an integer"]
+        );
+    }
+
+    #[test]
+    fn skips_parts_that_did_not_run() {
+        let answers = [None, None, None];
+        assert!(evaluate(&get_mock_checks(), Part::Two, "5", &answers).is_empty());
+    }
+}
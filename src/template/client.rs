@@ -0,0 +1,290 @@
+/// Native client for the Everybody Codes API, used instead of ec-cli with the `http_client`
+/// feature.
+///
+/// Inputs and descriptions are served encrypted with AES-256-CBC. The key of a part is
+/// returned by the quest endpoint once the previous part is solved. Requests authenticate
+/// with the session cookie from `EC_TOKEN`, or the `token` file in the global config
+/// directory.
+use std::{collections::HashMap, env, fs};
+
+use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, KeyIvInit};
+use reqwest::blocking::{Client, RequestBuilder};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    all_parts, dirs::config_dir, ec_cli::EcCommandError, html, urls, Day, Event, Part,
+};
+
+type Aes256CbcDec = cbc::Decryptor<aes::Aes256>;
+
+static TOKEN_ENV: &str = "EC_TOKEN";
+static TOKEN_FILE: &str = "token";
+static USER_AGENT: &str = "github.com/finnhartshorn/everybody-codes-rust";
+
+/// The session cookie, from `EC_TOKEN` first and the token file second.
+pub fn token() -> Result<String, EcCommandError> {
+    env::var(TOKEN_ENV)
+        .ok()
+        .or_else(|| fs::read_to_string(config_dir()?.join(TOKEN_FILE)).ok())
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+        .ok_or(EcCommandError::MissingToken)
+}
+
+/// Fetch a part and write its input, sample, sample answer and description to the given paths.
+/// Fails if the part is not unlocked yet.
+pub fn fetch_part(
+    day: Day,
+    part: Part,
+    input_path: &str,
+    sample_path: &str,
+    sample_answer_path: &str,
+    description_path: &str,
+) -> Result<(), EcCommandError> {
+    let client = Client::new();
+    let event = Event::current();
+
+    let key = quest_key(&client, event, day, part)?;
+    let seed = get_json(client.get(urls::api_me()))?;
+    let seed = to_plain_string(field(&seed, "seed")?);
+
+    let inputs = get_json(client.get(urls::cdn_inputs(event, day, &seed)))?;
+    let input = decrypt(field_str(&inputs, &part.to_string())?, &key)?;
+
+    let description = description(&client, event, day, part, &key)?;
+
+    write(input_path, &input)?;
+    write(description_path, &description)?;
+    if let Some(sample) = extract_sample(&description) {
+        write(sample_path, &sample)?;
+    }
+    if let Some(answer) = extract_sample_answer(&description) {
+        write(sample_answer_path, &answer)?;
+    }
+
+    Ok(())
+}
+
+/// The descriptions of all unlocked parts of a quest, as plain text.
+pub fn read(day: Day) -> Result<String, EcCommandError> {
+    let client = Client::new();
+    let event = Event::current();
+
+    let mut text = String::new();
+    for part in all_parts() {
+        // later parts are locked until the previous one is solved.
+        let key = match quest_key(&client, event, day, part) {
+            Ok(key) => key,
+            Err(_) if part != Part::One => break,
+            Err(e) => return Err(e),
+        };
+        let description = description(&client, event, day, part, &key)?;
+        text.push_str(&html::to_text(&description));
+        text.push('\n');
+    }
+
+    Ok(text)
+}
+
+/// The answers the site accepted for each part of a quest, `None` for unsolved parts.
+pub fn accepted_answers(day: Day) -> Result<[Option<String>; 3], EcCommandError> {
+    let client = Client::new();
+    let quest = get_json(client.get(urls::api_quest(Event::current(), day)))?;
+    Ok(Part::ALL.map(|part| {
+        field(&quest, &format!("answer{part}"))
+            .ok()
+            .filter(|answer| !matches!(answer, JsonValue::Null))
+            .map(to_plain_string)
+    }))
+}
+
+/// Submit an answer and print the verdict. Returns whether the answer was correct.
+pub fn submit(day: Day, part: Part, answer: &str) -> Result<bool, EcCommandError> {
+    let client = Client::new();
+    let mut body = HashMap::new();
+    body.insert("answer".to_string(), JsonValue::String(answer.to_string()));
+    let body = JsonValue::Object(body).stringify().unwrap_or_default();
+
+    let response = get_json(
+        client
+            .post(urls::api_answer(Event::current(), day, part))
+            .header("Content-Type", "application/json")
+            .body(body),
+    )?;
+
+    let is_true = |key: &str| matches!(field(&response, key), Ok(JsonValue::Boolean(true)));
+
+    if is_true("correct") {
+        println!("✅ Part {part}: `{answer}` is correct!");
+        return Ok(true);
+    }
+
+    println!("❌ Part {part}: `{answer}` is not correct.");
+    println!(
+        "   Length is {}, first character is {}.",
+        if is_true("lengthCorrect") {
+            "correct"
+        } else {
+            "wrong"
+        },
+        if is_true("firstCorrect") {
+            "correct"
+        } else {
+            "wrong"
+        },
+    );
+    Ok(false)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn quest_key(
+    client: &Client,
+    event: Event,
+    day: Day,
+    part: Part,
+) -> Result<String, EcCommandError> {
+    let keys = get_json(client.get(urls::api_quest(event, day)))?;
+    field_str(&keys, &format!("key{part}"))
+        .map(ToString::to_string)
+        .map_err(|_| EcCommandError::Request(format!("part {part} is not unlocked yet.")))
+}
+
+fn description(
+    client: &Client,
+    event: Event,
+    day: Day,
+    part: Part,
+    key: &str,
+) -> Result<String, EcCommandError> {
+    let descriptions = get_json(client.get(urls::cdn_descriptions(event, day)))?;
+    decrypt(field_str(&descriptions, &part.to_string())?, key)
+}
+
+fn get_json(request: RequestBuilder) -> Result<JsonValue, EcCommandError> {
+    let response = request
+        .header("Cookie", format!("everybody-codes={}", token()?))
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .map_err(|e| {
+            if e.is_connect() || e.is_timeout() {
+                EcCommandError::Unreachable(e.to_string())
+            } else {
+                EcCommandError::Request(e.to_string())
+            }
+        })?;
+
+    let body = response
+        .text()
+        .map_err(|e| EcCommandError::Request(e.to_string()))?;
+    body.parse()
+        .map_err(|_| EcCommandError::Request("response is not valid JSON.".into()))
+}
+
+/// Strings without quotes, and integral numbers without a fraction.
+fn to_plain_string(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Number(n) => n.to_string(),
+        _ => value.stringify().unwrap_or_default(),
+    }
+}
+
+fn field<'a>(json: &'a JsonValue, key: &str) -> Result<&'a JsonValue, EcCommandError> {
+    json.get::<HashMap<String, JsonValue>>()
+        .and_then(|map| map.get(key))
+        .ok_or_else(|| EcCommandError::Request(format!("response has no `{key}`.")))
+}
+
+fn field_str<'a>(json: &'a JsonValue, key: &str) -> Result<&'a str, EcCommandError> {
+    field(json, key)?
+        .get::<String>()
+        .map(String::as_str)
+        .ok_or_else(|| EcCommandError::Request(format!("`{key}` is not a string.")))
+}
+
+fn write(path: &str, contents: &str) -> Result<(), EcCommandError> {
+    fs::write(path, contents).map_err(|e| EcCommandError::Request(format!("{path}: {e}")))
+}
+
+/// Decrypt hex encoded AES-256-CBC data. The key is used as is, its first 16 bytes are the IV.
+fn decrypt(hex: &str, key: &str) -> Result<String, EcCommandError> {
+    let invalid = || EcCommandError::Request("could not decrypt the response.".into());
+
+    let key = key.as_bytes();
+    if key.len() != 32 || !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return Err(invalid());
+    }
+
+    let data = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| invalid())?;
+
+    let plain = Aes256CbcDec::new(key.into(), key[..16].into())
+        .decrypt_padded_vec_mut::<Pkcs7>(&data)
+        .map_err(|_| invalid())?;
+    String::from_utf8(plain).map_err(|_| invalid())
+}
+
+/// The first `<pre>` block of a description, which holds the example.
+fn extract_sample(html: &str) -> Option<String> {
+    let start = html.find("<pre")?;
+    let start = start + html[start..].find('>')? + 1;
+    let end = start + html[start..].find("</pre>")?;
+    Some(
+        html::to_text(&html[start..end])
+            .trim_matches('\n')
+            .to_string(),
+    )
+}
+
+/// The answer of the first example, which the descriptions give as the last `<code>` after it.
+fn extract_sample_answer(html: &str) -> Option<String> {
+    let after = &html[html.find("</pre>")? + "</pre>".len()..];
+    let start = after.rfind("<code")?;
+    let start = start + after[start..].find('>')? + 1;
+    let end = start + after[start..].find("</code>")?;
+    let answer = html::to_text(&after[start..end]).trim().to_string();
+    (!answer.is_empty()).then_some(answer)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use aes::cipher::{block_padding::Pkcs7, BlockEncryptMut, KeyIvInit};
+
+    use super::{decrypt, extract_sample, extract_sample_answer};
+
+    #[test]
+    fn decrypts_hex() {
+        let key = "0123456789abcdef0123456789abcdef";
+        let data =
+            cbc::Encryptor::<aes::Aes256>::new(key.as_bytes().into(), key[..16].as_bytes().into())
+                .encrypt_padded_vec_mut::<Pkcs7>(b"A:1,2\nB:3");
+        let hex: String = data.iter().map(|b| format!("{b:02x}")).collect();
+
+        assert_eq!(decrypt(&hex, key).unwrap(), "A:1,2\nB:3");
+        assert!(decrypt(&hex, "too short").is_err());
+        assert!(decrypt("zz", key).is_err());
+    }
+
+    #[test]
+    fn extracts_sample() {
+        let html = "<p>Example:</p><pre class=\"note\">\n#.&lt;\n<b>..</b>\n</pre><pre>2</pre>";
+        assert_eq!(extract_sample(html), Some("#.<\n..".into()));
+        assert_eq!(extract_sample("<p>no example</p>"), None);
+    }
+
+    #[test]
+    fn extracts_sample_answer() {
+        let html = "<p>Take <code>a</code>:</p><pre class=\"note\">a-b</pre>\
+            <p>Counting <code>b</code> too, the total is <code><b>12</b></code>.</p>";
+        assert_eq!(extract_sample_answer(html), Some("12".into()));
+        assert_eq!(extract_sample_answer("<pre>1</pre><p>no answer</p>"), None);
+        assert_eq!(extract_sample_answer("<p><code>3</code></p>"), None);
+    }
+}
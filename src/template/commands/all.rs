@@ -1,5 +1,34 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{num::NonZeroUsize, thread};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days_for, json_output, run_multi::run_multi, runner, Day, DaySet, Event,
+};
+
+pub fn handle(
+    is_release: bool,
+    days: Option<DaySet>,
+    from: Option<Day>,
+    timeout: Option<u64>,
+    jobs: Option<NonZeroUsize>,
+    json: bool,
+) {
+    if json {
+        json_output::enable();
+    }
+    if let Some(secs) = timeout {
+        runner::set_timeout(secs);
+    }
+
+    let mut days_to_run = all_days_for(Event::current()).collect();
+    if let Some(days) = days {
+        days_to_run = days.filter(days_to_run);
+    }
+    if let Some(from) = from {
+        days_to_run.retain(|day| *day >= from);
+    }
+
+    let jobs = jobs
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get);
+    run_multi(&days_to_run, is_release, false, jobs);
 }
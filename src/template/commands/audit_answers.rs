@@ -0,0 +1,78 @@
+use std::{path::Path, process};
+
+use crate::template::{
+    all_days, all_parts,
+    answers::{audit, read_real_answer, AuditStatus},
+    client, ec_cli,
+    run_multi::get_path_for_bin,
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+pub fn handle() {
+    ec_cli::require();
+
+    let days: Vec<Day> = all_days()
+        .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+        .collect();
+
+    if days.is_empty() {
+        eprintln!("No scaffolded days found. Run `cargo scaffold <day>` first.");
+        process::exit(1);
+    }
+
+    println!("{ANSI_BOLD}Day   Part  Status{ANSI_RESET}");
+    println!("----------------------------");
+
+    let (mut problems, mut failures) = (0, 0);
+    for day in days {
+        let remote = match client::accepted_answers(day) {
+            Ok(remote) => remote,
+            Err(e) => {
+                println!("{day}    -     ❌ {e}");
+                failures += 1;
+                continue;
+            }
+        };
+
+        for (part, remote) in all_parts().zip(remote) {
+            let local = read_real_answer(day, part);
+            let Some(status) = audit(local.as_deref(), remote.as_deref()) else {
+                continue;
+            };
+
+            if status != AuditStatus::Match {
+                problems += 1;
+            }
+
+            let status = match status {
+                AuditStatus::Match => "✅ matches the site".to_string(),
+                AuditStatus::LocalOnly => {
+                    "⚠️  recorded locally, but not solved on the site".to_string()
+                }
+                AuditStatus::RemoteOnly => format!(
+                    "⚠️  solved on the site as `{}`, but not recorded locally",
+                    remote.unwrap_or_default()
+                ),
+                AuditStatus::Mismatch => format!(
+                    "❌ recorded `{}`, but the site accepted `{}`",
+                    local.unwrap_or_default(),
+                    remote.unwrap_or_default()
+                ),
+            };
+
+            println!("{day}    {part}     {status}");
+        }
+    }
+
+    println!();
+    if problems > 0 {
+        eprintln!("{problems} record(s) do not match the site, check data/real_answers.");
+    }
+    if failures > 0 {
+        eprintln!("{failures} quest(s) could not be checked.");
+    }
+    if problems + failures > 0 {
+        process::exit(1);
+    }
+    println!("All recorded answers match the site.");
+}
@@ -0,0 +1,68 @@
+use std::{
+    fs,
+    path::Path,
+    process::{self, Command},
+};
+
+use crate::template::{
+    all_days,
+    bench::{harness, uses_solution_macro, with_bench_target, BENCH_NAME, HARNESS_PATH},
+    read_only,
+    run_multi::get_path_for_bin,
+    Day,
+};
+
+pub fn handle(day: Option<Day>) {
+    read_only::refuse("generate the benchmark harness");
+
+    let days: Vec<Day> = all_days()
+        .filter(|day| {
+            fs::read_to_string(get_path_for_bin(*day)).is_ok_and(|src| uses_solution_macro(&src))
+        })
+        .collect();
+
+    if let Some(day) = day {
+        if !days.contains(&day) {
+            eprintln!("Day {day} has no solution written with `solution!` to benchmark.");
+            process::exit(1);
+        }
+    } else if days.is_empty() {
+        eprintln!("No quests to benchmark, scaffold one with `cargo scaffold <day>`.");
+        process::exit(1);
+    }
+
+    let manifest = fs::read_to_string("Cargo.toml").unwrap_or_default();
+    if let Some(manifest) = with_bench_target(&manifest) {
+        if let Err(e) = fs::write("Cargo.toml", manifest) {
+            eprintln!("Failed to add the benchmark to Cargo.toml: {e}");
+            process::exit(1);
+        }
+        println!("Added criterion and the `{BENCH_NAME}` benchmark to Cargo.toml.");
+    }
+
+    let path = Path::new(HARNESS_PATH);
+    let written = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, harness(&days)));
+    if let Err(e) = written {
+        eprintln!("Failed to write \"{HARNESS_PATH}\": {e}");
+        process::exit(1);
+    }
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["bench", "--bench", BENCH_NAME]);
+    if let Some(day) = day {
+        // criterion filters benchmarks by their `day 07/part 1` ids.
+        cmd.args(["--", &format!("day {day}/")]);
+    }
+
+    match cmd.status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to run `cargo bench`: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::{
+    fs::File,
+    path::Path,
+    process::{self, Command},
+    time::{Instant, SystemTime},
+};
+
+use crate::template::{
+    all_days,
+    authors::bin_name,
+    build_times::{self, BuildTime},
+    run_multi::get_path_for_bin,
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+pub fn handle(release: bool) {
+    let days: Vec<Day> = all_days()
+        .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+        .collect();
+
+    if days.is_empty() {
+        eprintln!("No scaffolded quests to build.");
+        process::exit(1);
+    }
+
+    let mut args = vec!["build", "--quiet", "--lib"];
+    if release {
+        args.push("--release");
+    }
+
+    println!("Building dependencies...");
+    if !cargo(&args) {
+        eprintln!("Build failed.");
+        process::exit(1);
+    }
+
+    let mut times = vec![];
+    for day in days {
+        let bin = bin_name(day, None);
+        print!("Building day {day}...");
+
+        // bump the modification time so cargo rebuilds the binary.
+        let touched = File::options()
+            .append(true)
+            .open(get_path_for_bin(day))
+            .and_then(|file| file.set_modified(SystemTime::now()));
+        if let Err(e) = touched {
+            println!();
+            eprintln!("Failed to touch {}: {e}", get_path_for_bin(day));
+            process::exit(1);
+        }
+
+        let mut args = vec!["build", "--quiet", "--bin", &bin];
+        if release {
+            args.push("--release");
+        }
+
+        let start = Instant::now();
+        if !cargo(&args) {
+            println!();
+            eprintln!("Build of day {day} failed.");
+            process::exit(1);
+        }
+        let duration = start.elapsed();
+
+        println!("\rBuilt day {day} in {:.2}s", duration.as_secs_f64());
+        times.push(BuildTime { day, duration });
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Build times{ANSI_RESET}");
+    println!("------");
+    print!("{}", build_times::render(&times));
+}
+
+fn cargo(args: &[&str]) -> bool {
+    Command::new("cargo")
+        .args(args)
+        .status()
+        .is_ok_and(|status| status.success())
+}
@@ -0,0 +1,87 @@
+use std::{
+    io::{BufRead, BufReader},
+    path::Path,
+    process::{self, Command, Stdio},
+};
+
+use crate::template::{
+    all_parts,
+    authors::{answers_match, bin_name, fastest, get_path_for_author_bin, list_authors, AuthorRun},
+    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+pub fn handle(day: Day, authors: Vec<String>) {
+    let authors = if authors.is_empty() {
+        list_authors(day)
+    } else {
+        authors
+    };
+
+    if authors.len() < 2 {
+        eprintln!(
+            "Need at least two authors to compare, found {:?}. Create one with `cargo scaffold {day} --author <name>`.",
+            authors
+        );
+        process::exit(1);
+    }
+
+    let runs: Vec<AuthorRun> = authors
+        .iter()
+        .map(|author| {
+            if !Path::new(&get_path_for_author_bin(day, Some(author))).exists() {
+                eprintln!("No solution for day {day} by {author}.");
+                process::exit(1);
+            }
+            println!("Running {}...", bin_name(day, Some(author)));
+            AuthorRun::from_output(author, day, &run_bin(&bin_name(day, Some(author))))
+        })
+        .collect();
+
+    let width = authors.iter().map(String::len).max().unwrap_or(0);
+
+    println!();
+    println!("{ANSI_BOLD}Day {day}: {}{ANSI_RESET}", authors.join(" vs "));
+    println!("------");
+
+    for part in all_parts() {
+        println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+        let fastest = fastest(&runs, part);
+
+        for run in &runs {
+            let index = part.index();
+            println!(
+                "  {:<width$}  {}  {ANSI_ITALIC}{}{ANSI_RESET}{}",
+                run.author,
+                run.answers[index].as_deref().unwrap_or("✖"),
+                run.times[index].as_deref().unwrap_or("-"),
+                if fastest == Some(run.author.as_str()) {
+                    " ⚡"
+                } else {
+                    ""
+                }
+            );
+        }
+
+        match answers_match(&runs, part) {
+            Some(true) => println!("  ✅ answers match"),
+            Some(false) => println!("  ❌ answers differ"),
+            None => {}
+        }
+    }
+}
+
+/// Benchmark a solution binary, capturing its output.
+fn run_bin(bin: &str) -> Vec<String> {
+    let mut cmd = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--bin", bin, "--", "--time"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    let stdout = BufReader::new(cmd.stdout.take().unwrap());
+    let output: Vec<String> = stdout.lines().map_while(Result::ok).collect();
+    cmd.wait().unwrap();
+
+    output
+}
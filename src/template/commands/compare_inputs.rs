@@ -0,0 +1,45 @@
+use std::process;
+
+use crate::template::{
+    data_dir,
+    input_stats::{notes, render, InputStats},
+    inputs, Day, Part, ANSI_BOLD, ANSI_RESET,
+};
+
+pub fn handle(day: Day, part: Option<Part>) {
+    let parts = part.map_or(Part::ALL.to_vec(), |part| vec![part]);
+    let mut compared = 0;
+
+    for part in parts {
+        let read = |folder: &str| {
+            inputs::read_to_string(&data_dir().join(folder).join(format!("{day}-{part}.txt")))
+                .ok()
+                .filter(|s| !s.trim().is_empty())
+        };
+
+        let (Some(sample), Some(input)) = (read("samples"), read("inputs")) else {
+            println!("Part {part}: sample or input is missing, skipping.");
+            continue;
+        };
+
+        if compared > 0 {
+            println!();
+        }
+        compared += 1;
+
+        let sample = InputStats::from_input(&sample);
+        let input = InputStats::from_input(&input);
+
+        println!("{ANSI_BOLD}Day {day} Part {part}{ANSI_RESET}");
+        println!("------");
+        print!("{}", render(&sample, &input));
+        for note in notes(&sample, &input) {
+            println!("⚠️  {note}");
+        }
+    }
+
+    if compared == 0 {
+        eprintln!("Nothing to compare, download the quest with `cargo download {day}` first.");
+        process::exit(1);
+    }
+}
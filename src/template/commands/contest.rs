@@ -0,0 +1,219 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    process::{self, Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use crate::template::answers::{read_real_answer, store_real_answer};
+use crate::template::contest::{format_elapsed, Attempt, Session};
+use crate::template::run_multi::{child_commands::parse_answer, get_path_for_bin};
+use crate::template::{
+    all_parts, authors::bin_name, ec_cli, read_only, submissions, Day, Part, ANSI_BOLD,
+    ANSI_ITALIC, ANSI_RESET,
+};
+
+pub fn handle(day: Day, max_tries: usize, review: bool, reset: bool) {
+    read_only::refuse("run a contest");
+
+    if reset {
+        match Session::remove_file(day) {
+            Ok(()) => println!("Removed contest session for day {day}."),
+            Err(_) => println!("No contest session for day {day}."),
+        }
+        return;
+    }
+
+    if review {
+        match Session::read_from_file(day) {
+            Some(session) => print_review(&session),
+            None => println!("No contest session for day {day}."),
+        }
+        return;
+    }
+
+    if !std::path::Path::new(&get_path_for_bin(day)).exists() {
+        eprintln!("Day {day} has not been scaffolded yet. Run `cargo scaffold {day}` first.");
+        process::exit(1);
+    }
+
+    let mut session = Session::read_from_file(day).unwrap_or_else(|| {
+        println!("⏱️  Starting contest session for day {day}. Good luck!");
+        Session::new(day)
+    });
+    store(&session);
+
+    let Some(part) = session.current_part() else {
+        println!("All parts solved.");
+        print_review(&session);
+        return;
+    };
+
+    let tries = session.tries(part);
+    if tries >= max_tries {
+        println!("🔒 Out of tries for part {part} ({tries}/{max_tries}).");
+        print_review(&session);
+        return;
+    }
+
+    println!(
+        "{ANSI_BOLD}Day {day} Part {part}{ANSI_RESET} {ANSI_ITALIC}({} elapsed, try {}/{max_tries}){ANSI_RESET}",
+        format_elapsed(session.elapsed_secs()),
+        tries + 1
+    );
+
+    let Some(answer) = run_part(day, part, None) else {
+        println!("No answer for part {part} yet, keep going.");
+        return;
+    };
+
+    println!("Answer: {ANSI_BOLD}{answer}{ANSI_RESET}");
+
+    if !confirm("Submit this answer? [y/N] ") {
+        println!("Not submitted, no try used.");
+        return;
+    }
+
+    let correct = check_answer(day, part, &answer);
+    let elapsed_secs = session.elapsed_secs();
+
+    session.attempts.push(Attempt {
+        part,
+        answer,
+        elapsed_secs,
+        correct,
+    });
+    store(&session);
+
+    if correct {
+        println!(
+            "✅ Part {part} solved after {} with {} tr{}.",
+            format_elapsed(elapsed_secs),
+            tries + 1,
+            if tries == 0 { "y" } else { "ies" }
+        );
+        if session.current_part().is_none() {
+            println!();
+            print_review(&session);
+        }
+    } else {
+        println!(
+            "❌ Wrong answer, {} tr{} left for part {part}.",
+            max_tries - tries - 1,
+            if max_tries - tries - 1 == 1 {
+                "y"
+            } else {
+                "ies"
+            }
+        );
+    }
+}
+
+fn store(session: &Session) {
+    if let Err(e) = session.store_file() {
+        eprintln!("Failed to record contest session: {e}");
+        process::exit(1);
+    }
+}
+
+/// Run a single part against the real input, hiding everything but the answer.
+pub(crate) fn run_part(day: Day, part: Part, author: Option<&str>) -> Option<String> {
+    let bin = bin_name(day, author);
+    let part_str = part.to_string();
+
+    let mut cmd = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--bin",
+            &bin,
+            "--",
+            "--part",
+            &part_str,
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    let stdout = BufReader::new(cmd.stdout.take().unwrap());
+    let output: Vec<String> = stdout.lines().map_while(Result::ok).collect();
+    cmd.wait().unwrap();
+
+    parse_answer(&output, part)
+}
+
+/// Check an answer against the recorded real answer, or submit it via ec-cli if none is known.
+/// Exits without using up a try if the answer could not be submitted.
+fn check_answer(day: Day, part: Part, answer: &str) -> bool {
+    if let Some(expected) = read_real_answer(day, part) {
+        return expected == answer;
+    }
+
+    if ec_cli::check().is_err() {
+        eprintln!("No known answer in data/real_answers and ec-cli is not available to submit.");
+        process::exit(1);
+    }
+
+    // wait out the cooldown instead of burning a try on a throttled submission.
+    if let Some(secs) = submissions::check_cooldown() {
+        println!("Waiting {secs}s for the submission cooldown...");
+        thread::sleep(Duration::from_secs(secs));
+    }
+
+    // only a rejected answer uses up a try, not a submission that could not be sent.
+    let correct = match ec_cli::submit(day, part, answer) {
+        Ok(_) => true,
+        Err(e) if e.is_rejection() => false,
+        Err(e) => {
+            eprintln!("Not submitted, no try used: {e}");
+            process::exit(1);
+        }
+    };
+    if correct {
+        match store_real_answer(day, part, answer) {
+            Ok(path) => println!("Recorded answer to \"{}\".", path.display()),
+            Err(e) => eprintln!("Failed to record answer: {e}"),
+        }
+    }
+    correct
+}
+
+pub(crate) fn confirm(prompt: &str) -> bool {
+    print!("{prompt}");
+    let _ = io::stdout().flush();
+
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).is_ok() && line.trim().eq_ignore_ascii_case("y")
+}
+
+fn print_review(session: &Session) {
+    println!(
+        "{ANSI_BOLD}Contest review for day {}{ANSI_RESET}",
+        session.day
+    );
+    println!("------");
+
+    for attempt in &session.attempts {
+        println!(
+            "[{:>11}] Part {}: {} {}",
+            format_elapsed(attempt.elapsed_secs),
+            attempt.part,
+            attempt.answer,
+            if attempt.correct { "✅" } else { "❌" }
+        );
+    }
+
+    for part in all_parts() {
+        match session.solved_at(part) {
+            Some(secs) => println!(
+                "Part {part}: solved at {} in {} tr{}",
+                format_elapsed(secs),
+                session.tries(part),
+                if session.tries(part) == 1 { "y" } else { "ies" }
+            ),
+            None => println!("Part {part}: unsolved after {} tries", session.tries(part)),
+        }
+    }
+}
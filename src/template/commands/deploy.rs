@@ -0,0 +1,105 @@
+use std::{
+    fs,
+    path::Path,
+    process::{self, Command},
+};
+
+use crate::template::{
+    all_days, all_parts, authors::bin_name, data_dir, deploy::DeployPlan, inputs,
+    run_multi::get_path_for_bin, Day, ANSI_BOLD, ANSI_RESET,
+};
+
+pub fn handle(
+    year: u16,
+    host: String,
+    target: Option<String>,
+    remote_dir: String,
+    day: Option<Day>,
+    solution_args: &[String],
+) {
+    let days: Vec<Day> = match day {
+        Some(day) => vec![day],
+        None => all_days()
+            .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+            .collect(),
+    };
+
+    if days.is_empty() {
+        eprintln!("No scaffolded quests to deploy.");
+        process::exit(1);
+    }
+
+    let plan = DeployPlan {
+        year,
+        host,
+        target,
+        remote_dir,
+        days,
+    };
+
+    println!("Building {} quest(s)...", plan.days.len());
+    run_or_exit("cargo", &plan.build_args(), "Build failed.");
+
+    if let Err(e) = stage(&plan) {
+        eprintln!("Failed to stage binaries and inputs: {e}");
+        process::exit(1);
+    }
+
+    println!("Copying to {}:{}...", plan.host, plan.remote_path());
+    run_or_exit(
+        "rsync",
+        &plan.rsync_args(),
+        "rsync failed, is the host reachable over ssh?",
+    );
+
+    for (i, day) in plan.days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {day} @ {}{ANSI_RESET}", plan.host);
+        println!("------");
+        run_or_exit(
+            "ssh",
+            &plan.run_args(*day, solution_args),
+            "Remote run failed.",
+        );
+    }
+}
+
+/// Copy the built binaries and their inputs into the staging directory.
+fn stage(plan: &DeployPlan) -> Result<(), std::io::Error> {
+    let stage_dir = plan.stage_dir();
+    let inputs_dir = stage_dir.join(data_dir()).join("inputs");
+    fs::create_dir_all(&inputs_dir)?;
+
+    for day in &plan.days {
+        let bin = bin_name(*day, None);
+        fs::copy(plan.binary_dir().join(&bin), stage_dir.join(&bin))?;
+
+        for part in all_parts() {
+            let input = format!("{day}-{part}.txt");
+            let path = data_dir().join("inputs").join(&input);
+            if inputs::exists(&path) {
+                fs::write(inputs_dir.join(&input), inputs::read(&path)?)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_or_exit(program: &str, args: &[String], message: &str) {
+    let status = Command::new(program).args(args).status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(_) => {
+            eprintln!("{message}");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to run `{program}`: {e}");
+            process::exit(1);
+        }
+    }
+}
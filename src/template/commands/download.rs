@@ -1,14 +1,148 @@
-use crate::template::{ec_cli, Day};
-use std::process;
+use crate::template::contest::now;
+use crate::template::ec_cli::{self, PartStatus};
+use crate::template::pending::PendingDownloads;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{
+    all_days, all_parts, inputs, notes, quest_meta, read_only, Day, ANSI_BOLD, ANSI_RESET,
+};
+use std::{fs, path::Path, process};
 
 pub fn handle(day: Day) {
-    if ec_cli::check().is_err() {
-        eprintln!("command \"ec-cli\" not found or not callable. Try installing it from https://github.com/finnhartshorn/ec-cli");
+    match ec_cli::check().and_then(|()| ec_cli::download(day)) {
+        Ok(_) => {}
+        Err(e) if e.is_offline() => {
+            eprintln!("failed to download the quest: {e}");
+            work_offline(&[(day, e)]);
+        }
+        Err(e) => {
+            eprintln!("failed to download the quest: {e}");
+            process::exit(1);
+        }
+    }
+    quest_meta::warn_if_stale(day);
+    update_note(day);
+}
+
+/// Download every scaffolded day in parallel and print a per-day summary.
+pub fn handle_all(jobs: usize) {
+    let days: Vec<Day> = all_days()
+        .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+        .collect();
+
+    if days.is_empty() {
+        eprintln!("No scaffolded days found. Run `cargo scaffold <day>` first.");
         process::exit(1);
     }
 
-    if let Err(e) = ec_cli::download(day) {
-        eprintln!("failed to call ec-cli: {e}");
+    if let Err(e) = ec_cli::check() {
+        eprintln!("failed to download the quests: {e}");
+        if !e.is_offline() {
+            process::exit(1);
+        }
+        work_offline(
+            &days
+                .into_iter()
+                .map(|day| (day, e.to_string()))
+                .collect::<Vec<_>>(),
+        );
+        return;
+    }
+
+    println!(
+        "Downloading {} day(s) with up to {jobs} parallel job(s)...",
+        days.len()
+    );
+
+    let results = ec_cli::download_all(&days, jobs);
+
+    println!();
+    println!("{ANSI_BOLD}Day   Part 1  Part 2  Part 3{ANSI_RESET}");
+    println!("----------------------------");
+
+    let mut failures = vec![];
+    for (day, statuses) in results {
+        let cells: Vec<&str> = statuses
+            .iter()
+            .map(|status| match status {
+                PartStatus::Downloaded => "✅",
+                PartStatus::Unavailable => "⚠️ ",
+                PartStatus::Failed(_) => "❌",
+            })
+            .collect();
+        println!("{day}    {}      {}      {}", cells[0], cells[1], cells[2]);
+
+        if let [PartStatus::Failed(e), ..] = statuses {
+            failures.push((day, e));
+        }
+    }
+
+    println!();
+    println!("✅ downloaded  ⚠️  not available yet  ❌ failed");
+
+    for day in &days {
+        quest_meta::warn_if_stale(*day);
+        update_note(*day);
+    }
+
+    if failures.is_empty() {
+        return;
+    }
+
+    eprintln!();
+    for (day, e) in &failures {
+        eprintln!("Day {day}: {e}");
+    }
+    let (offline, failed): (Vec<_>, Vec<_>) =
+        failures.into_iter().partition(|(_, e)| e.is_offline());
+    if !offline.is_empty() {
+        work_offline(&offline);
+    }
+    if !failed.is_empty() {
         process::exit(1);
-    };
+    }
+}
+
+fn update_note(day: Day) {
+    if let Err(e) = notes::update_title(day) {
+        eprintln!("Failed to add the title to the notes of day {day}: {e}");
+    }
+}
+
+/// Keep going without the failed downloads: create the missing input and sample files so the
+/// quests can be worked on, and queue the days for `cargo ec sync`.
+fn work_offline(failures: &[(Day, impl ToString)]) {
+    let mut pending = PendingDownloads::read_from_file();
+    let dir = read_only::download_dir();
+
+    for (day, e) in failures {
+        for part in all_parts() {
+            for folder in ["inputs", "samples"] {
+                let path = dir.join(folder).join(format!("{day}-{part}.txt"));
+                // inputs may only be stored encrypted, see `inputs::exists`.
+                if inputs::exists(&path) {
+                    continue;
+                }
+                let created =
+                    fs::create_dir_all(dir.join(folder)).and_then(|()| fs::write(&path, ""));
+                if let Err(e) = created {
+                    eprintln!("Failed to create \"{}\": {e}", path.display());
+                    process::exit(1);
+                }
+            }
+        }
+        pending.add(*day, e.to_string(), now());
+    }
+
+    if let Err(e) = pending.store_file() {
+        eprintln!("Failed to queue the download: {e}");
+        process::exit(1);
+    }
+
+    let days: Vec<String> = failures.iter().map(|(day, _)| day.to_string()).collect();
+    eprintln!();
+    eprintln!(
+        "⚠️  Working offline: created the missing inputs and samples empty and queued day(s) {}.",
+        days.join(", ")
+    );
+    eprintln!("Fetch them once ec-cli and the network are available with `cargo ec sync`.");
 }
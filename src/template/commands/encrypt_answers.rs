@@ -0,0 +1,22 @@
+use std::process;
+
+use crate::template::{answers, read_only};
+
+pub fn handle() {
+    read_only::refuse("encrypt answers");
+
+    match answers::encrypt_all() {
+        Ok(encrypted) if encrypted.is_empty() => {
+            println!("No plaintext answers to encrypt.");
+        }
+        Ok(encrypted) => {
+            for path in encrypted {
+                println!("🔒 Encrypted answer file \"{}\"", path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to encrypt answer files: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::process;
+
+use crate::template::{data_dir, inputs, read_only, snapshots::objects_dir};
+
+pub fn handle() {
+    read_only::refuse("encrypt inputs");
+
+    let encrypted = inputs::encrypt_all(&data_dir().join("inputs"), |path| {
+        path.extension().is_some_and(|ext| ext == "txt")
+    })
+    .and_then(|mut encrypted| {
+        encrypted.extend(inputs::encrypt_all(&objects_dir(), |_| true)?);
+        Ok(encrypted)
+    });
+
+    match encrypted {
+        Ok(encrypted) if encrypted.is_empty() => {
+            println!("No plaintext inputs to encrypt.");
+        }
+        Ok(encrypted) => {
+            for path in encrypted {
+                println!("🔒 Encrypted input file \"{}\"", path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to encrypt input files: {e}");
+            process::exit(1);
+        }
+    }
+}
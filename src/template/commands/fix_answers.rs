@@ -0,0 +1,22 @@
+use std::process;
+
+use crate::template::{answers, read_only};
+
+pub fn handle() {
+    read_only::refuse("fix answer files");
+
+    match answers::fix_all() {
+        Ok(fixed) if fixed.is_empty() => {
+            println!("All answer files are clean.");
+        }
+        Ok(fixed) => {
+            for path in fixed {
+                println!("🧹 Normalized answer file \"{}\"", path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to fix answer files: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::{
+    fs,
+    path::Path,
+    process::{self, Command, Stdio},
+};
+
+use crate::template::{authors::bin_name, data_dir, read_only, run_multi::get_path_for_bin, Day};
+
+pub fn handle(day: Day, size: usize, seed: u64) {
+    read_only::refuse("store generated inputs");
+
+    if !Path::new(&get_path_for_bin(day)).exists() {
+        eprintln!("Day {day} is not scaffolded yet.");
+        process::exit(1);
+    }
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--bin",
+            &bin_name(day, None),
+            "--",
+        ])
+        .args([
+            "--gen",
+            "--size",
+            &size.to_string(),
+            "--seed",
+            &seed.to_string(),
+        ])
+        .stderr(Stdio::inherit())
+        .output();
+
+    let output = match output {
+        Ok(output) if output.status.success() => output.stdout,
+        Ok(_) => process::exit(1),
+        Err(e) => {
+            eprintln!("Failed to run day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let dir = data_dir().join("tests");
+    let path = dir.join(format!("{day}-{size}-{seed}.txt"));
+
+    if let Err(e) = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, output)) {
+        eprintln!("Failed to write \"{}\": {e}", path.display());
+        process::exit(1);
+    }
+
+    println!("Generated input: \"{}\"", path.display());
+    println!(
+        "Run it with `cargo solve {day} --release --input {}`.",
+        path.display()
+    );
+}
@@ -0,0 +1,161 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+use super::solve::build_bin;
+use crate::template::{
+    authors::{bin_name, get_path_for_author_bin},
+    data_dir, inputs,
+    json_output::Record,
+    read_only,
+    reduce::panic_location,
+    truncate::{default_step, head, head_sizes},
+    Day, Part, ANSI_BOLD, ANSI_RESET,
+};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    part: Part,
+    step: Option<usize>,
+    input: Option<&str>,
+    run: bool,
+    author: Option<&str>,
+    release: bool,
+    timeout: Option<u64>,
+) {
+    read_only::refuse("store truncated inputs");
+
+    let input_path = input.map_or_else(
+        || read_only::quest_file(format!("inputs/{day}-{part}.txt")),
+        Into::into,
+    );
+    let original = match inputs::read_to_string(&input_path) {
+        Ok(original) => original,
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {e}", input_path.display());
+            process::exit(1);
+        }
+    };
+
+    let total = original.lines().count();
+    let step = step.unwrap_or_else(|| default_step(total));
+    let sizes = head_sizes(total, step);
+    if sizes.is_empty() {
+        eprintln!("The input has only {total} line(s), use a `--lines` step below that.");
+        process::exit(1);
+    }
+
+    let dir = data_dir().join("tests");
+    let variants: Vec<(usize, PathBuf)> = sizes
+        .into_iter()
+        .map(|lines| (lines, dir.join(format!("{day}-{part}-head-{lines}.txt"))))
+        .collect();
+    let written = fs::create_dir_all(&dir).and_then(|()| {
+        variants
+            .iter()
+            .try_for_each(|(lines, path)| fs::write(path, head(&original, *lines)))
+    });
+    if let Err(e) = written {
+        eprintln!("Failed to write \"{}\": {e}", dir.display());
+        process::exit(1);
+    }
+
+    println!(
+        "Wrote {} truncated input(s) of the {total} lines to \"{}\", every {step} lines.",
+        variants.len(),
+        dir.display()
+    );
+
+    if !run {
+        let (_, first) = &variants[0];
+        println!(
+            "Run them with `cargo head-input {day} --part {part} --run`, or one with `cargo solve {day} --input {}`.",
+            first.display()
+        );
+        return;
+    }
+
+    if !Path::new(&get_path_for_author_bin(day, author)).exists() {
+        eprintln!("No solution for day {day}, scaffold one with `cargo scaffold {day}`.");
+        process::exit(1);
+    }
+    let exe = build_bin(&bin_name(day, author), release);
+
+    println!();
+    println!("{ANSI_BOLD}Lines     Part {part}{ANSI_RESET}");
+    println!("------------------------------");
+
+    let mut last_good = None;
+    for (lines, path) in &variants {
+        match run_variant(&exe, path, part, timeout) {
+            Ok(status) => {
+                println!("{lines:<9} {status}");
+                last_good = Some(lines);
+            }
+            Err(failure) => {
+                println!("{lines:<9} ❌ {failure}");
+                println!();
+                match last_good {
+                    Some(good) => println!(
+                        "Part {part} breaks between {good} and {lines} lines, rerun it with `cargo solve {day} --input {}`.",
+                        path.display()
+                    ),
+                    None => println!(
+                        "Part {part} already breaks on the first {lines} lines, try a smaller `--lines` step."
+                    ),
+                }
+                process::exit(1);
+            }
+        }
+    }
+
+    println!();
+    println!("Part {part} answered on every truncated input.");
+}
+
+/// Run the part on a truncated input, returning its answer and time or why it failed.
+fn run_variant(
+    exe: &Path,
+    input_path: &Path,
+    part: Part,
+    timeout: Option<u64>,
+) -> Result<String, String> {
+    let mut cmd = Command::new(exe);
+    cmd.arg("--input")
+        .arg(input_path)
+        .args(["--part", &part.to_string()]);
+    if let Some(secs) = timeout {
+        cmd.args(["--timeout", &secs.to_string()]);
+    }
+    let output = cmd
+        .env("EC_OUTPUT", "json")
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    let record = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(Record::parse)
+        .find(|record| record.part == part);
+    match record {
+        Some(Record {
+            error: Some(error), ..
+        }) => Err(error),
+        Some(Record {
+            answer: Some(answer),
+            duration,
+            ..
+        }) => Ok(match duration {
+            Some(duration) => format!("{answer} ({duration:.1?})"),
+            None => answer,
+        }),
+        _ => Err(
+            panic_location(&String::from_utf8_lossy(&output.stderr)).map_or_else(
+                || "no answer".to_string(),
+                |location| format!("panicked at {location}"),
+            ),
+        ),
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::Path,
+    process::{self, Command},
+};
+
+use directories::BaseDirs;
+
+use super::{migrate_layout, scaffold};
+use crate::{
+    day,
+    template::{
+        authors::get_path_for_author_bin,
+        dirs::config_dir,
+        migrate::is_legacy_layout,
+        read_only,
+        setup::{clean_token, default_features, with_default_features, with_home_year, FEATURES},
+        Year,
+    },
+};
+
+static CARGO_CONFIG_PATH: &str = ".cargo/config.toml";
+static MANIFEST_PATH: &str = "Cargo.toml";
+
+pub fn handle() {
+    read_only::refuse("set up the repository");
+
+    println!("Setting up the repository, press enter to keep the answer in brackets.");
+
+    let year = setup_year();
+    let features = setup_features();
+    setup_layout(year);
+    setup_token(features.iter().any(|feature| feature == "http_client"));
+    setup_first_day();
+
+    println!("---");
+    println!("✨ All set! Run `cargo ec selftest` to check that everything works.");
+}
+
+fn setup_year() -> Year {
+    step("Event");
+    let home = Year::home();
+    let year = loop {
+        let answer = ask(&format!("Which year's event are you solving? [{home}] "));
+        if answer.is_empty() {
+            break home;
+        }
+        match answer.parse().ok().and_then(Year::new) {
+            Some(year) => break year,
+            None => println!("\"{answer}\" is not an Everybody Codes event, try again."),
+        }
+    };
+
+    edit(CARGO_CONFIG_PATH, |config| Ok(with_home_year(config, year)));
+    // later steps of the wizard already work on the new event.
+    env::set_var("EC_YEAR", year.to_string());
+    println!("✅ Set EC_YEAR = \"{year}\" in {CARGO_CONFIG_PATH}");
+    year
+}
+
+fn setup_features() -> Vec<String> {
+    step("Features");
+    let manifest = read(MANIFEST_PATH);
+    let current = default_features(&manifest);
+
+    // features the wizard doesn't know about stay enabled.
+    let mut enabled: Vec<String> = current
+        .iter()
+        .filter(|feature| FEATURES.iter().all(|(name, _)| name != feature))
+        .cloned()
+        .collect();
+    for (name, about) in FEATURES {
+        let on = current.iter().any(|feature| feature == name);
+        if ask_yes_no(&format!("Enable `{name}`, to {about}?"), on) {
+            enabled.push((*name).to_string());
+        }
+    }
+
+    if enabled == current {
+        println!("✅ Kept the default features in {MANIFEST_PATH}");
+    } else {
+        let names: Vec<&str> = enabled.iter().map(String::as_str).collect();
+        edit(MANIFEST_PATH, |manifest| {
+            with_default_features(manifest, &names)
+        });
+        println!("✅ Enabled {} by default in {MANIFEST_PATH}", list(&names));
+    }
+    enabled
+}
+
+fn setup_layout(year: Year) {
+    step("Data");
+    let legacy = is_legacy_layout();
+    let per_year = ask_yes_no(
+        &format!("Keep several events side by side, with this one in `data/{year}`?"),
+        !legacy,
+    );
+
+    match (legacy, per_year) {
+        (true, true) => migrate_layout::handle(false),
+        (true, false) => println!("✅ Quest data goes to `data/`"),
+        (false, true) => println!("✅ Quest data goes to `data/{year}`"),
+        (false, false) => println!(
+            "⚠️  This repository already keeps each event in `data/<year>`, quest data goes to `data/{year}`."
+        ),
+    }
+}
+
+fn setup_token(http_client: bool) {
+    step("Session token");
+    let (path, existing) = if http_client {
+        let path = config_dir().map(|dir| dir.join("token"));
+        (path.clone(), env_or_file("EC_TOKEN", path.as_deref()))
+    } else {
+        if Command::new("ec-cli").arg("--version").output().is_err() {
+            println!("⚠️  ec-cli is not installed, get it from https://github.com/finnhartshorn/ec-cli or enable `http_client`.");
+        }
+        let path = BaseDirs::new().map(|dirs| dirs.home_dir().join(".everybodycodes.cookie"));
+        (path.clone(), env_or_file("EC_COOKIE", path.as_deref()))
+    };
+
+    if let Some(source) = existing {
+        if ask_yes_no(
+            &format!("Found a session token in {source}, keep it?"),
+            true,
+        ) {
+            println!("✅ Kept the session token");
+            return;
+        }
+    }
+    let Some(path) = path else {
+        println!("⚠️  No home directory found, set the token in an environment variable instead.");
+        return;
+    };
+
+    println!("Log into https://everybody.codes and copy the value of its `everybody-codes` cookie from the developer tools.");
+    let token = loop {
+        let answer = ask("Session token (empty to skip): ");
+        if answer.is_empty() {
+            println!("⚠️  Skipped, downloading and submitting will not work until a token is set.");
+            return;
+        }
+        match clean_token(&answer) {
+            Ok(token) => break token,
+            Err(e) => println!("Invalid token: {e}"),
+        }
+    };
+
+    let written = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&path, token + "\n"));
+    match written {
+        Ok(()) => println!("✅ Stored the session token in \"{}\"", path.display()),
+        Err(e) => {
+            eprintln!("Failed to write \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
+fn setup_first_day() {
+    step("First quest");
+    let day = day!(1);
+    let module_path = get_path_for_author_bin(day, None);
+    if Path::new(&module_path).exists() {
+        println!("✅ Day {day} is already scaffolded in \"{module_path}\"");
+    } else {
+        scaffold::handle(day, false, None, None);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn step(name: &str) {
+    println!();
+    println!("# {name}");
+}
+
+/// Print a prompt and read the trimmed answer, exiting when stdin is closed.
+fn ask(prompt: &str) -> String {
+    print!("{prompt}");
+    let _ = io::stdout().flush();
+
+    let mut line = String::new();
+    match io::stdin().read_line(&mut line) {
+        Ok(0) | Err(_) => {
+            println!();
+            eprintln!("Aborted, the steps above were saved.");
+            process::exit(1);
+        }
+        Ok(_) => line.trim().to_string(),
+    }
+}
+
+fn ask_yes_no(question: &str, default: bool) -> bool {
+    let options = if default { "[Y/n]" } else { "[y/N]" };
+    loop {
+        match ask(&format!("{question} {options} "))
+            .to_lowercase()
+            .as_str()
+        {
+            "" => return default,
+            "y" | "yes" => return true,
+            "n" | "no" => return false,
+            _ => println!("Please answer y or n."),
+        }
+    }
+}
+
+/// Where a token is already set: the environment variable, or a non-empty file.
+fn env_or_file(var: &str, path: Option<&Path>) -> Option<String> {
+    if env::var(var).is_ok_and(|token| !token.trim().is_empty()) {
+        return Some(format!("`{var}`"));
+    }
+    let path = path?;
+    let token = fs::read_to_string(path).ok()?;
+    (!token.trim().is_empty()).then(|| format!("\"{}\"", path.display()))
+}
+
+fn list(names: &[&str]) -> String {
+    if names.is_empty() {
+        "no features".to_string()
+    } else {
+        names
+            .iter()
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Failed to read \"{path}\": {e}");
+        process::exit(1);
+    })
+}
+
+fn edit(path: &str, change: impl FnOnce(&str) -> Result<String, String>) {
+    let path = Path::new(path);
+    let contents = fs::read_to_string(path).unwrap_or_default();
+    let result = change(&contents).and_then(|changed| {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(path, changed).map_err(|e| e.to_string())
+    });
+    if let Err(e) = result {
+        eprintln!("Failed to update \"{}\": {e}", path.display());
+        process::exit(1);
+    }
+}
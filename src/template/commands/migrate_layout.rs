@@ -0,0 +1,54 @@
+use std::process;
+
+use crate::template::{
+    migrate::{is_legacy_layout, MigrationPlan},
+    read_only, Year,
+};
+
+pub fn handle(dry_run: bool) {
+    if !is_legacy_layout() {
+        println!("This repository already keeps each event in `data/<year>`, nothing to migrate.");
+        return;
+    }
+
+    let plan = match MigrationPlan::detect(Year::current()) {
+        Ok(plan) => plan,
+        Err(e) => {
+            eprintln!("Failed to list the quest files: {e}");
+            process::exit(1);
+        }
+    };
+
+    let conflicts = plan.conflicts();
+    if !conflicts.is_empty() {
+        for path in conflicts {
+            eprintln!("\"{}\" already exists.", path.display());
+        }
+        eprintln!("Move these files out of the way and try again, nothing was changed.");
+        process::exit(1);
+    }
+
+    for rename in &plan.renames {
+        println!("{} -> {}", rename.from.display(), rename.to.display());
+    }
+
+    if dry_run {
+        println!("---");
+        println!("Dry run, nothing was moved. Run without `--dry-run` to migrate.");
+        return;
+    }
+
+    read_only::refuse("migrate the layout");
+
+    if let Err(e) = plan.apply() {
+        eprintln!("Failed to migrate: {e}");
+        eprintln!("Some files may have been moved already, check `git status` before retrying.");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!(
+        "🚚 Moved the {} event to `data/{}`. Run `cargo all` to check that everything still works.",
+        plan.year, plan.year
+    );
+}
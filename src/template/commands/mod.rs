@@ -1,6 +1,40 @@
 pub mod all;
+#[cfg(feature = "http_client")]
+pub mod audit_answers;
+pub mod bench;
+pub mod build_times;
+pub mod compare;
+pub mod compare_inputs;
+pub mod contest;
+pub mod deploy;
 pub mod download;
+#[cfg(feature = "encrypt")]
+pub mod encrypt_answers;
+#[cfg(feature = "encrypt")]
+pub mod encrypt_inputs;
+pub mod explain;
+pub mod fix_answers;
+pub mod gen;
+pub mod head_input;
+pub mod init;
+pub mod migrate_layout;
+pub mod modgraph;
+pub mod notes;
+pub mod oracle;
+pub mod profile;
 pub mod read;
+pub mod reduce;
 pub mod scaffold;
+pub mod scaffold_utils;
+pub mod scale_bench;
+pub mod scratch;
+pub mod selftest;
 pub mod solve;
+pub mod stats;
+pub mod submit;
+pub mod sync;
 pub mod time;
+pub mod today;
+pub mod verify;
+pub mod watch;
+pub mod web;
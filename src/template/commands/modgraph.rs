@@ -0,0 +1,19 @@
+use crate::template::modgraph::ModuleGraph;
+
+pub fn handle(mermaid: bool) {
+    let graph = ModuleGraph::from_sources();
+
+    if mermaid {
+        println!("{}", graph.to_mermaid());
+    } else {
+        println!("{}", graph.to_dot());
+    }
+
+    let unused: Vec<_> = graph.unused_modules().map(String::as_str).collect();
+    if !unused.is_empty() {
+        eprintln!(
+            "Utility modules not used by any quest: {}",
+            unused.join(", ")
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use std::{fs, path::PathBuf, process};
+
+use crate::template::{all_days, notes, read_only, Event};
+
+pub fn handle(output: Option<&str>) {
+    read_only::refuse("compile the notes");
+
+    let notes: Vec<String> = all_days()
+        .filter_map(|day| fs::read_to_string(notes::note_path(day)).ok())
+        .filter(|note| !note.trim().is_empty())
+        .collect();
+
+    if notes.is_empty() {
+        eprintln!(
+            "No notes found in \"{}\". `cargo scaffold <day>` creates them.",
+            notes::notes_dir().display()
+        );
+        process::exit(1);
+    }
+
+    let path = output.map_or_else(|| notes::notes_dir().join("writeup.md"), PathBuf::from);
+    let document = notes::compile(Event::current(), &notes);
+    let written = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&path, document));
+
+    if let Err(e) = written {
+        eprintln!("Failed to write \"{}\": {e}", path.display());
+        process::exit(1);
+    }
+    println!(
+        "📓 Compiled {} note(s) into \"{}\"",
+        notes.len(),
+        path.display()
+    );
+}
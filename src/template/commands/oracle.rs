@@ -0,0 +1,205 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::Path,
+    process::{self, Command, Stdio},
+};
+
+use super::solve::build_bin;
+use crate::template::{
+    all_parts,
+    answers::normalize,
+    authors::{bin_name, get_path_for_author_bin},
+    data_dir,
+    json_output::Record,
+    read_only,
+    reduce::panic_location,
+    Day, Part,
+};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    reference: &str,
+    part: Option<Part>,
+    runs: u64,
+    size: usize,
+    seed: u64,
+    author: Option<&str>,
+    timeout: Option<u64>,
+) {
+    if !Path::new(&get_path_for_author_bin(day, author)).exists() {
+        eprintln!("No solution for day {day}, scaffold one with `cargo scaffold {day}`.");
+        process::exit(1);
+    }
+
+    let exe = build_bin(&bin_name(day, author), true);
+    let parts: Vec<Part> = all_parts()
+        .filter(|p| part.is_none_or(|part| *p == part))
+        .collect();
+    let input_path = env::temp_dir().join(format!("ec-oracle-{}.txt", process::id()));
+
+    println!(
+        "Comparing day {day} with `{reference}` on {runs} generated input(s) of size {size}..."
+    );
+
+    for seed in seed..seed.saturating_add(runs) {
+        let input = generate(&exe, size, seed);
+        if let Err(e) = fs::write(&input_path, &input) {
+            eprintln!("Failed to write \"{}\": {e}", input_path.display());
+            process::exit(1);
+        }
+
+        let (records, panic) = solve(&exe, &input_path, part, timeout);
+        let _ = fs::remove_file(&input_path);
+
+        for &part in &parts {
+            let expected = match run_reference(reference, day, part, &input) {
+                Ok(expected) => expected,
+                Err(e) => {
+                    eprintln!("Reference failed on seed {seed} part {part}: {e}");
+                    save(day, size, seed, &input);
+                    process::exit(1);
+                }
+            };
+
+            let record = records.iter().find(|record| record.part == part);
+            let answer = match record {
+                Some(record) if record.answer.as_deref().map(normalize) == Some(&expected) => {
+                    continue
+                }
+                Some(Record {
+                    error: Some(error), ..
+                }) => error.clone(),
+                Some(Record {
+                    answer: Some(answer),
+                    ..
+                }) => format!("`{answer}`"),
+                _ => panic.as_ref().map_or("no answer".to_string(), |location| {
+                    format!("panicked at {location}")
+                }),
+            };
+
+            println!(
+                "❌ Seed {seed} part {part}: {answer}, but the reference answered `{expected}`."
+            );
+            save(day, size, seed, &input);
+            process::exit(1);
+        }
+    }
+
+    println!("✅ All {runs} input(s) agree with the reference.");
+}
+
+/// Generate an input with the quest's `gen_input` generator.
+fn generate(exe: &Path, size: usize, seed: u64) -> String {
+    let output = Command::new(exe)
+        .args([
+            "--gen",
+            "--size",
+            &size.to_string(),
+            "--seed",
+            &seed.to_string(),
+        ])
+        .stderr(Stdio::inherit())
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).to_string()
+        }
+        Ok(_) => process::exit(1),
+        Err(e) => {
+            eprintln!("Failed to generate an input: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Run the parts on an input, returning their records and where the solution panicked.
+fn solve(
+    exe: &Path,
+    input_path: &Path,
+    part: Option<Part>,
+    timeout: Option<u64>,
+) -> (Vec<Record>, Option<String>) {
+    let mut cmd = Command::new(exe);
+    cmd.arg("--input").arg(input_path);
+    if let Some(part) = part {
+        cmd.args(["--part", &part.to_string()]);
+    }
+    if let Some(secs) = timeout {
+        cmd.args(["--timeout", &secs.to_string()]);
+    }
+    let output = cmd
+        .env("EC_OUTPUT", "json")
+        .output()
+        .expect("failed to run the solution");
+
+    let records = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(Record::parse)
+        .collect();
+    let panic = panic_location(&String::from_utf8_lossy(&output.stderr)).map(str::to_string);
+    (records, panic)
+}
+
+/// Pipe the input to the reference through the shell, with the quest in `EC_DAY` and `EC_PART`,
+/// and return the last line it printed.
+fn run_reference(reference: &str, day: Day, part: Part, input: &str) -> Result<String, String> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+
+    let mut child = cmd
+        .arg(reference)
+        .env("EC_DAY", day.to_string())
+        .env("EC_PART", part.to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    // a reference that doesn't read all of its input closes the pipe early, which is fine.
+    let _ = child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(input.as_bytes());
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!("exited with {}", output.status));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(normalize)
+        .rfind(|line| !line.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| "printed no answer".to_string())
+}
+
+/// Keep an input the solution and the reference disagree on in `data/tests`, like `cargo gen`.
+fn save(day: Day, size: usize, seed: u64, input: &str) {
+    if read_only::is_enabled() {
+        return;
+    }
+
+    let dir = data_dir().join("tests");
+    let path = dir.join(format!("{day}-{size}-{seed}.txt"));
+    match fs::create_dir_all(&dir).and_then(|()| fs::write(&path, input)) {
+        Ok(()) => println!(
+            "Wrote the input to \"{}\", rerun it with `cargo solve {day} --release --input {}`.",
+            path.display(),
+            path.display()
+        ),
+        Err(e) => eprintln!("Failed to write \"{}\": {e}", path.display()),
+    }
+}
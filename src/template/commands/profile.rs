@@ -0,0 +1,105 @@
+use std::{
+    env,
+    fs::{self, File},
+    path::Path,
+    process::{self, Command, Stdio},
+};
+
+use super::solve::bin_path;
+use crate::template::{
+    authors::{bin_name, get_path_for_author_bin},
+    profile::{missing_tools, profile_path, FREQUENCY},
+    Day, Part,
+};
+
+pub fn handle(day: Day, part: Part, author: Option<&str>) {
+    if !Path::new(&get_path_for_author_bin(day, author)).exists() {
+        eprintln!("No solution for day {day}, scaffold one with `cargo scaffold {day}`.");
+        process::exit(1);
+    }
+
+    let missing = missing_tools();
+    if !missing.is_empty() {
+        for (tool, install) in missing {
+            eprintln!("command \"{tool}\" not found, {install}.");
+        }
+        process::exit(1);
+    }
+
+    // frames are only resolved with debug info, which release builds leave out by default.
+    let bin = bin_name(day, author);
+    let built = Command::new("cargo")
+        .args(["build", "--quiet", "--release", "--bin", &bin])
+        .env("CARGO_PROFILE_RELEASE_DEBUG", "true")
+        .status();
+    if !built.is_ok_and(|status| status.success()) {
+        process::exit(1);
+    }
+
+    let data = env::temp_dir().join(format!("ec-{day}-{part}.perf.data"));
+    let recorded = Command::new("perf")
+        .args(["record", "--quiet", "--call-graph", "dwarf"])
+        .args(["-F", &FREQUENCY.to_string(), "-o"])
+        .arg(&data)
+        .arg("--")
+        .arg(bin_path(&bin, true))
+        .args(["--part", &part.to_string(), "--time"])
+        .status();
+    if !recorded.is_ok_and(|status| status.success()) {
+        eprintln!("Failed to record a profile of day {day} part {part}.");
+        let _ = fs::remove_file(&data);
+        process::exit(1);
+    }
+
+    let path = profile_path(day, part);
+    let result = render(&data, &path, &format!("Day {day} Part {part}"));
+    let _ = fs::remove_file(&data);
+
+    match result {
+        Ok(()) => println!("Wrote flamegraph to \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("Failed to render the flamegraph: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Render recorded perf data as a flamegraph, piping `perf script` through inferno.
+fn render(data: &Path, path: &Path, title: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let svg = File::create(path).map_err(|e| e.to_string())?;
+
+    let mut script = Command::new("perf")
+        .args(["script", "-i"])
+        .arg(data)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    let mut collapse = Command::new("inferno-collapse-perf")
+        .stdin(script.stdout.take().expect("stdout is piped"))
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    let flamegraph = Command::new("inferno-flamegraph")
+        .args(["--title", title])
+        .stdin(collapse.stdout.take().expect("stdout is piped"))
+        .stdout(svg)
+        .status()
+        .map_err(|e| e.to_string())?;
+
+    let collapsed = collapse.wait().map_err(|e| e.to_string())?;
+    let scripted = script.wait().map_err(|e| e.to_string())?;
+    for (tool, status) in [
+        ("perf script", scripted),
+        ("inferno-collapse-perf", collapsed),
+        ("inferno-flamegraph", flamegraph),
+    ] {
+        if !status.success() {
+            return Err(format!("{tool} exited with {status}"));
+        }
+    }
+    Ok(())
+}
@@ -1,15 +1,82 @@
-use std::process;
+use std::{
+    env, fs,
+    io::{self, IsTerminal, Write},
+    process::{self, Command, Stdio},
+};
 
-use crate::template::{ec_cli, Day};
+use crate::template::{ec_cli, html, quest_meta, read_only, Day, Part};
 
-pub fn handle(day: Day) {
-    if ec_cli::check().is_err() {
-        eprintln!("command \"ec-cli\" not found or not callable. Try installing it from https://github.com/finnhartshorn/ec-cli");
-        process::exit(1);
+use super::{contest::confirm, download};
+
+pub fn handle(day: Day, part: Option<Part>, pager: bool) {
+    if quest_meta::warn_if_stale(day) && confirm("Download the quest again first? [y/N] ") {
+        download::handle(day);
+    }
+
+    let parts = part.map_or(Part::ALL.to_vec(), |part| vec![part]);
+    let descriptions: Vec<String> = parts
+        .iter()
+        .filter_map(|part| {
+            fs::read_to_string(read_only::quest_file(format!(
+                "descriptions/{day}-{part}.html"
+            )))
+            .ok()
+        })
+        .collect();
+
+    if descriptions.is_empty() {
+        if let Some(part) = part {
+            eprintln!(
+                "The description of part {part} of day {day} is not downloaded, run `cargo download {day}` first."
+            );
+            process::exit(1);
+        }
+
+        // nothing stored yet, show the quest as the CLI renders it.
+        ec_cli::require();
+        if let Err(e) = ec_cli::read(day) {
+            eprintln!("failed to read the quest: {e}");
+            process::exit(1);
+        };
+        return;
+    }
+
+    let text = descriptions
+        .iter()
+        .map(|description| html::to_terminal(description))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if !pager || !io::stdout().is_terminal() || !page(&text) {
+        print!("{text}");
     }
+}
 
-    if let Err(e) = ec_cli::read(day) {
-        eprintln!("failed to call ec-cli: {e}");
-        process::exit(1);
+/// Show `text` in `$PAGER`, or `less` if it isn't set. Returns whether a pager could be started.
+fn page(text: &str) -> bool {
+    let pager = env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| "less".into());
+    let mut words = pager.split_whitespace();
+    let Some(program) = words.next() else {
+        return false;
     };
+
+    let mut command = Command::new(program);
+    command.args(words).stdin(Stdio::piped());
+    if env::var_os("LESS").is_none() {
+        // keep the formatting, and don't page descriptions that fit on one screen.
+        command.env("LESS", "FRX");
+    }
+
+    let Ok(mut child) = command.spawn() else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // the pager closing early is not an error.
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    let _ = child.wait();
+    true
 }
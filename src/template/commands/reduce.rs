@@ -0,0 +1,145 @@
+use std::{
+    env, fs,
+    path::Path,
+    process::{self, Command, Output},
+};
+
+use super::solve::build_bin;
+use crate::template::{
+    authors::{bin_name, get_path_for_author_bin},
+    data_dir, inputs, read_only,
+    reduce::{panic_location, reduce_input, Predicate},
+    run_multi::child_commands::parse_answer,
+    Day, Part,
+};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    part: Part,
+    predicate: Predicate,
+    reference: Option<&str>,
+    author: Option<&str>,
+    input: Option<&str>,
+    release: bool,
+    timeout: Option<u64>,
+) {
+    read_only::refuse("store reduced inputs");
+
+    let reference = match (predicate, reference) {
+        (Predicate::Differs, None) => {
+            eprintln!("`--predicate differs` needs a `--reference <author>` to compare with.");
+            process::exit(1);
+        }
+        (Predicate::Differs, reference) => reference,
+        (Predicate::Panics, _) => None,
+    };
+
+    for author in [Some(author), reference.map(Some)].into_iter().flatten() {
+        let path = get_path_for_author_bin(day, author);
+        if !Path::new(&path).exists() {
+            eprintln!("No solution for day {day} at \"{path}\".");
+            process::exit(1);
+        }
+    }
+
+    let input_path = input.map_or_else(
+        || read_only::quest_file(format!("inputs/{day}-{part}.txt")),
+        Into::into,
+    );
+    let original = match inputs::read_to_string(&input_path) {
+        Ok(original) => original,
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {e}", input_path.display());
+            process::exit(1);
+        }
+    };
+
+    let exe = build_bin(&bin_name(day, author), release);
+    let reference_exe =
+        reference.map(|reference| build_bin(&bin_name(day, Some(reference)), release));
+
+    let candidate_path = env::temp_dir().join(format!("ec-reduce-{}.txt", process::id()));
+    let run = |exe: &Path, input: &str| -> Output {
+        fs::write(&candidate_path, input).unwrap();
+        let mut cmd = Command::new(exe);
+        cmd.arg("--input").arg(&candidate_path);
+        cmd.args(["--part", &part.to_string()]);
+        if let Some(timeout) = timeout {
+            cmd.args(["--timeout", &timeout.to_string()]);
+        }
+        cmd.output().unwrap()
+    };
+    let answer = |output: &Output| {
+        let lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect();
+        parse_answer(&lines, part)
+    };
+
+    // the failure of the original input, which every reduced input has to reproduce.
+    let location =
+        panic_location(&String::from_utf8_lossy(&run(&exe, &original).stderr)).map(str::to_string);
+    let holds = |input: &str| match (predicate, &reference_exe) {
+        (Predicate::Differs, Some(reference_exe)) => {
+            let output = run(&exe, input);
+            let expected = run(reference_exe, input);
+            matches!((answer(&output), answer(&expected)), (Some(a), Some(b)) if a != b)
+        }
+        _ => {
+            location.is_some()
+                && panic_location(&String::from_utf8_lossy(&run(&exe, input).stderr))
+                    == location.as_deref()
+        }
+    };
+
+    if !holds(&original) {
+        let _ = fs::remove_file(&candidate_path);
+        match predicate {
+            Predicate::Panics => eprintln!("Day {day} Part {part} does not panic on this input."),
+            Predicate::Differs => eprintln!(
+                "Day {day} Part {part} answers the same as {} on this input.",
+                reference.unwrap_or_default()
+            ),
+        }
+        process::exit(1);
+    }
+    if let Some(location) = &location {
+        println!("Reducing an input that panics at {location}...");
+    } else {
+        println!("Reducing an input that answers differently...");
+    }
+
+    let mut runs = 0;
+    let mut smallest = original.lines().count();
+    let reduced = reduce_input(&original, |candidate| {
+        runs += 1;
+        let lines = candidate.lines().count();
+        let holds = holds(candidate);
+        if holds && lines < smallest {
+            smallest = lines;
+            println!("  {lines} lines left after {runs} runs");
+        }
+        holds
+    });
+    let _ = fs::remove_file(&candidate_path);
+
+    let dir = data_dir().join("tests");
+    let path = dir.join(format!("{day}-{part}-reduced.txt"));
+    if let Err(e) = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, &reduced)) {
+        eprintln!("Failed to write \"{}\": {e}", path.display());
+        process::exit(1);
+    }
+
+    println!(
+        "Reduced {} lines to {} in {runs} runs: \"{}\"",
+        original.lines().count(),
+        reduced.lines().count(),
+        path.display()
+    );
+    println!(
+        "Run it with `cargo solve {day} --input {}`.",
+        path.display()
+    );
+}
@@ -1,15 +1,19 @@
 use std::{
     fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::{
+    all_parts,
+    authors::bin_name,
+    data_dir, inputs, notes, read_only,
+    scaffold::{load_template, render},
+    today, Day, Event, Year,
+};
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+pub(crate) fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -27,22 +31,34 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    // Ensure directories exist
-    if let Err(e) = fs::create_dir_all("data/inputs") {
-        eprintln!("Failed to create data/inputs directory: {e}");
-        process::exit(1);
-    }
-    if let Err(e) = fs::create_dir_all("data/samples") {
-        eprintln!("Failed to create data/samples directory: {e}");
+pub fn handle(day: Day, overwrite: bool, author: Option<&str>, template: Option<&str>) {
+    read_only::refuse("scaffold a day");
+
+    let event = Event::current();
+    if day.into_inner() > event.quest_count() {
+        eprintln!("{event} only has {} quests.", event.quest_count());
         process::exit(1);
     }
-    if let Err(e) = fs::create_dir_all("data/descriptions") {
-        eprintln!("Failed to create data/descriptions directory: {e}");
-        process::exit(1);
+
+    let template = match load_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load the template: {e}");
+            process::exit(1);
+        }
+    };
+
+    // Ensure directories exist
+    let data_dir = data_dir();
+    for folder in ["inputs", "samples", "answers", "descriptions"] {
+        let dir = data_dir.join(folder);
+        if let Err(e) = fs::create_dir_all(&dir) {
+            eprintln!("Failed to create {} directory: {e}", dir.display());
+            process::exit(1);
+        }
     }
 
-    let module_path = format!("src/bin/{day}.rs");
+    let module_path = format!("src/bin/{}.rs", bin_name(day, author));
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -52,11 +68,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(render(&template, day, Year::current(), &today()).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -67,9 +79,17 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     // Create input and sample files for all 3 parts
-    for part in 1..=3 {
-        let input_path = format!("data/inputs/{day}-{part}.txt");
-        let sample_path = format!("data/samples/{day}-{part}.txt");
+    for part in all_parts() {
+        let input_path = format!("{}/inputs/{day}-{part}.txt", data_dir.display());
+        let sample_path = format!("{}/samples/{day}-{part}.txt", data_dir.display());
+
+        // other authors share the quest's files, keep them if they already exist.
+        if author.is_some()
+            && inputs::exists(Path::new(&input_path))
+            && Path::new(&sample_path).exists()
+        {
+            continue;
+        }
 
         match create_file(&input_path) {
             Ok(_) => {
@@ -92,6 +112,31 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    // notes are never overwritten, they are shared by all authors of a quest.
+    let note_path = notes::note_path(day);
+    if !note_path.exists() {
+        let note = notes::render(day, notes::quest_title(day).as_deref());
+        match fs::create_dir_all(notes::notes_dir()).and_then(|()| fs::write(&note_path, note)) {
+            Ok(()) => println!("Created notes file \"{}\"", note_path.display()),
+            Err(e) => {
+                eprintln!("Failed to create notes file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let mut command = format!("cargo solve {day}");
+    if let Some(author) = author {
+        command.push_str(&format!(" --author {author}"));
+    }
+    match Event::current() {
+        Event::Story { id } => command.push_str(&format!(" --story {id}")),
+        Event::MainEvent { year } if year != Year::home() => {
+            command.push_str(&format!(" --year {year}"));
+        }
+        Event::MainEvent { .. } => {}
+    }
+
     println!("---");
-    println!("🎯 Type `cargo solve {day}` to run your solution.");
+    println!("🎯 Type `{command}` to run your solution.");
 }
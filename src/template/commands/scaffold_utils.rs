@@ -0,0 +1,104 @@
+use std::{
+    fs,
+    io::{ErrorKind, Write},
+    path::Path,
+    process,
+};
+
+use crate::template::{
+    read_only,
+    util_modules::{self, declare_module, PRELUDE_HEADER},
+};
+
+use super::scaffold::safe_create_file;
+
+const UTILS_HEADER: &str =
+    "//! Reusable helpers for solutions, available as `everybody_codes::utils`.\n\n";
+
+pub fn handle(preset: &str, overwrite: bool) {
+    read_only::refuse("scaffold utility modules");
+
+    let modules = match util_modules::preset(preset) {
+        Ok(modules) => modules,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = fs::create_dir_all("src/utils") {
+        eprintln!("Failed to create \"src/utils\": {e}");
+        process::exit(1);
+    }
+
+    let mut generated = vec![];
+    for module in modules {
+        let path = format!("src/utils/{}.rs", module.name);
+        let mut file = match safe_create_file(&path, overwrite) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                println!("Skipped \"{path}\", it exists already.");
+                continue;
+            }
+            Err(e) => {
+                eprintln!("Failed to create module file: {e}");
+                process::exit(1);
+            }
+        };
+        if let Err(e) = file.write_all(module.source.as_bytes()) {
+            eprintln!("Failed to write module contents: {e}");
+            process::exit(1);
+        }
+        println!("Created module \"{path}\"");
+        generated.push(module);
+    }
+
+    if generated.is_empty() {
+        return;
+    }
+
+    let wired = update("src/lib.rs", "", |s| {
+        declare_module(s, "utils").unwrap_or_else(|| s.to_string())
+    })
+    .and_then(|()| {
+        update("src/utils/mod.rs", UTILS_HEADER, |s| {
+            ["prelude"]
+                .iter()
+                .chain(generated.iter().map(|m| &m.name))
+                .fold(s.to_string(), |s, name| {
+                    declare_module(&s, name).unwrap_or(s)
+                })
+        })
+    })
+    .and_then(|()| {
+        update("src/utils/prelude.rs", PRELUDE_HEADER, |s| {
+            generated
+                .iter()
+                .fold(s.to_string(), |s, m| util_modules::add_exports(&s, m))
+        })
+    });
+
+    if let Err(e) = wired {
+        eprintln!("Failed to declare the modules: {e}");
+        process::exit(1);
+    }
+    println!("Declared the modules in \"src/utils/mod.rs\" and \"src/utils/prelude.rs\"");
+
+    println!("---");
+    println!("🧰 Add `use everybody_codes::utils::prelude::*;` to a solution to use them.");
+    println!("🧪 Type `cargo test --features test_lib` to run their tests.");
+}
+
+/// Rewrite the file at `path`, starting from `default` if it doesn't exist yet.
+fn update(path: &str, default: &str, f: impl FnOnce(&str) -> String) -> std::io::Result<()> {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) if e.kind() == ErrorKind::NotFound => default.to_string(),
+        Err(e) => return Err(e),
+    };
+    let updated = f(&source);
+    if updated == source && Path::new(path).exists() {
+        return Ok(());
+    }
+    fs::write(path, updated)
+}
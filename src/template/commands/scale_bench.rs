@@ -0,0 +1,50 @@
+use std::{
+    path::Path,
+    process::{self, Command},
+};
+
+use crate::template::{
+    authors::{bin_name, get_path_for_author_bin},
+    Day, Part,
+};
+
+pub fn handle(
+    day: Day,
+    part: Option<Part>,
+    size: Option<usize>,
+    enforce: bool,
+    author: Option<&str>,
+) {
+    if !Path::new(&get_path_for_author_bin(day, author)).exists() {
+        eprintln!("No solution for day {day}, scaffold one with `cargo scaffold {day}`.");
+        process::exit(1);
+    }
+
+    let mut cmd = Command::new("cargo");
+    cmd.args([
+        "run",
+        "--quiet",
+        "--release",
+        "--bin",
+        &bin_name(day, author),
+    ]);
+    cmd.args(["--", "--scale-bench"]);
+    if let Some(part) = part {
+        cmd.args(["--part", &part.to_string()]);
+    }
+    if let Some(size) = size {
+        cmd.args(["--size", &size.to_string()]);
+    }
+    if enforce {
+        cmd.arg("--enforce");
+    }
+
+    match cmd.status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to run the solution: {e}");
+            process::exit(1);
+        }
+    }
+}
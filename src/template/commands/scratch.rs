@@ -0,0 +1,47 @@
+use std::{io::Write, process};
+
+use crate::template::{read_only, Day};
+
+use super::scaffold::safe_create_file;
+
+const SCRATCH_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/src/scratch_template.txt"
+));
+
+#[must_use]
+pub fn get_scratch_bin_name(day: Day) -> String {
+    format!("scratch_{day}")
+}
+
+pub fn handle(day: Day, overwrite: bool) {
+    read_only::refuse("create a scratch file");
+
+    let bin_name = get_scratch_bin_name(day);
+    let module_path = format!("src/bin/{bin_name}.rs");
+
+    let mut file = match safe_create_file(&module_path, overwrite) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("Failed to create scratch file: {e}");
+            process::exit(1);
+        }
+    };
+
+    match file.write_all(
+        SCRATCH_TEMPLATE
+            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+            .as_bytes(),
+    ) {
+        Ok(()) => {
+            println!("Created scratch file \"{}\"", &module_path);
+        }
+        Err(e) => {
+            eprintln!("Failed to write scratch contents: {e}");
+            process::exit(1);
+        }
+    }
+
+    println!("---");
+    println!("🧮 Type `cargo run --bin {bin_name}` to run your scratchpad.");
+}
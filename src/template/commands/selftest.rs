@@ -0,0 +1,203 @@
+use std::{
+    env, fs,
+    path::Path,
+    process::{self, Command, Stdio},
+};
+
+use crate::template::{
+    all_parts,
+    answers::get_answer_path,
+    commands::scaffold,
+    ec_cli, read_only, readme_benchmarks,
+    run_multi::child_commands::{parse_answer, parse_exec_time, run_solution},
+    timings::Timings,
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+const MANIFEST_DIR: &str = env!("CARGO_MANIFEST_DIR");
+
+type Step = fn(Day) -> Result<(), String>;
+
+/// Synthetic quest: sum, maximum and count of the numbers in the input.
+const STUB_PARTS: [&str; 3] = [
+    "Some(input.lines().filter_map(|l| l.parse::<u64>().ok()).sum())",
+    "input.lines().filter_map(|l| l.parse::<u64>().ok()).max()",
+    "Some(input.lines().count() as u64)",
+];
+const STUB_INPUT: &str = "3\n1\n4\n1\n5\n9\n2\n6\n";
+const STUB_ANSWERS: [&str; 3] = ["31", "9", "8"];
+const STUB_SAMPLE: &str = "1\n2\n3\n";
+const STUB_SAMPLE_ANSWERS: [&str; 3] = ["6", "3", "3"];
+
+const README: &str =
+    "# Self-test\n\n<!--- benchmarking table --->\n<!--- benchmarking table --->\n";
+
+pub fn handle(keep: bool) {
+    let day = Day::new(1).unwrap();
+    let repo_dir = env::current_dir().unwrap();
+    let dir = env::temp_dir().join(format!("ec-selftest-{}", process::id()));
+
+    println!(
+        "{ANSI_BOLD}Running self-test in \"{}\"{ANSI_RESET}",
+        dir.display()
+    );
+    println!("------");
+
+    if let Err(e) = create_project(&dir) {
+        eprintln!("Failed to create temporary project: {e}");
+        process::exit(1);
+    }
+
+    // reuse build artifacts of the template between self-test runs.
+    env::set_var(
+        "CARGO_TARGET_DIR",
+        Path::new(MANIFEST_DIR).join("target").join("selftest"),
+    );
+    env::set_current_dir(&dir).unwrap();
+    // the temporary project is thrown away, so the self-test may write to it.
+    read_only::disable();
+
+    let steps: [(&str, Step); 3] = [
+        ("Scaffold quest", scaffold_stub),
+        ("Run tests against samples", run_tests),
+        ("Run, time and render benchmarks", run_stub),
+    ];
+
+    let mut results = vec![];
+    for (name, step) in steps {
+        let result = step(day);
+        let failed = result.is_err();
+        results.push((name, result));
+        if failed {
+            break;
+        }
+    }
+
+    println!();
+    let mut passed = true;
+    for (name, result) in &results {
+        match result {
+            Ok(()) => println!("✅ {name}"),
+            Err(e) => {
+                passed = false;
+                println!("❌ {name}: {e}");
+            }
+        }
+    }
+
+    match ec_cli::check() {
+        #[cfg(not(feature = "http_client"))]
+        Ok(()) => println!("✅ ec-cli is installed"),
+        #[cfg(feature = "http_client")]
+        Ok(()) => println!("✅ session token found"),
+        Err(e) => println!("⚠️  {e} Downloading and submitting will not work."),
+    }
+
+    env::set_current_dir(repo_dir).unwrap();
+    if keep {
+        println!("Kept temporary project in \"{}\".", dir.display());
+    } else {
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    if !passed {
+        process::exit(1);
+    }
+
+    println!("---");
+    println!("✨ Your setup works, good luck!");
+}
+
+fn create_project(dir: &Path) -> Result<(), std::io::Error> {
+    fs::create_dir_all(dir.join("src").join("bin"))?;
+    // like a fresh checkout of the template, which keeps its data directly in `data/`.
+    fs::create_dir_all(dir.join("data").join("inputs"))?;
+    fs::create_dir_all(dir.join("data").join("answers"))?;
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"ec-selftest\"\nversion = \"0.1.0\"\nedition = \"2021\"\npublish = false\n\n\
+             [dependencies]\neverybody_codes = {{ path = {MANIFEST_DIR:?} }}\n\n[workspace]\n"
+        ),
+    )?;
+    fs::write(dir.join("README.md"), README)
+}
+
+/// Scaffold the quest with the regular template, then fill in the synthetic solution and data.
+fn scaffold_stub(day: Day) -> Result<(), String> {
+    scaffold::handle(day, true, None, None);
+
+    let path = format!("src/bin/{day}.rs");
+    let mut source = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    for stub in STUB_PARTS {
+        if !source.contains("    None\n") {
+            return Err("template does not contain a `None` part body.".into());
+        }
+        source = source.replacen("    None\n", &format!("    {stub}\n"), 1);
+    }
+
+    let write = |path: String, contents: &str| {
+        fs::write(&path, contents).map_err(|e| format!("{path}: {e}"))
+    };
+
+    write(path, &source)?;
+    for part in all_parts() {
+        write(format!("data/inputs/{day}-{part}.txt"), STUB_INPUT)?;
+        write(format!("data/samples/{day}-{part}.txt"), STUB_SAMPLE)?;
+        write(
+            get_answer_path(day, part).display().to_string(),
+            STUB_SAMPLE_ANSWERS[part.index()],
+        )?;
+    }
+
+    Ok(())
+}
+
+fn run_tests(day: Day) -> Result<(), String> {
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .stdout(Stdio::null())
+        .status()
+        .map_err(|e| e.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err("`cargo test` failed.".into())
+    }
+}
+
+/// Benchmark the solution, check its answers and render the README benchmark table.
+fn run_stub(day: Day) -> Result<(), String> {
+    let output = run_solution(day, true, true).map_err(|e| format!("{e:?}"))?;
+
+    for (part, expected) in all_parts().zip(STUB_ANSWERS) {
+        let answer = parse_answer(&output, part);
+        if answer.as_deref() != Some(expected) {
+            return Err(format!(
+                "expected part {part} to be {expected}, got {answer:?}."
+            ));
+        }
+    }
+
+    let timing = parse_exec_time(&output, day);
+    if timing.part_1.is_none() || timing.part_2.is_none() || timing.part_3.is_none() {
+        return Err("could not parse timings.".into());
+    }
+
+    readme_benchmarks::update(
+        &Timings {
+            data: vec![timing],
+            history: vec![],
+        },
+        &[day],
+    )
+    .map_err(|e| format!("could not render README: {e:?}"))?;
+
+    let readme = fs::read_to_string("README.md").map_err(|e| e.to_string())?;
+    if !readme.contains("| [Day 1]") {
+        return Err("README benchmark table was not rendered.".into());
+    }
+
+    Ok(())
+}
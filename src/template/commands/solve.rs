@@ -1,21 +1,95 @@
-use std::process::{Command, Stdio};
+use std::{
+    env, fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::Instant,
+};
 
-use crate::template::Day;
+use crate::template::{
+    all_days,
+    authors::bin_name,
+    json_output,
+    notify::notify_if_slow,
+    read_only,
+    recording::{Recording, Utf8Chunks},
+    run_multi::get_path_for_bin,
+    Day, Part,
+};
 
-pub fn handle(day: Day, release: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Option<Day>,
+    release: bool,
+    submit_part: Option<Part>,
+    author: Option<&str>,
+    record: bool,
+    explain: bool,
+    checks: bool,
+    input: Option<&str>,
+    sample: bool,
+    timeout: Option<u64>,
+    json: bool,
+    chrome_trace: Option<&str>,
+) {
+    if json {
+        json_output::enable();
+    }
+
+    let day = day.unwrap_or_else(infer_day);
+    let bin = bin_name(day, author);
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin.clone()];
 
     if release {
         cmd_args.push("--release".to_string());
     }
 
-    cmd_args.push("--".to_string());
+    let mut solution_args = vec![];
 
     if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+        solution_args.push("--submit".to_string());
+        solution_args.push(submit_part.to_string());
+    }
+
+    if explain {
+        solution_args.push("--explain".to_string());
+    }
+
+    if checks {
+        solution_args.push("--checks".to_string());
+    }
+
+    if let Some(input) = input {
+        solution_args.push("--input".to_string());
+        solution_args.push(input.to_string());
+    }
+
+    if sample {
+        solution_args.push("--sample".to_string());
+    }
+
+    if let Some(timeout) = timeout {
+        solution_args.push("--timeout".to_string());
+        solution_args.push(timeout.to_string());
     }
 
+    if let Some(chrome_trace) = chrome_trace {
+        solution_args.push("--chrome-trace".to_string());
+        solution_args.push(chrome_trace.to_string());
+    }
+
+    if record {
+        read_only::refuse("store recordings");
+        run_recorded(&bin, release, &solution_args, day);
+        return;
+    }
+
+    cmd_args.push("--".to_string());
+    cmd_args.extend(solution_args);
+
+    let started = Instant::now();
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -23,5 +97,135 @@ pub fn handle(day: Day, release: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+    notify_if_slow(&format!("Day {day}"), started.elapsed(), status.success());
+}
+
+/// The quest to solve when no day was given: today's quest during the event, or the one whose
+/// solution was edited last.
+fn infer_day() -> Day {
+    #[cfg(feature = "today")]
+    if let Some(day) = Day::today() {
+        eprintln!("No day given, solving today's quest: day {day}.");
+        return day;
+    }
+
+    let last_edited = all_days()
+        .filter_map(|day| {
+            let modified = fs::metadata(get_path_for_bin(day)).ok()?.modified().ok()?;
+            Some((modified, day))
+        })
+        .max();
+
+    match last_edited {
+        Some((_, day)) => {
+            eprintln!("No day given, solving the last edited quest: day {day}.");
+            day
+        }
+        None => {
+            eprintln!(
+                "No day given and no scaffolded quest found. Run `cargo scaffold <day>` first."
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Run the solution while forwarding and recording its output as an asciinema cast.
+/// The binary is built up front and invoked directly, to keep compiler output out of the
+/// recording.
+fn run_recorded(bin: &str, release: bool, solution_args: &[String], day: Day) {
+    let exe = build_bin(bin, release);
+
+    let started = Instant::now();
+    let mut cmd = Command::new(exe)
+        .args(solution_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut recording = Recording::start(&format!("Everybody Codes - Day {day}"));
+    let (tx, rx) = mpsc::channel::<(Instant, Vec<u8>, bool)>();
+
+    let readers = [
+        (
+            Box::new(cmd.stdout.take().unwrap()) as Box<dyn Read + Send>,
+            false,
+        ),
+        (Box::new(cmd.stderr.take().unwrap()), true),
+    ]
+    .map(|(mut reader, is_stderr)| {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let mut buf = [0; 4096];
+            while let Ok(n) = reader.read(&mut buf) {
+                if n == 0
+                    || tx
+                        .send((Instant::now(), buf[..n].to_vec(), is_stderr))
+                        .is_err()
+                {
+                    break;
+                }
+            }
+        })
+    });
+    drop(tx);
+
+    let (mut stdout_chunks, mut stderr_chunks) = (Utf8Chunks::default(), Utf8Chunks::default());
+
+    for (at, bytes, is_stderr) in rx {
+        if is_stderr {
+            let _ = io::stderr().write_all(&bytes);
+            recording.output(at, &stderr_chunks.decode(&bytes).replace('\n', "\r\n"));
+        } else {
+            let _ = io::stdout().write_all(&bytes);
+            let _ = io::stdout().flush();
+            recording.output(at, &stdout_chunks.decode(&bytes).replace('\n', "\r\n"));
+        }
+    }
+
+    for reader in readers {
+        let _ = reader.join();
+    }
+    let status = cmd.wait().unwrap();
+    notify_if_slow(&format!("Day {day}"), started.elapsed(), status.success());
+
+    match recording.store_file(bin) {
+        Ok(path) => println!("Recorded session to \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("Failed to write recording: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Build a solution binary and return the path of the executable, exiting if the build fails.
+pub(crate) fn build_bin(bin: &str, release: bool) -> PathBuf {
+    try_build_bin(bin, release).unwrap_or_else(|| process::exit(1))
+}
+
+/// Build a solution binary and return the path of the executable, `None` if the build fails.
+pub(crate) fn try_build_bin(bin: &str, release: bool) -> Option<PathBuf> {
+    let mut build_args = vec!["build", "--quiet", "--bin", bin];
+    if release {
+        build_args.push("--release");
+    }
+
+    let status = Command::new("cargo").args(&build_args).status().ok()?;
+    if !status.success() {
+        return None;
+    }
+
+    Some(bin_path(bin, release))
+}
+
+/// The path of a built solution binary.
+pub(crate) fn bin_path(bin: &str, release: bool) -> PathBuf {
+    let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+    let profile = if release { "release" } else { "debug" };
+    Path::new(&target_dir)
+        .join(profile)
+        .join(bin)
+        .with_extension(env::consts::EXE_EXTENSION)
 }
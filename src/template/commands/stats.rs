@@ -0,0 +1,147 @@
+use std::{
+    fs,
+    path::Path,
+    process::{self, Command},
+};
+
+use super::solve::try_build_bin;
+use crate::template::{
+    all_days_for, all_parts,
+    answers::{get_encrypted_real_answer_path, get_real_answer_path},
+    authors::bin_name,
+    json_output::Record,
+    read_only,
+    run_multi::get_path_for_bin,
+    sample_paths,
+    stats::{implemented_parts, SampleStatus},
+    timings::Timings,
+    Day, Event, Part, ANSI_BOLD, ANSI_RESET,
+};
+
+pub fn handle() {
+    let days: Vec<Day> = all_days_for(Event::current())
+        .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+        .collect();
+
+    if days.is_empty() {
+        eprintln!("No scaffolded days found. Run `cargo scaffold <day>` first.");
+        process::exit(1);
+    }
+
+    let timings = Timings::read_from_file();
+
+    println!("{ANSI_BOLD}Day   Part  Solution  Answer  Samples  Runtime{ANSI_RESET}");
+    println!("------------------------------------------------");
+
+    let (mut parts, mut implemented, mut answered, mut passing) = (0, 0, 0, 0);
+    for day in days {
+        let source = match fs::read_to_string(get_path_for_bin(day)) {
+            Ok(source) => source,
+            Err(e) => {
+                println!("{day}    -     ❌ {e}");
+                continue;
+            }
+        };
+        let solved = implemented_parts(&source);
+        let timing = timings.latest(day);
+        // only quests with an implemented part are built, to run them on their samples.
+        let exe = solved
+            .contains(&true)
+            .then(|| try_build_bin(&bin_name(day, None), false));
+
+        for part in all_parts() {
+            let has_answer = has_real_answer(day, part);
+            parts += 1;
+            implemented += usize::from(solved[part.index()]);
+            answered += usize::from(has_answer);
+
+            let samples = match (solved[part.index()], &exe) {
+                (false, _) => "-".to_string(),
+                (true, Some(None)) => "❌ build".to_string(),
+                (true, Some(Some(exe))) => match sample_status(exe, day, part) {
+                    SampleStatus::Missing => "-".to_string(),
+                    SampleStatus::Unanswered => "⚠️".to_string(),
+                    SampleStatus::Checked { passed, total } if passed == total => {
+                        passing += 1;
+                        format!("✅ {total}")
+                    }
+                    SampleStatus::Checked { passed, total } => format!("❌ {passed}/{total}"),
+                },
+                (true, None) => unreachable!("quests with an implemented part are built"),
+            };
+            let runtime = timing.and_then(|t| t.part(part)).unwrap_or("-");
+
+            println!(
+                "{day}    {part}     {}  {}  {}  {runtime}",
+                pad(mark(solved[part.index()]), 8),
+                pad(mark(has_answer), 6),
+                pad(&samples, 7),
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "{implemented}/{parts} parts implemented, {passing}/{implemented} passing their samples, \
+        {answered}/{parts} real answers recorded."
+    );
+}
+
+fn mark(done: bool) -> &'static str {
+    if done {
+        "✅"
+    } else {
+        "-"
+    }
+}
+
+/// `text` padded to `width` columns, counting `✅` as two.
+fn pad(text: &str, width: usize) -> String {
+    let columns = text.chars().count() + text.matches('✅').count();
+    format!("{text}{}", " ".repeat(width.saturating_sub(columns)))
+}
+
+/// Whether an answer to the real input was recorded, encrypted or not.
+fn has_real_answer(day: Day, part: Part) -> bool {
+    [
+        get_real_answer_path(day, part),
+        get_encrypted_real_answer_path(day, part),
+    ]
+    .iter()
+    .any(|path| fs::metadata(path).is_ok_and(|m| m.len() > 0))
+}
+
+/// Run a part on each of its samples with `--sample` and compare the answers with the expected
+/// ones, paired by file name.
+fn sample_status(exe: &Path, day: Day, part: Part) -> SampleStatus {
+    let read = |path: &Path| fs::read_to_string(path).unwrap_or_default();
+    let paths = sample_paths("samples", day, part);
+    let samples: Vec<String> = paths.iter().map(|path| read(path)).collect();
+    let expected: Vec<String> = paths
+        .iter()
+        .filter_map(|sample| sample.file_name())
+        .map(|name| read(&read_only::quest_file(Path::new("answers").join(name))))
+        .collect();
+
+    let status = SampleStatus::new(&samples, &expected, &[]);
+    if !matches!(status, SampleStatus::Checked { .. }) {
+        return status;
+    }
+
+    // one record per sample, in the order of `sample_paths`.
+    let output = Command::new(exe)
+        .args(["--sample", "--part", &part.to_string()])
+        .env("EC_OUTPUT", "json")
+        .output();
+    let answers: Vec<Option<String>> = output
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(Record::parse)
+                .filter(|record| record.part == part)
+                .map(|record| record.answer)
+                .collect()
+        })
+        .unwrap_or_default();
+    SampleStatus::new(&samples, &expected, &answers)
+}
@@ -0,0 +1,74 @@
+use std::{path::Path, process};
+
+use super::contest::{confirm, run_part};
+use crate::template::{
+    answers::{read_real_answer, store_real_answer},
+    authors::get_path_for_author_bin,
+    ec_cli,
+    input_snapshot::InputSnapshot,
+    read_only, Day, Part, ANSI_BOLD, ANSI_RESET,
+};
+
+/// How often a part is run before giving up on an input that keeps changing.
+const MAX_RUNS: usize = 3;
+
+pub fn handle(day: Day, part: Part, author: Option<&str>, yes: bool) {
+    read_only::refuse("submit answers");
+    ec_cli::require();
+
+    if !Path::new(&get_path_for_author_bin(day, author)).exists() {
+        eprintln!("Day {day} has not been scaffolded yet. Run `cargo scaffold {day}` first.");
+        process::exit(1);
+    }
+
+    let mut runs = 0;
+    let answer = loop {
+        runs += 1;
+        let snapshot = InputSnapshot::take(day, part);
+
+        println!("Running day {day} part {part}...");
+        let Some(answer) = run_part(day, part, author) else {
+            eprintln!("Part {part} did not produce an answer, nothing to submit.");
+            process::exit(1);
+        };
+
+        println!("Answer: {ANSI_BOLD}{answer}{ANSI_RESET}");
+
+        if let Some(known) = read_real_answer(day, part) {
+            if known == answer {
+                println!("Part {part} is already solved with this answer.");
+            } else {
+                println!("Part {part} is already solved with \"{known}\".");
+            }
+            return;
+        }
+
+        if !yes && !confirm("Submit this answer? [y/N] ") {
+            println!("Not submitted.");
+            return;
+        }
+
+        // the input may have been downloaded again while the part ran or the prompt waited.
+        if !snapshot.is_stale() {
+            break answer;
+        }
+        if runs == MAX_RUNS {
+            eprintln!("Not submitted: the input of part {part} keeps changing.");
+            process::exit(1);
+        }
+        println!(
+            "⚠️  The input of part {part} changed since the answer was computed, running it again."
+        );
+    };
+
+    println!("Submitting result...");
+    if let Err(e) = ec_cli::submit(day, part, &answer) {
+        eprintln!("Submission failed: {e}");
+        process::exit(1);
+    }
+
+    match store_real_answer(day, part, &answer) {
+        Ok(path) => println!("Recorded answer to \"{}\".", path.display()),
+        Err(e) => eprintln!("Failed to record answer: {e}"),
+    }
+}
@@ -0,0 +1,40 @@
+use std::process;
+
+use crate::template::{contest::now, ec_cli, pending::PendingDownloads};
+
+pub fn handle() {
+    let mut pending = PendingDownloads::read_from_file();
+    if pending.is_empty() {
+        println!("No pending downloads.");
+        return;
+    }
+
+    ec_cli::require();
+
+    let days = pending.days();
+    println!("Retrying {} pending download(s)...", days.len());
+
+    for day in days {
+        match ec_cli::download(day) {
+            Ok(_) => pending.remove(day),
+            Err(e) => {
+                eprintln!("Day {day}: {e}");
+                pending.add(day, e.to_string(), now());
+            }
+        }
+    }
+
+    if let Err(e) = pending.store_file() {
+        eprintln!("Failed to update the pending downloads: {e}");
+        process::exit(1);
+    }
+
+    println!();
+    if pending.is_empty() {
+        println!("✅ All pending downloads are done.");
+    } else {
+        let days: Vec<String> = pending.days().iter().map(ToString::to_string).collect();
+        eprintln!("❌ Still pending: day(s) {}.", days.join(", "));
+        process::exit(1);
+    }
+}
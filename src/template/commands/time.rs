@@ -1,19 +1,41 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process, time::Duration};
 
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::timings::{TimingRun, Timings};
+use crate::template::{
+    all_days_for, contest::now, json_output, plugins, read_only, readme_benchmarks, share, sysinfo,
+    Day, DaySet, Event, ANSI_BOLD, ANSI_RESET,
+};
+
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    share: bool,
+    days: Option<DaySet>,
+    json: bool,
+    check: Option<f64>,
+) {
+    if json {
+        json_output::enable();
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
+    let mut days_to_run = day.map_or_else(
         || {
-            if run_all {
-                all_days().collect()
+            if check.is_some() {
+                // the regression check compares against the stored timings.
+                stored_timings
+                    .data
+                    .iter()
+                    .map(|timing| timing.day)
+                    .collect()
+            } else if run_all {
+                all_days_for(Event::current()).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_days_for(Event::current())
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,20 +43,131 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    if let Some(days) = days {
+        days_to_run = days.filter(days_to_run);
+    }
+
+    let mut timings = run_multi(&days_to_run, true, true, 1).unwrap();
+    let machine = sysinfo::capture();
+    for timing in &mut timings.data {
+        timing.machine.clone_from(&machine);
+    }
+    for timing in &timings.data {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        plugins::emit(&plugins::Event::Benchmarked {
+            day: timing.day,
+            parts: [
+                timing.part_1.as_deref(),
+                timing.part_2.as_deref(),
+                timing.part_3.as_deref(),
+            ],
+            total: Duration::from_nanos(timing.total_nanos as u64),
+        });
+    }
+
+    // keep stdout to the JSON records, so it can be piped into other tools.
+    let status = |message: &str| {
+        if json {
+            eprintln!("{message}");
+        } else {
+            println!("{message}");
+        }
+    };
+
+    // timings from another machine are not comparable, so point out what changed.
+    if let Some(machine) = &machine {
+        let days: Vec<Day> = timings.data.iter().map(|timing| timing.day).collect();
+        for (days, changes) in stored_timings.machine_changes(machine, &days) {
+            let days: Vec<String> = days.iter().map(Day::to_string).collect();
+            status("");
+            status(&format!(
+                "Day {} {} last stored on a different machine:",
+                days.join(", "),
+                if days.len() == 1 { "was" } else { "were" }
+            ));
+            for (field, stored, current) in changes {
+                status(&format!("  {field}: {stored} → {current}"));
+            }
+        }
+    }
+
+    let regressions = check.map(|threshold| stored_timings.regressions(&timings, threshold));
+    if let (Some(regressions), Some(threshold)) = (&regressions, check) {
+        status("");
+        if regressions.is_empty() {
+            status(&format!(
+                "✅ No part got more than {threshold}% slower than its stored timing."
+            ));
+        } else {
+            status(&format!(
+                "{ANSI_BOLD}Regressions{ANSI_RESET} (more than {threshold}% slower)"
+            ));
+            status("------");
+            for regression in regressions {
+                status(&format!(
+                    "Day {} Part {}: {} → {} (+{:.1}%)",
+                    regression.day,
+                    regression.part,
+                    regression.stored,
+                    regression.current,
+                    regression.slowdown
+                ));
+            }
+        }
+    }
+
+    if read_only::is_enabled() {
+        if store {
+            status("");
+            status("Read-only mode, benchmarks were not stored.");
+        }
+    } else {
+        // every run is added to the history, only `--store` replaces the latest timings.
+        let mut updated_timings = if store {
+            stored_timings.merge(&timings)
+        } else {
+            stored_timings
+        };
+        updated_timings
+            .history
+            .push(TimingRun::new(timings.data.clone(), now()));
+        updated_timings.store_file().unwrap();
 
-    if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        if store {
+            // only the rows of the days that just ran are rewritten.
+            let days: Vec<Day> = timings.data.iter().map(|timing| timing.day).collect();
+            status("");
+            match readme_benchmarks::update(&updated_timings, &days) {
+                Ok(()) => {
+                    status("Stored updated benchmarks.");
+                }
+                Err(_) => {
+                    eprintln!("Failed to store updated benchmarks.");
+                }
+            }
+        }
+    }
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
+    if share {
+        status("");
+        match share::share(&timings) {
+            Ok(standings) => {
+                status(&format!("{ANSI_BOLD}Community standings{ANSI_RESET}"));
+                status("------");
+                for standing in standings {
+                    status(&format!(
+                        "Day {} Part {}: faster than {:.1}% of shared timings",
+                        standing.day, standing.part, standing.percentile
+                    ));
+                }
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to share benchmarks: {e}");
             }
         }
     }
+
+    if regressions.is_some_and(|regressions| !regressions.is_empty()) {
+        process::exit(1);
+    }
 }
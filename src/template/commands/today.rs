@@ -0,0 +1,89 @@
+use std::{
+    env,
+    path::Path,
+    process::{self, Command},
+};
+
+use super::{download, read, scaffold};
+use crate::template::{authors::get_path_for_author_bin, set_year, Date, Day, Year};
+
+/// Work on today's quest, or on the quest of `day` or of `date` when given.
+pub fn handle(day: Option<Day>, date: Option<Date>, edit: bool) {
+    let day = match (day, date) {
+        (Some(day), _) => day,
+        (None, Some(date)) => {
+            let Some(day) = Day::on_date(date) else {
+                eprintln!(
+                    "No quest is released on {date}, the event runs on the weekdays of the four weeks from the first Monday of November."
+                );
+                process::exit(1);
+            };
+            // the date also picks the event, unless `--year` did.
+            if let Some(year) = Year::new(date.year) {
+                set_year(year);
+            }
+            day
+        }
+        (None, None) => current_day(),
+    };
+
+    let module_path = get_path_for_author_bin(day, None);
+    // rerunning `today` later in the day keeps the solution and fetches what was unlocked since.
+    if Path::new(&module_path).exists() {
+        println!("Day {day} is already scaffolded in \"{module_path}\".");
+    } else {
+        scaffold::handle(day, false, None, None);
+    }
+    download::handle(day);
+    read::handle(day, None, true);
+
+    if edit {
+        if let Err(e) = open_editor(&module_path) {
+            eprintln!("Failed to open \"{module_path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "today")]
+fn current_day() -> Day {
+    let Some(day) = Day::today() else {
+        eprintln!(
+            "`today` command can only be run during an active Everybody Codes event. \
+            Please pass the quest with `--day <day>` or `--date <date>`, or use `scaffold`."
+        );
+        process::exit(1);
+    };
+    day
+}
+
+#[cfg(not(feature = "today"))]
+fn current_day() -> Day {
+    eprintln!(
+        "Finding today's quest needs the clock of the `today` feature, which is not enabled. \
+        Enable it in Cargo.toml, or pass the quest with `--day <day>` or `--date <date>`."
+    );
+    process::exit(1);
+}
+
+/// Open `path` in `$VISUAL` or `$EDITOR`, which may include arguments like `code --wait`.
+fn open_editor(path: &str) -> Result<(), String> {
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .find_map(|var| env::var(var).ok().filter(|value| !value.trim().is_empty()))
+        .ok_or("set $EDITOR to the editor to use.")?;
+
+    let mut words = editor.split_whitespace();
+    let program = words.next().expect("editor is not blank");
+    let status = Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|e| format!("could not start `{editor}`: {e}"))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("`{editor}` exited with {status}"))
+    }
+}
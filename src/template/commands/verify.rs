@@ -0,0 +1,145 @@
+use std::{
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+use super::solve::try_build_bin;
+use crate::template::{
+    all_days, all_parts,
+    answers::read_real_answer,
+    authors::{bin_name, get_path_for_author_bin},
+    json_output::Record,
+    reduce::panic_location,
+    snapshots::{object_path, SnapshotIndex},
+    Date, Day, DaySet, Event, Part, ANSI_BOLD, ANSI_RESET,
+};
+
+/// The records of a run and where it panicked, or why it could not run.
+type Run = Result<(Vec<Record>, Option<String>), String>;
+
+pub fn handle(
+    days: Option<DaySet>,
+    author: Option<&str>,
+    timeout: Option<u64>,
+    as_of: Option<Date>,
+) {
+    let days_to_verify: Vec<Day> = all_days()
+        .filter(|day| days.as_ref().is_none_or(|days| days.contains(*day)))
+        .filter(|day| Path::new(&get_path_for_author_bin(*day, author)).exists())
+        .collect();
+
+    if days_to_verify.is_empty() {
+        eprintln!("No scaffolded days found. Run `cargo scaffold <day>` first.");
+        process::exit(1);
+    }
+
+    println!("{ANSI_BOLD}Day   Part  Status{ANSI_RESET}");
+    println!("----------------------------");
+
+    // with `--as-of`, parts run on the input their answer was accepted with back then.
+    let pinned = as_of.map(|as_of| (SnapshotIndex::read_from_file(), as_of));
+    let event = Event::current().slug();
+
+    let (mut passed, mut failed) = (0, 0);
+    for day in days_to_verify {
+        let expected: Vec<(Part, String, Option<PathBuf>)> = all_parts()
+            .filter_map(|part| match &pinned {
+                Some((index, as_of)) => {
+                    let snapshot = index.as_of(&event, day, part, *as_of)?;
+                    Some((part, snapshot.answer()?, Some(object_path(&snapshot.input))))
+                }
+                None => Some((part, read_real_answer(day, part)?, None)),
+            })
+            .collect();
+        if expected.is_empty() {
+            continue;
+        }
+
+        let Some(exe) = try_build_bin(&bin_name(day, author), true) else {
+            for (part, ..) in &expected {
+                println!("{day}    {part}     ❌ failed to build");
+            }
+            failed += expected.len();
+            continue;
+        };
+
+        // without pinned inputs, one run answers all parts.
+        let shared = pinned.is_none().then(|| run_bin(&exe, timeout, None));
+        for (part, expected, input) in expected {
+            let run = match &input {
+                Some(input) => &run_bin(&exe, timeout, Some((input, part))),
+                None => shared.as_ref().unwrap(),
+            };
+            match check_part(run, part, &expected) {
+                Ok(()) => {
+                    passed += 1;
+                    println!("{day}    {part}     ✅ {expected}");
+                }
+                Err(status) => {
+                    failed += 1;
+                    println!("{day}    {part}     ❌ {status}");
+                }
+            }
+        }
+    }
+
+    println!();
+    if passed + failed == 0 {
+        match &pinned {
+            Some((_, as_of)) => println!("No answers accepted by {as_of} in data/.snapshots."),
+            None => println!("No real answers recorded in data/real_answers."),
+        }
+    } else if failed > 0 {
+        eprintln!(
+            "{failed} of {} part(s) do not match their recorded answer.",
+            passed + failed
+        );
+        process::exit(1);
+    } else {
+        println!("All {passed} part(s) match their recorded answer.");
+    }
+}
+
+/// Whether a part returned the expected answer, or why not.
+fn check_part(run: &Run, part: Part, expected: &str) -> Result<(), String> {
+    let (records, panic) = run.as_ref().map_err(String::clone)?;
+    match records.iter().find(|record| record.part == part) {
+        Some(record) if record.answer.as_deref() == Some(expected) => Ok(()),
+        Some(Record {
+            error: Some(error), ..
+        }) => Err(error.clone()),
+        Some(Record {
+            answer: Some(answer),
+            ..
+        }) => Err(format!("got `{answer}`, expected `{expected}`")),
+        _ => Err(panic.as_ref().map_or("no answer".to_string(), |location| {
+            format!("panicked at {location}")
+        })),
+    }
+}
+
+/// Run all parts of a quest on the real input, or one part on another input, returning their
+/// records and where it panicked. Parts after a panic never ran, so they have no record.
+fn run_bin(exe: &Path, timeout: Option<u64>, input: Option<(&Path, Part)>) -> Run {
+    let mut cmd = Command::new(exe);
+    if let Some((path, part)) = input {
+        cmd.arg("--input")
+            .arg(path)
+            .args(["--part", &part.to_string()]);
+    }
+    if let Some(secs) = timeout {
+        cmd.args(["--timeout", &secs.to_string()]);
+    }
+    let output = cmd
+        .env("EC_OUTPUT", "json")
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    let records: Vec<Record> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(Record::parse)
+        .collect();
+
+    let panic = panic_location(&String::from_utf8_lossy(&output.stderr)).map(str::to_string);
+    Ok((records, panic))
+}
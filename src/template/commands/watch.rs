@@ -0,0 +1,95 @@
+use std::{
+    fs,
+    path::Path,
+    process::{self, Command},
+    thread,
+    time::Duration,
+};
+
+use super::solve::try_build_bin;
+use crate::template::{
+    all_parts,
+    answers::{get_answer_path, normalize},
+    authors::{bin_name, get_path_for_author_bin},
+    json_output::Record,
+    read_only,
+    reduce::panic_location,
+    watch::{render, watched_files, SampleRun, Snapshot},
+    Day, Part, ANSI_BOLD, ANSI_RESET,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+pub fn handle(day: Day, author: Option<&str>, timeout: u64) {
+    if !Path::new(&get_path_for_author_bin(day, author)).exists() {
+        eprintln!("No solution for day {day}, scaffold one with `cargo scaffold {day}`.");
+        process::exit(1);
+    }
+
+    let bin = bin_name(day, author);
+    let mut last = Snapshot::default();
+    let mut runs = 0;
+
+    loop {
+        let snapshot = Snapshot::take(watched_files(day, author));
+        if snapshot != last {
+            last = snapshot;
+            runs += 1;
+            run_samples(day, &bin, runs, timeout);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn run_samples(day: Day, bin: &str, runs: usize, timeout: u64) {
+    // clear the screen, so only the latest results are visible.
+    print!("\x1b[2J\x1b[H");
+    println!(
+        "{ANSI_BOLD}Day {day}{ANSI_RESET} · run {runs} · watching for changes, Ctrl-C to stop"
+    );
+    println!();
+
+    let Some(exe) = try_build_bin(bin, false) else {
+        println!();
+        println!("❌ Build failed");
+        return;
+    };
+
+    for part in all_parts() {
+        println!("{}", render(part, &run_sample(&exe, day, part, timeout)));
+    }
+}
+
+fn run_sample(exe: &Path, day: Day, part: Part, timeout: u64) -> SampleRun {
+    let sample = read_only::quest_file(format!("samples/{day}-{part}.txt"));
+    if fs::read_to_string(&sample).map_or(true, |sample| sample.trim().is_empty()) {
+        return SampleRun::NoSample;
+    }
+
+    let output = Command::new(exe)
+        .arg("--input")
+        .arg(&sample)
+        .args(["--part", &part.to_string()])
+        .args(["--timeout", &timeout.to_string()])
+        .env("EC_OUTPUT", "json")
+        .output()
+        .expect("failed to run the solution");
+
+    if let Some(location) = panic_location(&String::from_utf8_lossy(&output.stderr)) {
+        return SampleRun::Panicked(location.to_string());
+    }
+
+    let record = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(Record::parse)
+        .find(|record| record.part == part);
+
+    SampleRun::Ran {
+        answer: record.as_ref().and_then(|record| record.answer.clone()),
+        expected: fs::read_to_string(get_answer_path(day, part))
+            .ok()
+            .map(|raw| normalize(&raw).to_string())
+            .filter(|expected| !expected.is_empty()),
+        duration: record.and_then(|record| record.duration),
+    }
+}
@@ -0,0 +1,44 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::{urls, Day, Event};
+
+pub fn handle(day: Option<Day>, leaderboard: bool) {
+    let event = Event::current();
+    let url = match day {
+        _ if leaderboard => urls::leaderboard(event),
+        Some(day) => urls::quest(event, day),
+        None => urls::event(event),
+    };
+
+    println!("Opening {url}");
+    if let Err(e) = open(&url) {
+        eprintln!("Failed to open a browser: {e}");
+        process::exit(1);
+    }
+}
+
+/// Open `url` in the default browser.
+fn open(url: &str) -> Result<(), String> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        Command::new("xdg-open")
+    };
+
+    let status = cmd
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| format!("{e}, open the link above instead."))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{status}, open the link above instead."))
+    }
+}
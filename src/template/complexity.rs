@@ -0,0 +1,335 @@
+/// Expected growth of parts, checked against measurements by `cargo scale-bench`.
+///
+/// A part is annotated with its complexity class and the size of the real input, in the unit
+/// of the `size` the quest's `gen_input` generator takes:
+///
+/// ```ignore
+/// everybody_codes::solution!(19, gen_input = gen_input, complexity = [(1, "n", 1000), (3, "n^2", 150)]);
+/// ```
+///
+/// `cargo scale-bench 19` times each part on generated inputs up to that size and fits how its
+/// run time grows. With `--enforce`, it fails when a part grows noticeably faster than claimed,
+/// before the real input finds out.
+use std::{
+    env,
+    fmt::Display,
+    hint::black_box,
+    process,
+    str::FromStr,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use crate::template::{hooks, runner::should_run_part, Part, Solution};
+
+/// How much faster than its class a part may grow, as an exponent of `n`, before it fails.
+/// Generous, since small inputs are dominated by constant overhead.
+pub const TOLERANCE: f64 = 0.5;
+
+/// The size parts without an annotation are measured up to, unless `--size` is given.
+pub const DEFAULT_SIZE: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Complexity {
+    Constant,
+    Log,
+    Linear,
+    NLogN,
+    /// `n^k` for `k >= 2`.
+    Power(u32),
+    Exponential,
+}
+
+impl Complexity {
+    /// The natural logarithm of the cost of an input of size `n`.
+    #[allow(clippy::cast_precision_loss)]
+    fn ln_cost(self, n: usize) -> f64 {
+        let n = n.max(2) as f64;
+        let ln_log = n.ln().max(1.0).ln();
+        match self {
+            Complexity::Constant => 0.0,
+            Complexity::Log => ln_log,
+            Complexity::Linear => n.ln(),
+            Complexity::NLogN => n.ln() + ln_log,
+            Complexity::Power(k) => f64::from(k) * n.ln(),
+            Complexity::Exponential => n * std::f64::consts::LN_2,
+        }
+    }
+}
+
+impl FromStr for Complexity {
+    type Err = ComplexityFromStrError;
+
+    /// Parse a class like `n log n`, also written as `O(n log n)`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let class: String = s
+            .to_lowercase()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        let class = class
+            .strip_prefix("o(")
+            .and_then(|class| class.strip_suffix(')'))
+            .unwrap_or(&class);
+
+        match class {
+            "1" => Ok(Complexity::Constant),
+            "logn" => Ok(Complexity::Log),
+            "n" | "n^1" => Ok(Complexity::Linear),
+            "nlogn" => Ok(Complexity::NLogN),
+            "2^n" => Ok(Complexity::Exponential),
+            _ => class
+                .strip_prefix("n^")
+                .and_then(|k| k.parse().ok())
+                .filter(|&k| k >= 2)
+                .map(Complexity::Power)
+                .ok_or(ComplexityFromStrError),
+        }
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Complexity::Constant => write!(f, "1"),
+            Complexity::Log => write!(f, "log n"),
+            Complexity::Linear => write!(f, "n"),
+            Complexity::NLogN => write!(f, "n log n"),
+            Complexity::Power(k) => write!(f, "n^{k}"),
+            Complexity::Exponential => write!(f, "2^n"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Complexity`].
+#[derive(Debug)]
+pub struct ComplexityFromStrError;
+
+impl Display for ComplexityFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `1`, `log n`, `n`, `n log n`, `n^k` or `2^n`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The expected complexity of a part, up to the size of the real input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Annotation {
+    pub part: Part,
+    pub class: Complexity,
+    pub size: usize,
+}
+
+static ANNOTATIONS: OnceLock<Vec<Annotation>> = OnceLock::new();
+
+/// Register the annotations of the quest as `(part, class, size)`. Only the first registration
+/// takes effect.
+///
+/// # Panics
+/// If a part or class is invalid.
+pub fn register(table: &[(u8, &str, usize)]) {
+    let annotations = table
+        .iter()
+        .map(|&(part, class, size)| Annotation {
+            part: Part::new(part).unwrap_or_else(|| panic!("invalid part {part} in `complexity`")),
+            class: class
+                .parse()
+                .unwrap_or_else(|e| panic!("invalid complexity `{class}`: {e}")),
+            size,
+        })
+        .collect();
+    let _ = ANNOTATIONS.set(annotations);
+}
+
+pub fn annotation(part: Part) -> Option<Annotation> {
+    ANNOTATIONS
+        .get()?
+        .iter()
+        .find(|annotation| annotation.part == part)
+        .copied()
+}
+
+/// The input sizes a part is measured at, doubling up to `max`.
+pub fn sizes(max: usize) -> Vec<usize> {
+    let mut sizes: Vec<usize> = [8, 4, 2, 1]
+        .iter()
+        .map(|divisor| max / divisor)
+        .filter(|&size| size > 0)
+        .collect();
+    sizes.dedup();
+    sizes
+}
+
+/// How much faster than `class` the measured times grow, as the slope of `ln(time / cost(n))`
+/// over `ln n`. Near 0 when a part grows as claimed, 1 when it is a factor of `n` slower.
+#[allow(clippy::cast_precision_loss)]
+pub fn excess_growth(samples: &[(usize, Duration)], class: Complexity) -> Option<f64> {
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|&(n, time)| {
+            let time = time.as_secs_f64().max(1e-9);
+            ((n.max(1) as f64).ln(), time.ln() - class.ln_cost(n))
+        })
+        .collect();
+
+    // least squares fit of the slope.
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / count;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / count;
+    let covariance: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let variance: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/// The exponent `k` of the `n^k` the measured times grow like.
+pub fn growth_exponent(samples: &[(usize, Duration)]) -> Option<f64> {
+    excess_growth(samples, Complexity::Constant)
+}
+
+/// Average time of `func`, repeated for at least 20ms so fast parts are not all noise.
+fn measure(mut func: impl FnMut()) -> Duration {
+    let started = Instant::now();
+    let mut runs = 0;
+    while runs == 0 || started.elapsed() < Duration::from_millis(20) && runs < 1000 {
+        func();
+        runs += 1;
+    }
+    started.elapsed() / runs
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// With `--scale-bench`, time each part on generated inputs of growing size, compare the growth
+/// with the annotated complexity and return `true`. With `--enforce`, exits with an error when a
+/// part grows faster than annotated.
+pub(crate) fn run_scale_bench<S: Solution>() -> bool {
+    let args: Vec<String> = env::args().collect();
+    if !args.iter().any(|x| x == "--scale-bench") {
+        return false;
+    }
+
+    let Some(generator) = hooks::gen_input() else {
+        eprintln!(
+            "Day {} has no input generator, register one with `solution!({}, gen_input = ...)`.",
+            S::DAY,
+            S::DAY.into_inner()
+        );
+        process::exit(1);
+    };
+
+    let enforce = args.iter().any(|x| x == "--enforce");
+    let size = args
+        .iter()
+        .position(|x| x == "--size")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok());
+
+    let mut failed = false;
+    for &part in S::PARTS {
+        if !should_run_part(part) {
+            continue;
+        }
+
+        let annotation = annotation(part);
+        let max = size.or(annotation.map(|a| a.size)).unwrap_or(DEFAULT_SIZE);
+        match annotation {
+            Some(a) => println!("Part {part}: expected {}, up to n = {max}", a.class),
+            None => println!("Part {part}: not annotated, up to n = {max}"),
+        }
+
+        let samples: Vec<(usize, Duration)> = sizes(max)
+            .into_iter()
+            .map(|n| {
+                let input = generator(n, 0);
+                let time = measure(|| {
+                    black_box(S::part(part, S::parse(black_box(&input))));
+                });
+                println!("  n = {n:<8} {time:.1?}");
+                (n, time)
+            })
+            .collect();
+
+        let Some(exponent) = growth_exponent(&samples) else {
+            println!("  Too few sizes to fit a growth, pass a larger `--size`.");
+            continue;
+        };
+
+        match annotation {
+            Some(a) if excess_growth(&samples, a.class).is_some_and(|e| e > TOLERANCE) => {
+                println!("  ❌ grows like n^{exponent:.2}, faster than {}", a.class);
+                failed = true;
+            }
+            Some(a) => println!(
+                "  ✅ grows like n^{exponent:.2}, as expected for {}",
+                a.class
+            ),
+            None => println!("  grows like n^{exponent:.2}"),
+        }
+    }
+
+    if enforce && failed {
+        eprintln!("Parts grow faster than their annotated complexity.");
+        process::exit(1);
+    }
+    true
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{excess_growth, growth_exponent, sizes, Complexity};
+
+    #[allow(clippy::cast_precision_loss)]
+    fn samples(cost: impl Fn(f64) -> f64) -> Vec<(usize, Duration)> {
+        sizes(4000)
+            .into_iter()
+            .map(|n| (n, Duration::from_secs_f64(cost(n as f64) * 1e-9)))
+            .collect()
+    }
+
+    #[test]
+    fn parses_classes() {
+        assert_eq!(
+            "n log n".parse::<Complexity>().ok(),
+            Some(Complexity::NLogN)
+        );
+        assert_eq!(
+            "O(N^2)".parse::<Complexity>().ok(),
+            Some(Complexity::Power(2))
+        );
+        assert_eq!("n^1".parse::<Complexity>().ok(), Some(Complexity::Linear));
+        assert_eq!(
+            "2^n".parse::<Complexity>().ok(),
+            Some(Complexity::Exponential)
+        );
+        assert!("n!".parse::<Complexity>().is_err());
+        assert_eq!(Complexity::Power(3).to_string(), "n^3");
+    }
+
+    #[test]
+    fn fits_growth() {
+        assert_eq!(sizes(1000), vec![125, 250, 500, 1000]);
+        assert_eq!(sizes(3), vec![1, 3]);
+
+        let quadratic = samples(|n| 50.0 * n * n);
+        assert!((growth_exponent(&quadratic).unwrap() - 2.0).abs() < 1e-6);
+        assert!(
+            excess_growth(&quadratic, Complexity::Power(2))
+                .unwrap()
+                .abs()
+                < 1e-6
+        );
+        assert!(excess_growth(&quadratic, Complexity::NLogN).unwrap() > 0.5);
+
+        let n_log_n = samples(|n| 100.0 * n * n.ln());
+        assert!(excess_growth(&n_log_n, Complexity::NLogN).unwrap().abs() < 0.1);
+        assert!(excess_growth(&n_log_n, Complexity::Linear).unwrap() < 0.5);
+
+        assert_eq!(growth_exponent(&samples(|n| n)[..1]), None);
+    }
+}
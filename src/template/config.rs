@@ -0,0 +1,228 @@
+/// Module that reads the optional `ec.toml` configuration file from the repository root,
+/// layered over the optional global `config.toml` shared by all repositories (see [`dirs`]).
+///
+/// Besides settings, the config holds command aliases and per-command default options for the
+/// command line:
+///
+/// ```toml
+/// [aliases]
+/// fast = "solve --release"
+///
+/// [defaults]
+/// solve.release = true
+/// all.timeout = 10
+/// ```
+///
+/// [`dirs`]: crate::template::dirs
+use std::{fs, path::Path, sync::OnceLock};
+
+use toml::{Table, Value};
+
+use crate::template::dirs::global_config_path;
+
+static CONFIG_FILE_PATH: &str = "./ec.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Settings read from `ec.toml`. Every setting is optional.
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    table: Table,
+}
+
+impl Config {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        s.parse::<Table>()
+            .map(|table| Config { table })
+            .map_err(|e| e.to_string())
+    }
+
+    /// Read the global and the repository config files. Missing files are treated as empty
+    /// configs, invalid ones are reported and ignored.
+    pub fn read_from_file() -> Self {
+        let global = global_config_path()
+            .map(|path| Config::read_path(&path))
+            .unwrap_or_default();
+
+        global.merge(Config::read_path(Path::new(CONFIG_FILE_PATH)))
+    }
+
+    fn read_path(path: &Path) -> Self {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Config::default();
+        };
+
+        Config::parse(&contents).unwrap_or_else(|e| {
+            eprintln!("Ignoring invalid {}: {e}", path.display());
+            Config::default()
+        })
+    }
+
+    /// Layer `other` over this config. Tables are merged recursively, other values in
+    /// `other` replace the ones in `self`.
+    pub fn merge(mut self, other: Config) -> Self {
+        merge_tables(&mut self.table, other.table);
+        self
+    }
+
+    /// Look up a value by its dotted path, e.g. `hooks.before_part`.
+    pub fn get(&self, path: &str) -> Option<&Value> {
+        let mut keys = path.split('.');
+        let first = self.table.get(keys.next()?)?;
+        keys.try_fold(first, |value, key| value.get(key))
+    }
+
+    pub fn get_str(&self, path: &str) -> Option<&str> {
+        self.get(path)?.as_str()
+    }
+
+    /// The arguments an alias from `[aliases]` expands to, given as a string split at whitespace
+    /// or as an array of strings.
+    pub fn alias(&self, name: &str) -> Option<Result<Vec<String>, String>> {
+        let invalid = || format!("alias `{name}` must be a string or an array of strings");
+        let args = match self.table.get("aliases")?.get(name)? {
+            Value::String(s) => Ok(s.split_whitespace().map(ToString::to_string).collect()),
+            Value::Array(values) => values
+                .iter()
+                .map(|value| value.as_str().map(ToString::to_string).ok_or_else(invalid))
+                .collect(),
+            _ => Err(invalid()),
+        };
+        Some(args.and_then(|args: Vec<String>| {
+            if args.is_empty() {
+                Err(invalid())
+            } else {
+                Ok(args)
+            }
+        }))
+    }
+
+    /// The default options of `command` from `[defaults]`, as option names like `--release`
+    /// with their value. `true` turns a flag on, `false` leaves it off.
+    pub fn default_options(&self, command: &str) -> Result<Vec<(String, Option<String>)>, String> {
+        let Some(defaults) = self.table.get("defaults").and_then(|d| d.get(command)) else {
+            return Ok(vec![]);
+        };
+        let Value::Table(defaults) = defaults else {
+            return Err(format!("defaults of `{command}` must be a table"));
+        };
+
+        let mut options = vec![];
+        for (key, value) in defaults {
+            let option = format!("--{key}");
+            match value {
+                Value::Boolean(true) => options.push((option, None)),
+                Value::Boolean(false) => {}
+                Value::String(s) => options.push((option, Some(s.clone()))),
+                Value::Integer(n) => options.push((option, Some(n.to_string()))),
+                Value::Float(n) => options.push((option, Some(n.to_string()))),
+                _ => {
+                    return Err(format!(
+                        "default `{command}.{key}` must be a boolean, string or number"
+                    ))
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn merge_tables(base: &mut Table, other: Table) {
+    for (key, value) in other {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(other)) => merge_tables(base, other),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// The config of the current repository, read once on first access.
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(Config::read_from_file)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Config;
+
+    #[test]
+    fn reads_nested_values() {
+        let config = Config::parse("[hooks]\nbefore_part = \"echo hi\"").unwrap();
+        assert_eq!(config.get_str("hooks.before_part"), Some("echo hi"));
+        assert_eq!(config.get_str("hooks.after_part"), None);
+        assert_eq!(config.get_str("missing.key"), None);
+    }
+
+    #[test]
+    fn handles_empty_config() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config.get("hooks").is_none(), true);
+    }
+
+    #[test]
+    fn layers_repository_config_over_global() {
+        let global =
+            Config::parse("[submit]\ncooldown_secs = 60\n[hooks]\nbefore_part = \"a\"").unwrap();
+        let repo = Config::parse("[hooks]\nbefore_part = \"b\"\nafter_part = \"c\"").unwrap();
+        let config = global.merge(repo);
+
+        assert_eq!(
+            config
+                .get("submit.cooldown_secs")
+                .and_then(toml::Value::as_integer),
+            Some(60)
+        );
+        assert_eq!(config.get_str("hooks.before_part"), Some("b"));
+        assert_eq!(config.get_str("hooks.after_part"), Some("c"));
+    }
+
+    #[test]
+    fn reads_aliases() {
+        let config = Config::parse(
+            "[aliases]\nfast = \"solve --release\"\nlist = [\"all\", \"--days\", \"1-5, 7\"]\nbad = 1",
+        )
+        .unwrap();
+        assert_eq!(
+            config.alias("fast").unwrap().unwrap(),
+            vec!["solve", "--release"]
+        );
+        assert_eq!(
+            config.alias("list").unwrap().unwrap(),
+            vec!["all", "--days", "1-5, 7"]
+        );
+        assert!(config.alias("bad").unwrap().is_err());
+        assert!(config.alias("missing").is_none());
+    }
+
+    #[test]
+    fn reads_default_options() {
+        let config = Config::parse(
+            "[defaults]\nsolve.release = true\nsolve.checks = false\nall.timeout = 10\nall.days = \"1-5\"",
+        )
+        .unwrap();
+        assert_eq!(
+            config.default_options("solve").unwrap(),
+            vec![("--release".to_string(), None)]
+        );
+        assert_eq!(
+            config.default_options("all").unwrap(),
+            vec![
+                ("--days".to_string(), Some("1-5".to_string())),
+                ("--timeout".to_string(), Some("10".to_string()))
+            ]
+        );
+        assert!(config.default_options("time").unwrap().is_empty());
+        assert!(Config::parse("[defaults]\nsolve = 1")
+            .unwrap()
+            .default_options("solve")
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_config() {
+        Config::parse("[hooks").unwrap();
+    }
+}
@@ -0,0 +1,286 @@
+/// Contest practice sessions, recorded to `data/contests/{day}.json` for later review.
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{all_parts, data_dir, Day, Part};
+
+fn contests_dir() -> PathBuf {
+    data_dir().join("contests")
+}
+
+/// A single answer attempt during a contest session.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attempt {
+    pub part: Part,
+    pub answer: String,
+    /// Seconds since the session started.
+    pub elapsed_secs: u64,
+    pub correct: bool,
+}
+
+/// A contest session for a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Session {
+    pub day: Day,
+    /// Unix timestamp of the session start.
+    pub started_at: u64,
+    pub attempts: Vec<Attempt>,
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Format seconds as e.g. `1h 02m 03s`.
+pub fn format_elapsed(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m {seconds:02}s")
+    } else {
+        format!("{minutes}m {seconds:02}s")
+    }
+}
+
+impl Session {
+    pub fn new(day: Day) -> Self {
+        Session {
+            day,
+            started_at: now(),
+            attempts: vec![],
+        }
+    }
+
+    fn path(day: Day) -> String {
+        format!("{}/{day}.json", contests_dir().display())
+    }
+
+    /// Rehydrate a session from its JSON file, if present.
+    pub fn read_from_file(day: Day) -> Option<Self> {
+        fs::read_to_string(Self::path(day))
+            .ok()
+            .and_then(|s| Session::try_from(s).ok())
+    }
+
+    /// Dehydrate the session to its JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        fs::create_dir_all(contests_dir())?;
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(Self::path(self.day))?;
+        json.format_to(&mut file)
+    }
+
+    pub fn remove_file(day: Day) -> Result<(), Error> {
+        fs::remove_file(Self::path(day))
+    }
+
+    pub fn tries(&self, part: Part) -> usize {
+        self.attempts.iter().filter(|a| a.part == part).count()
+    }
+
+    /// Seconds after the session start when `part` was solved.
+    pub fn solved_at(&self, part: Part) -> Option<u64> {
+        self.attempts
+            .iter()
+            .find(|a| a.part == part && a.correct)
+            .map(|a| a.elapsed_secs)
+    }
+
+    /// The first part that has not been solved yet.
+    pub fn current_part(&self) -> Option<Part> {
+        all_parts().find(|part| self.solved_at(*part).is_none())
+    }
+
+    pub fn elapsed_secs(&self) -> u64 {
+        now().saturating_sub(self.started_at)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Session> for JsonValue {
+    fn from(value: &Session) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "started_at".into(),
+            JsonValue::Number(value.started_at as f64),
+        );
+        map.insert(
+            "attempts".into(),
+            JsonValue::Array(value.attempts.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Session {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("expected `json.day` to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let started_at = json
+            .get("started_at")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("expected `json.started_at` to be a number.")?;
+
+        let attempts = json
+            .get("attempts")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected `json.attempts` to be an array.")?
+            .iter()
+            .map(Attempt::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Session {
+            day,
+            started_at,
+            attempts,
+        })
+    }
+}
+
+impl From<&Attempt> for JsonValue {
+    fn from(value: &Attempt) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "part".into(),
+            JsonValue::Number(f64::from(value.part.into_inner())),
+        );
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "elapsed_secs".into(),
+            JsonValue::Number(value.elapsed_secs as f64),
+        );
+        map.insert("correct".into(), JsonValue::Boolean(value.correct));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Attempt {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected attempt to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .and_then(|v| Part::new(*v as u8))
+            .ok_or("Expected attempt.part to be a part number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected attempt.answer to be a string.")?;
+
+        let elapsed_secs = json
+            .get("elapsed_secs")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected attempt.elapsed_secs to be a number.")?;
+
+        let correct = json
+            .get("correct")
+            .and_then(|v| v.get::<bool>())
+            .copied()
+            .ok_or("Expected attempt.correct to be a boolean.")?;
+
+        Ok(Attempt {
+            part,
+            answer,
+            elapsed_secs,
+            correct,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_elapsed, Attempt, Session};
+    use crate::{day, template::Part};
+
+    fn get_mock_session() -> Session {
+        Session {
+            day: day!(18),
+            started_at: 1_700_000_000,
+            attempts: vec![
+                Attempt {
+                    part: Part::One,
+                    answer: "12".into(),
+                    elapsed_secs: 300,
+                    correct: false,
+                },
+                Attempt {
+                    part: Part::One,
+                    answer: "13".into(),
+                    elapsed_secs: 420,
+                    correct: true,
+                },
+                Attempt {
+                    part: Part::Two,
+                    answer: "99".into(),
+                    elapsed_secs: 900,
+                    correct: false,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn tracks_progress() {
+        let session = get_mock_session();
+        assert_eq!(session.tries(Part::One), 2);
+        assert_eq!(session.tries(Part::Two), 1);
+        assert_eq!(session.solved_at(Part::One), Some(420));
+        assert_eq!(session.solved_at(Part::Two), None);
+        assert_eq!(session.current_part(), Some(Part::Two));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let session = get_mock_session();
+        let json = tinyjson::JsonValue::from(&session).stringify().unwrap();
+        assert_eq!(Session::try_from(json).unwrap(), session);
+    }
+
+    #[test]
+    fn formats_elapsed_time() {
+        assert_eq!(format_elapsed(65), "1m 05s");
+        assert_eq!(format_elapsed(3723), "1h 02m 03s");
+    }
+}
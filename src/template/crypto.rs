@@ -0,0 +1,133 @@
+/// Symmetric encryption for files that should not be committed in plaintext.
+///
+/// Data is encrypted with XChaCha20-Poly1305 using a 32 byte key read from an environment
+/// variable as 64 hex characters, and stored as hex encoded `nonce || ciphertext`.
+use std::{env, fmt::Display};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Key, XChaCha20Poly1305, XNonce,
+};
+
+const NONCE_LEN: usize = 24;
+
+#[derive(Debug)]
+pub enum CryptoError {
+    MissingKey(&'static str),
+    InvalidKey(&'static str),
+    InvalidData,
+    DecryptionFailed,
+}
+
+impl Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptoError::MissingKey(var) => write!(f, "`{var}` is not set."),
+            CryptoError::InvalidKey(var) => {
+                write!(f, "`{var}` must be 64 hex characters (32 bytes).")
+            }
+            CryptoError::InvalidData => write!(f, "encrypted data is malformed."),
+            CryptoError::DecryptionFailed => {
+                write!(f, "decryption failed, is the key correct?")
+            }
+        }
+    }
+}
+
+/// A cipher keyed from an environment variable.
+pub struct Cipher {
+    cipher: XChaCha20Poly1305,
+}
+
+impl Cipher {
+    pub fn from_env(var: &'static str) -> Result<Self, CryptoError> {
+        let hex = env::var(var).map_err(|_| CryptoError::MissingKey(var))?;
+        Cipher::from_hex(&hex, var)
+    }
+
+    /// Create a cipher from a hex key, `source` names where the key came from for errors.
+    pub fn from_hex(hex: &str, source: &'static str) -> Result<Self, CryptoError> {
+        let key = decode_hex(hex.trim())
+            .filter(|key| key.len() == 32)
+            .ok_or(CryptoError::InvalidKey(source))?;
+
+        Ok(Cipher {
+            cipher: XChaCha20Poly1305::new(Key::from_slice(&key)),
+        })
+    }
+
+    /// Encrypt `plaintext` into a hex string.
+    pub fn encrypt(&self, plaintext: &[u8]) -> String {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext)
+            .expect("encryption should not fail for in-memory buffers");
+
+        let mut data = nonce.to_vec();
+        data.extend(ciphertext);
+        encode_hex(&data)
+    }
+
+    /// Decrypt a hex string produced by [`Cipher::encrypt`].
+    pub fn decrypt(&self, hex: &str) -> Result<Vec<u8>, CryptoError> {
+        let data = decode_hex(hex.trim()).ok_or(CryptoError::InvalidData)?;
+        if data.len() < NONCE_LEN {
+            return Err(CryptoError::InvalidData);
+        }
+
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CryptoError::DecryptionFailed)
+    }
+}
+
+fn encode_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_hex, encode_hex, Cipher};
+    use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305};
+
+    fn get_mock_cipher(byte: u8) -> Cipher {
+        Cipher {
+            cipher: XChaCha20Poly1305::new(Key::from_slice(&[byte; 32])),
+        }
+    }
+
+    #[test]
+    fn roundtrips_hex() {
+        assert_eq!(encode_hex(&[0, 15, 255]), "000fff");
+        assert_eq!(decode_hex("000fff"), Some(vec![0, 15, 255]));
+        assert_eq!(decode_hex("0f0"), None);
+        assert_eq!(decode_hex("zz"), None);
+    }
+
+    #[test]
+    fn roundtrips_encryption() {
+        let cipher = get_mock_cipher(7);
+        let encrypted = cipher.encrypt(b"1234");
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), b"1234");
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_wrong_key() {
+        let encrypted = get_mock_cipher(7).encrypt(b"1234");
+        get_mock_cipher(8).decrypt(&encrypted).unwrap();
+    }
+}
@@ -1,12 +1,18 @@
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::Event;
+
 #[cfg(feature = "today")]
-use chrono::{Datelike, Duration, NaiveDate, Utc, Weekday};
+use chrono::{Datelike, Duration, Utc};
 
 /// A valid quest day number (i.e. an integer in range 1 to 25).
 ///
+/// Events have fewer quests than that, use [`Day::new_for`] and [`all_days_for`] to stay within
+/// the quests of an [`Event`].
+///
 /// # Display
 /// This value displays as a two digit number.
 ///
@@ -28,6 +34,18 @@ impl Day {
         Some(Self(day))
     }
 
+    /// Creates a [`Day`] from the provided value if `event` has a quest of that number,
+    /// returns [`None`] otherwise.
+    ///
+    /// ```
+    /// # use everybody_codes::{template::Event, Day};
+    /// assert!(Day::new_for(Event::Story { id: 1 }, 3).is_some());
+    /// assert!(Day::new_for(Event::Story { id: 1 }, 4).is_none());
+    /// ```
+    pub fn new_for(event: impl Into<Event>, day: u8) -> Option<Self> {
+        Self::new(day).filter(|_| day <= event.into().quest_count())
+    }
+
     // Not part of the public API
     #[doc(hidden)]
     pub const fn __new_unchecked(day: u8) -> Self {
@@ -38,6 +56,32 @@ impl Day {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Returns the quest day released on a date of the main event, `None` otherwise.
+    ///
+    /// The event starts on the first Monday of November and runs for 20 weekdays
+    /// (Monday-Friday only), so weekends and dates outside of those four weeks have no quest.
+    ///
+    /// ```
+    /// # use everybody_codes::{template::Date, Day};
+    /// let date: Date = "2025-11-12".parse().unwrap();
+    /// assert_eq!(Day::on_date(date), Day::new(8));
+    /// ```
+    pub fn on_date(date: Date) -> Option<Self> {
+        if date.month != 11 {
+            return None;
+        }
+
+        let first_monday = (1..=7).find(|&day| Date { day, ..date }.weekday() == 0)?;
+        let offset = date.day.checked_sub(first_monday)?;
+
+        // weeks start on a Monday, so the offset within a week is the weekday.
+        if offset % 7 >= 5 {
+            return None;
+        }
+        let day = offset / 7 * 5 + offset % 7 + 1;
+        (day <= 20).then_some(Self(day))
+    }
 }
 
 #[cfg(feature = "today")]
@@ -54,47 +98,11 @@ impl Day {
         let adjusted_time = now - Duration::hours(23);
         let adjusted_date = adjusted_time.date_naive();
 
-        // Only run in November (after adjustment)
-        if adjusted_date.month() != 11 {
-            return None;
-        }
-
-        let year = adjusted_date.year();
-
-        // Find the first Monday of November
-        let first_monday = (1..=7)
-            .find_map(|day| {
-                NaiveDate::from_ymd_opt(year, 11, day)
-                    .filter(|date| date.weekday() == Weekday::Mon)
-            })?;
-
-        // Check if today is before the event starts
-        if adjusted_date < first_monday {
-            return None;
-        }
-
-        // Check if today is a weekday
-        if matches!(adjusted_date.weekday(), Weekday::Sat | Weekday::Sun) {
-            return None;
-        }
-
-        // Count weekdays since the first Monday
-        let mut weekday_count = 0;
-        let mut current = first_monday;
-
-        while current <= adjusted_date {
-            if !matches!(current.weekday(), Weekday::Sat | Weekday::Sun) {
-                weekday_count += 1;
-            }
-            current = current.succ_opt()?;
-        }
-
-        // Event runs for 20 weekdays
-        if weekday_count > 0 && weekday_count <= 20 {
-            Self::new(weekday_count)
-        } else {
-            None
-        }
+        Self::on_date(Date {
+            year: u16::try_from(adjusted_date.year()).ok()?,
+            month: u8::try_from(adjusted_date.month()).ok()?,
+            day: u8::try_from(adjusted_date.day()).ok()?,
+        })
     }
 }
 
@@ -146,15 +154,28 @@ pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every quest day from the 1st to the 25th.
+/// An iterator that yields the quest days of `event`, e.g. the 1st to the 20th of a main event.
+pub fn all_days_for(event: impl Into<Event>) -> AllDays {
+    AllDays {
+        current: 1,
+        last: event.into().quest_count().min(25),
+    }
+}
+
+/// An iterator that yields every quest day from the 1st to the 25th, or to the last quest of an
+/// event, see [`all_days_for`].
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self {
+            current: 1,
+            last: 25,
+        }
     }
 }
 
@@ -162,7 +183,7 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
         // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
@@ -175,6 +196,157 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A set of days parsed from a comma separated list of days and ranges, e.g. `1-5,7,20-`.
+/// Ranges are inclusive and may be open on either end. Rust style ranges work as well, e.g.
+/// `5..=10`, `5..11` or `12..`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet(HashSet<Day>);
+
+impl DaySet {
+    pub fn contains(&self, day: Day) -> bool {
+        self.0.contains(&day)
+    }
+
+    /// Keep only the days of `days` that are in the set.
+    pub fn filter(&self, days: HashSet<Day>) -> HashSet<Day> {
+        days.into_iter().filter(|day| self.contains(*day)).collect()
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || DaySetFromStrError(s.to_string());
+        let parse_bound = |bound: &str, default: u8| {
+            if bound.trim().is_empty() {
+                Ok(Day(default))
+            } else {
+                bound.trim().parse::<Day>().map_err(|_| error())
+            }
+        };
+
+        let mut days = HashSet::new();
+        for item in s.split(',') {
+            let (start, end) = if let Some((start, end)) = item.split_once("..=") {
+                (parse_bound(start, 1)?, parse_bound(end, 25)?)
+            } else if let Some((start, end)) = item.split_once("..") {
+                let end = match end.trim() {
+                    "" => Day(25),
+                    end => end
+                        .parse::<u8>()
+                        .ok()
+                        .and_then(|end| Day::new(end.checked_sub(1)?))
+                        .ok_or_else(error)?,
+                };
+                (parse_bound(start, 1)?, end)
+            } else if let Some((start, end)) = item.split_once('-') {
+                (parse_bound(start, 1)?, parse_bound(end, 25)?)
+            } else {
+                let day = item.trim().parse::<Day>().map_err(|_| error())?;
+                (day, day)
+            };
+
+            if start > end {
+                return Err(error());
+            }
+            days.extend((start.0..=end.0).map(Day));
+        }
+
+        Ok(DaySet(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid days `{}`, expecting days between 1 and 25 or ranges like `1-5,7,20-` or `5..=10`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A calendar date, e.g. `2025-11-20`, to find the quest of a date without the `today` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// The day of the week, from 0 for Monday to 6 for Sunday.
+    fn weekday(self) -> u8 {
+        // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year - era * 400;
+        let mp = (i64::from(self.month) + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146_097 + doe - 719_468;
+
+        // 1970-01-01 was a Thursday.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let weekday = (days + 3).rem_euclid(7) as u8;
+        weekday
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = DateFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [year, month, day] = s.split('-').collect::<Vec<_>>()[..] else {
+            return Err(DateFromStrError);
+        };
+        let is_number =
+            |part: &str, len: usize| part.len() == len && part.bytes().all(|b| b.is_ascii_digit());
+        if !is_number(year, 4) || !is_number(month, 2) || !is_number(day, 2) {
+            return Err(DateFromStrError);
+        }
+
+        let date = Date {
+            year: year.parse().map_err(|_| DateFromStrError)?,
+            month: month.parse().map_err(|_| DateFromStrError)?,
+            day: day.parse().map_err(|_| DateFromStrError)?,
+        };
+        if !(1..=12).contains(&date.month) || !(1..=31).contains(&date.day) {
+            return Err(DateFromStrError);
+        }
+        Ok(date)
+    }
+}
+
+/// An error which can be returned when parsing a [`Date`].
+#[derive(Debug)]
+pub struct DateFromStrError;
+
+impl Error for DateFromStrError {}
+
+impl Display for DateFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a date like `2025-11-20`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -195,7 +367,8 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, all_days_for, Date, Day, DaySet};
+    use crate::{template::Event, year};
 
     #[test]
     fn all_days_iterator() {
@@ -228,6 +401,76 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn limits_days_to_the_event() {
+        let main = Event::from(year!(2025));
+        let story = Event::Story { id: 1 };
+
+        assert_eq!(all_days_for(main).last(), Some(Day(20)));
+        assert_eq!(
+            all_days_for(story).collect::<Vec<_>>(),
+            vec![Day(1), Day(2), Day(3)]
+        );
+
+        assert_eq!(Day::new_for(main, 20), Some(Day(20)));
+        assert_eq!(Day::new_for(main, 21), None);
+        assert_eq!(Day::new_for(story, 0), None);
+    }
+
+    #[test]
+    fn finds_the_quest_of_a_date() {
+        let quest = |date: &str| Day::on_date(date.parse::<Date>().unwrap()).map(Day::into_inner);
+
+        // 2025 started on Monday, November 3rd.
+        assert_eq!(quest("2025-11-02"), None);
+        assert_eq!(quest("2025-11-03"), Some(1));
+        assert_eq!(quest("2025-11-07"), Some(5));
+        assert_eq!(quest("2025-11-08"), None);
+        assert_eq!(quest("2025-11-10"), Some(6));
+        assert_eq!(quest("2025-11-28"), Some(20));
+        assert_eq!(quest("2025-12-01"), None);
+        // 2024 started on Monday, November 4th.
+        assert_eq!(quest("2024-11-04"), Some(1));
+        assert_eq!(quest("2024-11-29"), Some(20));
+
+        assert_eq!(
+            "2025-11-20".parse::<Date>().unwrap().to_string(),
+            "2025-11-20"
+        );
+        assert!("2025-13-01".parse::<Date>().is_err());
+        assert!("2025-1-01".parse::<Date>().is_err());
+        assert!("2025-11-01-01".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn parses_day_sets() {
+        let days: DaySet = "1-3, 7,24-".parse().unwrap();
+        let selected: Vec<u8> = all_days()
+            .filter(|day| days.contains(*day))
+            .map(Day::into_inner)
+            .collect();
+        assert_eq!(selected, vec![1, 2, 3, 7, 24, 25]);
+
+        let days: DaySet = "-2".parse().unwrap();
+        assert_eq!(days.contains(Day(2)), true);
+        assert_eq!(days.contains(Day(3)), false);
+
+        let days: DaySet = "5..=7,10..12,23..".parse().unwrap();
+        let selected: Vec<u8> = all_days()
+            .filter(|day| days.contains(*day))
+            .map(Day::into_inner)
+            .collect();
+        assert_eq!(selected, vec![5, 6, 7, 10, 11, 23, 24, 25]);
+        assert_eq!("..=2".parse::<DaySet>().ok(), "1-2".parse::<DaySet>().ok());
+        assert_eq!("..26".parse::<DaySet>().ok(), "1-25".parse::<DaySet>().ok());
+
+        assert_eq!("5..5".parse::<DaySet>().is_err(), true);
+        assert_eq!("1..=26".parse::<DaySet>().is_err(), true);
+        assert_eq!("5-3".parse::<DaySet>().is_err(), true);
+        assert_eq!("1-26".parse::<DaySet>().is_err(), true);
+        assert_eq!("".parse::<DaySet>().is_err(), true);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
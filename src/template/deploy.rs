@@ -0,0 +1,160 @@
+/// Deploying solution binaries to a remote machine, e.g. a server with more cores and memory
+/// for heavy part 3s.
+///
+/// Binaries are (cross-)compiled locally, staged with the inputs under `target/deploy/{year}`,
+/// copied with `rsync` and run over `ssh`, so the remote machine needs neither a Rust toolchain
+/// nor the sources.
+use std::path::PathBuf;
+
+use crate::template::{authors::bin_name, Day};
+
+pub const DEFAULT_REMOTE_DIR: &str = "everybody-codes";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeployPlan {
+    pub year: u16,
+    pub host: String,
+    /// Target triple to cross-compile for, e.g. `x86_64-unknown-linux-musl`.
+    pub target: Option<String>,
+    /// Directory on the remote machine, relative to the remote home unless absolute.
+    pub remote_dir: String,
+    pub days: Vec<Day>,
+}
+
+impl DeployPlan {
+    /// Arguments for `cargo` to build the release binaries of all days.
+    pub fn build_args(&self) -> Vec<String> {
+        let mut args = vec!["build".to_string(), "--release".into()];
+
+        if let Some(target) = &self.target {
+            args.push("--target".into());
+            args.push(target.clone());
+        }
+
+        for day in &self.days {
+            args.push("--bin".into());
+            args.push(bin_name(*day, None));
+        }
+
+        args
+    }
+
+    /// Directory containing the built binaries.
+    pub fn binary_dir(&self) -> PathBuf {
+        let mut dir = PathBuf::from("target");
+        if let Some(target) = &self.target {
+            dir.push(target);
+        }
+        dir.join("release")
+    }
+
+    /// Local directory mirroring the remote layout.
+    pub fn stage_dir(&self) -> PathBuf {
+        PathBuf::from("target")
+            .join("deploy")
+            .join(self.year.to_string())
+    }
+
+    pub fn remote_path(&self) -> String {
+        format!("{}/{}", self.remote_dir.trim_end_matches('/'), self.year)
+    }
+
+    /// Arguments for `rsync` to copy the staged directory to the remote machine.
+    pub fn rsync_args(&self) -> Vec<String> {
+        vec![
+            "-az".into(),
+            "--mkpath".into(),
+            format!("{}/", self.stage_dir().display()),
+            format!("{}:{}/", self.host, self.remote_path()),
+        ]
+    }
+
+    /// Arguments for `ssh` to run the solution of `day` on the remote machine.
+    pub fn run_args(&self, day: Day, solution_args: &[String]) -> Vec<String> {
+        let mut command = format!(
+            "cd {} && ./{}",
+            shell_quote(&self.remote_path()),
+            bin_name(day, None)
+        );
+        for arg in solution_args {
+            command.push(' ');
+            command.push_str(&shell_quote(arg));
+        }
+
+        vec![self.host.clone(), command]
+    }
+}
+
+/// Quote a string for a POSIX shell.
+fn shell_quote(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=".contains(c))
+    {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{shell_quote, DeployPlan, DEFAULT_REMOTE_DIR};
+    use crate::day;
+
+    fn get_mock_plan() -> DeployPlan {
+        DeployPlan {
+            year: 2025,
+            host: "myserver".into(),
+            target: Some("x86_64-unknown-linux-musl".into()),
+            remote_dir: DEFAULT_REMOTE_DIR.into(),
+            days: vec![day!(7), day!(19)],
+        }
+    }
+
+    #[test]
+    fn builds_for_target() {
+        let plan = get_mock_plan();
+        assert_eq!(
+            plan.build_args().join(" "),
+            "build --release --target x86_64-unknown-linux-musl --bin 07 --bin 19"
+        );
+        assert_eq!(
+            plan.binary_dir().to_str(),
+            Some("target/x86_64-unknown-linux-musl/release")
+        );
+
+        let native = DeployPlan {
+            target: None,
+            ..get_mock_plan()
+        };
+        assert_eq!(native.binary_dir().to_str(), Some("target/release"));
+    }
+
+    #[test]
+    fn syncs_and_runs_remotely() {
+        let plan = get_mock_plan();
+        assert_eq!(
+            plan.rsync_args(),
+            vec![
+                "-az",
+                "--mkpath",
+                "target/deploy/2025/",
+                "myserver:everybody-codes/2025/"
+            ]
+        );
+        assert_eq!(
+            plan.run_args(day!(7), &["--part".into(), "3".into()]),
+            vec!["myserver", "cd everybody-codes/2025 && ./07 --part 3"]
+        );
+    }
+
+    #[test]
+    fn quotes_shell_arguments() {
+        assert_eq!(shell_quote("~/my dir"), "'~/my dir'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+}
@@ -0,0 +1,37 @@
+/// Directories shared by every checkout of the template, for settings and secrets that
+/// should neither be committed nor set up again per repository.
+///
+/// The platform conventions are followed, e.g. `$XDG_CONFIG_HOME/everybody-codes` and
+/// `$XDG_CACHE_HOME/everybody-codes` on Linux. `EC_CONFIG_DIR` and `EC_CACHE_DIR` override
+/// them. Quest data stays in the repository's `data/` folder.
+use std::{env, path::PathBuf};
+
+use directories::ProjectDirs;
+
+static CONFIG_DIR_ENV: &str = "EC_CONFIG_DIR";
+static CACHE_DIR_ENV: &str = "EC_CACHE_DIR";
+
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", "everybody-codes")
+}
+
+fn from_env(var: &str) -> Option<PathBuf> {
+    env::var_os(var)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+/// The global config directory, if a home directory can be determined.
+pub fn config_dir() -> Option<PathBuf> {
+    from_env(CONFIG_DIR_ENV).or_else(|| Some(project_dirs()?.config_dir().to_path_buf()))
+}
+
+/// The global cache directory, if a home directory can be determined.
+pub fn cache_dir() -> Option<PathBuf> {
+    from_env(CACHE_DIR_ENV).or_else(|| Some(project_dirs()?.cache_dir().to_path_buf()))
+}
+
+/// Path of the global `config.toml`, which `ec.toml` settings take precedence over.
+pub fn global_config_path() -> Option<PathBuf> {
+    Some(config_dir()?.join("config.toml"))
+}
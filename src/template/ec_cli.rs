@@ -1,16 +1,47 @@
-/// Wrapper module around the "ec-cli" command-line.
+/// Wrapper module around the "ec-cli" command-line. With the `http_client` feature, requests
+/// go through [`client`](crate::template::client) instead and ec-cli is not needed.
 use std::{
     fmt::Display,
-    process::{Command, Output, Stdio},
+    fs,
+    path::Path,
+    process::{self, Output},
+    sync::{mpsc, Mutex},
+    thread,
 };
 
-use crate::template::Day;
+#[cfg(not(feature = "http_client"))]
+use std::process::{Command, Stdio};
+
+#[cfg(feature = "http_client")]
+use crate::template::client;
+#[cfg(not(feature = "http_client"))]
+use crate::template::Event;
+use crate::template::{
+    all_parts, inputs, manifest, plugins, quest_meta, read_only, submissions, Day, Part,
+};
 
 #[derive(Debug)]
 pub enum EcCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    /// The fetch was not attempted because an earlier part failed.
+    Skipped,
+    /// The submission was not sent because the previous one was less than the cooldown ago.
+    Throttled(u64),
+    /// The submission was not sent because read-only mode is enabled.
+    ReadOnly,
+    /// No session token was found for the HTTP client.
+    #[cfg(feature = "http_client")]
+    MissingToken,
+    #[cfg(feature = "http_client")]
+    Request(String),
+    /// The HTTP client could not connect to the site, or it did not respond in time.
+    #[cfg(feature = "http_client")]
+    Unreachable(String),
+    /// The HTTP client submitted a wrong answer.
+    #[cfg(feature = "http_client")]
+    Incorrect,
 }
 
 impl Display for EcCommandError {
@@ -21,10 +52,57 @@ impl Display for EcCommandError {
             EcCommandError::BadExitStatus(_) => {
                 write!(f, "ec-cli exited with a non-zero status.")
             }
+            EcCommandError::Skipped => write!(f, "skipped because an earlier part failed."),
+            EcCommandError::Throttled(secs) => write!(
+                f,
+                "the previous submission was too recent, wait {secs}s before submitting again."
+            ),
+            EcCommandError::ReadOnly => write!(f, "submissions are disabled in read-only mode."),
+            #[cfg(feature = "http_client")]
+            EcCommandError::MissingToken => write!(
+                f,
+                "no session token found, set `EC_TOKEN` or store it in the `token` file of the global config directory."
+            ),
+            #[cfg(feature = "http_client")]
+            EcCommandError::Request(e) => write!(f, "request failed: {e}"),
+            #[cfg(feature = "http_client")]
+            EcCommandError::Unreachable(e) => write!(f, "the site is unreachable: {e}"),
+            #[cfg(feature = "http_client")]
+            EcCommandError::Incorrect => write!(f, "the answer is not correct."),
+        }
+    }
+}
+
+impl EcCommandError {
+    /// Whether the error comes from working without ec-cli or without a network, rather than
+    /// from the quest or the configuration.
+    pub fn is_offline(&self) -> bool {
+        match self {
+            EcCommandError::CommandNotFound | EcCommandError::CommandNotCallable => true,
+            #[cfg(feature = "http_client")]
+            EcCommandError::Unreachable(_) => true,
+            _ => false,
         }
     }
+
+    /// Whether a submission failed because the site rejected the answer, rather than because
+    /// it could not be sent. ec-cli exits with a non-zero status for wrong answers.
+    pub fn is_rejection(&self) -> bool {
+        match self {
+            EcCommandError::BadExitStatus(_) => true,
+            #[cfg(feature = "http_client")]
+            EcCommandError::Incorrect => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "http_client")]
+pub fn check() -> Result<(), EcCommandError> {
+    client::token().map(|_| ())
 }
 
+#[cfg(not(feature = "http_client"))]
 pub fn check() -> Result<(), EcCommandError> {
     Command::new("ec-cli")
         .arg("--version")
@@ -33,93 +111,295 @@ pub fn check() -> Result<(), EcCommandError> {
     Ok(())
 }
 
+/// Exit with setup instructions if quests can't be downloaded or submitted.
+pub fn require() {
+    match check() {
+        Ok(()) => {}
+        Err(EcCommandError::CommandNotFound | EcCommandError::CommandNotCallable) => {
+            eprintln!("command \"ec-cli\" not found or not callable. Try installing it from https://github.com/finnhartshorn/ec-cli");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "http_client")]
+pub fn read(day: Day) -> Result<Output, EcCommandError> {
+    print!("{}", client::read(day)?);
+    Ok(empty_output())
+}
+
+#[cfg(not(feature = "http_client"))]
 pub fn read(day: Day) -> Result<Output, EcCommandError> {
     let mut args = vec!["read".to_string(), "-d".to_string(), day.to_string()];
 
-    if let Some(year) = get_year() {
-        args.push("-y".to_string());
-        args.push(year.to_string());
-    }
+    args.push("-y".to_string());
+    args.push(Event::current().id().to_string());
 
     call_ec_cli(&args)
 }
 
+/// Outcome of fetching a single part of a quest.
+#[derive(Debug)]
+pub enum PartStatus {
+    Downloaded,
+    /// The part is not available yet, defaults were written to the sample and answer files.
+    Unavailable,
+    Failed(EcCommandError),
+}
+
 pub fn download(day: Day) -> Result<Output, EcCommandError> {
     // Download all 3 parts for Everybody Codes
-    for part in 1..=3 {
+    for part in all_parts() {
         let input_path = get_input_path(day, part);
         let sample_path = get_sample_path(day, part);
         let sample_answer_path = get_sample_answer_path(day, part);
         let desc_path = get_description_path(day, part);
 
-        let mut args = vec![
-            "fetch".to_string(),
-            "-d".to_string(),
-            day.to_string(),
-            "-p".to_string(),
-            part.to_string(),
-            "--sample-path".to_string(),
-            sample_path.clone(),
-            "--sample-answer-path".to_string(),
-            sample_answer_path.clone(),
-            "--input-path".to_string(),
-            input_path.clone(),
-            "--description-path".to_string(),
-            desc_path.clone(),
-        ];
-
-        if let Some(year) = get_year() {
-            args.push("-y".to_string());
-            args.push(year.to_string());
-        }
-
-        let result = call_ec_cli(&args);
+        let result = fetch_part(day, part, true);
 
-        if part == 1 {
+        if part == Part::One {
             println!("---");
         }
 
         match result {
-            Ok(_) => {
+            PartStatus::Downloaded => {
                 println!("📝 Successfully wrote description to \"{}\".", &desc_path);
                 println!("📥 Successfully wrote input to \"{}\".", &input_path);
                 println!("🧪 Successfully wrote sample to \"{}\".", &sample_path);
-                println!("✅ Successfully wrote sample answer to \"{}\".", &sample_answer_path);
+                println!(
+                    "✅ Successfully wrote sample answer to \"{}\".",
+                    &sample_answer_path
+                );
             }
-            Err(_) => {
-                // If download fails (e.g., part not available yet), write "0" to sample and answer
-                if part > 1 {
-                    if let Err(e) = std::fs::write(&sample_path, "0") {
-                        eprintln!("Failed to write default sample to {}: {}", &sample_path, e);
-                    }
-                    if let Err(e) = std::fs::write(&sample_answer_path, "0") {
-                        eprintln!(
-                            "Failed to write default sample answer to {}: {}",
-                            &sample_answer_path, e
-                        );
-                    }
-                    println!("⚠️  Part {} not available, wrote defaults to sample and answer files.", part);
-                } else {
-                    // Part 1 should always be available, so return the error
-                    return Err(result.unwrap_err());
-                }
+            PartStatus::Unavailable => {
+                println!(
+                    "⚠️  Part {} not available, wrote defaults to sample and answer files.",
+                    part
+                );
+            }
+            PartStatus::Failed(e) => {
+                // Part 1 should always be available, so return the error
+                return Err(e);
             }
         }
 
-        if part < 3 {
+        if part != Part::Three {
             println!();
         }
     }
 
     println!("---");
-    Ok(Output {
+    Ok(empty_output())
+}
+
+fn empty_output() -> Output {
+    Output {
         status: std::process::ExitStatus::default(),
         stdout: vec![],
         stderr: vec![],
-    })
+    }
+}
+
+/// Download several days at once, running at most `jobs` ec-cli invocations in parallel.
+/// Output of ec-cli is captured so that concurrent downloads do not interleave.
+pub fn download_all(days: &[Day], jobs: usize) -> Vec<(Day, [PartStatus; 3])> {
+    let queue = Mutex::new(days.to_vec());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            let sender = sender.clone();
+            let queue = &queue;
+
+            scope.spawn(move || loop {
+                let Some(day) = queue.lock().unwrap().pop() else {
+                    break;
+                };
+
+                let first = fetch_part(day, Part::One, false);
+                let statuses = if matches!(first, PartStatus::Failed(_)) {
+                    let skipped = || PartStatus::Failed(EcCommandError::Skipped);
+                    [first, skipped(), skipped()]
+                } else {
+                    [
+                        first,
+                        fetch_part(day, Part::Two, false),
+                        fetch_part(day, Part::Three, false),
+                    ]
+                };
+
+                sender.send((day, statuses)).unwrap();
+            });
+        }
+    });
+
+    drop(sender);
+
+    let mut results: Vec<_> = receiver.into_iter().collect();
+    results.sort_unstable_by_key(|(day, _)| *day);
+    results
+}
+
+/// Fetch a single part. Files are written to temporary paths first and only renamed into
+/// place once the download succeeded, so an aborted download never leaves truncated files.
+fn fetch_part(day: Day, part: Part, inherit_output: bool) -> PartStatus {
+    let input_path = get_input_path(day, part);
+    let sample_path = get_sample_path(day, part);
+    let sample_answer_path = get_sample_answer_path(day, part);
+    let desc_path = get_description_path(day, part);
+
+    let paths = [&input_path, &sample_path, &sample_answer_path, &desc_path];
+
+    if read_only::is_enabled() {
+        for path in paths {
+            if let Some(dir) = Path::new(path).parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+        }
+    }
+
+    match fetch_to_temp_paths(day, part, inherit_output) {
+        Ok(()) => {
+            for path in paths {
+                let temp_path = get_temp_path(path);
+                if Path::new(&temp_path).exists() {
+                    if let Err(e) = fs::rename(&temp_path, path) {
+                        eprintln!("Failed to move {} into place: {}", &temp_path, e);
+                    }
+                }
+            }
+            if let Err(e) = manifest::record(&[Path::new(&input_path), Path::new(&sample_path)]) {
+                eprintln!("Failed to record the hashes of day {day} part {part}: {e}");
+            }
+            if let Err(e) = inputs::encrypt_in_place(Path::new(&input_path)) {
+                eprintln!("Failed to encrypt the input of day {day} part {part}: {e}");
+            }
+            if Path::new(&desc_path).exists() {
+                quest_meta::record_fetch(day, part);
+            }
+            plugins::emit(&plugins::Event::Downloaded { day, part });
+            PartStatus::Downloaded
+        }
+        Err(e) => {
+            for path in paths {
+                let _ = fs::remove_file(get_temp_path(path));
+            }
+
+            // If download fails (e.g., part not available yet), write "0" to sample and answer
+            if part == 1 {
+                return PartStatus::Failed(e);
+            }
+
+            if let Err(e) = write_atomic(&sample_path, "0") {
+                eprintln!("Failed to write default sample to {}: {}", &sample_path, e);
+            }
+            if let Err(e) = write_atomic(&sample_answer_path, "0") {
+                eprintln!(
+                    "Failed to write default sample answer to {}: {}",
+                    &sample_answer_path, e
+                );
+            }
+            PartStatus::Unavailable
+        }
+    }
+}
+
+#[cfg(feature = "http_client")]
+fn fetch_to_temp_paths(day: Day, part: Part, _inherit_output: bool) -> Result<(), EcCommandError> {
+    client::fetch_part(
+        day,
+        part,
+        &get_temp_path(&get_input_path(day, part)),
+        &get_temp_path(&get_sample_path(day, part)),
+        &get_temp_path(&get_sample_answer_path(day, part)),
+        &get_temp_path(&get_description_path(day, part)),
+    )
+}
+
+#[cfg(not(feature = "http_client"))]
+fn fetch_to_temp_paths(day: Day, part: Part, inherit_output: bool) -> Result<(), EcCommandError> {
+    let input_path = get_input_path(day, part);
+    let sample_path = get_sample_path(day, part);
+    let sample_answer_path = get_sample_answer_path(day, part);
+    let desc_path = get_description_path(day, part);
+
+    let mut args = vec![
+        "fetch".to_string(),
+        "-d".to_string(),
+        day.to_string(),
+        "-p".to_string(),
+        part.to_string(),
+        "--sample-path".to_string(),
+        get_temp_path(&sample_path),
+        "--sample-answer-path".to_string(),
+        get_temp_path(&sample_answer_path),
+        "--input-path".to_string(),
+        get_temp_path(&input_path),
+        "--description-path".to_string(),
+        get_temp_path(&desc_path),
+    ];
+
+    args.push("-y".to_string());
+    args.push(Event::current().id().to_string());
+
+    let result = if inherit_output {
+        call_ec_cli(&args)
+    } else {
+        call_ec_cli_captured(&args)
+    };
+    result.map(|_| ())
+}
+
+/// Write a file by writing to a temporary sibling first and renaming it into place.
+pub(crate) fn write_atomic(path: &str, contents: &str) -> Result<(), std::io::Error> {
+    let temp_path = get_temp_path(path);
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)
+}
+
+/// Submit an answer, unless the previous submission for any quest was less than the
+/// configured cooldown ago.
+#[cfg(feature = "http_client")]
+pub fn submit(day: Day, part: Part, result: &str) -> Result<Output, EcCommandError> {
+    if read_only::is_enabled() {
+        return Err(EcCommandError::ReadOnly);
+    }
+    if let Some(secs) = submissions::check_cooldown() {
+        return Err(EcCommandError::Throttled(secs));
+    }
+
+    let correct = client::submit(day, part, result);
+    if let Ok(correct) = correct {
+        submissions::record_submission(day, part);
+        plugins::emit(&plugins::Event::Submitted {
+            day,
+            part,
+            answer: result,
+            correct,
+        });
+    }
+
+    match correct? {
+        true => Ok(empty_output()),
+        false => Err(EcCommandError::Incorrect),
+    }
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, EcCommandError> {
+/// Submit an answer, unless the previous submission for any quest was less than the
+/// configured cooldown ago.
+#[cfg(not(feature = "http_client"))]
+pub fn submit(day: Day, part: Part, result: &str) -> Result<Output, EcCommandError> {
+    if read_only::is_enabled() {
+        return Err(EcCommandError::ReadOnly);
+    }
+    if let Some(secs) = submissions::check_cooldown() {
+        return Err(EcCommandError::Throttled(secs));
+    }
+
     let mut args = vec![
         "submit".to_string(),
         "-d".to_string(),
@@ -129,37 +409,55 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, EcCommandError
         result.to_string(),
     ];
 
-    if let Some(year) = get_year() {
-        args.push("-y".to_string());
-        args.push(year.to_string());
-    }
+    args.push("-y".to_string());
+    args.push(Event::current().id().to_string());
 
-    call_ec_cli(&args)
+    let output = call_ec_cli(&args);
+    if matches!(output, Ok(_) | Err(EcCommandError::BadExitStatus(_))) {
+        submissions::record_submission(day, part);
+        plugins::emit(&plugins::Event::Submitted {
+            day,
+            part,
+            answer: result,
+            correct: output.is_ok(),
+        });
+    }
+    output
 }
 
-fn get_input_path(day: Day, part: u8) -> String {
-    format!("data/inputs/{day}-{part}.txt")
+fn get_temp_path(path: &str) -> String {
+    format!("{path}.tmp")
 }
 
-fn get_sample_path(day: Day, part: u8) -> String {
-    format!("data/samples/{day}-{part}.txt")
+fn get_input_path(day: Day, part: Part) -> String {
+    format!(
+        "{}/inputs/{day}-{part}.txt",
+        read_only::download_dir().display()
+    )
 }
 
-fn get_sample_answer_path(day: Day, part: u8) -> String {
-    format!("data/answers/{day}-{part}.txt")
+fn get_sample_path(day: Day, part: Part) -> String {
+    format!(
+        "{}/samples/{day}-{part}.txt",
+        read_only::download_dir().display()
+    )
 }
 
-fn get_description_path(day: Day, part: u8) -> String {
-    format!("data/descriptions/{day}-{part}.html")
+fn get_sample_answer_path(day: Day, part: Part) -> String {
+    format!(
+        "{}/answers/{day}-{part}.txt",
+        read_only::download_dir().display()
+    )
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("EC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+fn get_description_path(day: Day, part: Part) -> String {
+    format!(
+        "{}/descriptions/{day}-{part}.html",
+        read_only::download_dir().display()
+    )
 }
 
+#[cfg(not(feature = "http_client"))]
 fn call_ec_cli(args: &[String]) -> Result<Output, EcCommandError> {
     // println!("Calling >ec-cli with: {}", args.join(" "));
     let output = Command::new("ec-cli")
@@ -175,3 +473,17 @@ fn call_ec_cli(args: &[String]) -> Result<Output, EcCommandError> {
         Err(EcCommandError::BadExitStatus(output))
     }
 }
+
+#[cfg(not(feature = "http_client"))]
+fn call_ec_cli_captured(args: &[String]) -> Result<Output, EcCommandError> {
+    let output = Command::new("ec-cli")
+        .args(args)
+        .output()
+        .map_err(|_| EcCommandError::CommandNotCallable)?;
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(EcCommandError::BadExitStatus(output))
+    }
+}
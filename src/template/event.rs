@@ -0,0 +1,172 @@
+/// The event quests belong to: the main event in November, or one of the Stories, mini-events of
+/// three quests published during the rest of the year.
+///
+/// A story keeps its quest data in `data/story{id}` and its solutions in
+/// `src/bin/story{id}_{day}.rs`, next to the events of the [`Year`] layout. Commands work on a
+/// story with `--story <id>`, solutions know theirs from their binary name, or from
+/// `solution!(1, story = 1)`.
+///
+/// The API numbers stories like events, so story 1 is served as event `1`.
+use std::{error::Error, fmt::Display, str::FromStr, sync::OnceLock};
+
+use crate::template::{set_year, Year};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Event {
+    MainEvent { year: Year },
+    Story { id: u8 },
+}
+
+static STORY: OnceLock<u8> = OnceLock::new();
+
+impl Event {
+    /// The event commands and solutions work on: a story given with `--story` or in the name
+    /// of the running solution binary, otherwise the main event of [`Year::current`].
+    pub fn current() -> Self {
+        STORY
+            .get()
+            .copied()
+            .or_else(|| {
+                let exe = std::env::current_exe().ok()?;
+                story_from_bin_name(exe.file_stem()?.to_str()?)
+            })
+            .map_or_else(|| Event::from(Year::current()), |id| Event::Story { id })
+    }
+
+    /// The number of the event in the API, its year or the number of the story.
+    pub fn id(self) -> u16 {
+        match self {
+            Event::MainEvent { year } => year.into_inner(),
+            Event::Story { id } => id.into(),
+        }
+    }
+
+    /// Name of the event's data folder and prefix of its binaries, e.g. `2024` or `story1`.
+    pub fn slug(self) -> String {
+        match self {
+            Event::MainEvent { year } => year.to_string(),
+            Event::Story { id } => format!("story{id}"),
+        }
+    }
+
+    /// The number of quests of the event.
+    pub fn quest_count(self) -> u8 {
+        match self {
+            Event::MainEvent { .. } => 20,
+            Event::Story { .. } => 3,
+        }
+    }
+}
+
+impl From<Year> for Event {
+    fn from(year: Year) -> Self {
+        Event::MainEvent { year }
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::MainEvent { year } => write!(f, "{year}"),
+            Event::Story { id } => write!(f, "Story {id}"),
+        }
+    }
+}
+
+/// Work on a story instead of a main event in this process, see `--story`.
+pub fn set_story(id: StoryId) {
+    let _ = STORY.set(id.0);
+}
+
+/// Work on `event` in this process, e.g. the one given to `solution!`.
+pub fn set_event(event: Event) {
+    match event {
+        Event::MainEvent { year } => set_year(year),
+        Event::Story { id } => {
+            let _ = STORY.set(id);
+        }
+    }
+}
+
+/// The story of a solution binary or its test harness, e.g. `story1_02` or `story1_02-1a2b3c`.
+fn story_from_bin_name(name: &str) -> Option<u8> {
+    let (event, _) = name.split_once('_')?;
+    event
+        .strip_prefix("story")?
+        .parse::<StoryId>()
+        .ok()
+        .map(|id| id.0)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The number of a story, from 1 onwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoryId(u8);
+
+impl StoryId {
+    pub fn new(id: u8) -> Option<Self> {
+        (id >= 1).then_some(Self(id))
+    }
+}
+
+impl FromStr for StoryId {
+    type Err = StoryIdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .ok()
+            .and_then(Self::new)
+            .ok_or(StoryIdFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`StoryId`].
+#[derive(Debug)]
+pub struct StoryIdFromStrError;
+
+impl Error for StoryIdFromStrError {}
+
+impl Display for StoryIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a story number from 1")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{story_from_bin_name, Event, StoryId};
+    use crate::year;
+
+    #[test]
+    fn reads_story_from_bin_name() {
+        assert_eq!(story_from_bin_name("story1_02"), Some(1));
+        assert_eq!(story_from_bin_name("story12_03_alice"), Some(12));
+        assert_eq!(story_from_bin_name("story2_01-1a2b3c4d"), Some(2));
+        assert_eq!(story_from_bin_name("story0_01"), None);
+        assert_eq!(story_from_bin_name("2024_07"), None);
+        assert_eq!(story_from_bin_name("07"), None);
+    }
+
+    #[test]
+    fn names_events() {
+        let main = Event::from(year!(2024));
+        let story = Event::Story { id: 2 };
+
+        assert_eq!(
+            (main.id(), main.slug(), main.to_string()),
+            (2024, "2024".into(), "2024".into())
+        );
+        assert_eq!(
+            (story.id(), story.slug(), story.to_string()),
+            (2, "story2".into(), "Story 2".into())
+        );
+        assert_eq!(story.quest_count(), 3);
+
+        assert!("1".parse::<StoryId>().is_ok());
+        assert!("0".parse::<StoryId>().is_err());
+        assert!("one".parse::<StoryId>().is_err());
+    }
+}
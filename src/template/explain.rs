@@ -0,0 +1,533 @@
+/// Checkpoints for intermediate values of a part, compared against a fixture of expected
+/// values for the sample in `data/fixtures/{day}-{part}.toml`.
+///
+/// Solutions call [`checkpoint`] at interesting stages. Running a quest with `--explain` runs
+/// each part against its sample and reports the first checkpoint that diverges from the
+/// fixture, which narrows down where a wrong answer comes from. Without `--explain`,
+/// checkpoints do nothing.
+///
+/// With `--describe`, as run by `cargo explain <day> <part>`, a part runs on the real input
+/// instead and its checkpoints are summarized as phases for a writeup: how often each stage was
+/// reached, when, and which values it went through.
+///
+/// ```toml
+/// parsed = 12            # compared with the first `checkpoint("parsed", ...)`
+/// state = ["AB", "BA"]   # compared with the first two `checkpoint("state", ...)`
+/// ```
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    fmt::{Display, Write},
+    fs,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+use toml::{Table, Value};
+
+use crate::template::{
+    answers::get_answer_path, data_dir, input_stats::InputStats, read_answer, runner::read_input,
+    Day, Part, ANSI_BOLD, ANSI_RESET,
+};
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+static DESCRIBING: OnceLock<bool> = OnceLock::new();
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static STARTED: Mutex<Option<Instant>> = Mutex::new(None);
+static CHECKPOINTS: Mutex<Vec<Checkpoint>> = Mutex::new(vec![]);
+
+/// Whether the binary was invoked with `--explain` or `--describe`, so checkpoints are recorded.
+pub fn is_enabled() -> bool {
+    *ENABLED.get_or_init(|| env::args().any(|x| x == "--explain" || x == "--describe"))
+}
+
+/// Whether the binary was invoked with `--describe`, to summarize the checkpoints of a run on
+/// the real input instead of comparing them against the sample's fixture.
+pub fn is_describing() -> bool {
+    *DESCRIBING.get_or_init(|| env::args().any(|x| x == "--describe"))
+}
+
+/// A value recorded with [`checkpoint`], `at` the time since the part started.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checkpoint {
+    pub name: String,
+    pub value: String,
+    pub at: Duration,
+}
+
+/// Record an intermediate value of the running part. Values are compared by their
+/// `Display` output, use `format!("{value:?}")` for types without one.
+pub fn checkpoint<T: Display>(name: &str, value: T) {
+    if is_enabled() && RECORDING.load(Ordering::Relaxed) {
+        let at = STARTED
+            .lock()
+            .unwrap()
+            .map_or(Duration::ZERO, |started| started.elapsed());
+        CHECKPOINTS.lock().unwrap().push(Checkpoint {
+            name: name.to_string(),
+            value: value.to_string(),
+            at,
+        });
+    }
+}
+
+pub(crate) fn begin() {
+    CHECKPOINTS.lock().unwrap().clear();
+    *STARTED.lock().unwrap() = Some(Instant::now());
+    RECORDING.store(true, Ordering::Relaxed);
+}
+
+/// Stop recording, e.g. before the part is benchmarked.
+pub(crate) fn end() {
+    RECORDING.store(false, Ordering::Relaxed);
+}
+
+fn get_fixture_path(day: Day, part: Part) -> String {
+    format!("{}/fixtures/{day}-{part}.toml", data_dir().display())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The first checkpoint whose value differs from the fixture.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Divergence {
+    pub label: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Comparison {
+    /// Checkpoints that matched the fixture, as `(label, value)`, up to the divergence.
+    pub matched: Vec<(String, String)>,
+    pub divergence: Option<Divergence>,
+    /// Expected checkpoints that were never recorded.
+    pub missing: Vec<String>,
+}
+
+fn value_to_string(value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Integer(i) => Ok(i.to_string()),
+        Value::Float(f) => Ok(f.to_string()),
+        Value::Boolean(b) => Ok(b.to_string()),
+        _ => Err(format!(
+            "unsupported fixture value of type {}.",
+            value.type_str()
+        )),
+    }
+}
+
+/// Parse a fixture into the expected values of each checkpoint, in recording order.
+pub fn parse_fixture(s: &str) -> Result<HashMap<String, Vec<String>>, String> {
+    let table = s.parse::<Table>().map_err(|e| e.to_string())?;
+
+    table
+        .iter()
+        .map(|(name, value)| {
+            let values = match value {
+                Value::Array(values) => values.iter().map(value_to_string).collect(),
+                value => value_to_string(value).map(|v| vec![v]),
+            }?;
+            Ok((name.clone(), values))
+        })
+        .collect()
+}
+
+/// Compare recorded checkpoints with the expected values, stopping at the first divergence.
+/// Checkpoints without an expected value are ignored.
+pub fn compare(
+    expected: &HashMap<String, Vec<String>>,
+    recorded: &[(String, String)],
+) -> Comparison {
+    let mut comparison = Comparison::default();
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+
+    for (name, actual) in recorded {
+        let index = seen.entry(name).or_default();
+        let Some(expected_values) = expected.get(name) else {
+            continue;
+        };
+        let Some(expected_value) = expected_values.get(*index) else {
+            continue;
+        };
+
+        let label = if expected_values.len() == 1 {
+            name.clone()
+        } else {
+            format!("{name}[{index}]")
+        };
+        *index += 1;
+
+        if expected_value == actual {
+            comparison.matched.push((label, actual.clone()));
+        } else {
+            comparison.divergence = Some(Divergence {
+                label,
+                expected: expected_value.clone(),
+                actual: actual.clone(),
+            });
+            return comparison;
+        }
+    }
+
+    let mut names: Vec<&String> = expected.keys().collect();
+    names.sort();
+    for name in names {
+        let count = seen.get(name.as_str()).copied().unwrap_or(0);
+        let values = &expected[name];
+        for index in count..values.len() {
+            comparison.missing.push(if values.len() == 1 {
+                name.clone()
+            } else {
+                format!("{name}[{index}]")
+            });
+        }
+    }
+
+    comparison
+}
+
+/// Print the comparison of the recorded checkpoints and the answer of a part against the
+/// sample's fixture and answer file.
+pub(crate) fn report(day: Day, part: Part, answer: Option<&str>) {
+    end();
+    let recorded: Vec<(String, String)> = std::mem::take(&mut *CHECKPOINTS.lock().unwrap())
+        .into_iter()
+        .map(|checkpoint| (checkpoint.name, checkpoint.value))
+        .collect();
+    let path = get_fixture_path(day, part);
+
+    println!("{ANSI_BOLD}🔍 Part {part} checkpoints{ANSI_RESET} ({path})");
+
+    let Ok(fixture) = fs::read_to_string(&path) else {
+        if recorded.is_empty() {
+            println!("  No fixture and no checkpoints recorded.");
+        } else {
+            println!("  No fixture, recorded checkpoints:");
+        }
+        for (name, value) in &recorded {
+            println!("  • {name} = {value}");
+        }
+        report_answer(day, part, answer);
+        return;
+    };
+
+    match parse_fixture(&fixture) {
+        Ok(expected) => {
+            let comparison = compare(&expected, &recorded);
+            for (label, value) in &comparison.matched {
+                println!("  ✅ {label} = {value}");
+            }
+            if let Some(d) = &comparison.divergence {
+                println!(
+                    "  ❌ {} diverges first: expected `{}`, got `{}`",
+                    d.label, d.expected, d.actual
+                );
+            }
+            for label in &comparison.missing {
+                println!("  ⚠️  {label} was never recorded");
+            }
+        }
+        Err(e) => println!("  Invalid fixture: {e}"),
+    }
+
+    report_answer(day, part, answer);
+}
+
+fn report_answer(day: Day, part: Part, answer: Option<&str>) {
+    if get_answer_path(day, part).exists() {
+        let expected = read_answer(day, part);
+        match answer {
+            Some(answer) if answer == expected => println!("  ✅ answer = {answer}"),
+            answer => println!(
+                "  ❌ answer: expected `{expected}`, got `{}`",
+                answer.unwrap_or("✖")
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A run of consecutive checkpoints with the same name, e.g. the states of a simulation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Phase {
+    pub name: String,
+    pub count: usize,
+    pub first: String,
+    pub last: String,
+    pub distinct: usize,
+    /// Smallest and largest value, if all values are integers.
+    pub range: Option<(i128, i128)>,
+    pub start: Duration,
+    pub end: Duration,
+}
+
+/// Group recorded checkpoints into phases, in recording order.
+pub fn phases(recorded: &[Checkpoint]) -> Vec<Phase> {
+    recorded
+        .chunk_by(|a, b| a.name == b.name)
+        .map(|run| {
+            let (first, last) = (&run[0], &run[run.len() - 1]);
+            let numbers: Option<Vec<i128>> = run.iter().map(|c| c.value.parse().ok()).collect();
+
+            Phase {
+                name: first.name.clone(),
+                count: run.len(),
+                first: first.value.clone(),
+                last: last.value.clone(),
+                distinct: run.iter().map(|c| &c.value).collect::<HashSet<_>>().len(),
+                range: numbers.and_then(|n| n.iter().min().copied().zip(n.iter().max().copied())),
+                start: first.at,
+                end: last.at,
+            }
+        })
+        .collect()
+}
+
+/// A value for a table cell: the first line of multi-line values, shortened to a glance.
+fn cell(value: &str) -> String {
+    let line = value.lines().next().unwrap_or_default();
+    let mut short: String = line.chars().take(24).collect();
+    if short.len() < line.len() || value.lines().nth(1).is_some() {
+        short.push('…');
+    }
+    format!("`{}`", short.replace('|', "\\|"))
+}
+
+/// Render the story of a part as Markdown, to paste into a writeup.
+pub fn describe(
+    day: Day,
+    part: Part,
+    input: &InputStats,
+    phases: &[Phase],
+    answer: Option<&str>,
+    duration: Duration,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "## Day {day} Part {part}\n");
+
+    let _ = write!(out, "The input has {} lines", input.lines);
+    if let Some((width, height)) = input.grid {
+        let _ = write!(out, " forming a {width}×{height} grid");
+    }
+    if let Some((min, max)) = input.number_range {
+        let _ = write!(out, " with {} numbers in {min}..={max}", input.numbers);
+    }
+    let _ = writeln!(out, ".\n");
+
+    if phases.is_empty() {
+        let _ = writeln!(
+            out,
+            "No checkpoints were recorded, add `checkpoint(name, value)` calls at the \
+             interesting stages of the part.\n"
+        );
+    } else {
+        let _ = writeln!(out, "| # | Phase | Count | Time | Values |");
+        let _ = writeln!(out, "|---|-------|------:|------|--------|");
+        for (i, phase) in phases.iter().enumerate() {
+            let time = if phase.count == 1 {
+                format!("{:.1?}", phase.start)
+            } else {
+                format!("{:.1?} - {:.1?}", phase.start, phase.end)
+            };
+            let mut values = cell(&phase.first);
+            if phase.count > 1 {
+                let _ = write!(
+                    values,
+                    " → {}, {} distinct",
+                    cell(&phase.last),
+                    phase.distinct
+                );
+                if let Some((min, max)) = phase.range {
+                    let _ = write!(values, ", {min}..={max}");
+                }
+            }
+            let _ = writeln!(
+                out,
+                "| {} | `{}` | {} | {time} | {values} |",
+                i + 1,
+                phase.name,
+                phase.count
+            );
+        }
+        out.push('\n');
+    }
+
+    match answer {
+        Some(answer) if answer.contains('\n') => {
+            let _ = writeln!(
+                out,
+                "The answer, after {duration:.1?}:\n\n```\n{answer}\n```"
+            );
+        }
+        Some(answer) => {
+            let _ = writeln!(out, "The answer is **{answer}**, after {duration:.1?}.");
+        }
+        None => {
+            let _ = writeln!(out, "The part returned no answer after {duration:.1?}.");
+        }
+    }
+
+    out
+}
+
+/// Print the story of a part that ran with `--describe`.
+pub(crate) fn report_description(day: Day, part: Part, answer: Option<&str>, duration: Duration) {
+    end();
+    let recorded = std::mem::take(&mut *CHECKPOINTS.lock().unwrap());
+    let input = InputStats::from_input(&String::from_utf8_lossy(&read_input(day, part)));
+
+    println!();
+    print!(
+        "{}",
+        describe(day, part, &input, &phases(&recorded), answer, duration)
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, describe, parse_fixture, phases, Checkpoint, Divergence};
+    use crate::{
+        day,
+        template::{input_stats::InputStats, Part},
+    };
+
+    fn recorded(values: &[(&str, &str)]) -> Vec<(String, String)> {
+        values
+            .iter()
+            .map(|(n, v)| (n.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_fixtures() {
+        let fixture = parse_fixture("parsed = 12\nstate = [\"AB\", \"BA\"]\nok = true").unwrap();
+        assert_eq!(fixture["parsed"], vec!["12"]);
+        assert_eq!(fixture["state"], vec!["AB", "BA"]);
+        assert_eq!(fixture["ok"], vec!["true"]);
+        assert_eq!(parse_fixture("nested = { a = 1 }").is_err(), true);
+    }
+
+    #[test]
+    fn finds_first_divergence() {
+        let fixture = parse_fixture("parsed = 12\nstate = [\"AB\", \"BA\", \"AA\"]").unwrap();
+        let comparison = compare(
+            &fixture,
+            &recorded(&[
+                ("parsed", "12"),
+                ("debug", "ignored"),
+                ("state", "AB"),
+                ("state", "BB"),
+                ("state", "AA"),
+            ]),
+        );
+
+        assert_eq!(
+            comparison.matched,
+            recorded(&[("parsed", "12"), ("state[0]", "AB")])
+        );
+        assert_eq!(
+            comparison.divergence,
+            Some(Divergence {
+                label: "state[1]".into(),
+                expected: "BA".into(),
+                actual: "BB".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn reports_missing_checkpoints() {
+        let fixture = parse_fixture("parsed = 12\nstate = [\"AB\", \"BA\"]").unwrap();
+        let comparison = compare(
+            &fixture,
+            &recorded(&[("state", "AB"), ("state", "BA"), ("state", "AB")]),
+        );
+
+        assert_eq!(comparison.divergence, None);
+        assert_eq!(comparison.matched.len(), 2);
+        assert_eq!(comparison.missing, vec!["parsed"]);
+    }
+
+    fn checkpoints(values: &[(&str, &str, u64)]) -> Vec<Checkpoint> {
+        values
+            .iter()
+            .map(|(name, value, millis)| Checkpoint {
+                name: name.to_string(),
+                value: value.to_string(),
+                at: Duration::from_millis(*millis),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn groups_phases() {
+        let phases = phases(&checkpoints(&[
+            ("parsed", "12", 1),
+            ("state", "AB", 2),
+            ("state", "BA", 3),
+            ("state", "AB", 5),
+            ("energy", "7", 6),
+            ("energy", "-3", 8),
+            ("state", "AA", 9),
+        ]));
+
+        let summary: Vec<_> = phases
+            .iter()
+            .map(|p| (p.name.as_str(), p.count, p.distinct, p.range))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("parsed", 1, 1, Some((12, 12))),
+                ("state", 3, 2, None),
+                ("energy", 2, 2, Some((-3, 7))),
+                ("state", 1, 1, None),
+            ]
+        );
+        assert_eq!(phases[1].first, "AB");
+        assert_eq!(phases[1].last, "AB");
+        assert_eq!(
+            (phases[1].start, phases[1].end),
+            (Duration::from_millis(2), Duration::from_millis(5))
+        );
+    }
+
+    #[test]
+    fn describes_parts() {
+        let input = InputStats::from_input("#..#\n##.#\n");
+        let phases = phases(&checkpoints(&[
+            ("plants", "12", 1),
+            ("state", "#..#\n##.#", 2),
+            ("state", "a|b", 4),
+        ]));
+        let report = describe(
+            day!(7),
+            Part::Two,
+            &input,
+            &phases,
+            Some("42"),
+            Duration::from_millis(5),
+        );
+
+        assert!(report.starts_with("## Day 07 Part 2\n\nThe input has 2 lines forming a 4×2 grid."));
+        assert!(report.contains("| 1 | `plants` | 1 | 1.0ms | `12` |"));
+        assert!(
+            report.contains("| 2 | `state` | 2 | 2.0ms - 4.0ms | `#..#…` → `a\\|b`, 2 distinct |")
+        );
+        assert!(report.ends_with("The answer is **42**, after 5.0ms.\n"));
+
+        let report = describe(day!(7), Part::Two, &input, &[], None, Duration::ZERO);
+        assert!(report.contains("No checkpoints were recorded"));
+        assert!(report.contains("The part returned no answer"));
+    }
+}
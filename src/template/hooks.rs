@@ -0,0 +1,115 @@
+/// Hooks that run around every solution part, e.g. to start a profiler or dump caches.
+///
+/// Hooks are registered from the quest module through `solution!` or configured as shell
+/// commands in `ec.toml`:
+///
+/// ```toml
+/// [hooks]
+/// before_part = "echo starting day $EC_DAY part $EC_PART"
+/// after_part = "echo answer $EC_ANSWER took ${EC_DURATION_NANOS}ns"
+/// ```
+use std::{process::Command, sync::OnceLock, time::Duration};
+
+use crate::template::{
+    config::config,
+    plugins::{self, Event},
+    Day, Part,
+};
+
+/// Information about the part a hook runs for.
+/// `answer` and `duration` are only set for `after_part` hooks.
+#[derive(Clone, Debug)]
+pub struct PartInfo<'a> {
+    pub day: Day,
+    pub part: Part,
+    pub answer: Option<&'a str>,
+    pub duration: Option<Duration>,
+}
+
+pub type Hook = fn(&PartInfo);
+
+/// Generates a synthetic input of roughly `size` from a seed, see `cargo gen`.
+pub type InputGenerator = fn(usize, u64) -> String;
+
+static BEFORE_PART: OnceLock<Hook> = OnceLock::new();
+static AFTER_PART: OnceLock<Hook> = OnceLock::new();
+static GEN_INPUT: OnceLock<InputGenerator> = OnceLock::new();
+
+/// Register a hook that runs before each part. Only the first registration takes effect.
+pub fn register_before_part(hook: Hook) {
+    let _ = BEFORE_PART.set(hook);
+}
+
+/// Register a hook that runs after each part. Only the first registration takes effect.
+pub fn register_after_part(hook: Hook) {
+    let _ = AFTER_PART.set(hook);
+}
+
+/// Register the input generator of the quest. Only the first registration takes effect.
+pub fn register_gen_input(generator: InputGenerator) {
+    let _ = GEN_INPUT.set(generator);
+}
+
+pub(crate) fn gen_input() -> Option<InputGenerator> {
+    GEN_INPUT.get().copied()
+}
+
+pub(crate) fn run_before_part(info: &PartInfo) {
+    run_script("hooks.before_part", info);
+    if let Some(hook) = BEFORE_PART.get() {
+        hook(info);
+    }
+}
+
+/// Run the `after_part` hooks, then tell the plugins that the part ran if it ran on the real
+/// input, so samples and synthetic inputs never look like solves.
+pub(crate) fn run_after_part(info: &PartInfo, real_input: bool) {
+    if let Some(hook) = AFTER_PART.get() {
+        hook(info);
+    }
+    run_script("hooks.after_part", info);
+
+    if let (true, Some(duration)) = (real_input, info.duration) {
+        plugins::emit(&Event::Ran {
+            day: info.day,
+            part: info.part,
+            answer: info.answer,
+            duration,
+        });
+    }
+}
+
+/// Run the shell command configured under `key`, exposing the part info as `EC_*` env vars.
+fn run_script(key: &str, info: &PartInfo) {
+    let Some(script) = config().get_str(key) else {
+        return;
+    };
+
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+
+    cmd.arg(script)
+        .env("EC_DAY", info.day.to_string())
+        .env("EC_PART", info.part.to_string());
+
+    if let Some(answer) = info.answer {
+        cmd.env("EC_ANSWER", answer);
+    }
+
+    if let Some(duration) = info.duration {
+        cmd.env("EC_DURATION_NANOS", duration.as_nanos().to_string());
+    }
+
+    match cmd.status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!("Hook `{key}` exited with {status}."),
+        Err(e) => eprintln!("Hook `{key}` could not be run: {e}"),
+    }
+}
@@ -0,0 +1,196 @@
+/// Plain and terminal text from the HTML of downloaded quest descriptions.
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Strip tags and decode the entities used in descriptions.
+pub fn to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    decode(&text)
+}
+
+fn decode(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Format a description for the terminal: headings and inline code in bold, emphasis in
+/// italics, paragraphs and list items on their own lines and code blocks indented as they are.
+pub fn to_terminal(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(tag) = rest.strip_prefix('<') {
+            let Some(end) = tag.find('>') else {
+                break;
+            };
+            let closing = tag.starts_with('/');
+            let name = tag[..end]
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            rest = &tag[end + 1..];
+
+            match (name.as_str(), closing) {
+                ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                    paragraph(&mut out);
+                    out.push_str(ANSI_BOLD);
+                }
+                ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => {
+                    out.push_str(ANSI_RESET);
+                    paragraph(&mut out);
+                }
+                ("p" | "div" | "ul" | "ol" | "blockquote", _) => paragraph(&mut out),
+                ("br", _) => line(&mut out),
+                ("li", false) => {
+                    line(&mut out);
+                    out.push_str("  • ");
+                }
+                ("pre", false) => {
+                    paragraph(&mut out);
+                    in_pre = true;
+                    rest = rest.strip_prefix('\n').unwrap_or(rest);
+                }
+                ("pre", true) => {
+                    in_pre = false;
+                    paragraph(&mut out);
+                }
+                ("em" | "i", false) => out.push_str(ANSI_ITALIC),
+                ("strong" | "b", false) => out.push_str(ANSI_BOLD),
+                ("code", false) if !in_pre => out.push_str(ANSI_BOLD),
+                ("em" | "i" | "strong" | "b", true) => out.push_str(ANSI_RESET),
+                ("code", true) if !in_pre => out.push_str(ANSI_RESET),
+                _ => {}
+            }
+            continue;
+        }
+
+        let end = rest.find('<').unwrap_or(rest.len());
+        let text = decode(&rest[..end]);
+        rest = &rest[end..];
+
+        for c in text.chars() {
+            if in_pre {
+                if c != '\n' && (out.is_empty() || out.ends_with('\n')) {
+                    out.push_str("    ");
+                }
+                out.push(c);
+            } else if !c.is_whitespace() {
+                out.push(c);
+            } else if !(out.is_empty() || out.ends_with([' ', '\n'])) {
+                out.push(' ');
+            }
+        }
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+/// End the current line.
+fn line(out: &mut String) {
+    let trimmed = out.trim_end_matches(' ').len();
+    out.truncate(trimmed);
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// End the current paragraph with an empty line.
+fn paragraph(out: &mut String) {
+    line(out);
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+/// The title of a quest: the text of the first heading that isn't just a part number like
+/// `Part 1`, without a `Quest 7:` prefix.
+pub fn title(html: &str) -> Option<String> {
+    let mut rest = html;
+    while let Some(start) = find_heading(rest) {
+        let open = start + rest[start..].find('>')? + 1;
+        let close = open + rest[open..].find("</h")?;
+        let heading = to_text(&rest[open..close]);
+        let heading = heading.trim();
+        rest = &rest[close..];
+
+        if heading.is_empty() || is_part_heading(heading) {
+            continue;
+        }
+        let title = heading
+            .split_once(':')
+            .filter(|(prefix, _)| prefix.starts_with("Quest"))
+            .map_or(heading, |(_, title)| title.trim());
+        return Some(title.to_string());
+    }
+    None
+}
+
+/// The position of the next `<h1>` to `<h6>` tag.
+fn find_heading(html: &str) -> Option<usize> {
+    html.match_indices("<h").map(|(i, _)| i).find(|&i| {
+        html.as_bytes()
+            .get(i + 2)
+            .is_some_and(|c| (b'1'..=b'6').contains(c))
+    })
+}
+
+fn is_part_heading(heading: &str) -> bool {
+    heading
+        .strip_prefix("Part")
+        .is_some_and(|rest| rest.trim().chars().all(|c| c.is_ascii_digit() || c == 'I'))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{title, to_terminal, to_text};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn strips_tags() {
+        assert_eq!(to_text("<h2>Part 1</h2><p>a &amp; b</p>"), "Part 1a & b");
+    }
+
+    #[test]
+    fn formats_for_the_terminal() {
+        let html = "<h2 id=\"part1\">Part 1</h2>\n<p>The  <em>knights</em>\n stand in a\n<code>grid</code>:</p><pre class=\"note\">\n#.&lt;\n..#\n</pre><ul><li>one</li><li>two</li></ul><p>Done.</p>";
+
+        assert_eq!(
+            to_terminal(html),
+            format!(
+                "{ANSI_BOLD}Part 1{ANSI_RESET}\n\nThe {ANSI_ITALIC}knights{ANSI_RESET} stand in a {ANSI_BOLD}grid{ANSI_RESET}:\n\n    #.<\n    ..#\n\n  • one\n  • two\n\nDone.\n"
+            )
+        );
+    }
+
+    #[test]
+    fn finds_titles() {
+        assert_eq!(
+            title("<h1>Quest 7: Namegrid &amp; Nails</h1><h2>Part 1</h2><p>...</p>").as_deref(),
+            Some("Namegrid & Nails")
+        );
+        assert_eq!(
+            title("<h2>Part II</h2><h3 id=\"t\"><em>Whispers</em> in the Shell</h3>").as_deref(),
+            Some("Whispers in the Shell")
+        );
+        assert_eq!(title("<h2>Part 1</h2><p>no title</p><hr>"), None);
+    }
+}
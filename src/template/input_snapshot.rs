@@ -0,0 +1,73 @@
+/// Hashes of the real input taken when an answer is computed, so a submission can tell that the
+/// input was downloaded again in the meantime and refuse to send an answer for the old one.
+///
+/// Submitting is two-phase: the answer is computed with a snapshot of its input, and right
+/// before it is sent the snapshot is compared to the input on disk. On a mismatch the part is
+/// run again on the current input instead.
+use std::path::PathBuf;
+
+use crate::template::{inputs, manifest, read_only, Day, Part};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputSnapshot {
+    pub day: Day,
+    pub part: Part,
+    /// Hash of the input, `None` if there was no input.
+    hash: Option<String>,
+}
+
+impl InputSnapshot {
+    /// Snapshot of the input as it is on disk now.
+    pub fn take(day: Day, part: Part) -> Self {
+        Self::of(
+            day,
+            part,
+            inputs::read(&input_path(day, part)).ok().as_deref(),
+        )
+    }
+
+    /// Snapshot of input contents that were already read.
+    pub fn of(day: Day, part: Part, contents: Option<&[u8]>) -> Self {
+        Self {
+            day,
+            part,
+            hash: contents.map(manifest::hash),
+        }
+    }
+
+    /// Whether the input on disk no longer matches the snapshot.
+    pub fn is_stale(&self) -> bool {
+        *self != Self::take(self.day, self.part)
+    }
+}
+
+fn input_path(day: Day, part: Part) -> PathBuf {
+    read_only::quest_file(format!("inputs/{day}-{part}.txt"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSnapshot;
+    use crate::{day, template::Part};
+
+    #[test]
+    fn compares_inputs_by_hash() {
+        let before = InputSnapshot::of(day!(7), Part::One, Some(b"1,2,3"));
+
+        assert_eq!(
+            before,
+            InputSnapshot::of(day!(7), Part::One, Some(b"1,2,3"))
+        );
+        assert_ne!(
+            before,
+            InputSnapshot::of(day!(7), Part::One, Some(b"4,5,6"))
+        );
+        assert_ne!(before, InputSnapshot::of(day!(7), Part::One, None));
+        assert_ne!(
+            before,
+            InputSnapshot::of(day!(7), Part::Two, Some(b"1,2,3"))
+        );
+    }
+}
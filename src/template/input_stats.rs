@@ -0,0 +1,263 @@
+/// Structural statistics of an input, used to compare the sample with the real input and
+/// anticipate scaling issues before the first real run.
+use std::{collections::BTreeSet, fmt::Write};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputStats {
+    pub lines: usize,
+    pub chars: usize,
+    /// Shortest and longest line, ignoring a trailing empty line.
+    pub line_lengths: Option<(usize, usize)>,
+    /// Width and height when all lines have the same length.
+    pub grid: Option<(usize, usize)>,
+    /// Distinct characters, excluding whitespace and digits.
+    pub alphabet: BTreeSet<char>,
+    pub numbers: usize,
+    /// Smallest and largest integer, signed when preceded by `-`.
+    pub number_range: Option<(i128, i128)>,
+}
+
+impl InputStats {
+    pub fn from_input(input: &str) -> Self {
+        let lines: Vec<&str> = input.lines().collect();
+        let lengths: Vec<usize> = lines.iter().map(|l| l.chars().count()).collect();
+
+        let line_lengths = lengths
+            .iter()
+            .min()
+            .copied()
+            .zip(lengths.iter().max().copied());
+
+        let grid = match line_lengths {
+            Some((min, max)) if min == max && min > 1 && lines.len() > 1 => {
+                Some((min, lines.len()))
+            }
+            _ => None,
+        };
+
+        let numbers = parse_numbers(input);
+
+        InputStats {
+            lines: lines.len(),
+            chars: input.chars().count(),
+            line_lengths,
+            grid,
+            alphabet: input
+                .chars()
+                .filter(|c| !c.is_whitespace() && !c.is_ascii_digit())
+                .collect(),
+            numbers: numbers.len(),
+            number_range: numbers
+                .iter()
+                .min()
+                .copied()
+                .zip(numbers.iter().max().copied()),
+        }
+    }
+}
+
+/// Integers in the input. A `-` directly before the digits makes a number negative unless
+/// it follows a letter or digit, e.g. in `a-1` or `3-4`.
+fn parse_numbers(input: &str) -> Vec<i128> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut numbers = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        if !chars[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len() && chars[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let digits: String = chars[start..i].iter().collect();
+        let negative = start > 0
+            && chars[start - 1] == '-'
+            && (start < 2 || !chars[start - 2].is_alphanumeric());
+        let value = digits.parse::<i128>().unwrap_or(i128::MAX);
+        numbers.push(if negative { -value } else { value });
+    }
+
+    numbers
+}
+
+fn format_alphabet(alphabet: &BTreeSet<char>) -> String {
+    if alphabet.is_empty() {
+        "-".into()
+    } else if alphabet.len() > 20 {
+        format!("{} distinct", alphabet.len())
+    } else {
+        format!("`{}`", alphabet.iter().collect::<String>())
+    }
+}
+
+fn format_range(range: Option<(usize, usize)>) -> String {
+    match range {
+        Some((min, max)) if min == max => min.to_string(),
+        Some((min, max)) => format!("{min}-{max}"),
+        None => "-".into(),
+    }
+}
+
+fn format_ratio(sample: usize, real: usize) -> String {
+    if sample == 0 {
+        return "-".into();
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let ratio = real as f64 / sample as f64;
+    format!("×{ratio:.1}")
+}
+
+/// Render the statistics of the sample and the real input side by side.
+pub fn render(sample: &InputStats, real: &InputStats) -> String {
+    let cells = |s: &InputStats| s.grid.map_or(0, |(w, h)| w * h);
+    let grid = |s: &InputStats| s.grid.map_or("-".into(), |(w, h)| format!("{w}×{h}"));
+    let numbers = |s: &InputStats| match s.number_range {
+        Some((min, max)) => format!("{} in {min}..={max}", s.numbers),
+        None => "-".into(),
+    };
+
+    let rows = [
+        (
+            "Lines",
+            sample.lines.to_string(),
+            real.lines.to_string(),
+            format_ratio(sample.lines, real.lines),
+        ),
+        (
+            "Characters",
+            sample.chars.to_string(),
+            real.chars.to_string(),
+            format_ratio(sample.chars, real.chars),
+        ),
+        (
+            "Line length",
+            format_range(sample.line_lengths),
+            format_range(real.line_lengths),
+            format_ratio(
+                sample.line_lengths.map_or(0, |l| l.1),
+                real.line_lengths.map_or(0, |l| l.1),
+            ),
+        ),
+        (
+            "Grid",
+            grid(sample),
+            grid(real),
+            format_ratio(cells(sample), cells(real)),
+        ),
+        (
+            "Numbers",
+            numbers(sample),
+            numbers(real),
+            format_ratio(sample.numbers, real.numbers),
+        ),
+        (
+            "Alphabet",
+            format_alphabet(&sample.alphabet),
+            format_alphabet(&real.alphabet),
+            String::new(),
+        ),
+    ];
+
+    let mut s = String::from("| | Sample | Input | Scale |\n| --- | --- | --- | ---: |\n");
+    for (name, sample, real, ratio) in rows {
+        let _ = writeln!(s, "| {name} | {sample} | {real} | {ratio} |");
+    }
+    s
+}
+
+/// Differences between the sample and the real input that are worth a second look.
+pub fn notes(sample: &InputStats, real: &InputStats) -> Vec<String> {
+    let mut notes = vec![];
+
+    let new_chars: String = real.alphabet.difference(&sample.alphabet).collect();
+    if !new_chars.is_empty() {
+        notes.push(format!(
+            "The input contains characters that are not in the sample: `{new_chars}`."
+        ));
+    }
+
+    if let Some((min, max)) = real.number_range {
+        let magnitude = min.unsigned_abs().max(max.unsigned_abs());
+        if magnitude > i64::MAX as u128 {
+            notes.push("Numbers exceed the range of `i64`.".into());
+        } else if magnitude > u32::MAX as u128 {
+            notes.push("Numbers exceed the range of `u32`, use 64 bit integers.".into());
+        }
+
+        if min < 0 && sample.number_range.is_none_or(|(min, _)| min >= 0) {
+            notes.push("The input contains negative numbers, the sample does not.".into());
+        }
+    }
+
+    if sample.grid.is_some() && real.grid.is_none() && real.lines > 1 {
+        notes.push("The sample is a grid but the input has lines of different lengths.".into());
+    }
+
+    if let (Some((sw, sh)), Some((rw, rh))) = (sample.grid, real.grid) {
+        if rw * rh >= 100 * sw * sh {
+            notes.push(format!(
+                "The grid has {}× as many cells, brute force over cells may not scale.",
+                rw * rh / (sw * sh)
+            ));
+        }
+    }
+
+    notes
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{notes, parse_numbers, render, InputStats};
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(
+            parse_numbers("a-1, -2 3-4 x=-5\n99999999999999999999"),
+            vec![1, -2, 3, 4, -5, 99_999_999_999_999_999_999]
+        );
+    }
+
+    #[test]
+    fn detects_grids() {
+        let stats = InputStats::from_input("#.#\n...\n#S#\n");
+        assert_eq!(stats.lines, 3);
+        assert_eq!(stats.grid, Some((3, 3)));
+        assert_eq!(stats.alphabet.iter().collect::<String>(), "#.S");
+
+        let stats = InputStats::from_input("A:1,2\nB:3\n");
+        assert_eq!(stats.grid, None);
+        assert_eq!(stats.line_lengths, Some((3, 5)));
+        assert_eq!(stats.numbers, 3);
+        assert_eq!(stats.number_range, Some((1, 3)));
+    }
+
+    #[test]
+    fn notes_differences() {
+        let sample = InputStats::from_input("#.\n.#\n");
+        let real = InputStats::from_input("#.S-5000000000\n#.\n");
+        assert_eq!(
+            notes(&sample, &real),
+            vec![
+                "The input contains characters that are not in the sample: `-S`.",
+                "Numbers exceed the range of `u32`, use 64 bit integers.",
+                "The sample is a grid but the input has lines of different lengths.",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_table() {
+        let sample = InputStats::from_input("1 2\n3 4\n");
+        let real = InputStats::from_input("10 20 30\n40 50 60\n70 80 90\n");
+        let table = render(&sample, &real);
+        assert!(table.contains("| Lines | 2 | 3 | ×1.5 |"));
+        assert!(table.contains("| Numbers | 4 in 1..=4 | 9 in 10..=90 | ×2.2 |"));
+    }
+}
@@ -0,0 +1,224 @@
+/// Reading and writing puzzle inputs, which may be stored encrypted so they can be committed.
+///
+/// With the `encrypt` feature and a key in `EC_INPUT_KEY` or the global `input.key` file,
+/// downloaded inputs are stored encrypted with [`Cipher`](crate::template::crypto::Cipher) as
+/// e.g. `data/inputs/07-1.txt.enc`, and so are the copies kept in `data/.snapshots`. Reading an
+/// input prefers the plaintext file and decrypts the encrypted one when there is none, so
+/// solutions don't notice the difference.
+use std::{
+    ffi::OsString,
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "encrypt")]
+use std::env;
+
+#[cfg(feature = "encrypt")]
+use crate::template::{
+    crypto::{Cipher, CryptoError},
+    dirs::config_dir,
+};
+
+/// Environment variable holding the hex key used to encrypt inputs.
+#[cfg(feature = "encrypt")]
+pub const INPUT_KEY_ENV: &str = "EC_INPUT_KEY";
+
+/// File in the global config directory holding the key when `EC_INPUT_KEY` is not set.
+#[cfg(feature = "encrypt")]
+pub const INPUT_KEY_FILE: &str = "input.key";
+
+/// The cipher for inputs, keyed from `EC_INPUT_KEY` or the global `input.key` file.
+#[cfg(feature = "encrypt")]
+pub fn input_cipher() -> Result<Cipher, CryptoError> {
+    if env::var_os(INPUT_KEY_ENV).is_some() {
+        return Cipher::from_env(INPUT_KEY_ENV);
+    }
+
+    match config_dir().and_then(|dir| fs::read_to_string(dir.join(INPUT_KEY_FILE)).ok()) {
+        Some(hex) => Cipher::from_hex(&hex, INPUT_KEY_FILE),
+        None => Cipher::from_env(INPUT_KEY_ENV),
+    }
+}
+
+/// The path of the encrypted copy of a file, e.g. `07-1.txt.enc` for `07-1.txt`.
+#[must_use]
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().map(OsString::from).unwrap_or_default();
+    name.push(".enc");
+    path.with_file_name(name)
+}
+
+/// Whether the file at `path` exists, in plaintext or encrypted.
+#[must_use]
+pub fn exists(path: &Path) -> bool {
+    path.exists() || encrypted_path(path).exists()
+}
+
+/// Read an input, decrypting its encrypted copy if there is no plaintext file.
+pub fn read(path: &Path) -> io::Result<Vec<u8>> {
+    match fs::read(path) {
+        Err(e) if e.kind() == ErrorKind::NotFound && encrypted_path(path).exists() => {
+            read_encrypted(&encrypted_path(path))
+        }
+        result => result,
+    }
+}
+
+/// Like [`read`], for inputs that are valid UTF-8.
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+}
+
+#[cfg(feature = "encrypt")]
+fn read_encrypted(path: &Path) -> io::Result<Vec<u8>> {
+    let encrypted = fs::read_to_string(path)?;
+    input_cipher()
+        .and_then(|cipher| cipher.decrypt(&encrypted))
+        .map_err(|e| io::Error::other(format!("could not decrypt \"{}\": {e}", path.display())))
+}
+
+#[cfg(not(feature = "encrypt"))]
+fn read_encrypted(path: &Path) -> io::Result<Vec<u8>> {
+    Err(io::Error::other(format!(
+        "\"{}\" is encrypted, enable the `encrypt` feature to read it",
+        path.display()
+    )))
+}
+
+/// Write an input and return the path written to: encrypted if an input key is set, in
+/// plaintext otherwise. The other copy is removed so reads never see a stale one.
+pub fn write(path: &Path, contents: &[u8]) -> io::Result<PathBuf> {
+    #[cfg(feature = "encrypt")]
+    if let Ok(cipher) = input_cipher() {
+        return seal(path, contents, &cipher);
+    }
+
+    fs::write(path, contents)?;
+    remove_if_exists(&encrypted_path(path))?;
+    Ok(path.to_path_buf())
+}
+
+#[cfg(feature = "encrypt")]
+fn seal(path: &Path, contents: &[u8], cipher: &Cipher) -> io::Result<PathBuf> {
+    let encrypted = encrypted_path(path);
+    fs::write(&encrypted, cipher.encrypt(contents))?;
+    remove_if_exists(path)?;
+    Ok(encrypted)
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Encrypt the plaintext file at `path` in place, returning the path of the encrypted copy.
+/// Does nothing if no input key is set.
+#[cfg(feature = "encrypt")]
+pub fn encrypt_in_place(path: &Path) -> io::Result<Option<PathBuf>> {
+    match input_cipher() {
+        Ok(cipher) => seal(path, &fs::read(path)?, &cipher).map(Some),
+        Err(_) => Ok(None),
+    }
+}
+
+#[cfg(not(feature = "encrypt"))]
+pub fn encrypt_in_place(_path: &Path) -> io::Result<Option<PathBuf>> {
+    Ok(None)
+}
+
+/// Encrypt every plaintext file in `dir` whose path passes `filter`, removing the plaintext and
+/// returning the paths of the encrypted files. A missing `dir` has nothing to encrypt.
+#[cfg(feature = "encrypt")]
+pub fn encrypt_all(dir: &Path, filter: impl Fn(&Path) -> bool) -> Result<Vec<PathBuf>, String> {
+    let cipher = input_cipher().map_err(|e| e.to_string())?;
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let mut encrypted = vec![];
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if !path.is_file() || path.extension().is_some_and(|ext| ext == "enc") || !filter(&path) {
+            continue;
+        }
+
+        let contents = fs::read(&path).map_err(|e| e.to_string())?;
+        encrypted.push(seal(&path, &contents, &cipher).map_err(|e| e.to_string())?);
+    }
+
+    encrypted.sort();
+    Ok(encrypted)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, path::Path};
+
+    use super::{encrypted_path, exists, read, read_to_string};
+
+    #[test]
+    fn names_encrypted_copies() {
+        assert_eq!(
+            encrypted_path(Path::new("data/inputs/07-1.txt")),
+            Path::new("data/inputs/07-1.txt.enc")
+        );
+        assert_eq!(
+            encrypted_path(Path::new("data/.snapshots/objects/9f86d0")),
+            Path::new("data/.snapshots/objects/9f86d0.enc")
+        );
+    }
+
+    #[test]
+    fn prefers_plaintext() {
+        let dir = env::temp_dir().join(format!("ec-inputs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("07-1.txt");
+        fs::write(&path, "1 2 3").unwrap();
+        fs::write(encrypted_path(&path), "not hex").unwrap();
+
+        assert!(exists(&path));
+        assert_eq!(read_to_string(&path).unwrap(), "1 2 3");
+
+        fs::remove_file(&path).unwrap();
+        assert!(exists(&path));
+        assert!(read(&path).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(!exists(&path));
+    }
+
+    #[cfg(feature = "encrypt")]
+    #[test]
+    fn decrypts_encrypted_copies() {
+        use super::{seal, write};
+        use crate::template::crypto::Cipher;
+
+        let dir = env::temp_dir().join(format!("ec-inputs-enc-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("07-1.txt");
+        let cipher = Cipher::from_hex(&"ab".repeat(32), "test").unwrap();
+
+        let sealed = seal(&path, b"1 2 3", &cipher).unwrap();
+        assert_eq!(sealed, encrypted_path(&path));
+        assert!(!path.exists());
+        let stored = fs::read_to_string(&sealed).unwrap();
+        assert_eq!(cipher.decrypt(&stored).unwrap(), b"1 2 3");
+
+        // without a key in the environment, plaintext writes replace the encrypted copy.
+        if super::input_cipher().is_err() {
+            assert_eq!(write(&path, b"4 5 6").unwrap(), path);
+            assert!(!sealed.exists());
+            assert_eq!(read(&path).unwrap(), b"4 5 6");
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,175 @@
+/// Machine readable output for dashboards and scripts, enabled with `--json`.
+///
+/// Solutions print one JSON object per part instead of the formatted text, and `cargo all` and
+/// `cargo time` print the records of all quests, one per line:
+///
+/// ```text
+/// {"year":2025,"day":"07","part":1,"answer":"42","duration_ns":1200,"samples":1,"success":true}
+/// ```
+///
+/// The mode is passed on to solution binaries through `EC_OUTPUT=json`.
+use std::{collections::HashMap, env, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Part, Year};
+
+static OUTPUT_ENV: &str = "EC_OUTPUT";
+
+/// Enable JSON output for this process and the solutions it runs.
+pub fn enable() {
+    env::set_var(OUTPUT_ENV, "json");
+}
+
+pub fn is_enabled() -> bool {
+    env::args().any(|arg| arg == "--json") || env::var(OUTPUT_ENV).is_ok_and(|v| v == "json")
+}
+
+/// The outcome of running a part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub year: Year,
+    pub day: Day,
+    pub part: Part,
+    pub answer: Option<String>,
+    /// Duration of the first run, or the average of the benchmark samples.
+    pub duration: Option<Duration>,
+    pub samples: u128,
+    /// Why the part did not succeed, e.g. `timed out after 10s`.
+    pub error: Option<String>,
+    pub build: String,
+}
+
+impl Record {
+    /// A part succeeded if it returned an answer that passed its sanity checks.
+    pub fn success(&self) -> bool {
+        self.answer.is_some() && self.error.is_none()
+    }
+
+    pub fn print(&self) {
+        println!("{}", JsonValue::from(self).stringify().unwrap());
+    }
+
+    /// Parse a line printed by [`Record::print`].
+    pub fn parse(line: &str) -> Option<Record> {
+        Record::try_from(&JsonValue::from_str(line).ok()?).ok()
+    }
+}
+
+impl From<&Record> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Record) -> Self {
+        let string_or_null = |s: &Option<String>| {
+            s.as_ref()
+                .map_or(JsonValue::Null, |s| JsonValue::String(s.clone()))
+        };
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.year.into_inner())),
+        );
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part".into(),
+            JsonValue::Number(f64::from(value.part.into_inner())),
+        );
+        map.insert("answer".into(), string_or_null(&value.answer));
+        map.insert(
+            "duration_ns".into(),
+            value.duration.map_or(JsonValue::Null, |duration| {
+                JsonValue::Number(duration.as_nanos() as f64)
+            }),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("success".into(), JsonValue::Boolean(value.success()));
+        if value.error.is_some() {
+            map.insert("error".into(), string_or_null(&value.error));
+        }
+        map.insert("build".into(), JsonValue::String(value.build.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Record {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let number = |key: &str| json.get(key).and_then(|v| v.get::<f64>()).copied();
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        let year = number("year")
+            .and_then(|year| Year::new(year as u16))
+            .ok_or("Expected record.year to be an event year.")?;
+        let day = string("day")
+            .and_then(|day| Day::from_str(&day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+        let part = number("part")
+            .and_then(|part| Part::new(part as u8))
+            .ok_or("Expected record.part to be a part number.")?;
+
+        Ok(Record {
+            year,
+            day,
+            part,
+            answer: string("answer"),
+            duration: number("duration_ns").map(|nanos| Duration::from_nanos(nanos as u64)),
+            samples: number("samples").map_or(1, |samples| samples as u128),
+            error: string("error"),
+            build: string("build").unwrap_or_default(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Record;
+    use crate::{day, template::Part, year};
+
+    #[test]
+    fn round_trips_records() {
+        let record = Record {
+            year: year!(2025),
+            day: day!(7),
+            part: Part::Two,
+            answer: Some("A,B\n\"C\"".into()),
+            duration: Some(Duration::from_nanos(1234)),
+            samples: 100,
+            error: None,
+            build: "release (opt-level=3, lto=false, codegen-units=16)".into(),
+        };
+        let json = tinyjson::JsonValue::from(&record).stringify().unwrap();
+        assert!(json.contains("\"success\":true"));
+        assert!(json.contains("\"day\":\"07\""));
+        assert_eq!(Record::parse(&json), Some(record));
+    }
+
+    #[test]
+    fn marks_failed_parts() {
+        let record = Record {
+            year: year!(2025),
+            day: day!(7),
+            part: Part::Three,
+            answer: None,
+            duration: None,
+            samples: 1,
+            error: Some("timed out after 10s".into()),
+            build: String::new(),
+        };
+        let json = tinyjson::JsonValue::from(&record).stringify().unwrap();
+        assert!(json.contains("\"success\":false"));
+        assert!(json.contains("\"answer\":null"));
+        assert_eq!(Record::parse(&json), Some(record));
+        assert_eq!(Record::parse("Part 1: 42 (1.2ms)"), None);
+    }
+}
@@ -0,0 +1,159 @@
+/// Hashes of downloaded quest files in `data/manifest.toml`, to catch inputs and samples that
+/// were edited by accident or truncated during a download.
+///
+/// `cargo download` records the SHA-256 hash of every input and sample it writes, keyed by
+/// the file's path in the data directory:
+///
+/// ```toml
+/// "inputs/07-1.txt" = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+/// ```
+///
+/// Reading a file whose contents no longer match prints a warning. Files without an entry,
+/// e.g. samples written by hand, are not checked.
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use sha2::{Digest, Sha256};
+
+static MANIFEST_FILE: &str = "manifest.toml";
+
+/// Held while the manifest is rewritten, as parts are downloaded in parallel.
+static WRITING: Mutex<()> = Mutex::new(());
+
+/// Files that were already reported, so a file read for every part only warns once.
+static WARNED: Mutex<Option<HashSet<PathBuf>>> = Mutex::new(None);
+
+/// Hex encoded SHA-256 hash of a file's contents.
+pub fn hash(contents: &[u8]) -> String {
+    Sha256::digest(contents)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Record the hashes of quest files, e.g. `data/inputs/07-1.txt`, in the manifest of the data
+/// directory they are in.
+pub fn record(paths: &[&Path]) -> io::Result<()> {
+    let _guard = WRITING.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut manifests: BTreeMap<PathBuf, BTreeMap<String, String>> = BTreeMap::new();
+    for path in paths {
+        let Some((root, key)) = locate(path) else {
+            continue;
+        };
+        let hashes = manifests
+            .entry(root.to_path_buf())
+            .or_insert_with(|| read(root));
+        hashes.insert(key, hash(&fs::read(path)?));
+    }
+
+    for (root, hashes) in manifests {
+        fs::write(root.join(MANIFEST_FILE), render(&hashes))?;
+    }
+    Ok(())
+}
+
+/// Warn if the contents of a quest file no longer match the hash recorded when it was
+/// downloaded.
+pub fn check(path: &Path, contents: &[u8]) {
+    let Some((root, key)) = locate(path) else {
+        return;
+    };
+    let Some(expected) = read(root).remove(&key) else {
+        return;
+    };
+    if hash(contents) == expected {
+        return;
+    }
+
+    let mut warned = WARNED.lock().unwrap_or_else(|e| e.into_inner());
+    if warned
+        .get_or_insert_with(HashSet::new)
+        .insert(path.to_path_buf())
+    {
+        eprintln!(
+            "⚠️  \"{}\" does not match the hash recorded when it was downloaded, it may have been edited or truncated. Download the quest again to restore it.",
+            path.display()
+        );
+    }
+}
+
+/// The data directory a quest file is in and its key in the manifest, e.g. `data` and
+/// `inputs/07-1.txt` for `data/inputs/07-1.txt`.
+fn locate(path: &Path) -> Option<(&Path, String)> {
+    let folder = path.parent()?;
+    let root = folder.parent()?;
+    let key = format!(
+        "{}/{}",
+        folder.file_name()?.to_string_lossy(),
+        path.file_name()?.to_string_lossy()
+    );
+    Some((root, key))
+}
+
+fn read(root: &Path) -> BTreeMap<String, String> {
+    fs::read_to_string(root.join(MANIFEST_FILE))
+        .map(|s| parse(&s))
+        .unwrap_or_default()
+}
+
+/// The hashes of a manifest, ignoring entries that aren't strings. A malformed manifest is
+/// treated as empty, so it is rebuilt by the next download.
+fn parse(s: &str) -> BTreeMap<String, String> {
+    let Ok(table) = s.parse::<toml::Table>() else {
+        return BTreeMap::new();
+    };
+    table
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value.as_str()?.to_string())))
+        .collect()
+}
+
+fn render(hashes: &BTreeMap<String, String>) -> String {
+    let mut s =
+        String::from("# SHA-256 hashes of downloaded quest files, written by `cargo download`.\n");
+    for (key, hash) in hashes {
+        s.push_str(&format!("\"{key}\" = \"{hash}\"\n"));
+    }
+    s
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::BTreeMap, path::Path};
+
+    use super::{hash, locate, parse, render};
+
+    #[test]
+    fn hashes_contents() {
+        assert_eq!(
+            hash(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn locates_quest_files() {
+        assert_eq!(
+            locate(Path::new("data/2024/inputs/07-1.txt")),
+            Some((Path::new("data/2024"), "inputs/07-1.txt".to_string()))
+        );
+        assert_eq!(locate(Path::new("07-1.txt")), None);
+    }
+
+    #[test]
+    fn round_trips_manifests() {
+        let hashes = BTreeMap::from([
+            ("inputs/07-1.txt".to_string(), hash(b"input")),
+            ("samples/07-1.txt".to_string(), hash(b"sample")),
+        ]);
+        assert_eq!(parse(&render(&hashes)), hashes);
+        assert_eq!(parse("not toml ="), BTreeMap::new());
+    }
+}
@@ -0,0 +1,130 @@
+/// Peak heap usage of parts, reported next to their run time.
+///
+/// With the `mem-profile` feature, a counting global allocator keeps track of the bytes in use,
+/// and the runner prints how far above the parsed input the heap grew during the first run of
+/// each part, e.g. `Part 3: 1234 (12.3ms, peak 48.0 MiB)`. Without the feature, nothing is
+/// counted and parts are reported as before.
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static BASELINE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+#[cfg(feature = "mem-profile")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    pub static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    pub static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    /// The system allocator, counting the bytes in use and the most that were in use at once.
+    pub struct CountingAllocator;
+
+    impl CountingAllocator {
+        fn grow(size: usize) {
+            let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+
+        fn shrink(size: usize) {
+            CURRENT.fetch_sub(size, Ordering::Relaxed);
+        }
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                Self::grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                Self::grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            Self::shrink(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                if new_size > layout.size() {
+                    Self::grow(new_size - layout.size());
+                } else {
+                    Self::shrink(layout.size() - new_size);
+                }
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+}
+
+/// Start measuring the peak heap usage of a part from the bytes currently in use.
+pub fn begin() {
+    #[cfg(feature = "mem-profile")]
+    {
+        let current = counting::CURRENT.load(Ordering::Relaxed);
+        BASELINE.store(current, Ordering::Relaxed);
+        counting::PEAK.store(current, Ordering::Relaxed);
+    }
+}
+
+/// Stop measuring, keeping the peak since [`begin`] for [`peak`]. Benchmark samples that run
+/// afterwards do not count towards it.
+pub fn end() {
+    #[cfg(feature = "mem-profile")]
+    PEAK.store(counting::PEAK.load(Ordering::Relaxed), Ordering::Relaxed);
+}
+
+/// The most bytes a part had allocated at once between [`begin`] and [`end`], or `None` without
+/// the `mem-profile` feature.
+pub fn peak() -> Option<usize> {
+    let peak = PEAK.load(Ordering::Relaxed);
+    (peak != usize::MAX).then(|| peak.saturating_sub(BASELINE.load(Ordering::Relaxed)))
+}
+
+/// Format a number of bytes for humans, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_bytes;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(48 * 1024 * 1024), "48.0 MiB");
+        assert_eq!(format_bytes(3 << 30), "3.0 GiB");
+    }
+}
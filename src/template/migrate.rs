@@ -0,0 +1,208 @@
+/// Upgrading a repository from the single year layout, with the quest data directly in `data/`
+/// and solutions in `src/bin/{day}.rs`, to the per-year layout of `data/{year}` and
+/// `src/bin/{year}_{day}.rs`.
+///
+/// Plans are computed from directory listings first, so they can be shown in a dry run and
+/// checked for conflicts before anything is moved.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{authors::is_valid_author, Year};
+
+static DATA_DIR: &str = "data";
+static BIN_DIR: &str = "src/bin";
+static README_PATH: &str = "README.md";
+
+/// Entries of `data/` that are shared by all events and stay in place.
+const SHARED_DATA: &[&str] = &["submissions.json"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rename {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MigrationPlan {
+    pub year: Year,
+    pub renames: Vec<Rename>,
+}
+
+/// Whether the repository still keeps quest data directly in `data/`.
+pub fn is_legacy_layout() -> bool {
+    Path::new(DATA_DIR).join("inputs").is_dir()
+}
+
+impl MigrationPlan {
+    /// Plan moving the entries of `data/` and the solutions in `src/bin`, both given as file
+    /// names, to the layout of `year`.
+    pub fn new(year: Year, data_entries: &[String], bin_files: &[String]) -> Self {
+        let data_dir = Path::new(DATA_DIR);
+        let mut renames: Vec<Rename> = data_entries
+            .iter()
+            .filter(|name| !SHARED_DATA.contains(&name.as_str()) && name.parse::<Year>().is_err())
+            .map(|name| Rename {
+                from: data_dir.join(name),
+                to: data_dir.join(year.to_string()).join(name),
+            })
+            .collect();
+
+        let bin_dir = Path::new(BIN_DIR);
+        renames.extend(
+            bin_files
+                .iter()
+                .filter(|name| is_solution_file(name))
+                .map(|name| Rename {
+                    from: bin_dir.join(name),
+                    to: bin_dir.join(format!("{year}_{name}")),
+                }),
+        );
+
+        renames.sort_by(|a, b| a.from.cmp(&b.from));
+        Self { year, renames }
+    }
+
+    /// Plan the migration of this repository's files.
+    pub fn detect(year: Year) -> io::Result<Self> {
+        Ok(Self::new(year, &list_dir(DATA_DIR)?, &list_dir(BIN_DIR)?))
+    }
+
+    /// Destinations that already exist and would be overwritten.
+    pub fn conflicts(&self) -> Vec<&Path> {
+        self.renames
+            .iter()
+            .map(|rename| rename.to.as_path())
+            .filter(|to| to.exists())
+            .collect()
+    }
+
+    /// Point links to renamed solutions, e.g. in the benchmark table, to their new paths.
+    pub fn rewrite_links(&self, readme: &str) -> String {
+        self.renames
+            .iter()
+            .filter(|rename| rename.from.starts_with(BIN_DIR))
+            .fold(readme.to_string(), |readme, rename| {
+                readme.replace(
+                    &format!("./{}", rename.from.display()),
+                    &format!("./{}", rename.to.display()),
+                )
+            })
+    }
+
+    /// Move all files and update the links in the README.
+    pub fn apply(&self) -> io::Result<()> {
+        fs::create_dir_all(Path::new(DATA_DIR).join(self.year.to_string()))?;
+        for rename in &self.renames {
+            fs::rename(&rename.from, &rename.to)?;
+        }
+
+        if let Ok(readme) = fs::read_to_string(README_PATH) {
+            let rewritten = self.rewrite_links(&readme);
+            if rewritten != readme {
+                fs::write(README_PATH, rewritten)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Solutions of the single year layout, e.g. `07.rs` or `07_alice.rs`.
+fn is_solution_file(file_name: &str) -> bool {
+    let Some(name) = file_name.strip_suffix(".rs") else {
+        return false;
+    };
+    let (day, author) = name
+        .split_once('_')
+        .map_or((name, None), |(d, a)| (d, Some(a)));
+
+    day.len() == 2 && day.chars().all(|c| c.is_ascii_digit()) && author.is_none_or(is_valid_author)
+}
+
+fn list_dir(path: &str) -> io::Result<Vec<String>> {
+    let mut names = vec![];
+    for entry in fs::read_dir(path)? {
+        if let Some(name) = entry?.file_name().to_str() {
+            names.push(name.to_string());
+        }
+    }
+    Ok(names)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{is_solution_file, MigrationPlan};
+    use crate::year;
+
+    fn get_mock_plan() -> MigrationPlan {
+        MigrationPlan::new(
+            year!(2025),
+            &[
+                "inputs".into(),
+                "answers".into(),
+                "timings.json".into(),
+                "submissions.json".into(),
+                "2024".into(),
+            ],
+            &[
+                "07.rs".into(),
+                "07_alice.rs".into(),
+                "scratch_07.rs".into(),
+                "2024_07.rs".into(),
+                ".keep".into(),
+            ],
+        )
+    }
+
+    #[test]
+    fn plans_renames() {
+        let plan = get_mock_plan();
+        let renames: Vec<(&Path, &Path)> = plan
+            .renames
+            .iter()
+            .map(|rename| (rename.from.as_path(), rename.to.as_path()))
+            .collect();
+
+        assert_eq!(
+            renames,
+            vec![
+                (Path::new("data/answers"), Path::new("data/2025/answers")),
+                (Path::new("data/inputs"), Path::new("data/2025/inputs")),
+                (
+                    Path::new("data/timings.json"),
+                    Path::new("data/2025/timings.json")
+                ),
+                (Path::new("src/bin/07.rs"), Path::new("src/bin/2025_07.rs")),
+                (
+                    Path::new("src/bin/07_alice.rs"),
+                    Path::new("src/bin/2025_07_alice.rs")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn recognizes_solution_files() {
+        assert!(is_solution_file("07.rs"));
+        assert!(is_solution_file("07_bob_2.rs"));
+        assert!(!is_solution_file("2024_07.rs"));
+        assert!(!is_solution_file("scratch_07.rs"));
+        assert!(!is_solution_file("07_Bob.rs"));
+        assert!(!is_solution_file("07.txt"));
+    }
+
+    #[test]
+    fn rewrites_readme_links() {
+        let readme = "| [Day 7](./src/bin/07.rs) | `1ms` |\n| [Day 8](./src/bin/08.rs) | `2ms` |";
+        assert_eq!(
+            get_mock_plan().rewrite_links(readme),
+            "| [Day 7](./src/bin/2025_07.rs) | `1ms` |\n| [Day 8](./src/bin/08.rs) | `2ms` |"
+        );
+    }
+}
@@ -1,60 +1,336 @@
-use std::{env, fs};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
 
-pub mod ec_cli;
+pub mod answers;
+pub mod authors;
+pub mod bench;
+pub mod build_times;
+pub mod checks;
+#[cfg(feature = "http_client")]
+pub mod client;
 pub mod commands;
+pub mod complexity;
+pub mod config;
+pub mod contest;
+#[cfg(feature = "encrypt")]
+pub mod crypto;
+pub mod deploy;
+pub mod dirs;
+pub mod ec_cli;
+pub mod event;
+pub mod explain;
+pub mod hooks;
+pub mod html;
+pub mod input_snapshot;
+pub mod input_stats;
+pub mod inputs;
+pub mod json_output;
+pub mod manifest;
+pub mod mem_profile;
+pub mod migrate;
+pub mod notes;
+pub mod notify;
+pub mod panic_report;
+pub mod pending;
+pub mod plugins;
+pub mod profile;
+pub mod quest_meta;
+pub mod read_only;
+pub mod recording;
+pub mod reduce;
 pub mod runner;
+pub mod scaffold;
+pub mod setup;
+pub mod snapshots;
+pub mod solution;
+pub mod stats;
+pub mod submissions;
+pub mod sysinfo;
+pub mod trace;
+pub mod truncate;
+pub mod urls;
+pub mod util_modules;
+pub mod watch;
 
+pub use answers::{assert_answer, read_answer, read_answers, Answer, EcAnswer};
 pub use day::*;
+pub use event::{set_event, set_story, Event, StoryId};
+pub use explain::checkpoint;
+pub use part::*;
+pub use solution::{Registry, Solution};
+pub use trace::span;
+pub use year::*;
 
 mod day;
+mod modgraph;
+mod part;
 mod readme_benchmarks;
 mod run_multi;
+mod share;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string with part suffix. E.g. like `01-1.txt`.
+/// Helper function that reads a text file of the current year to a string with part suffix.
+/// E.g. like `data/2025/inputs/01-1.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file(folder: &str, day: Day, part: Part) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = cwd.join(read_only::quest_file(
+        Path::new(folder).join(format!("{day}-{part}.txt")),
+    ));
+    let contents = inputs::read_to_string(&filepath).expect("could not open input file");
+    manifest::check(&filepath, contents.as_bytes());
+    contents
+}
+
+/// Paths of the examples of a part in a data folder: `{day}-{part}.txt` if it exists, followed
+/// by the numbered ones in order, e.g. `data/samples/07-1-2.txt` for the second example of a
+/// part with several.
+#[must_use]
+pub fn sample_paths(folder: &str, day: Day, part: Part) -> Vec<PathBuf> {
+    let prefix = format!("{day}-{part}-");
+    let mut numbered: Vec<(u32, String)> = fs::read_dir(data_dir().join(folder))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let n = name
+                .strip_prefix(&prefix)?
+                .strip_suffix(".txt")?
+                .parse()
+                .ok()?;
+            Some((n, name))
+        })
+        .collect();
+    numbered.sort_unstable();
+
+    let base = read_only::quest_file(Path::new(folder).join(format!("{day}-{part}.txt")));
+    let numbered = numbered
+        .into_iter()
+        .map(|(_, name)| read_only::quest_file(Path::new(folder).join(name)));
+    base.exists()
+        .then_some(base)
+        .into_iter()
+        .chain(numbered)
+        .collect()
+}
+
+/// Reads every example of a part in a data folder, see [`sample_paths`]. Use [`read_answers`]
+/// for their expected answers.
+#[must_use]
+pub fn read_samples(folder: &str, day: Day, part: Part) -> Vec<String> {
+    sample_paths(folder, day, part)
+        .iter()
+        .map(|path| {
+            let sample = fs::read_to_string(path).expect("could not open sample file");
+            manifest::check(path, sample.as_bytes());
+            sample
+        })
+        .collect()
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1, 2, or 3) allows you to only run a single part of the solution.
+///
+/// Parts can also be bound to arbitrary function expressions by name, which allows parts that only
+/// differ by a parameter to share a generic function. Output is still labelled by the part number:
+///
+/// ```ignore
+/// everybody_codes::solution!(7, part_one = solve::<1>, part_two = solve::<2>, part_three = solve::<3>);
+/// ```
+///
+/// Parts take `&str`, or `&[u8]` to get the raw input file for byte-level parsing:
+///
+/// ```ignore
+/// pub fn part_one(input: &[u8]) -> Option<u64> { ... }
+/// ```
+///
+/// Run the binary with `--explain` to run each part against its sample and compare the values
+/// recorded with [`checkpoint`] against `data/fixtures/{day}-{part}.toml`, see [`explain`].
+///
+/// `before_part` and `after_part` register [`hooks`] that run around each part:
+///
+/// ```ignore
+/// everybody_codes::solution!(7, before_part = start_profiler, after_part = stop_profiler);
+/// ```
+///
+/// `checks` registers sanity [`checks`] for the answers, failing parts are not submitted:
+///
+/// ```ignore
+/// everybody_codes::solution!(7, checks = checks);
+/// ```
+///
+/// `year` ties a solution to an event, for repositories holding several years. Binaries named
+/// like `src/bin/2024_07.rs` already know their year, see [`Year::current`]. Tests don't run
+/// `main`, so the scaffolded ones call the generated `init_event()` first:
+///
+/// ```ignore
+/// everybody_codes::solution!(7, year = 2024);
+/// ```
+///
+/// `story` does the same for a story, binaries like `src/bin/story1_02.rs` already know theirs,
+/// see [`Event::current`]:
+///
+/// ```ignore
+/// everybody_codes::solution!(2, story = 1);
+/// ```
+///
+/// `gen_input` registers a `fn(size: usize, seed: u64) -> String` that `cargo gen` uses to
+/// write synthetic inputs to `data/tests`. Run a solution on one with `--input <path>`:
+///
+/// ```ignore
+/// everybody_codes::solution!(19, gen_input = gen_input);
+/// ```
+///
+/// `complexity` annotates parts with their expected [`complexity`] class and the size of the
+/// real input, which `cargo scale-bench` checks against generated inputs:
+///
+/// ```ignore
+/// everybody_codes::solution!(19, gen_input = gen_input, complexity = [(1, "n", 1000), (2, "n^2", 200)]);
+/// ```
+///
+/// The macro implements [`Solution`] for a hidden type and runs it with [`solution::run`].
+/// Implement the trait directly for solutions that should be usable outside their binary.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2] [part_three, 3]);
+        $crate::solution!(@impl $day, {} () [part_one, $crate::template::Part::One] [part_two, $crate::template::Part::Two] [part_three, $crate::template::Part::Three]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, {} () [part_one, $crate::template::Part::One]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, {} () [part_two, $crate::template::Part::Two]);
     };
     ($day:expr, 3) => {
-        $crate::solution!(@impl $day, [part_three, 3]);
+        $crate::solution!(@impl $day, {} () [part_three, $crate::template::Part::Three]);
+    };
+    ($day:expr, $($rest:tt)+) => {
+        $crate::solution!(@named $day, [] {} (), $($rest)+);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@named $day:expr, [$($parts:tt)*] {$($hooks:tt)*} ($($event:tt)*), part_one = $func:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@named $day, [$($parts)* [$func, $crate::template::Part::One]] {$($hooks)*} ($($event)*), $($($rest)*)?);
+    };
+    (@named $day:expr, [$($parts:tt)*] {$($hooks:tt)*} ($($event:tt)*), part_two = $func:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@named $day, [$($parts)* [$func, $crate::template::Part::Two]] {$($hooks)*} ($($event)*), $($($rest)*)?);
+    };
+    (@named $day:expr, [$($parts:tt)*] {$($hooks:tt)*} ($($event:tt)*), part_three = $func:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@named $day, [$($parts)* [$func, $crate::template::Part::Three]] {$($hooks)*} ($($event)*), $($($rest)*)?);
+    };
+    (@named $day:expr, [$($parts:tt)*] {$($hooks:tt)*} ($($event:tt)*), before_part = $func:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@named $day, [$($parts)*] {$($hooks)* $crate::template::hooks::register_before_part($func);} ($($event)*), $($($rest)*)?);
+    };
+    (@named $day:expr, [$($parts:tt)*] {$($hooks:tt)*} ($($event:tt)*), after_part = $func:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@named $day, [$($parts)*] {$($hooks)* $crate::template::hooks::register_after_part($func);} ($($event)*), $($($rest)*)?);
+    };
+    (@named $day:expr, [$($parts:tt)*] {$($hooks:tt)*} ($($event:tt)*), checks = $func:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@named $day, [$($parts)*] {$($hooks)* $crate::template::checks::register($func);} ($($event)*), $($($rest)*)?);
+    };
+    (@named $day:expr, [$($parts:tt)*] {$($hooks:tt)*} ($($event:tt)*), year = $year:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@named $day, [$($parts)*] {$($hooks)*} ($crate::template::Event::MainEvent { year: $crate::year!($year) }), $($($rest)*)?);
+    };
+    (@named $day:expr, [$($parts:tt)*] {$($hooks:tt)*} ($($event:tt)*), story = $story:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@named $day, [$($parts)*] {$($hooks)*} ({
+            const _ASSERT: () = assert!($story >= 1, "stories are numbered from 1");
+            $crate::template::Event::Story { id: $story }
+        }), $($($rest)*)?);
+    };
+    (@named $day:expr, [$($parts:tt)*] {$($hooks:tt)*} ($($event:tt)*), gen_input = $func:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@named $day, [$($parts)*] {$($hooks)* $crate::template::hooks::register_gen_input($func);} ($($event)*), $($($rest)*)?);
+    };
+    (@named $day:expr, [$($parts:tt)*] {$($hooks:tt)*} ($($event:tt)*), complexity = $table:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@named $day, [$($parts)*] {$($hooks)* $crate::template::complexity::register(&$table);} ($($event)*), $($($rest)*)?);
+    };
+    (@named $day:expr, [] {$($hooks:tt)*} ($($event:tt)*), ) => {
+        $crate::solution!(@impl $day, {$($hooks)*} ($($event)*) [part_one, $crate::template::Part::One] [part_two, $crate::template::Part::Two] [part_three, $crate::template::Part::Three]);
+    };
+    (@named $day:expr, [$($parts:tt)+] {$($hooks:tt)*} ($($event:tt)*), ) => {
+        $crate::solution!(@impl $day, {$($hooks)*} ($($event)*) $($parts)+);
+    };
+
+    (@impl $day:expr, {$($hooks:tt)*} ($($event:expr)?) $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The event given with `year` or `story`, `None` to take it from the binary name.
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub const __EVENT: Option<$crate::template::Event> = $crate::solution!(@event $($event)?);
+
+        /// Work on the event of the solution. `main` does this itself, tests that read its
+        /// data have to call it first.
+        #[allow(dead_code)]
+        fn init_event() {
+            if let Some(event) = __EVENT {
+                $crate::template::set_event(event);
+            }
+        }
+
+        #[doc(hidden)]
+        pub struct __Solution;
+
+        impl $crate::template::Solution for __Solution {
+            const DAY: $crate::template::Day = DAY;
+            const PARTS: &'static [$crate::template::Part] = &[$($part),*];
+            const TRACE_PARSE: bool = false;
+            type Input<'a> = $crate::template::runner::RawInput<'a>;
+            type Answer = String;
+
+            fn parse(input: &str) -> Self::Input<'_> {
+                $crate::template::runner::RawInput::Text(input)
+            }
+
+            fn parse_bytes(input: &[u8]) -> Self::Input<'_> {
+                $crate::template::runner::RawInput::Bytes(input)
+            }
+
+            fn part(part: $crate::template::Part, input: Self::Input<'_>) -> Option<String> {
+                use $crate::template::runner::PartInput;
+                $(
+                    if part == $part {
+                        return ($func)(PartInput::from_raw(input)).map(|answer| $crate::template::EcAnswer::to_answer(&answer));
+                    }
+                )*
+                None
+            }
+        }
+
         fn main() {
-            use $crate::template::runner::*;
-            $(
-                let input = $crate::template::read_file("inputs", DAY, $part);
-                run_part($func, &input, DAY, $part);
-            )*
+            init_event();
+            $($hooks)*
+            $crate::template::solution::run::<__Solution>(env!("CARGO_CRATE_NAME"));
+        }
+    };
+
+    (@event) => {
+        None
+    };
+    (@event $event:expr) => {
+        Some($event)
+    };
+}
+
+/// Like `debug_assert!`, but toggled at runtime: the check runs in debug builds and in release
+/// builds invoked with `--checks`, e.g. `cargo solve 7 --release --checks`. This keeps
+/// expensive invariant checks in a solution without paying for them in benchmarks.
+///
+/// ```ignore
+/// ec_debug_assert!(grid.iter().all(|row| row.len() == width), "ragged grid");
+/// ```
+#[macro_export]
+macro_rules! ec_debug_assert {
+    ($($arg:tt)+) => {
+        if $crate::template::runner::checks_enabled() {
+            assert!($($arg)+);
         }
     };
 }
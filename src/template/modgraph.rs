@@ -0,0 +1,182 @@
+/// Module that maps which quest binaries use which `utils` modules.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use crate::template::{all_days, run_multi::get_path_for_bin, Day};
+
+static UTILS_DIR: &str = "./src/utils";
+
+/// Dependency graph between quest days and shared utility modules.
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    pub days: BTreeMap<Day, BTreeSet<String>>,
+    pub modules: BTreeSet<String>,
+}
+
+impl ModuleGraph {
+    /// Build the graph from the scaffolded binaries and the `src/utils` directory.
+    pub fn from_sources() -> Self {
+        let mut graph = ModuleGraph {
+            modules: list_util_modules(Path::new(UTILS_DIR)),
+            ..Default::default()
+        };
+
+        for day in all_days() {
+            if let Ok(source) = fs::read_to_string(get_path_for_bin(day)) {
+                let used = used_util_modules(&source);
+                graph.modules.extend(used.iter().cloned());
+                graph.days.insert(day, used);
+            }
+        }
+
+        graph
+    }
+
+    /// Modules that no quest uses yet.
+    pub fn unused_modules(&self) -> impl Iterator<Item = &String> {
+        self.modules
+            .iter()
+            .filter(|module| !self.days.values().any(|used| used.contains(*module)))
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec!["digraph modules {".to_string(), "    rankdir=LR;".into()];
+
+        for module in &self.modules {
+            lines.push(format!("    \"{module}\" [shape=box];"));
+        }
+
+        for (day, used) in &self.days {
+            lines.push(format!("    \"Day {day}\";"));
+            for module in used {
+                lines.push(format!("    \"Day {day}\" -> \"{module}\";"));
+            }
+        }
+
+        lines.push("}".into());
+        lines.join("\n")
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let mut lines = vec!["graph LR".to_string()];
+
+        for module in &self.modules {
+            lines.push(format!("    {module}[{module}]"));
+        }
+
+        for (day, used) in &self.days {
+            lines.push(format!("    day{day}([Day {day}])"));
+            for module in used {
+                lines.push(format!("    day{day} --> {module}"));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// List module names in a `utils` directory, either `name.rs` or `name/mod.rs`.
+fn list_util_modules(dir: &Path) -> BTreeSet<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return BTreeSet::new();
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                path.join("mod.rs")
+                    .exists()
+                    .then(|| path.file_name()?.to_str().map(str::to_string))?
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                path.file_stem()?.to_str().map(str::to_string)
+            } else {
+                None
+            }
+        })
+        .filter(|name| name != "mod")
+        .collect()
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Extract the names of `utils` modules referenced in a source file.
+/// Handles both `utils::grid::Grid` paths and `utils::{grid, parse::ints}` groups.
+fn used_util_modules(source: &str) -> BTreeSet<String> {
+    let mut used = BTreeSet::new();
+
+    for (pos, _) in source.match_indices("utils::") {
+        let rest = &source[pos + "utils::".len()..];
+
+        if let Some(group) = rest.strip_prefix('{') {
+            let group = group.split('}').next().unwrap_or_default();
+            used.extend(
+                group
+                    .split(',')
+                    .filter_map(|item| item.trim().split("::").next())
+                    .filter(|name| is_module_name(name))
+                    .map(str::to_string),
+            );
+        } else {
+            let name: String = rest.chars().take_while(|c| is_ident_char(*c)).collect();
+            if is_module_name(&name) {
+                used.insert(name);
+            }
+        }
+    }
+
+    used
+}
+
+fn is_module_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_lowercase()) && name != "self"
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{used_util_modules, ModuleGraph};
+    use crate::day;
+
+    #[test]
+    fn extracts_paths_and_groups() {
+        let source = "use everybody_codes::utils::grid::Grid;\n\
+                      use everybody_codes::utils::{parse::ints, dsu, self, Point};\n\
+                      let x = everybody_codes::utils::numbers::gcd(1, 2);";
+        let used = used_util_modules(source);
+        assert_eq!(
+            used.into_iter().collect::<Vec<_>>(),
+            vec!["dsu", "grid", "numbers", "parse"]
+        );
+    }
+
+    #[test]
+    fn ignores_sources_without_utils() {
+        assert_eq!(used_util_modules("fn main() {}").is_empty(), true);
+    }
+
+    #[test]
+    fn renders_graphs() {
+        let mut graph = ModuleGraph::default();
+        graph
+            .modules
+            .extend(["grid".to_string(), "dsu".to_string()]);
+        graph
+            .days
+            .insert(day!(1), ["grid".to_string()].into_iter().collect());
+
+        let dot = graph.to_dot();
+        assert_eq!(dot.contains("\"Day 01\" -> \"grid\";"), true);
+        assert_eq!(dot.contains("\"dsu\" [shape=box];"), true);
+
+        let mermaid = graph.to_mermaid();
+        assert_eq!(mermaid.contains("day01 --> grid"), true);
+
+        assert_eq!(graph.unused_modules().collect::<Vec<_>>(), vec!["dsu"]);
+    }
+}
@@ -0,0 +1,121 @@
+/// Per-quest approach notes in `notes/{day}.md`, created by `cargo scaffold` and compiled into a
+/// single write-up by `cargo notes`.
+///
+/// Notes follow the data layout: with several events, the notes of one live in e.g.
+/// `notes/2024/07.md`. A note starts with the quest title from its downloaded description, or
+/// with a placeholder that `cargo download` fills in once the description is there.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{data_dir, html, Day, Event};
+
+/// The directory holding the notes of the current event.
+pub fn notes_dir() -> PathBuf {
+    let data = data_dir();
+    Path::new("notes").join(data.strip_prefix("data").unwrap_or(&data))
+}
+
+pub fn note_path(day: Day) -> PathBuf {
+    notes_dir().join(format!("{day}.md"))
+}
+
+/// The title of a quest from the description of its first part, if it was downloaded.
+pub fn quest_title(day: Day) -> Option<String> {
+    let path = data_dir()
+        .join("descriptions")
+        .join(format!("{day}-1.html"));
+    html::title(&fs::read_to_string(path).ok()?)
+}
+
+fn heading(day: Day, title: Option<&str>) -> String {
+    match title {
+        Some(title) => format!("# Quest {day}: {title}"),
+        None => format!("# Quest {day}"),
+    }
+}
+
+/// A new note with a section per part.
+pub fn render(day: Day, title: Option<&str>) -> String {
+    format!(
+        "{}\n\n## Part 1\n\n## Part 2\n\n## Part 3\n",
+        heading(day, title)
+    )
+}
+
+/// `note` with its placeholder heading replaced by one with `title`, `None` if the note has a
+/// title already.
+pub fn with_title(note: &str, day: Day, title: &str) -> Option<String> {
+    let rest = note.strip_prefix(&heading(day, None))?;
+    if !rest.is_empty() && !rest.starts_with('\n') {
+        return None;
+    }
+    Some(format!("{}{rest}", heading(day, Some(title))))
+}
+
+/// Fill in the title of the note of `day` if it still has the placeholder and the description
+/// was downloaded since.
+pub fn update_title(day: Day) -> io::Result<()> {
+    let path = note_path(day);
+    let (Ok(note), Some(title)) = (fs::read_to_string(&path), quest_title(day)) else {
+        return Ok(());
+    };
+    match with_title(&note, day, &title) {
+        Some(note) => fs::write(path, note),
+        None => Ok(()),
+    }
+}
+
+/// The notes compiled into a single document, with every heading one level deeper under the
+/// document's title. Headings in code blocks are left alone.
+pub fn compile(event: Event, notes: &[String]) -> String {
+    let mut document = format!("# Everybody Codes {event}\n");
+    for note in notes {
+        document.push('\n');
+        let mut in_code = false;
+        for line in note.trim_end().lines() {
+            if line.trim_start().starts_with("```") {
+                in_code = !in_code;
+            }
+            if !in_code && line.starts_with('#') {
+                document.push('#');
+            }
+            document.push_str(line);
+            document.push('\n');
+        }
+    }
+    document
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compile, render, with_title};
+    use crate::{day, template::Event, year};
+
+    #[test]
+    fn fills_in_placeholder_titles() {
+        let note = render(day!(7), None);
+        assert!(note.starts_with("# Quest 07\n\n## Part 1\n"));
+
+        let titled = with_title(&note, day!(7), "Namegrid").unwrap();
+        assert_eq!(titled, render(day!(7), Some("Namegrid")));
+        assert_eq!(with_title(&titled, day!(7), "Other"), None);
+        assert_eq!(with_title("# Quest 07 notes", day!(7), "Namegrid"), None);
+    }
+
+    #[test]
+    fn nests_notes_under_one_title() {
+        let notes = [
+            "# Quest 01: Whispers\n\n## Part 1\n\nA `HashMap`.\n".to_string(),
+            "# Quest 02\n\n```sh\n# not a heading\n```\n".to_string(),
+        ];
+
+        assert_eq!(
+            compile(Event::MainEvent { year: year!(2025) }, &notes),
+            "# Everybody Codes 2025\n\n## Quest 01: Whispers\n\n### Part 1\n\nA `HashMap`.\n\n## Quest 02\n\n```sh\n# not a heading\n```\n"
+        );
+    }
+}
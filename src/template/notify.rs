@@ -0,0 +1,91 @@
+/// Desktop notifications when long runs finish, so you can tab away during slow part 3s.
+///
+/// Notifications require the `desktop-notify` feature. Runs taking at least
+/// `notify.after_secs` seconds (configurable in `ec.toml`) trigger one:
+///
+/// ```toml
+/// [notify]
+/// after_secs = 60
+/// ```
+use std::time::Duration;
+
+use crate::template::config::config;
+
+pub const DEFAULT_AFTER_SECS: u64 = 30;
+
+/// The configured minimum run time that triggers a notification.
+pub fn after_secs() -> u64 {
+    config()
+        .get("notify.after_secs")
+        .and_then(toml::Value::as_integer)
+        .and_then(|secs| u64::try_from(secs).ok())
+        .unwrap_or(DEFAULT_AFTER_SECS)
+}
+
+pub fn is_slow(elapsed: Duration, after_secs: u64) -> bool {
+    elapsed >= Duration::from_secs(after_secs)
+}
+
+/// Format an elapsed time for humans, e.g. `1m 05s`.
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    match secs {
+        0..=59 => format!("{:.1}s", elapsed.as_secs_f64()),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Show a desktop notification. Does nothing without the `desktop-notify` feature.
+#[cfg(feature = "desktop-notify")]
+pub fn notify(summary: &str, body: &str) {
+    let result = notify_rust::Notification::new()
+        .appname("Everybody Codes")
+        .summary(summary)
+        .body(body)
+        .show();
+
+    if let Err(e) = result {
+        eprintln!("Failed to show notification: {e}");
+    }
+}
+
+/// Show a desktop notification. Does nothing without the `desktop-notify` feature.
+#[cfg(not(feature = "desktop-notify"))]
+pub fn notify(_summary: &str, _body: &str) {}
+
+/// Notify that `what` finished if it took long enough, e.g. `notify_if_slow("Day 07", ...)`.
+pub fn notify_if_slow(what: &str, elapsed: Duration, success: bool) {
+    if !is_slow(elapsed, after_secs()) {
+        return;
+    }
+
+    let outcome = if success { "finished" } else { "failed" };
+    notify(
+        &format!("{what} {outcome}"),
+        &format!("{what} {outcome} after {}.", format_elapsed(elapsed)),
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_elapsed, is_slow};
+
+    #[test]
+    fn applies_threshold() {
+        assert_eq!(is_slow(Duration::from_secs(29), 30), false);
+        assert_eq!(is_slow(Duration::from_secs(30), 30), true);
+        assert_eq!(is_slow(Duration::ZERO, 0), true);
+    }
+
+    #[test]
+    fn formats_elapsed_time() {
+        assert_eq!(format_elapsed(Duration::from_millis(1_250)), "1.2s");
+        assert_eq!(format_elapsed(Duration::from_secs(65)), "1m 05s");
+        assert_eq!(format_elapsed(Duration::from_secs(7_380)), "2h 03m");
+    }
+}
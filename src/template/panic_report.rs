@@ -0,0 +1,153 @@
+/// Panic hook for solution binaries that reports the day, part and input file of a failing run
+/// together with a backtrace trimmed down to the frames of the solution itself.
+use std::{
+    backtrace::Backtrace,
+    env,
+    panic::{self, PanicHookInfo},
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use crate::template::{data_dir, runner, Day, Part, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+static CURRENT_PART: AtomicU8 = AtomicU8::new(0);
+
+/// Record which part is currently running, so a panic can be attributed to it.
+pub fn set_part(part: Part) {
+    CURRENT_PART.store(part.into_inner(), Ordering::Relaxed);
+}
+
+/// Install the panic hook for a solution binary.
+///
+/// `crate_name` is the name of the solution crate (e.g. `07`), used to recognize its frames
+/// in builds without debug info. With `RUST_BACKTRACE=full`, the default hook runs as well.
+pub fn install(day: Day, crate_name: &'static str) {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        report(info, day, crate_name);
+
+        if env::var("RUST_BACKTRACE").is_ok_and(|v| v == "full") {
+            default_hook(info);
+        }
+    }));
+}
+
+fn report(info: &PanicHookInfo, day: Day, crate_name: &str) {
+    let part = CURRENT_PART.load(Ordering::Relaxed);
+
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".into());
+
+    let location = info
+        .location()
+        .map(|l| format!(" at {}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_default();
+
+    eprintln!();
+    if part == 0 {
+        eprintln!("{ANSI_BOLD}💥 Day {day} panicked{location}{ANSI_RESET}");
+    } else {
+        eprintln!("{ANSI_BOLD}💥 Day {day} Part {part} panicked{location}{ANSI_RESET}");
+        eprintln!(
+            "Input: {}/{}/{day}-{part}.txt",
+            data_dir().display(),
+            runner::input_folder()
+        );
+    }
+    eprintln!("{message}");
+
+    let backtrace = Backtrace::force_capture().to_string();
+    let frames = solution_frames(&backtrace, crate_name);
+
+    if frames.is_empty() {
+        eprintln!("{ANSI_ITALIC}No solution frames found, run with RUST_BACKTRACE=full for the full backtrace.{ANSI_RESET}");
+    } else {
+        eprintln!();
+        eprintln!("Backtrace (solution frames):");
+        for frame in frames {
+            eprintln!("  {frame}");
+        }
+    }
+}
+
+/// Extract frames belonging to the solution from a formatted [`Backtrace`].
+///
+/// A frame is kept if its symbol lives in the solution crate or its source location is in the
+/// repository's `src/` directory, excluding the template itself.
+fn solution_frames(backtrace: &str, crate_name: &str) -> Vec<String> {
+    let crate_prefixes = [format!("{crate_name}::"), format!("_{crate_name}::")];
+    let mut frames: Vec<(String, Option<String>)> = vec![];
+
+    for line in backtrace.lines() {
+        let trimmed = line.trim();
+
+        if let Some(location) = trimmed.strip_prefix("at ") {
+            if let Some(frame) = frames.last_mut() {
+                frame.1 = Some(location.to_string());
+            }
+        } else if let Some((index, symbol)) = trimmed.split_once(": ") {
+            if index.chars().all(|c| c.is_ascii_digit()) {
+                frames.push((symbol.to_string(), None));
+            }
+        }
+    }
+
+    frames
+        .into_iter()
+        .filter(|(symbol, location)| {
+            let is_solution_symbol = crate_prefixes.iter().any(|p| symbol.starts_with(p));
+            let is_solution_location = location.as_ref().is_some_and(|l| {
+                l.contains("src/")
+                    && !l.contains("src/template/")
+                    && !l.contains("/rustc/")
+                    && !l.contains("/.cargo/")
+            });
+            (is_solution_symbol || is_solution_location) && !symbol.ends_with("::main")
+        })
+        .map(|(symbol, location)| match location {
+            Some(location) => format!("{symbol} ({location})"),
+            None => symbol,
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::solution_frames;
+
+    #[test]
+    fn keeps_solution_frames() {
+        let backtrace = [
+            "   0: __rustc::rust_begin_unwind",
+            "             at /rustc/abc/library/std/src/panicking.rs:689:5",
+            "   1: everybody_codes::template::read_file",
+            "             at ./src/template/mod.rs:27:7",
+            "   2: _07::parse",
+            "             at ./src/bin/07.rs:8:21",
+            "   3: _07::part_two",
+            "   4: everybody_codes::utils::grid::Grid<T>::get",
+            "             at ./src/utils/grid.rs:10:5",
+            "   5: _07::main",
+            "             at ./src/bin/07.rs:1:1",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            solution_frames(&backtrace, "07"),
+            vec![
+                "_07::parse (./src/bin/07.rs:8:21)",
+                "_07::part_two",
+                "everybody_codes::utils::grid::Grid<T>::get (./src/utils/grid.rs:10:5)",
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_empty_backtraces() {
+        assert_eq!(solution_frames("disabled backtrace", "07").is_empty(), true);
+    }
+}
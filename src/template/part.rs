@@ -0,0 +1,109 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// One of the three parts of a quest.
+///
+/// # Display
+/// This value displays as its number.
+///
+/// ```
+/// # use everybody_codes::template::Part;
+/// assert_eq!(Part::Two.to_string(), "2")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One = 1,
+    Two = 2,
+    Three = 3,
+}
+
+impl Part {
+    /// All parts in order, e.g. to build per-part arrays with [`array::map`](std::array).
+    pub const ALL: [Part; 3] = [Part::One, Part::Two, Part::Three];
+
+    /// Creates a [`Part`] from its number if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(part: u8) -> Option<Self> {
+        match part {
+            1 => Some(Self::One),
+            2 => Some(Self::Two),
+            3 => Some(Self::Three),
+            _ => None,
+        }
+    }
+
+    /// Converts the [`Part`] into its number.
+    pub fn into_inner(self) -> u8 {
+        self as u8
+    }
+
+    /// The position of the part in per-part arrays, e.g. `[Option<String>; 3]`.
+    pub fn index(self) -> usize {
+        usize::from(self.into_inner() - 1)
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
+impl PartialEq<u8> for Part {
+    fn eq(&self, other: &u8) -> bool {
+        self.into_inner().eq(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Part {
+    type Err = PartFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part = s.parse().map_err(|_| PartFromStrError)?;
+        Self::new(part).ok_or(PartFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Part`].
+#[derive(Debug)]
+pub struct PartFromStrError;
+
+impl Error for PartFromStrError {}
+
+impl Display for PartFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a part between 1 and 3")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An iterator that yields the parts of a quest in order.
+pub fn all_parts() -> std::array::IntoIter<Part, 3> {
+    Part::ALL.into_iter()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{all_parts, Part};
+
+    #[test]
+    fn parses_parts() {
+        assert_eq!("2".parse::<Part>().ok(), Some(Part::Two));
+        assert_eq!("0".parse::<Part>().is_err(), true);
+        assert_eq!("4".parse::<Part>().is_err(), true);
+        assert_eq!(Part::new(3), Some(Part::Three));
+        assert_eq!(Part::Three.index(), 2);
+    }
+
+    #[test]
+    fn all_parts_iterator() {
+        let parts: Vec<u8> = all_parts().map(Part::into_inner).collect();
+        assert_eq!(parts, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,191 @@
+/// Queue of downloads that failed because ec-cli or the network was unavailable, retried later
+/// with `cargo ec sync`.
+///
+/// The queue is stored as `pending.json` in the download directory, so every event keeps its
+/// own queue and read-only mode never touches the real one.
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{read_only, Day};
+
+fn get_pending_path() -> PathBuf {
+    read_only::download_dir().join("pending.json")
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingDownload {
+    pub day: Day,
+    /// Why the last attempt failed.
+    pub error: String,
+    /// Unix timestamp of the last attempt.
+    pub at: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PendingDownloads {
+    pub data: Vec<PendingDownload>,
+}
+
+impl PendingDownloads {
+    /// Rehydrate the queue from disk. If not present, returns an empty queue.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(get_pending_path())
+            .ok()
+            .and_then(|s| PendingDownloads::try_from(s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Store the queue, removing the file once nothing is pending anymore.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let path = get_pending_path();
+        if self.data.is_empty() {
+            return match fs::remove_file(path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+
+        let json = JsonValue::from(self);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The pending days, in day order.
+    pub fn days(&self) -> Vec<Day> {
+        let mut days: Vec<Day> = self.data.iter().map(|pending| pending.day).collect();
+        days.sort_unstable();
+        days
+    }
+
+    /// Queue `day`, replacing an earlier failure of the same day.
+    pub fn add(&mut self, day: Day, error: String, at: u64) {
+        self.remove(day);
+        self.data.push(PendingDownload { day, error, at });
+    }
+
+    pub fn remove(&mut self, day: Day) {
+        self.data.retain(|pending| pending.day != day);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PendingDownloads> for JsonValue {
+    fn from(value: &PendingDownloads) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for PendingDownloads {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        Ok(PendingDownloads {
+            data: json
+                .get("data")
+                .and_then(|v| v.get::<Vec<JsonValue>>())
+                .ok_or("expected `json.data` to be an array.")?
+                .iter()
+                .map(PendingDownload::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&PendingDownload> for JsonValue {
+    fn from(value: &PendingDownload) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("error".into(), JsonValue::String(value.error.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("at".into(), JsonValue::Number(value.at as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PendingDownload {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected pending download to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected pending.day to be a Day struct.")?;
+
+        let error = json
+            .get("error")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .unwrap_or_default();
+
+        let at = json
+            .get("at")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected pending.at to be a number.")?;
+
+        Ok(PendingDownload { day, error, at })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PendingDownloads;
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn queues_each_day_once() {
+        let mut pending = PendingDownloads::default();
+        pending.add(day!(7), "not found".into(), 1_000);
+        pending.add(day!(3), "not found".into(), 1_000);
+        pending.add(day!(7), "bad exit status".into(), 1_100);
+
+        assert_eq!(pending.days(), vec![day!(3), day!(7)]);
+        assert_eq!(pending.data[1].error, "bad exit status");
+
+        pending.remove(day!(3));
+        pending.remove(day!(7));
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut pending = PendingDownloads::default();
+        pending.add(day!(12), "request failed: timed out".into(), 1_700_000_000);
+
+        let json = JsonValue::from(&pending).stringify().unwrap();
+        assert_eq!(PendingDownloads::try_from(json), Ok(pending));
+        assert!(PendingDownloads::try_from("[]".to_string()).is_err());
+    }
+}
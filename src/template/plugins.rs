@@ -0,0 +1,109 @@
+/// Plugins for integrations like webhooks, git hooks or notifications, without wiring each of
+/// them into the commands.
+///
+/// A plugin implements [`EcPlugin`] and is listed in `PLUGINS` in `src/lib.rs`, which the CLI
+/// and every solution binary load:
+///
+/// ```ignore
+/// use everybody_codes::template::plugins::{EcPlugin, Event};
+///
+/// struct Webhook;
+///
+/// impl EcPlugin for Webhook {
+///     fn on_event(&self, event: &Event) {
+///         if let Event::Submitted { day, part, correct: true, .. } = event {
+///             // e.g. post to a chat
+///         }
+///     }
+/// }
+///
+/// pub static PLUGINS: &[&dyn EcPlugin] = &[&Webhook];
+/// ```
+///
+/// Events are delivered in the process they happen in: [`Event::Ran`] in the solution binary,
+/// the others in the CLI. [`Event::Ran`] is sent by [`hooks`](crate::template::hooks) after the
+/// `after_part` hooks, and only for runs on the real input.
+///
+/// Plugins don't replace the hooks: those belong to a single quest and run around every part,
+/// samples and synthetic inputs included, e.g. to start a profiler, while plugins apply to all
+/// quests and only hear about what happened to them. Desktop notifications aren't a plugin
+/// either, since they time a whole `cargo solve` in the CLI, build included, and no event
+/// covers that.
+use std::time::Duration;
+
+use crate::template::{Day, Part};
+
+/// Something that happened to a quest.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event<'a> {
+    /// A part was downloaded by `cargo download` or `cargo scaffold --download`.
+    Downloaded { day: Day, part: Part },
+    /// A part ran on its real input, with its answer or `None` if it failed. Runs on samples,
+    /// `--input` files and with `--explain` are not reported.
+    Ran {
+        day: Day,
+        part: Part,
+        answer: Option<&'a str>,
+        duration: Duration,
+    },
+    /// An answer was submitted to the site.
+    Submitted {
+        day: Day,
+        part: Part,
+        answer: &'a str,
+        correct: bool,
+    },
+    /// `cargo time` benchmarked a quest, with the formatted time of each part.
+    Benchmarked {
+        day: Day,
+        parts: [Option<&'a str>; 3],
+        total: Duration,
+    },
+}
+
+pub trait EcPlugin: Sync {
+    fn on_event(&self, event: &Event);
+}
+
+/// Notify the plugins in `PLUGINS` of an event.
+pub fn emit(event: &Event) {
+    emit_to(crate::PLUGINS, event);
+}
+
+fn emit_to(plugins: &[&dyn EcPlugin], event: &Event) {
+    for plugin in plugins {
+        plugin.on_event(event);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::{emit_to, EcPlugin, Event};
+    use crate::{day, template::Part};
+
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl EcPlugin for Recorder {
+        fn on_event(&self, event: &Event) {
+            self.0.lock().unwrap().push(format!("{event:?}"));
+        }
+    }
+
+    #[test]
+    fn delivers_events_to_every_plugin() {
+        let (first, second) = (Recorder(Mutex::default()), Recorder(Mutex::default()));
+        let event = Event::Downloaded {
+            day: day!(7),
+            part: Part::Two,
+        };
+        emit_to(&[&first, &second], &event);
+
+        for recorder in [first, second] {
+            assert_eq!(recorder.0.into_inner().unwrap(), vec![format!("{event:?}")]);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+/// Flamegraphs of single parts for `cargo profile`, sampled with `perf` and rendered with
+/// [inferno](https://github.com/jonhoo/inferno).
+///
+/// The part runs benchmarked with `--time`, so even fast parts run long enough to collect a
+/// useful number of samples, and the graph only contains the part itself and its parsing.
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::template::{Day, Part};
+
+static PROFILES_DIR: &str = "./profiles";
+
+/// Samples per second taken by `perf`, a prime so sampling doesn't run in lockstep with loops.
+pub const FREQUENCY: u32 = 997;
+
+/// Tools the profile is recorded and rendered with, and how to install them.
+pub const TOOLS: [(&str, &str); 3] = [
+    (
+        "perf",
+        "install `perf` with your package manager, e.g. `linux-tools`",
+    ),
+    ("inferno-collapse-perf", "run `cargo install inferno`"),
+    ("inferno-flamegraph", "run `cargo install inferno`"),
+];
+
+/// Where the flamegraph of a part is written, e.g. `profiles/07-1.svg`.
+pub fn profile_path(day: Day, part: Part) -> PathBuf {
+    Path::new(PROFILES_DIR).join(format!("{day}-{part}.svg"))
+}
+
+/// The [`TOOLS`] that can't be run, with how to install them.
+pub fn missing_tools() -> Vec<(&'static str, &'static str)> {
+    TOOLS
+        .into_iter()
+        .filter(|(tool, _)| Command::new(tool).arg("--version").output().is_err())
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::profile_path;
+    use crate::{day, template::Part};
+
+    #[test]
+    fn names_profiles() {
+        assert_eq!(
+            profile_path(day!(7), Part::Three),
+            Path::new("./profiles/07-3.svg")
+        );
+    }
+}
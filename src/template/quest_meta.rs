@@ -0,0 +1,263 @@
+/// When the description of each part was downloaded and when each part unlocked, to notice
+/// descriptions that were stored before a later part changed them.
+///
+/// A part unlocks when the previous one is solved, so solving a part through `cargo submit` or
+/// `cargo contest` records the unlock of the next one. The meta data is stored as `meta.json` in
+/// the download directory, next to the quest files it describes:
+///
+/// ```json
+/// {"data":[{"day":"07","part":1,"fetched":1700000000},{"day":"07","part":2,"unlocked":1700000600}]}
+/// ```
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr, sync::Mutex};
+use tinyjson::JsonValue;
+
+use crate::template::{contest::now, read_only, Day, Part};
+
+/// Serializes updates, as parts are downloaded in parallel.
+static WRITING: Mutex<()> = Mutex::new(());
+
+fn get_meta_path() -> PathBuf {
+    read_only::download_dir().join("meta.json")
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartMeta {
+    pub day: Day,
+    pub part: Part,
+    /// Unix timestamp of the last download of the part's description.
+    pub fetched: Option<u64>,
+    /// Unix timestamp of when the part unlocked, if it was seen.
+    pub unlocked: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QuestMeta {
+    pub data: Vec<PartMeta>,
+}
+
+impl QuestMeta {
+    /// Rehydrate the meta data from disk. If not present, returns empty meta data.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(get_meta_path())
+            .ok()
+            .and_then(|s| QuestMeta::try_from(s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let path = get_meta_path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    fn entry(&mut self, day: Day, part: Part) -> &mut PartMeta {
+        let index = match self
+            .data
+            .iter()
+            .position(|meta| meta.day == day && meta.part == part)
+        {
+            Some(index) => index,
+            None => {
+                self.data.push(PartMeta {
+                    day,
+                    part,
+                    fetched: None,
+                    unlocked: None,
+                });
+                self.data.len() - 1
+            }
+        };
+        &mut self.data[index]
+    }
+
+    pub fn set_fetched(&mut self, day: Day, part: Part, at: u64) {
+        self.entry(day, part).fetched = Some(at);
+    }
+
+    /// Record that `part` unlocked at `at`, keeping the first time it was seen.
+    pub fn set_unlocked(&mut self, day: Day, part: Part, at: u64) {
+        self.entry(day, part).unlocked.get_or_insert(at);
+    }
+
+    /// The latest part of a quest that unlocked after its descriptions were last downloaded,
+    /// `None` if they are up to date or were never downloaded.
+    pub fn stale_part(&self, day: Day) -> Option<Part> {
+        let parts = || self.data.iter().filter(|meta| meta.day == day);
+        let fetched = parts().filter_map(|meta| meta.fetched).max()?;
+
+        parts()
+            .filter(|meta| meta.unlocked.is_some_and(|unlocked| unlocked > fetched))
+            .map(|meta| meta.part)
+            .max_by_key(|part| part.into_inner())
+    }
+}
+
+fn update(f: impl FnOnce(&mut QuestMeta)) {
+    let _guard = WRITING.lock().unwrap_or_else(|e| e.into_inner());
+    let mut meta = QuestMeta::read_from_file();
+    f(&mut meta);
+    if let Err(e) = meta.store_file() {
+        eprintln!("Failed to store the quest meta data: {e}");
+    }
+}
+
+/// Record that the description of a part was just downloaded.
+pub fn record_fetch(day: Day, part: Part) {
+    update(|meta| meta.set_fetched(day, part, now()));
+}
+
+/// Record that a part was just solved, which unlocks the next one.
+pub fn record_solved(day: Day, part: Part) {
+    if let Some(next) = Part::new(part.into_inner() + 1) {
+        update(|meta| meta.set_unlocked(day, next, now()));
+    }
+}
+
+/// Print a warning if the descriptions of a quest predate the unlock of one of its parts.
+/// Returns whether they are stale.
+pub fn warn_if_stale(day: Day) -> bool {
+    let Some(part) = QuestMeta::read_from_file().stale_part(day) else {
+        return false;
+    };
+    eprintln!(
+        "⚠️  The description of day {day} was downloaded before part {part} unlocked, it may be outdated."
+    );
+    true
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&QuestMeta> for JsonValue {
+    fn from(value: &QuestMeta) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for QuestMeta {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        Ok(QuestMeta {
+            data: json
+                .get("data")
+                .and_then(|v| v.get::<Vec<JsonValue>>())
+                .ok_or("expected `json.data` to be an array.")?
+                .iter()
+                .map(PartMeta::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&PartMeta> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartMeta) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part".into(),
+            JsonValue::Number(f64::from(value.part.into_inner())),
+        );
+        if let Some(fetched) = value.fetched {
+            map.insert("fetched".into(), JsonValue::Number(fetched as f64));
+        }
+        if let Some(unlocked) = value.unlocked {
+            map.insert("unlocked".into(), JsonValue::Number(unlocked as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartMeta {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected quest meta to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected meta.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .and_then(|part| Part::new(*part as u8))
+            .ok_or("Expected meta.part to be a part number.")?;
+
+        let timestamp = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+        };
+
+        Ok(PartMeta {
+            day,
+            part,
+            fetched: timestamp("fetched"),
+            unlocked: timestamp("unlocked"),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::QuestMeta;
+    use crate::{day, template::Part};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn finds_parts_unlocked_after_the_download() {
+        let mut meta = QuestMeta::default();
+        assert_eq!(meta.stale_part(day!(7)), None);
+
+        meta.set_fetched(day!(7), Part::One, 1_000);
+        meta.set_unlocked(day!(7), Part::Two, 1_500);
+        assert_eq!(meta.stale_part(day!(7)), Some(Part::Two));
+        assert_eq!(meta.stale_part(day!(8)), None);
+
+        meta.set_fetched(day!(7), Part::Two, 2_000);
+        assert_eq!(meta.stale_part(day!(7)), None);
+
+        meta.set_unlocked(day!(7), Part::Three, 2_500);
+        meta.set_unlocked(day!(7), Part::Three, 3_000);
+        assert_eq!(meta.data.last().unwrap().unlocked, Some(2_500));
+        assert_eq!(meta.stale_part(day!(7)), Some(Part::Three));
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut meta = QuestMeta::default();
+        meta.set_fetched(day!(12), Part::One, 1_700_000_000);
+        meta.set_unlocked(day!(12), Part::Two, 1_700_000_600);
+
+        let json = JsonValue::from(&meta).stringify().unwrap();
+        assert_eq!(QuestMeta::try_from(json), Ok(meta));
+        assert!(QuestMeta::try_from("[]".to_string()).is_err());
+    }
+}
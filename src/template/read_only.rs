@@ -0,0 +1,61 @@
+/// Read-only mode for live demos and experiments, enabled with `--read-only`.
+///
+/// Commands leave quest data, answers, the README and the submission history untouched.
+/// Downloads go to a temporary copy of the data directory, which solutions read from when a
+/// file exists there, and timings are not stored. Commands that only exist to write files
+/// refuse to run.
+///
+/// The mode is passed on to solution binaries through `EC_READ_ONLY`.
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::data_dir;
+
+static READ_ONLY_ENV: &str = "EC_READ_ONLY";
+
+/// Enable read-only mode for this process and the solutions it runs.
+pub fn enable() {
+    env::set_var(READ_ONLY_ENV, "1");
+}
+
+/// Disable read-only mode, for commands that only write to a temporary project.
+pub fn disable() {
+    env::remove_var(READ_ONLY_ENV);
+}
+
+pub fn is_enabled() -> bool {
+    env::var_os(READ_ONLY_ENV).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Exit with an error if read-only mode is enabled, for commands that can't do their job
+/// without writing to the repository.
+pub fn refuse(action: &str) {
+    if is_enabled() {
+        eprintln!("Can't {action} in read-only mode.");
+        process::exit(1);
+    }
+}
+
+/// The directory quests are downloaded to: the current year's data directory, or a temporary
+/// copy of it in read-only mode.
+pub fn download_dir() -> PathBuf {
+    if is_enabled() {
+        env::temp_dir().join("ec-read-only").join(data_dir())
+    } else {
+        data_dir()
+    }
+}
+
+/// Path of a quest file in the data directory, e.g. `inputs/07-1.txt`. In read-only mode, a
+/// downloaded copy takes precedence.
+pub fn quest_file(file: impl AsRef<Path>) -> PathBuf {
+    let downloaded = download_dir().join(&file);
+    if is_enabled() && downloaded.exists() {
+        downloaded
+    } else {
+        data_dir().join(file)
+    }
+}
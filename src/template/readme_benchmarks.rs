@@ -1,11 +1,29 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// Each row ends with a marker of its day, e.g. `<!--- day 07 --->`, so storing the timings of
+/// one day only rewrites its own row and the totals, and tables stored on different branches
+/// or machines merge without conflicts in unrelated rows.
+use std::{collections::BTreeMap, fs, io, time::Duration};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::run_multi::child_commands::parse_duration;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{authors::bin_name, Day, Event, Year};
 
+/// Marks the table of a single-year README, replaced by the marker of its year on update.
 static MARKER: &str = "<!--- benchmarking table --->";
+static YEAR_MARKER_PREFIX: &str = "<!--- benchmarking table ";
+static DAY_MARKER_PREFIX: &str = "<!--- day ";
+
+/// Each year's table sits between two of its markers, e.g. `<!--- benchmarking table 2025 --->`.
+fn year_marker(year: Year) -> String {
+    format!("{YEAR_MARKER_PREFIX}{year} --->")
+}
+
+/// Ends the row of a day, e.g. `<!--- day 07 --->`.
+fn day_marker(day: Day) -> String {
+    format!("{DAY_MARKER_PREFIX}{day} --->")
+}
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -27,81 +45,187 @@ pub struct TablePosition {
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    format!("./src/bin/{}.rs", bin_name(day, None))
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_marker(readme: &str, marker: &str) -> Result<Option<TablePosition>, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
-    let pos_start = matches
+    Ok(matches
         .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+        .zip(matches.last())
+        .map(|(start, end)| TablePosition {
+            pos_start: start.0,
+            pos_end: end.0 + end.1.len(),
+        }))
+}
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+/// The table of `year`, or the single-year table to replace. Without either, a new table is
+/// appended after the tables of the other years.
+fn locate_table(readme: &str, year: Year) -> Result<TablePosition, Error> {
+    if let Some(position) = locate_marker(readme, &year_marker(year))? {
+        return Ok(position);
+    }
+    if let Some(position) = locate_marker(readme, MARKER)? {
+        return Ok(position);
+    }
 
-    Ok(TablePosition { pos_start, pos_end })
+    let pos_end = readme
+        .rmatch_indices(YEAR_MARKER_PREFIX)
+        .next()
+        .and_then(|(i, _)| readme[i..].find("-->").map(|end| i + end + "-->".len()))
+        .ok_or_else(|| Error::Parser("Could not find table position.".into()))?;
+
+    Ok(TablePosition {
+        pos_start: pos_end,
+        pos_end,
+    })
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn format_row(timing: &Timing) -> String {
+    let [part_1, part_2, part_3] = [&timing.part_1, &timing.part_2, &timing.part_3]
+        .map(|part| part.clone().unwrap_or_else(|| "-".into()));
+    format!(
+        "| [Day {}]({}) | `{part_1}` | `{part_2}` | `{part_3}` | `{}` | {}",
+        timing.day.into_inner(),
+        get_path_for_bin(timing.day),
+        format_nanos(timing.total_nanos),
+        day_marker(timing.day)
+    )
+}
+
+/// The durations in the part and total columns of a row in nanoseconds, `None` for parts
+/// without a timing.
+fn row_nanos(row: &str) -> [Option<f64>; 4] {
+    let mut nanos = [None; 4];
+    for (nanos, cell) in nanos.iter_mut().zip(row.split('|').skip(2)) {
+        *nanos = parse_duration(cell.trim().trim_matches('`'));
+    }
+    nanos
+}
+
+/// The rows of a table by day, `None` if a row has no day marker, as in tables stored before
+/// rows were updated on their own.
+fn parse_rows(table: &str) -> Option<BTreeMap<Day, String>> {
+    table
+        .lines()
+        .filter(|line| line.starts_with("| [Day "))
+        .map(|line| {
+            let day = line
+                .rsplit_once(DAY_MARKER_PREFIX)?
+                .1
+                .strip_suffix(" --->")?
+                .parse()
+                .ok()?;
+            Some((day, line.to_string()))
+        })
+        .collect()
+}
+
+fn construct_table(prefix: &str, year: Year, rows: &BTreeMap<Day, String>) -> String {
+    let marker = year_marker(year);
+    let header = format!("{prefix} {year}");
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 | Part 3 |".into(),
-        "| :---: | :---: | :---:  | :---:  |".into(),
+        "| Day | Part 1 | Part 2 | Part 3 | Total |".into(),
+        "| :---: | :---: | :---: | :---: | :---: |".into(),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into()),
-            timing.part_3.unwrap_or_else(|| "-".into())
-        ));
+    // columns without any timing show `-` like the rows, rather than a total of `0.0ns`.
+    let mut totals: [Option<f64>; 4] = [None; 4];
+    for row in rows.values() {
+        for (total, nanos) in totals.iter_mut().zip(row_nanos(row)) {
+            if let Some(nanos) = nanos {
+                *total = Some(total.unwrap_or_default() + nanos);
+            }
+        }
+        lines.push(row.clone());
     }
 
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    let [part_1, part_2, part_3, total] =
+        totals.map(|total| total.map_or_else(|| "-".into(), format_nanos));
+    lines.push(format!(
+        "| **Total** | `{part_1}` | `{part_2}` | `{part_3}` | **`{total}`** |"
+    ));
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Update the rows of `days` in the table of `year`, keeping the other rows as they are. A table
+/// that is new or has rows without a day marker is rebuilt from all `timings` instead. Rows of
+/// days past the last quest of the event are dropped.
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: &Timings,
+    days: &[Day],
+) -> Result<(), Error> {
+    let positions = locate_table(s, year)?;
+    let table = &s[positions.pos_start..positions.pos_end];
+
+    let mut rows = match parse_rows(table).filter(|_| table.starts_with(&year_marker(year))) {
+        Some(mut rows) => {
+            for timing in timings.data.iter().filter(|t| days.contains(&t.day)) {
+                rows.insert(timing.day, format_row(timing));
+            }
+            rows
+        }
+        None => timings
+            .data
+            .iter()
+            .map(|timing| (timing.day, format_row(timing)))
+            .collect(),
+    };
+    rows.retain(|day, _| *day <= Event::from(year).quest_count());
+
+    let mut table = construct_table("###", year, &rows);
+    if positions.pos_start == positions.pos_end {
+        table.insert_str(0, "\n\n");
+    }
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Store the timings of `days` in the README, see [`update_content`].
+pub fn update(timings: &Timings, days: &[Day]) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, Year::current(), timings, days)?;
     fs::write(path, &readme)?;
     Ok(())
 }
-
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, year_marker, MARKER};
+    use crate::{day, template::timings::Timing, template::timings::Timings, template::Day, year};
+
+    fn timing(day: u8, part_1: &str, total_nanos: f64) -> Timing {
+        Timing {
+            day: Day::new(day).unwrap(),
+            part_1: Some(part_1.into()),
+            part_2: None,
+            part_3: None,
+            total_nanos,
+            build: None,
+            machine: None,
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -111,23 +235,30 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     part_3: Some("30ms".into()),
-                    total_nanos: 6e+10,
+                    total_nanos: 6e+7,
+                    build: None,
+                    machine: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     part_3: Some("50ms".into()),
-                    total_nanos: 12e+10,
+                    total_nanos: 12e+7,
+                    build: None,
+                    machine: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
-                    part_3: Some("60ms".into()),
-                    total_nanos: 15e+10,
+                    part_3: None,
+                    total_nanos: 9e+7,
+                    build: None,
+                    machine: None,
                 },
             ],
+            history: vec![],
         }
     }
 
@@ -135,53 +266,133 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2025), &get_mock_timings(), &[]).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2025), &get_mock_timings(), &[]).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(&mut s, year!(2025), &get_mock_timings(), &[]).unwrap();
+        assert_eq!(s.contains("### 2025"), true);
+        assert_eq!(s.contains(MARKER), false);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        update_content(&mut s, year!(2025), &get_mock_timings(), &[]).unwrap();
+        update_content(&mut s, year!(2025), &get_mock_timings(), &[]).unwrap();
+        let marker = year_marker(year!(2025));
+        assert_eq!(s.matches(&marker).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(s.matches("### 2025").collect::<Vec<&str>>().len(), 1);
+    }
+
+    #[test]
+    fn keeps_a_table_per_year() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year!(2024), &get_mock_timings(), &[]).unwrap();
+        update_content(&mut s, year!(2025), &get_mock_timings(), &[]).unwrap();
+        update_content(&mut s, year!(2024), &Timings::default(), &[day!(1)]).unwrap();
+
+        let years: Vec<&str> = s.lines().filter(|l| l.starts_with("### ")).collect();
+        assert_eq!(years, vec!["### 2024", "### 2025"]);
+        assert_eq!(s.matches("| [Day 1]").count(), 2);
+        assert!(s.ends_with(&format!("{}\nbaz", year_marker(year!(2025)))));
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 330.0).unwrap();
+        update_content(&mut s, year!(2025), &get_mock_timings(), &[]).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
-            "",
-            "| Day | Part 1 | Part 2 | Part 3 |",
-            "| :---: | :---: | :---:  | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `30ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `50ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | `60ms` |",
+            "<!--- benchmarking table 2025 --->",
+            "### 2025",
             "",
-            "**Total: 330.00ms**",
-            "<!--- benchmarking table --->",
+            "| Day | Part 1 | Part 2 | Part 3 | Total |",
+            "| :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `30ms` | `60.0ms` | <!--- day 01 --->",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `50ms` | `120.0ms` | <!--- day 02 --->",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | `-` | `90.0ms` | <!--- day 04 --->",
+            "| **Total** | `80.0ms` | `110.0ms` | `80.0ms` | **`270.0ms`** |",
+            "<!--- benchmarking table 2025 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn updates_single_rows() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year!(2025), &get_mock_timings(), &[]).unwrap();
+
+        // day 1 was timed differently elsewhere, e.g. on another machine, but only 2 and 3 ran here.
+        let timings = Timings {
+            data: vec![
+                timing(1, "1s", 1e9),
+                timing(2, "5ms", 5e6),
+                timing(3, "1ms", 1e6),
+            ],
+            history: vec![],
+        };
+        update_content(&mut s, year!(2025), &timings, &[day!(2), day!(3)]).unwrap();
+
+        let rows: Vec<&str> = s.lines().filter(|l| l.starts_with("| [Day ")).collect();
+        assert_eq!(
+            rows,
+            vec![
+                "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `30ms` | `60.0ms` | <!--- day 01 --->",
+                "| [Day 2](./src/bin/02.rs) | `5ms` | `-` | `-` | `5.0ms` | <!--- day 02 --->",
+                "| [Day 3](./src/bin/03.rs) | `1ms` | `-` | `-` | `1.0ms` | <!--- day 03 --->",
+                "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | `-` | `90.0ms` | <!--- day 04 --->",
+            ]
+        );
+        assert!(s.contains("| **Total** | `56.0ms` | `70.0ms` | `30.0ms` | **`156.0ms`** |"));
+    }
+
+    #[test]
+    fn rebuilds_tables_without_day_markers() {
+        let marker = year_marker(year!(2025));
+        let mut s =
+            format!("{marker}\n| [Day 1](./src/bin/01.rs) | `1s` | `-` | `-` | `1.0s` |\n{marker}");
+        update_content(&mut s, year!(2025), &get_mock_timings(), &[day!(2)]).unwrap();
+
+        assert_eq!(s.matches("<!--- day ").count(), 3);
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` |"));
+    }
+
+    #[test]
+    fn drops_days_without_quests() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        let timings = Timings {
+            data: vec![timing(20, "1ms", 1e6), timing(21, "1s", 1e9)],
+            history: vec![],
+        };
+        update_content(&mut s, year!(2025), &timings, &[]).unwrap();
+
+        assert!(s.contains("<!--- day 20 --->"));
+        assert!(!s.contains("<!--- day 21 --->"));
+        assert!(s.contains("**`1.0ms`**"));
+    }
+
+    #[test]
+    fn leaves_totals_of_untimed_parts_empty() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        let timings = Timings {
+            data: vec![timing(1, "2ms", 2e6), timing(2, "1ms", 1e6)],
+            history: vec![],
+        };
+        update_content(&mut s, year!(2025), &timings, &[]).unwrap();
+
+        assert!(s.contains("| **Total** | `3.0ms` | `-` | `-` | **`3.0ms`** |"));
+    }
 }
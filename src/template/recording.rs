@@ -0,0 +1,160 @@
+/// Terminal recordings of solution runs in the asciinema cast v2 format, see
+/// <https://docs.asciinema.org/manual/asciicast/v2/>.
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::Error,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tinyjson::JsonValue;
+
+use crate::template::contest::now;
+
+static RECORDINGS_DIR: &str = "./recordings";
+
+/// Output events captured while running a command.
+#[derive(Debug)]
+pub struct Recording {
+    pub title: String,
+    pub width: u16,
+    pub height: u16,
+    /// Unix timestamp of the recording start.
+    pub started_at: u64,
+    started: Instant,
+    events: Vec<(Duration, String)>,
+}
+
+impl Recording {
+    /// Starts a recording sized to the current terminal, as reported by `COLUMNS` and
+    /// `LINES`, falling back to 80x24.
+    pub fn start(title: &str) -> Self {
+        let size = |var: &str, default: u16| {
+            env::var(var)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+
+        Recording {
+            title: title.to_string(),
+            width: size("COLUMNS", 80),
+            height: size("LINES", 24),
+            started_at: now(),
+            started: Instant::now(),
+            events: vec![],
+        }
+    }
+
+    /// Records terminal output at the time it arrived.
+    pub fn output(&mut self, at: Instant, data: &str) {
+        if !data.is_empty() {
+            self.events
+                .push((at.saturating_duration_since(self.started), data.to_string()));
+        }
+    }
+
+    pub fn to_cast(&self) -> String {
+        let mut header: HashMap<String, JsonValue> = HashMap::new();
+        header.insert("version".into(), JsonValue::Number(2.0));
+        header.insert("width".into(), JsonValue::Number(f64::from(self.width)));
+        header.insert("height".into(), JsonValue::Number(f64::from(self.height)));
+        #[allow(clippy::cast_precision_loss)]
+        header.insert(
+            "timestamp".into(),
+            JsonValue::Number(self.started_at as f64),
+        );
+        header.insert("title".into(), JsonValue::String(self.title.clone()));
+
+        let mut lines = vec![JsonValue::Object(header).stringify().unwrap()];
+
+        for (at, data) in &self.events {
+            let event = JsonValue::Array(vec![
+                JsonValue::Number((at.as_secs_f64() * 1e6).round() / 1e6),
+                JsonValue::String("o".into()),
+                JsonValue::String(data.clone()),
+            ]);
+            lines.push(event.stringify().unwrap());
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Writes the cast to `recordings/{name}-{timestamp}.cast`.
+    pub fn store_file(&self, name: &str) -> Result<PathBuf, Error> {
+        fs::create_dir_all(RECORDINGS_DIR)?;
+        let path = PathBuf::from(format!("{RECORDINGS_DIR}/{name}-{}.cast", self.started_at));
+        fs::write(&path, self.to_cast())?;
+        Ok(path)
+    }
+}
+
+/// Decodes a byte stream read in arbitrary chunks, holding back UTF-8 sequences that are
+/// split between chunks.
+#[derive(Debug, Default)]
+pub struct Utf8Chunks {
+    pending: Vec<u8>,
+}
+
+impl Utf8Chunks {
+    pub fn decode(&mut self, bytes: &[u8]) -> String {
+        self.pending.extend_from_slice(bytes);
+
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            // incomplete sequence at the end, wait for the next chunk.
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => {
+                let s = String::from_utf8_lossy(&self.pending).into_owned();
+                self.pending.clear();
+                return s;
+            }
+        };
+
+        let rest = self.pending.split_off(valid);
+        String::from_utf8(std::mem::replace(&mut self.pending, rest))
+            .expect("prefix was validated as UTF-8")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Recording, Utf8Chunks};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn renders_casts() {
+        let mut recording = Recording::start("Day 07");
+        recording.width = 100;
+        recording.height = 30;
+        recording.started_at = 1_700_000_000;
+
+        let start = Instant::now();
+        recording.started = start;
+        recording.output(
+            start + Duration::from_millis(1500),
+            "Part 1: \x1b[1m42\x1b[0m\r\n",
+        );
+        recording.output(start, "");
+
+        let cast = recording.to_cast();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].contains("\"version\":2"), true);
+        assert_eq!(lines[0].contains("\"width\":100"), true);
+        assert_eq!(lines[0].contains("\"timestamp\":1700000000"), true);
+        assert_eq!(lines[1], r#"[1.5,"o","Part 1: \u001b[1m42\u001b[0m\r\n"]"#);
+    }
+
+    #[test]
+    fn decodes_split_characters() {
+        let bytes = "✖ ok".as_bytes();
+        let mut chunks = Utf8Chunks::default();
+        assert_eq!(chunks.decode(&bytes[..1]), "");
+        assert_eq!(chunks.decode(&bytes[1..4]), "✖ ");
+        assert_eq!(chunks.decode(&bytes[4..]), "ok");
+        assert_eq!(chunks.decode(&[0xff, b'a']), "\u{fffd}a");
+    }
+}
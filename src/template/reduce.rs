@@ -0,0 +1,143 @@
+/// Delta debugging behind `cargo reduce`, which shrinks an input while a failure persists.
+///
+/// The input is reduced in two passes: first whole blocks separated by blank lines are removed,
+/// then single lines and runs of lines. Each candidate is kept when the [`Predicate`] still
+/// holds for it, so the result is an input where removing any one line makes the failure go
+/// away.
+use std::{collections::HashMap, fmt::Display, hash::Hash, str::FromStr};
+
+use crate::template::ANSI_RESET;
+
+/// The failure to preserve while reducing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Predicate {
+    /// The part panics at the same location as with the original input.
+    Panics,
+    /// The part and a reference solution both answer, but differently.
+    Differs,
+}
+
+impl FromStr for Predicate {
+    type Err = PredicateFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "panics" => Ok(Predicate::Panics),
+            "differs" => Ok(Predicate::Differs),
+            _ => Err(PredicateFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Predicate`].
+#[derive(Debug)]
+pub struct PredicateFromStrError;
+
+impl Display for PredicateFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting `panics` or `differs`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Remove items while `holds` is true for the remainder, trying ever smaller chunks (ddmin).
+/// Each distinct candidate is tested once.
+pub fn ddmin<T: Clone + Eq + Hash>(
+    mut items: Vec<T>,
+    mut holds: impl FnMut(&[T]) -> bool,
+) -> Vec<T> {
+    let mut tested: HashMap<Vec<T>, bool> = HashMap::new();
+    let mut chunks = 2;
+
+    while items.len() >= 2 {
+        let size = items.len().div_ceil(chunks);
+        let reduced = (0..items.len()).step_by(size).find_map(|start| {
+            let end = (start + size).min(items.len());
+            let candidate: Vec<T> = [&items[..start], &items[end..]].concat();
+            let result = *tested
+                .entry(candidate.clone())
+                .or_insert_with(|| holds(&candidate));
+            result.then_some(candidate)
+        });
+
+        match reduced {
+            Some(candidate) => {
+                items = candidate;
+                chunks = (chunks - 1).max(2);
+            }
+            None if chunks >= items.len() => break,
+            None => chunks = (chunks * 2).min(items.len()),
+        }
+    }
+
+    items
+}
+
+/// Reduce an input by its blocks, then by its lines.
+pub fn reduce_input(input: &str, mut holds: impl FnMut(&str) -> bool) -> String {
+    let blocks: Vec<String> = input.trim_end().split("\n\n").map(str::to_string).collect();
+    let blocks = ddmin(blocks, |blocks| holds(&with_newline(blocks.join("\n\n"))));
+
+    let lines: Vec<String> = blocks.join("\n\n").lines().map(str::to_string).collect();
+    let lines = ddmin(lines, |lines| holds(&with_newline(lines.join("\n"))));
+
+    with_newline(lines.join("\n"))
+}
+
+fn with_newline(mut input: String) -> String {
+    input.push('\n');
+    input
+}
+
+/// Where a solution panicked, from the report of its panic hook.
+pub fn panic_location(stderr: &str) -> Option<&str> {
+    stderr.lines().find_map(|line| {
+        let (_, location) = line.split_once(" panicked at ")?;
+        Some(location.trim_end_matches(ANSI_RESET))
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ddmin, panic_location, reduce_input, Predicate};
+
+    #[test]
+    fn finds_minimal_subsets() {
+        let items: Vec<u32> = (1..=20).collect();
+        let mut runs = 0;
+        let reduced = ddmin(items, |items| {
+            runs += 1;
+            items.contains(&3) && items.contains(&17)
+        });
+        assert_eq!(reduced, vec![3, 17]);
+        assert!(runs < 60);
+
+        assert_eq!(ddmin(vec![1, 2], |_| false), vec![1, 2]);
+    }
+
+    #[test]
+    fn reduces_blocks_and_lines() {
+        let input = "a\nb\n\nc\nbad\nd\n\ne\n";
+        assert_eq!(reduce_input(input, |input| input.contains("bad")), "bad\n");
+
+        let input = "x=1\ny=2\n\nx=3\n";
+        assert_eq!(
+            reduce_input(input, |input| input.contains("x=1")
+                && input.contains("y=2")),
+            "x=1\ny=2\n"
+        );
+    }
+
+    #[test]
+    fn parses_predicates_and_panics() {
+        assert_eq!("panics".parse::<Predicate>().ok(), Some(Predicate::Panics));
+        assert!("fails".parse::<Predicate>().is_err());
+
+        let stderr = "\n\x1b[1m💥 Day 07 Part 2 panicked at src/bin/07.rs:12:5\x1b[0m\nInput: x";
+        assert_eq!(panic_location(stderr), Some("src/bin/07.rs:12:5"));
+        assert_eq!(panic_location("Part 2: 42"), None);
+    }
+}
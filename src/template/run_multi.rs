@@ -1,50 +1,180 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet,
+    io,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Instant,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    all_parts, authors::bin_name, json_output, notify::notify_if_slow, Day, Event, Part, ANSI_BOLD,
+    ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
-    all_days,
+    all_days_for,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Run the selected quests and print their output in day order. With more than one job, the
+/// parts of all quests run in parallel and the output of a quest is printed once it is complete.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
+    let started = Instant::now();
     let mut need_space = false;
+    let mut scaffolded = 0;
+    let mut solved_parts = 0;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    // NOTE: use non-duplicate, sorted day values, without days the event has no quest for.
+    let days: Vec<Day> = all_days_for(Event::current())
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        .collect();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let json = json_output::is_enabled();
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let mut print_header = |day: Day| {
+        // JSON records name their day, and nothing but records is printed.
+        if json {
+            return;
+        }
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    };
 
-            if output.is_empty() {
+    let mut record = |day: Day, output: &[String]| {
+        if output.is_empty() {
+            if !json {
                 println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
             }
+        } else {
+            scaffolded += 1;
+            solved_parts += all_parts()
+                .filter(|part| child_commands::parse_answer(output, *part).is_some())
+                .count();
+            let val = child_commands::parse_exec_time(output, day);
+            timings.push(val);
+        }
+    };
+
+    if jobs > 1 {
+        run_parallel(&days, is_release, is_timed, jobs, |day, output| {
+            print_header(day);
+            output.replay();
+            record(day, &output.stdout());
         });
+    } else {
+        for &day in &days {
+            print_header(day);
+            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            record(day, &output);
+        }
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
+    if !json {
         println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            "\n{ANSI_BOLD}Summary:{ANSI_RESET} {scaffolded}/{} quests scaffolded, {solved_parts}/{} parts solved.",
+            days.len(),
+            scaffolded * 3
         );
+    }
+    notify_if_slow(
+        &format!("Run of {} quests", days.len()),
+        started.elapsed(),
+        true,
+    );
+
+    if is_timed {
+        let timings = Timings {
+            data: timings,
+            history: vec![],
+        };
+        let total_millis = timings.total_millis();
+        if !json {
+            println!(
+                "{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
         Some(timings)
     } else {
         None
     }
 }
+/// Run the parts of `days` on `jobs` threads. `report` gets the output of each day in order, as
+/// soon as the parts of that day and of all days before it are done.
+fn run_parallel(
+    days: &[Day],
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+    mut report: impl FnMut(Day, child_commands::Captured),
+) {
+    // days that are not scaffolded yet have no tasks and report an empty output.
+    let tasks: Vec<(usize, Day, Part)> = days
+        .iter()
+        .enumerate()
+        .filter(|(_, day)| Path::new(&get_path_for_bin(**day)).exists())
+        .flat_map(|(i, &day)| all_parts().map(move |part| (i, day, part)))
+        .collect();
+
+    let mut remaining = vec![0; days.len()];
+    for &(i, ..) in &tasks {
+        remaining[i] += 1;
+    }
+    let mut outputs: Vec<[Option<child_commands::Captured>; 3]> =
+        days.iter().map(|_| Default::default()).collect();
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(tasks.len()) {
+            let (tasks, next, tx) = (&tasks, &next, tx.clone());
+            scope.spawn(move || {
+                while let Some(&(i, day, part)) = tasks.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let output =
+                        child_commands::capture_solution(day, part, is_timed, is_release).unwrap();
+                    if tx.send((i, part, output)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        let mut printed = 0;
+        loop {
+            while printed < days.len() && remaining[printed] == 0 {
+                let mut output = child_commands::Captured::default();
+                for part_output in outputs[printed].iter_mut().filter_map(Option::take) {
+                    output.append(part_output);
+                }
+                report(days[printed], output);
+                printed += 1;
+            }
+
+            let Ok((i, part, output)) = rx.recv() else {
+                break;
+            };
+            outputs[i][part.index()] = Some(output);
+            remaining[i] -= 1;
+        }
+    });
+}
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -61,49 +191,157 @@ impl From<std::io::Error> for Error {
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    format!("./src/bin/{}.rs", bin_name(day, None))
 }
 
-/// All solutions live in isolated binaries.
+/// All solutions live in isolated binaries, most of them also in the `registry` binary with the
+/// `registry` feature.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{
+        authors::bin_name, commands::solve::bin_path, json_output::Record, Day, Part,
+    };
     use std::{
+        collections::HashSet,
         io::{BufRead, BufReader},
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
+        sync::{mpsc, OnceLock},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    /// The `registry` binary and the days it runs, built once for all quests of a run.
+    static REGISTRY: OnceLock<Option<(PathBuf, HashSet<Day>)>> = OnceLock::new();
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// The `registry` binary if it runs `day`, `None` if it doesn't, didn't build or the
+    /// `registry` feature is disabled.
+    fn registry_bin(day: Day, is_release: bool) -> Option<&'static Path> {
+        if !cfg!(feature = "registry") {
+            return None;
+        }
+        let (path, days) = REGISTRY
+            .get_or_init(|| build_registry(is_release))
+            .as_ref()?;
+        days.contains(&day).then_some(path.as_path())
+    }
 
+    fn build_registry(is_release: bool) -> Option<(PathBuf, HashSet<Day>)> {
+        let mut cmd = Command::new("cargo");
+        cmd.args([
+            "build",
+            "--quiet",
+            "--features",
+            "registry",
+            "--bin",
+            "registry",
+        ]);
         if is_release {
-            args.push("--release");
+            cmd.arg("--release");
+        }
+        // a solution that doesn't compile reports its errors when its own binary is built.
+        if !cmd.stderr(Stdio::null()).status().ok()?.success() {
+            return None;
+        }
+
+        let path = bin_path("registry", is_release);
+        let listed = Command::new(&path).arg("--list").output().ok()?;
+        let days = String::from_utf8_lossy(&listed.stdout)
+            .lines()
+            .filter_map(|day| day.parse().ok())
+            .collect();
+        Some((path, days))
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Stream {
+        Stdout,
+        Stderr,
+    }
+
+    /// Output of a solution that ran in the background, to be printed later.
+    #[derive(Default)]
+    pub struct Captured {
+        lines: Vec<(Stream, String)>,
+    }
+
+    impl Captured {
+        /// The lines printed to stdout, to parse answers and timings from.
+        pub fn stdout(&self) -> Vec<String> {
+            self.lines
+                .iter()
+                .filter(|(stream, _)| *stream == Stream::Stdout)
+                .map(|(_, line)| line.clone())
+                .collect()
+        }
+
+        /// Print the output as the solution did, stdout and stderr interleaved.
+        pub fn replay(&self) {
+            for (stream, line) in &self.lines {
+                match stream {
+                    Stream::Stdout => println!("{line}"),
+                    Stream::Stderr => eprintln!("{line}"),
+                }
+            }
         }
 
+        /// Add the output of another part of the same quest, without repeating the build info.
+        pub fn append(&mut self, other: Captured) {
+            let has_build = self
+                .lines
+                .iter()
+                .any(|(_, line)| line.starts_with("Build: "));
+            self.lines.extend(
+                other
+                    .lines
+                    .into_iter()
+                    .filter(|(_, line)| !(has_build && line.starts_with("Build: "))),
+            );
+        }
+    }
+
+    /// The `registry` binary or `cargo run` of the solution bin for a given day, with piped
+    /// output.
+    fn solution_command(day: Day, is_timed: bool, is_release: bool, part: Option<Part>) -> Command {
+        let mut cmd = match registry_bin(day, is_release) {
+            Some(registry) => {
+                let mut cmd = Command::new(registry);
+                cmd.args(["--day", &day.to_string()]);
+                cmd
+            }
+            None => {
+                let mut cmd = Command::new("cargo");
+                cmd.args(["run", "--quiet", "--bin", &bin_name(day, None)]);
+                if is_release {
+                    cmd.arg("--release");
+                }
+                cmd.arg("--");
+                cmd
+            }
+        };
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            cmd.arg("--time");
+        }
+        if let Some(part) = part {
+            cmd.args(["--part", &part.to_string()]);
+        }
+
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        cmd
+    }
+
+    /// Run the solution bin for a given day
+    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(vec![]);
         }
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut cmd = solution_command(day, is_timed, is_release, None).spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
@@ -128,6 +366,43 @@ pub mod child_commands {
         Ok(output)
     }
 
+    /// Run a single part of the solution bin for a given day, keeping its output for later.
+    pub fn capture_solution(
+        day: Day,
+        part: Part,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<Captured, Error> {
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(Captured::default());
+        }
+
+        let mut cmd = solution_command(day, is_timed, is_release, Some(part)).spawn()?;
+
+        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+
+        // both streams feed one channel, which keeps their lines roughly in order.
+        let (tx, rx) = mpsc::channel();
+        let err_tx = tx.clone();
+        let thread = thread::spawn(move || {
+            for line in stderr.lines() {
+                let _ = err_tx.send((Stream::Stderr, line.unwrap()));
+            }
+        });
+        for line in stdout.lines() {
+            let _ = tx.send((Stream::Stdout, line.unwrap()));
+        }
+        drop(tx);
+
+        thread.join().unwrap();
+        cmd.wait()?;
+
+        Ok(Captured {
+            lines: rx.into_iter().collect(),
+        })
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
@@ -135,12 +410,20 @@ pub mod child_commands {
             part_2: None,
             part_3: None,
             total_nanos: 0_f64,
+            build: None,
+            machine: None,
         };
 
+        timings.build = output
+            .iter()
+            .find_map(|l| l.strip_prefix("Build: "))
+            .map(str::to_string);
+
         output
             .iter()
             .filter_map(|l| {
-                if !l.contains(" samples)") {
+                // benchmarked parts, possibly with their peak memory, e.g. `(1.2ms @ 10 samples)`.
+                if !l.contains(" samples)") && !l.contains(" samples, peak ") {
                     return None;
                 }
 
@@ -164,9 +447,88 @@ pub mod child_commands {
                 timings.total_nanos += nanos;
             });
 
+        // benchmarked JSON records, as printed with `--json`.
+        for record in output.iter().filter_map(|l| Record::parse(l)) {
+            if !record.build.is_empty() {
+                timings.build = Some(record.build.clone());
+            }
+            let Some(duration) = record
+                .duration
+                .filter(|_| record.samples > 1 && record.answer.is_some())
+            else {
+                continue;
+            };
+            let timing = Some(format!("{duration:.1?}"));
+            match record.part {
+                Part::One => timings.part_1 = timing,
+                Part::Two => timings.part_2 = timing,
+                Part::Three => timings.part_3 = timing,
+            }
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timings.total_nanos += duration.as_nanos() as f64;
+            }
+        }
+
         timings
     }
 
+    /// Extract the answer printed for `part`, or `None` if the part was not solved.
+    pub fn parse_answer(output: &[String], part: Part) -> Option<String> {
+        if let Some(record) = output
+            .iter()
+            .filter_map(|l| Record::parse(l))
+            .find(|record| record.part == part)
+        {
+            return record.answer;
+        }
+
+        let prefix = format!("Part {part}: ");
+
+        let line = output
+            .iter()
+            .filter_map(|l| l.rsplit('\r').next())
+            .map(strip_ansi)
+            .find(|l| l.starts_with(&prefix))?;
+
+        let answer = line[prefix.len()..].trim_end();
+        // strip the trailing duration, e.g. ` (1.2ms @ 100 samples)`.
+        let answer = answer
+            .strip_suffix(')')
+            .and_then(|s| s.rsplit_once(" ("))
+            .filter(|(_, duration)| {
+                parse_duration(duration.split('@').next().unwrap_or_default().trim()).is_some()
+            })
+            .map_or(answer, |(answer, _)| answer)
+            .trim();
+
+        if answer.is_empty() || answer.starts_with('✖') || answer.starts_with('▼') {
+            None
+        } else {
+            Some(answer.to_string())
+        }
+    }
+
+    fn strip_ansi(s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut chars = s.chars();
+
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                // skip until the end of the escape sequence, e.g. `\x1b[1m`.
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            } else {
+                out.push(c);
+            }
+        }
+
+        out
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -174,22 +536,25 @@ pub mod child_commands {
     fn parse_time(line: &str) -> Option<(&str, f64)> {
         // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
-            .split(" samples)")
-            .next()?
+            .rsplit_once(" samples")?
+            .0
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
+        Some((str_timing, parse_duration(str_timing)?))
+    }
+
+    /// Parse a duration string as printed by the runner (e.g. `74.13ms`) into nanoseconds.
+    pub fn parse_duration(str_timing: &str) -> Option<f64> {
+        match str_timing {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        }
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -208,14 +573,15 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answer, parse_exec_time, Captured, Stream};
 
-        use crate::day;
+        use crate::{day, template::Part};
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
+                    "Build: release (opt-level=3, lto=false, codegen-units=16)".into(),
                     "Part 1: 0 (74.13ns @ 100000 samples)".into(),
                     "Part 2: 10 (74.13ms @ 99999 samples)".into(),
                     "Part 3: 20 (100µs @ 50000 samples)".into(),
@@ -227,6 +593,25 @@ pub mod child_commands {
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
             assert_eq!(res.part_3.unwrap(), "100µs");
+            assert_eq!(
+                res.build.unwrap(),
+                "release (opt-level=3, lto=false, codegen-units=16)"
+            );
+        }
+
+        #[test]
+        fn parses_times_with_peak_memory() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples, peak 1.5 KiB)".into(),
+                    "Part 2: 10 (2ms @ 10 samples, peak 3 MiB)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 2000074.13_f64);
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            assert_eq!(res.part_2.unwrap(), "2ms");
         }
 
         #[test]
@@ -262,5 +647,55 @@ pub mod child_commands {
             assert_eq!(res.part_2.is_none(), true);
             assert_eq!(res.part_3.is_none(), true);
         }
+
+        #[test]
+        fn parses_answers() {
+            let output = [
+                "Build: release (opt-level=3, lto=false, codegen-units=16)".into(),
+                "Part 1: \x1b[1m1234\x1b[0m\rPart 1: \x1b[1m1234\x1b[0m (1.2ms)".into(),
+                "Part 2: \x1b[1mA,B (C)\x1b[0m (5µs @ 10 samples)".into(),
+                "Part 3: ✖\rPart 3: ✖             ".into(),
+            ];
+            assert_eq!(parse_answer(&output, Part::One), Some("1234".into()));
+            assert_eq!(parse_answer(&output, Part::Two), Some("A,B (C)".into()));
+            assert_eq!(parse_answer(&output, Part::Three), None);
+        }
+
+        #[test]
+        fn parses_json_records() {
+            let output = [
+                r#"{"year":2025,"day":"01","part":1,"answer":"42","duration_ns":1500,"samples":10,"success":true,"build":"release"}"#.into(),
+                r#"{"year":2025,"day":"01","part":2,"answer":null,"duration_ns":20,"samples":10,"success":false,"build":"release"}"#.into(),
+                r#"{"year":2025,"day":"01","part":3,"answer":null,"duration_ns":null,"samples":0,"success":false,"error":"timed out after 1s","build":"release"}"#.into(),
+            ];
+            assert_eq!(parse_answer(&output, Part::One), Some("42".into()));
+            assert_eq!(parse_answer(&output, Part::Three), None);
+
+            let res = parse_exec_time(&output, day!(1));
+            assert_approx_eq!(res.total_nanos, 1500_f64);
+            assert_eq!(res.part_1.unwrap(), "1.5µs");
+            assert!(res.part_2.is_none());
+            assert!(res.part_3.is_none());
+            assert_eq!(res.build.unwrap(), "release");
+        }
+
+        #[test]
+        fn merges_captured_parts() {
+            let part = |n: u8| Captured {
+                lines: vec![
+                    (Stream::Stdout, "Build: debug".into()),
+                    (Stream::Stderr, format!("debug {n}")),
+                    (Stream::Stdout, format!("Part {n}: {n}")),
+                ],
+            };
+            let mut output = part(1);
+            output.append(part(2));
+
+            assert_eq!(
+                output.stdout(),
+                vec!["Build: debug", "Part 1: 1", "Part 2: 2"]
+            );
+            assert_eq!(output.lines.len(), 5);
+        }
     }
 }
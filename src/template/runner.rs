@@ -1,24 +1,438 @@
 /// Encapsulates code that interacts with solution functions.
-use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
 use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{
+    cmp, env, fs, panic,
+    path::{Path, PathBuf},
+    process, thread,
+};
 
+use crate::template::answers::{get_answer_path, normalize, EcAnswer};
+use crate::template::checks;
+use crate::template::hooks::{self, PartInfo};
+use crate::template::watch::compact;
 use crate::template::ANSI_BOLD;
-use crate::template::{ec_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    ec_cli, explain,
+    input_snapshot::InputSnapshot,
+    inputs,
+    json_output::{self, Record},
+    manifest, mem_profile, panic_report, read_only, sample_paths, trace, Day, Part, Year,
+    ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Describes the build this binary was compiled with, as captured by `build.rs`.
+#[must_use]
+pub fn build_info() -> String {
+    format!(
+        "{} (opt-level={}, lto={}, codegen-units={})",
+        env!("EC_BUILD_PROFILE"),
+        env!("EC_BUILD_OPT_LEVEL"),
+        env!("EC_BUILD_LTO"),
+        env!("EC_BUILD_CODEGEN_UNITS"),
+    )
+}
+
+/// Print the build profile so debug and release timings are not mixed up.
+pub fn print_build_info() {
+    // JSON records include the build instead.
+    if json_output::is_enabled() {
+        return;
+    }
+    println!("Build: {}", build_info());
+}
+
+/// The value following `key` in the arguments of the binary.
+fn arg_value(key: &str) -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .position(|x| x == key)
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+}
+
+/// Returns `false` if the binary was invoked with `--part <n>` for a different part.
+#[must_use]
+pub fn should_run_part(part: Part) -> bool {
+    arg_value("--part")
+        .and_then(|x| x.parse::<Part>().ok())
+        .is_none_or(|selected| selected == part)
+}
+
+/// Seconds a part may run before the binary gives up on it, see [`timeout`].
+pub const TIMEOUT_ENV: &str = "EC_TIMEOUT_SECS";
+
+/// Limit parts run by this process and the solutions it starts to `secs` seconds.
+pub fn set_timeout(secs: u64) {
+    env::set_var(TIMEOUT_ENV, secs.to_string());
+}
+
+/// How long a part may take before it is aborted, from `--timeout <secs>` or `EC_TIMEOUT_SECS`.
+/// Only the first run is limited, not the benchmark samples after it.
+#[must_use]
+pub fn timeout() -> Option<Duration> {
+    arg_value("--timeout")
+        .or_else(|| env::var(TIMEOUT_ENV).ok())
+        .and_then(|secs| secs.parse().ok())
+        .filter(|&secs| secs > 0)
+        .map(Duration::from_secs)
+}
+
+static CHECKS: OnceLock<bool> = OnceLock::new();
+static CHECKING_SAMPLE: AtomicBool = AtomicBool::new(false);
+
+/// Whether [`ec_debug_assert!`](crate::ec_debug_assert) is active: always in debug builds, and
+/// with `--checks` in release builds.
+#[must_use]
+pub fn checks_enabled() -> bool {
+    *CHECKS.get_or_init(|| cfg!(debug_assertions) || env::args().any(|x| x == "--checks"))
+}
+
+/// Whether the binary was invoked with `--sample`, to run the parts on their samples instead of
+/// the real inputs.
+#[must_use]
+pub fn sample_enabled() -> bool {
+    env::args().any(|x| x == "--sample")
+}
+
+/// The data folder parts read their input from: the samples with `--sample`, `--explain` or
+/// while checking a sample before the real input, the real inputs otherwise, also when
+/// describing a part with `--describe`.
+#[must_use]
+pub fn input_folder() -> &'static str {
+    if sample_enabled()
+        || CHECKING_SAMPLE.load(Ordering::Relaxed)
+        || explain::is_enabled() && !explain::is_describing()
+    {
+        "samples"
+    } else {
+        "inputs"
+    }
+}
+
+/// The input file given with `--input <path>`, e.g. a synthetic input from `cargo gen`.
+#[must_use]
+pub fn input_override() -> Option<String> {
+    arg_value("--input")
+}
+
+/// The sample a `--sample` run is on, one of the [`sample_paths`] of its part.
+static SAMPLE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// The samples to run a part on: every example of the part with `--sample`, see
+/// [`sample_paths`], and just its usual input as `None` otherwise.
+#[must_use]
+pub fn sample_runs(day: Day, part: Part) -> Vec<Option<PathBuf>> {
+    let samples = if sample_enabled() && input_override().is_none() {
+        sample_paths("samples", day, part)
+    } else {
+        Vec::new()
+    };
+    if samples.is_empty() {
+        return vec![None];
+    }
+    samples.into_iter().map(Some).collect()
+}
+
+/// Run the following parts on one of the [`sample_runs`], `None` for their usual input.
+pub fn select_sample(sample: Option<PathBuf>) {
+    *SAMPLE.lock().unwrap_or_else(|e| e.into_inner()) = sample;
+}
+
+fn selected_sample() -> Option<PathBuf> {
+    SAMPLE.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// The number of the selected sample, e.g. `2` for `07-1-2.txt`, `None` for the first one.
+fn sample_number(day: Day, part: Part) -> Option<String> {
+    let name = selected_sample()?
+        .file_name()?
+        .to_string_lossy()
+        .to_string();
+    let number = name
+        .strip_prefix(&format!("{day}-{part}-"))?
+        .strip_suffix(".txt")?;
+    Some(number.to_string())
+}
+
+/// Read the raw input of a part, from `--input <path>` or the selected sample when given.
+#[must_use]
+pub fn read_input(day: Day, part: Part) -> Vec<u8> {
+    if let Some(path) = input_override() {
+        return inputs::read(Path::new(&path))
+            .unwrap_or_else(|e| panic!("could not open input file `{path}`: {e}"));
+    }
+    if let Some(path) = selected_sample() {
+        let input = inputs::read(&path)
+            .unwrap_or_else(|e| panic!("could not open input file `{}`: {e}", path.display()));
+        manifest::check(&path, &input);
+        return input;
+    }
+
+    let folder = input_folder();
+    let path = read_only::quest_file(format!("{folder}/{day}-{part}.txt"));
+    let input = inputs::read(&path)
+        .unwrap_or_else(|e| panic!("could not open input file `{}`: {e}", path.display()));
+    manifest::check(&path, &input);
+    if folder == "inputs" {
+        SNAPSHOTS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(InputSnapshot::of(day, part, Some(&input)));
+    }
+    input
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Snapshots of the inputs the parts were run on, checked before their answers are submitted.
+static SNAPSHOTS: Mutex<Vec<InputSnapshot>> = Mutex::new(Vec::new());
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// Set on a solution binary that was started again because its input changed.
+static RERUN_ENV: &str = "EC_RERUN_ON_NEW_INPUT";
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+/// Run a part on its sample before the real input and print whether it returned the sample's
+/// answer, e.g. `Part 1 sample: ✔`. Parts without a sample or answer, runs on another input or
+/// the sample itself and JSON or `--explain` runs are not checked.
+pub fn check_sample(day: Day, part: Part, solve: impl FnOnce(&[u8]) -> Option<String>) {
+    if json_output::is_enabled()
+        || explain::is_enabled()
+        || input_override().is_some()
+        || sample_enabled()
+    {
+        return;
+    }
+
+    let sample_path = read_only::quest_file(format!("samples/{day}-{part}.txt"));
+    let sample = fs::read(&sample_path).unwrap_or_default();
+    let expected = fs::read_to_string(get_answer_path(day, part)).unwrap_or_default();
+    let expected = normalize(&expected);
+    if sample.trim_ascii().is_empty() || expected.is_empty() {
+        return;
+    }
+    manifest::check(&sample_path, &sample);
+
+    // a panic on the sample is reported with the sample as its input.
+    panic_report::set_part(part);
+    CHECKING_SAMPLE.store(true, Ordering::Relaxed);
+    let answer = solve(&sample);
+    CHECKING_SAMPLE.store(false, Ordering::Relaxed);
+
+    report_sample(&format!("Part {part} sample"), answer.as_deref(), expected);
+}
+
+/// Print whether `answer` is the `expected` answer of a sample, e.g. `Part 1 sample: ✔`.
+fn report_sample(label: &str, answer: Option<&str>, expected: &str) {
+    match answer.map(normalize) {
+        Some(answer) if answer == expected => println!("{label}: ✔"),
+        Some(answer) => println!(
+            "{label}: ✘ {}, expected {}",
+            compact(answer),
+            compact(expected)
+        ),
+        None => println!("{label}: ✘ no answer, expected {}", compact(expected)),
+    }
+}
+
+/// Input types a part can take: `&str`, or `&[u8]` to parse the raw input file byte by byte.
+pub trait PartInput<'a>: Copy {
+    fn from_bytes(bytes: &'a [u8]) -> Self;
+
+    /// Convert an input that was already checked to be valid UTF-8.
+    fn from_text(text: &'a str) -> Self;
+
+    fn from_raw(raw: RawInput<'a>) -> Self {
+        match raw {
+            RawInput::Text(text) => Self::from_text(text),
+            RawInput::Bytes(bytes) => Self::from_bytes(bytes),
+        }
+    }
+}
+
+/// An input file, as text if it is valid UTF-8.
+#[derive(Clone, Copy, Debug)]
+pub enum RawInput<'a> {
+    Text(&'a str),
+    Bytes(&'a [u8]),
+}
+
+impl<'a> PartInput<'a> for &'a [u8] {
+    fn from_bytes(bytes: &'a [u8]) -> Self {
+        bytes
+    }
+
+    fn from_text(text: &'a str) -> Self {
+        text.as_bytes()
+    }
+}
+
+impl<'a> PartInput<'a> for &'a str {
+    fn from_bytes(bytes: &'a [u8]) -> Self {
+        std::str::from_utf8(bytes)
+            .expect("input is not valid UTF-8, take `&[u8]` in the part to read it as bytes")
+    }
+
+    fn from_text(text: &'a str) -> Self {
+        text
+    }
+}
+
+/// With `--gen`, print an input from the registered generator using `--size` and `--seed`
+/// and return `true`.
+pub fn print_generated_input(day: Day) -> bool {
+    if !env::args().any(|x| x == "--gen") {
+        return false;
+    }
+
+    let Some(generator) = hooks::gen_input() else {
+        eprintln!(
+            "Day {day} has no input generator, register one with `solution!({}, gen_input = ...)`.",
+            day.into_inner()
+        );
+        process::exit(1);
+    };
+
+    let size = arg_value("--size")
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(1000);
+    let seed = arg_value("--seed")
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(0);
+    print!("{}", generator(size, seed));
+    true
+}
+
+pub fn run_part<I: Copy + Send, T: EcAnswer + Send>(
+    func: impl Fn(I) -> Option<T> + Sync,
+    input: I,
+    day: Day,
+    part: Part,
+) {
+    panic_report::set_part(part);
+    let sample_number = sample_number(day, part);
+    let part_str = match &sample_number {
+        Some(number) => format!("Part {part} (sample {number})"),
+        None => format!("Part {part}"),
+    };
+
+    hooks::run_before_part(&PartInfo {
+        day,
+        part,
+        answer: None,
+        duration: None,
+    });
+
+    if explain::is_enabled() {
+        explain::begin();
+    }
+    trace::begin(part);
+    mem_profile::begin();
+
+    let json = json_output::is_enabled();
+    let hook = |result: &Option<T>| {
+        // only record checkpoints and memory of the first run, not of benchmark samples.
+        mem_profile::end();
+        explain::end();
+        trace::end();
+        if !json {
+            print_result(result.as_ref().map(T::to_answer).as_deref(), &part_str, "");
+        }
+    };
+    let (result, duration, samples) = match timeout() {
+        Some(limit) => run_timed_with_limit(&func, input, hook, limit, day, part),
+        None => run_timed(func, input, hook),
+    };
+    trace::write();
+
+    let answer = result.as_ref().map(T::to_answer);
+    if !json {
+        let stats = format_duration(&duration, samples, mem_profile::peak());
+        print_result(answer.as_deref(), &part_str, &stats);
+    }
+
+    let failures = answer
+        .as_deref()
+        .map(|answer| checks::verify(part, answer))
+        .unwrap_or_default();
+    for failure in &failures {
+        eprintln!("⚠️  {part_str} failed a sanity check: {failure}.");
+    }
+
+    if json {
+        Record {
+            year: Year::current(),
+            day,
+            part,
+            answer: answer.clone(),
+            duration: Some(duration),
+            samples,
+            error: (!failures.is_empty())
+                .then(|| format!("failed a sanity check: {}", failures.join(", "))),
+            build: build_info(),
+        }
+        .print();
+    }
+
+    let real_input = input_override().is_none() && input_folder() == "inputs";
+    hooks::run_after_part(
+        &PartInfo {
+            day,
+            part,
+            answer: answer.as_deref(),
+            duration: Some(duration),
+        },
+        real_input,
+    );
+
+    if explain::is_describing() {
+        explain::report_description(day, part, answer.as_deref(), duration);
+        return;
+    } else if explain::is_enabled() {
+        explain::report(day, part, answer.as_deref());
+        return;
+    }
+
+    // answers for synthetic inputs are not worth submitting.
+    if input_override().is_some() {
+        return;
+    }
+
+    // answers for samples are checked against the expected ones instead, JSON records leave
+    // that to the reader.
+    if sample_enabled() {
+        if json {
+            return;
+        }
+        let answer_path = selected_sample()
+            .and_then(|sample| {
+                sample
+                    .file_name()
+                    .map(|name| Path::new("answers").join(name))
+            })
+            .map_or_else(|| get_answer_path(day, part), read_only::quest_file);
+        let expected = fs::read_to_string(answer_path).unwrap_or_default();
+        if !normalize(&expected).is_empty() {
+            let label = match &sample_number {
+                Some(number) => format!("Part {part} sample {number}"),
+                None => format!("Part {part} sample"),
+            };
+            report_sample(&label, answer.as_deref(), normalize(&expected));
+        }
+        return;
+    }
+
+    if !failures.is_empty() {
+        if submit_result_requested(part) {
+            eprintln!("Not submitted: {part_str} failed its sanity checks.");
+        }
+        return;
+    }
+
+    if let Some(answer) = answer {
+        submit_result(&answer, day, part);
     }
 }
 
@@ -45,11 +459,58 @@ fn run_timed<I: Copy, T>(
     (result, run.0, run.1)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Like [`run_timed`], but on a worker thread. The process exits if the first run takes longer
+/// than `limit`, instead of hanging forever on e.g. an infinite loop.
+fn run_timed_with_limit<I: Copy + Send, T: Send>(
+    func: impl Fn(I) -> T + Sync,
+    input: I,
+    hook: impl Fn(&T) + Sync,
+    limit: Duration,
+    day: Day,
+    part: Part,
+) -> (T, Duration, u128) {
+    let (sender, receiver) = mpsc::channel();
+
+    let (func, hook) = (&func, &hook);
+    thread::scope(|scope| {
+        let worker = scope.spawn(move || {
+            run_timed(func, input, |result| {
+                hook(result);
+                let _ = sender.send(());
+            })
+        });
+
+        if let Err(RecvTimeoutError::Timeout) = receiver.recv_timeout(limit) {
+            let error = format!("timed out after {}s", limit.as_secs());
+            if json_output::is_enabled() {
+                Record {
+                    year: Year::current(),
+                    day,
+                    part,
+                    answer: None,
+                    duration: None,
+                    samples: 0,
+                    error: Some(error),
+                    build: build_info(),
+                }
+                .print();
+            } else {
+                print!("\r");
+                println!("Part {part}: ✖ {error}");
+            }
+            let _ = stdout().flush();
+            process::exit(1);
+        }
+
+        worker.join().unwrap_or_else(|e| panic::resume_unwind(e))
+    })
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+    if !json_output::is_enabled() {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
@@ -77,20 +538,23 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+fn format_duration(duration: &Duration, samples: u128, peak: Option<usize>) -> String {
+    let peak = peak
+        .map(|bytes| format!(", peak {}", mem_profile::format_bytes(bytes)))
+        .unwrap_or_default();
     if samples == 1 {
-        format!(" ({duration:.1?})")
+        format!(" ({duration:.1?}{peak})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?} @ {samples} samples{peak})")
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: Option<&str>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -120,13 +584,43 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
+/// Refuse to submit an answer computed on an input that was downloaded again since, and run
+/// the binary again on the current input instead. Only one rerun is attempted.
+fn rerun_on_new_input(part: Part) -> ! {
+    if env::var_os(RERUN_ENV).is_some() {
+        eprintln!("Not submitted: the input of part {part} keeps changing.");
+        process::exit(1);
+    }
+    println!("⚠️  The input of part {part} changed since the answer was computed, running it again before submitting.");
+    println!();
+
+    let status = env::current_exe().and_then(|exe| {
+        process::Command::new(exe)
+            .args(env::args_os().skip(1))
+            .env(RERUN_ENV, "1")
+            .status()
+    });
+    match status {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Not submitted: failed to run the solution again: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Whether the binary was invoked with `--submit <part>`.
+fn submit_result_requested(part: Part) -> bool {
+    arg_value("--submit").and_then(|x| x.parse::<Part>().ok()) == Some(part)
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. ec-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+fn submit_result(
+    result: &str,
     day: Day,
-    part: u8,
+    part: Part,
 ) -> Option<Result<Output, ec_cli::EcCommandError>> {
     let args: Vec<String> = env::args().collect();
 
@@ -141,7 +635,7 @@ fn submit_result<T: Display>(
 
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
+    let Ok(part_submit) = args[part_index].parse::<Part>() else {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
         process::exit(1);
     };
@@ -150,11 +644,28 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if ec_cli::check().is_err() {
-        eprintln!("command \"ec-cli\" not found or not callable. Try installing it from https://github.com/finnhartshorn/ec-cli");
-        process::exit(1);
+    ec_cli::require();
+
+    let stale = SNAPSHOTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .any(|snapshot| snapshot.part == part && snapshot.is_stale());
+    if stale {
+        rerun_on_new_input(part);
     }
 
-    println!("Submitting result via ec-cli...");
-    Some(ec_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+    let output = ec_cli::submit(day, part, result);
+    match &output {
+        Err(e @ (ec_cli::EcCommandError::Throttled(_) | ec_cli::EcCommandError::ReadOnly)) => {
+            eprintln!("Not submitted: {e}")
+        }
+        #[cfg(feature = "http_client")]
+        Err(e @ (ec_cli::EcCommandError::Request(_) | ec_cli::EcCommandError::Unreachable(_))) => {
+            eprintln!("Submission failed: {e}")
+        }
+        _ => {}
+    }
+    Some(output)
 }
@@ -0,0 +1,61 @@
+/// The solution file `cargo scaffold` creates for a quest.
+///
+/// The built-in `src/template.txt` can be replaced by your own boilerplate, either per call
+/// with `--template <path>` or for the whole repository in `templates/day.rs.tpl`. Templates
+/// may use these variables:
+///
+/// | Variable | Example |
+/// | --- | --- |
+/// | `%DAY_NUMBER%` | `7` |
+/// | `%DAY_PADDED%` | `07` |
+/// | `%YEAR%` | `2025` |
+/// | `%DATE%` | `2025-11-07`, the day the quest was scaffolded |
+use std::{fs, path::Path};
+
+use crate::template::{Day, Year};
+
+const DEFAULT_TEMPLATE: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+/// Used instead of the built-in template when it exists.
+static USER_TEMPLATE: &str = "templates/day.rs.tpl";
+
+/// The template given with `--template`, or the repository's own, or the built-in one.
+pub fn load_template(path: Option<&str>) -> Result<String, String> {
+    let path = match path {
+        Some(path) => Path::new(path),
+        None if Path::new(USER_TEMPLATE).exists() => Path::new(USER_TEMPLATE),
+        None => return Ok(DEFAULT_TEMPLATE.to_string()),
+    };
+    fs::read_to_string(path).map_err(|e| format!("could not read \"{}\": {e}", path.display()))
+}
+
+/// Fill in the variables of a template.
+pub fn render(template: &str, day: Day, year: Year, date: &str) -> String {
+    template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY_PADDED%", &day.to_string())
+        .replace("%YEAR%", &year.to_string())
+        .replace("%DATE%", date)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, DEFAULT_TEMPLATE};
+    use crate::{day, template::Year};
+
+    #[test]
+    fn renders_variables() {
+        let template = "// %YEAR% quest %DAY_PADDED%, started %DATE%\nsolution!(%DAY_NUMBER%);";
+        assert_eq!(
+            render(template, day!(7), Year::new(2024).unwrap(), "2024-11-07"),
+            "// 2024 quest 07, started 2024-11-07\nsolution!(7);"
+        );
+
+        let rendered = render(DEFAULT_TEMPLATE, day!(7), Year::new(2024).unwrap(), "");
+        assert!(rendered.contains("everybody_codes::solution!(7);"));
+        assert!(!rendered.contains('%'));
+    }
+}
@@ -0,0 +1,160 @@
+/// Edits behind `cargo ec init`, which walks newcomers through configuring a fresh checkout.
+///
+/// Configuration files are edited line by line instead of being re-rendered, so comments and
+/// formatting in `.cargo/config.toml` and `Cargo.toml` survive the wizard.
+use toml::{Table, Value};
+
+use crate::template::Year;
+
+/// Optional features offered by the wizard, with what they do.
+pub const FEATURES: &[(&str, &str)] = &[
+    (
+        "http_client",
+        "download and submit with the built-in client instead of ec-cli",
+    ),
+    (
+        "desktop-notify",
+        "notify when a long run of `cargo solve`, `cargo all` or `cargo time` finishes",
+    ),
+    ("mem-profile", "show how much heap each part allocates"),
+    ("sysinfo", "store machine info with `cargo time --store`"),
+];
+
+/// Set the repository's event in `EC_YEAR` of a `.cargo/config.toml`.
+pub fn with_home_year(config: &str, year: Year) -> String {
+    let line = format!("EC_YEAR = \"{year}\"");
+    let mut lines: Vec<String> = config.lines().map(str::to_string).collect();
+
+    if let Some(existing) = lines.iter_mut().find(|l| is_key(l, "EC_YEAR")) {
+        *existing = line;
+    } else if let Some(env) = lines.iter().position(|l| l.trim() == "[env]") {
+        lines.insert(env + 1, line);
+    } else {
+        if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push("[env]".into());
+        lines.push(line);
+    }
+    lines.join("\n") + "\n"
+}
+
+/// The features enabled by default in a `Cargo.toml`.
+pub fn default_features(manifest: &str) -> Vec<String> {
+    let Ok(table) = manifest.parse::<Table>() else {
+        return vec![];
+    };
+    table
+        .get("features")
+        .and_then(|features| features.get("default"))
+        .and_then(Value::as_array)
+        .map(|default| {
+            default
+                .iter()
+                .filter_map(|feature| Some(feature.as_str()?.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Replace the `default` features of a `Cargo.toml`. The existing list has to fit on one line.
+pub fn with_default_features(manifest: &str, features: &[&str]) -> Result<String, String> {
+    let list = features
+        .iter()
+        .map(|feature| format!("\"{feature}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let line = format!("default = [{list}]");
+    let mut lines: Vec<String> = manifest.lines().map(str::to_string).collect();
+
+    let header = lines
+        .iter()
+        .position(|l| l.trim() == "[features]")
+        .ok_or("Cargo.toml has no [features] table.")?;
+    let end = lines[header + 1..]
+        .iter()
+        .position(|l| l.trim_start().starts_with('['))
+        .map_or(lines.len(), |i| header + 1 + i);
+
+    match (header + 1..end).find(|&i| is_key(&lines[i], "default")) {
+        Some(i) if !lines[i].contains(']') => {
+            return Err("the default features in Cargo.toml span several lines.".into());
+        }
+        Some(i) => lines[i] = line,
+        None => lines.insert(header + 1, line),
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// A session cookie as pasted from the browser, with or without its `everybody-codes=` name.
+pub fn clean_token(token: &str) -> Result<String, String> {
+    let token = token.trim();
+    let token = token.strip_prefix("everybody-codes=").unwrap_or(token);
+    if token.is_empty() {
+        Err("the token is empty.".into())
+    } else if token.chars().any(|c| c.is_whitespace() || c == ';') {
+        Err("paste only the value of the `everybody-codes` cookie.".into())
+    } else {
+        Ok(token.to_string())
+    }
+}
+
+fn is_key(line: &str, key: &str) -> bool {
+    line.split_once('=')
+        .is_some_and(|(name, _)| name.trim() == key)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{clean_token, default_features, with_default_features, with_home_year};
+    use crate::template::Year;
+
+    #[test]
+    fn sets_the_home_year() {
+        let year = Year::new(2024).unwrap();
+        let config = "[alias]\nec = \"run --\"\n\n[env]\nEC_YEAR = \"2025\"\n";
+        assert_eq!(
+            with_home_year(config, year),
+            "[alias]\nec = \"run --\"\n\n[env]\nEC_YEAR = \"2024\"\n"
+        );
+        assert_eq!(
+            with_home_year("[env]\nFOO = \"1\"\n", year),
+            "[env]\nEC_YEAR = \"2024\"\nFOO = \"1\"\n"
+        );
+        assert_eq!(
+            with_home_year("[alias]\nec = \"run --\"\n", year),
+            "[alias]\nec = \"run --\"\n\n[env]\nEC_YEAR = \"2024\"\n"
+        );
+    }
+
+    #[test]
+    fn sets_default_features() {
+        let manifest =
+            "[package]\nname = \"ec\"\n\n[features]\ntoday = [\"chrono\"]\n\n[dependencies]\n";
+        assert!(default_features(manifest).is_empty());
+
+        let enabled = with_default_features(manifest, &["http_client", "sysinfo"]).unwrap();
+        assert_eq!(
+            enabled,
+            "[package]\nname = \"ec\"\n\n[features]\ndefault = [\"http_client\", \"sysinfo\"]\ntoday = [\"chrono\"]\n\n[dependencies]\n"
+        );
+        assert_eq!(default_features(&enabled), ["http_client", "sysinfo"]);
+
+        let replaced = with_default_features(&enabled, &[]).unwrap();
+        assert!(replaced.contains("[features]\ndefault = []\ntoday"));
+        assert_eq!(replaced.matches("default").count(), 1);
+
+        assert!(with_default_features("[package]\n", &[]).is_err());
+        assert!(with_default_features("[features]\ndefault = [\n  \"a\",\n]\n", &[]).is_err());
+    }
+
+    #[test]
+    fn cleans_pasted_tokens() {
+        assert_eq!(clean_token("  abc123\n").unwrap(), "abc123");
+        assert_eq!(clean_token("everybody-codes=abc123").unwrap(), "abc123");
+        assert!(clean_token("").is_err());
+        assert!(clean_token("abc; other=1").is_err());
+    }
+}
@@ -0,0 +1,265 @@
+/// Module that shares anonymized benchmark timings with a community endpoint.
+/// Only per-part durations and a coarse machine class are sent, never inputs or answers.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    io::{self, Write},
+    process::{Command, Stdio},
+    str::FromStr,
+    thread,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::run_multi::child_commands::parse_duration;
+use crate::template::timings::Timings;
+use crate::template::{Day, Part, Year};
+
+static SHARE_URL_ENV: &str = "EC_SHARE_URL";
+
+#[derive(Debug)]
+pub enum Error {
+    MissingEndpoint,
+    CurlNotCallable,
+    BadExitStatus(String),
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingEndpoint => {
+                write!(
+                    f,
+                    "no community endpoint configured, set `{SHARE_URL_ENV}`."
+                )
+            }
+            Error::CurlNotCallable => write!(f, "curl could not be called."),
+            Error::BadExitStatus(stderr) => write!(f, "upload failed: {}", stderr.trim()),
+            Error::Parser(e) => write!(f, "unexpected response: {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Percentile standing of a single part, as reported by the community endpoint.
+#[derive(Clone, Debug)]
+pub struct Standing {
+    pub day: Day,
+    pub part: Part,
+    pub percentile: f64,
+}
+
+/// Upload `timings` and return the percentile standings of each shared part.
+pub fn share(timings: &Timings) -> Result<Vec<Standing>, Error> {
+    let url = env::var(SHARE_URL_ENV).map_err(|_| Error::MissingEndpoint)?;
+    let payload = build_payload(timings)
+        .stringify()
+        .map_err(|e| Error::Parser(e.to_string()))?;
+    let response = post_json(&url, payload)?;
+    parse_standings(&response)
+}
+
+/// Describe the machine only by architecture, OS and logical core count.
+fn machine_class() -> JsonValue {
+    let cores = thread::available_parallelism().map_or(1, std::num::NonZero::get);
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("arch".into(), JsonValue::String(env::consts::ARCH.into()));
+    map.insert("os".into(), JsonValue::String(env::consts::OS.into()));
+    #[allow(clippy::cast_precision_loss)]
+    map.insert("cores".into(), JsonValue::Number(cores as f64));
+    JsonValue::Object(map)
+}
+
+fn build_payload(timings: &Timings) -> JsonValue {
+    let parts = timings
+        .data
+        .iter()
+        .flat_map(|timing| {
+            [&timing.part_1, &timing.part_2, &timing.part_3]
+                .into_iter()
+                .zip(Part::ALL)
+                .filter_map(move |(duration, part)| {
+                    let nanos = parse_duration(duration.as_deref()?)?;
+
+                    let mut map: HashMap<String, JsonValue> = HashMap::new();
+                    map.insert("day".into(), JsonValue::String(timing.day.to_string()));
+                    map.insert(
+                        "part".into(),
+                        JsonValue::Number(f64::from(part.into_inner())),
+                    );
+                    map.insert("nanos".into(), JsonValue::Number(nanos));
+                    Some(JsonValue::Object(map))
+                })
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert(
+        "year".into(),
+        JsonValue::Number(f64::from(Year::current().into_inner())),
+    );
+    map.insert("machine".into(), machine_class());
+    map.insert("timings".into(), JsonValue::Array(parts));
+    JsonValue::Object(map)
+}
+
+fn post_json(url: &str, payload: String) -> Result<String, Error> {
+    let mut cmd = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "-X",
+            "POST",
+            "-H",
+            "Content-Type: application/json",
+            "--data-binary",
+            "@-",
+            url,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|_| Error::CurlNotCallable)?;
+
+    cmd.stdin
+        .take()
+        .ok_or(Error::CurlNotCallable)?
+        .write_all(payload.as_bytes())?;
+
+    let output = cmd.wait_with_output()?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(Error::BadExitStatus(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ))
+    }
+}
+
+fn parse_standings(response: &str) -> Result<Vec<Standing>, Error> {
+    let json = JsonValue::from_str(response).or(Err(Error::Parser("not valid JSON.".into())))?;
+
+    json.get::<HashMap<String, JsonValue>>()
+        .and_then(|map| map.get("standings"))
+        .and_then(|standings| standings.get::<Vec<JsonValue>>())
+        .ok_or_else(|| Error::Parser("expected key `standings` to be an array.".into()))?
+        .iter()
+        .map(|value| {
+            let map = value
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or_else(|| Error::Parser("expected standing to be an object.".into()))?;
+
+            let day = map
+                .get("day")
+                .and_then(|v| v.get::<String>())
+                .and_then(|day| Day::from_str(day).ok())
+                .ok_or_else(|| Error::Parser("expected standing.day to be a day.".into()))?;
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let part = map
+                .get("part")
+                .and_then(|v| v.get::<f64>())
+                .filter(|part| part.fract() == 0.0 && (1.0..=3.0).contains(*part))
+                .and_then(|part| Part::new(*part as u8))
+                .ok_or_else(|| Error::Parser("expected standing.part to be a part.".into()))?;
+
+            let percentile = map
+                .get("percentile")
+                .and_then(|v| v.get::<f64>())
+                .copied()
+                .ok_or_else(|| {
+                    Error::Parser("expected standing.percentile to be a number.".into())
+                })?;
+
+            Ok(Standing {
+                day,
+                part,
+                percentile,
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use tinyjson::JsonValue;
+
+    use super::{build_payload, parse_standings};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+        template::Part,
+    };
+
+    #[test]
+    fn builds_payload_without_missing_parts() {
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some("10ms".into()),
+                part_2: None,
+                part_3: Some("2µs".into()),
+                total_nanos: 10_002_000_f64,
+                build: None,
+                machine: None,
+            }],
+            history: vec![],
+        };
+
+        let payload = build_payload(&timings);
+        let parts = payload
+            .get::<HashMap<String, JsonValue>>()
+            .unwrap()
+            .get("timings")
+            .unwrap()
+            .get::<Vec<JsonValue>>()
+            .unwrap();
+
+        assert_eq!(parts.len(), 2);
+        let nanos = parts[1]
+            .get::<HashMap<String, JsonValue>>()
+            .unwrap()
+            .get("nanos")
+            .unwrap()
+            .get::<f64>()
+            .unwrap();
+        assert_eq!(*nanos, 2000_f64);
+    }
+
+    #[test]
+    fn parses_standings() {
+        let response = r#"{ "standings": [{ "day": "03", "part": 2, "percentile": 87.5 }] }"#;
+        let standings = parse_standings(response).unwrap();
+        assert_eq!(standings.len(), 1);
+        assert_eq!(standings[0].day, day!(3));
+        assert_eq!(standings[0].part, Part::Two);
+        assert_eq!(standings[0].percentile, 87.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_standings() {
+        parse_standings(r#"{ "standings": [{ "day": "03" }] }"#).unwrap();
+    }
+
+    #[test]
+    fn rejects_unknown_parts() {
+        let response = r#"{ "standings": [{ "day": "03", "part": 4, "percentile": 87.5 }] }"#;
+        assert!(parse_standings(response).is_err());
+    }
+}
@@ -0,0 +1,301 @@
+/// Copies of the inputs accepted answers were computed on, so `cargo verify --as-of <date>` can
+/// check old solutions against exactly those inputs after inputs were downloaded again or the
+/// data layout changed.
+///
+/// Inputs are stored by the SHA-256 hash of their contents in `data/.snapshots/objects`, so a
+/// quest solved in several parts on the same input keeps one copy. `data/.snapshots/index.json`
+/// lists the accepted answers in the order they were recorded, keyed by event slug instead of
+/// data folder:
+///
+/// ```json
+/// {"data":[{"event":"2025","day":"07","part":1,"answer":"42","input":"9f86d0...","date":"2025-11-13"}]}
+/// ```
+///
+/// With the `encrypt` feature and an answer key, answers are stored encrypted like the ones in
+/// `data/real_answers`. With an input key, so are the inputs, see [`inputs`].
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+#[cfg(feature = "encrypt")]
+use crate::template::answers::answer_cipher;
+use crate::template::{inputs, manifest, read_only, today, Date, Day, Event, Part};
+
+fn get_snapshots_dir() -> PathBuf {
+    Path::new("data").join(".snapshots")
+}
+
+fn get_index_path() -> PathBuf {
+    get_snapshots_dir().join("index.json")
+}
+
+/// Path of a stored input by its hash.
+pub fn object_path(hash: &str) -> PathBuf {
+    objects_dir().join(hash)
+}
+
+/// The directory holding the inputs by hash, see [`object_path`].
+pub fn objects_dir() -> PathBuf {
+    get_snapshots_dir().join("objects")
+}
+
+/// Marks an answer stored encrypted.
+static ENCRYPTED_PREFIX: &str = "enc:";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    /// Slug of the event, e.g. `2025` or `story1`.
+    pub event: String,
+    pub day: Day,
+    pub part: Part,
+    /// The accepted answer as stored, see [`Snapshot::answer`].
+    pub answer: String,
+    /// Hash of the input, see [`object_path`].
+    pub input: String,
+    /// Date the answer was accepted, e.g. `2025-11-13`.
+    pub date: String,
+}
+
+impl Snapshot {
+    /// The accepted answer, `None` if it is encrypted and can't be decrypted.
+    pub fn answer(&self) -> Option<String> {
+        match self.answer.strip_prefix(ENCRYPTED_PREFIX) {
+            Some(encrypted) => decrypt(encrypted),
+            None => Some(self.answer.clone()),
+        }
+    }
+}
+
+/// The answer as it is stored in the index.
+#[cfg(feature = "encrypt")]
+fn seal(answer: &str) -> String {
+    match answer_cipher() {
+        Ok(cipher) => format!("{ENCRYPTED_PREFIX}{}", cipher.encrypt(answer.as_bytes())),
+        Err(_) => answer.to_string(),
+    }
+}
+
+#[cfg(not(feature = "encrypt"))]
+fn seal(answer: &str) -> String {
+    answer.to_string()
+}
+
+#[cfg(feature = "encrypt")]
+fn decrypt(encrypted: &str) -> Option<String> {
+    let bytes = answer_cipher()
+        .and_then(|cipher| cipher.decrypt(encrypted))
+        .ok()?;
+    Some(String::from_utf8_lossy(&bytes).to_string())
+}
+
+#[cfg(not(feature = "encrypt"))]
+fn decrypt(_encrypted: &str) -> Option<String> {
+    None
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SnapshotIndex {
+    pub data: Vec<Snapshot>,
+}
+
+impl SnapshotIndex {
+    /// Rehydrate the index from disk. If not present, returns an empty index.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(get_index_path())
+            .ok()
+            .and_then(|s| SnapshotIndex::try_from(s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        fs::create_dir_all(get_snapshots_dir())?;
+        let mut file = fs::File::create(get_index_path())?;
+        json.format_to(&mut file)
+    }
+
+    /// The answer of a part that was accepted last on or before `date`.
+    pub fn as_of(&self, event: &str, day: Day, part: Part, date: Date) -> Option<&Snapshot> {
+        self.data
+            .iter()
+            .rev()
+            .filter(|s| s.event == event && s.day == day && s.part == part)
+            .find(|s| {
+                s.date
+                    .parse::<Date>()
+                    .is_ok_and(|accepted| accepted <= date)
+            })
+    }
+}
+
+/// Store the current input of a part with its accepted answer.
+pub fn record(day: Day, part: Part, answer: &str) -> Result<(), io::Error> {
+    let input = inputs::read(&read_only::quest_file(format!("inputs/{day}-{part}.txt")))?;
+    let hash = manifest::hash(&input);
+
+    let object = object_path(&hash);
+    if !inputs::exists(&object) {
+        fs::create_dir_all(objects_dir())?;
+        inputs::write(&object, &input)?;
+    }
+
+    let mut index = SnapshotIndex::read_from_file();
+    index.data.push(Snapshot {
+        event: Event::current().slug(),
+        day,
+        part,
+        answer: seal(answer),
+        input: hash,
+        date: today(),
+    });
+    index.store_file()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&SnapshotIndex> for JsonValue {
+    fn from(value: &SnapshotIndex) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for SnapshotIndex {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        Ok(SnapshotIndex {
+            data: json
+                .get("data")
+                .and_then(|v| v.get::<Vec<JsonValue>>())
+                .ok_or("expected `json.data` to be an array.")?
+                .iter()
+                .map(Snapshot::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Snapshot> for JsonValue {
+    fn from(value: &Snapshot) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("event".into(), JsonValue::String(value.event.clone()));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part".into(),
+            JsonValue::Number(f64::from(value.part.into_inner())),
+        );
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert("input".into(), JsonValue::String(value.input.clone()));
+        map.insert("date".into(), JsonValue::String(value.date.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Snapshot {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected snapshot to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected snapshot.{key} to be a string."))
+        };
+
+        let day = string("day")?
+            .parse()
+            .map_err(|_| "Expected snapshot.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .and_then(|v| Part::new(*v as u8))
+            .ok_or("Expected snapshot.part to be a part number.")?;
+
+        Ok(Snapshot {
+            event: string("event")?,
+            day,
+            part,
+            answer: string("answer")?,
+            input: string("input")?,
+            date: string("date")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Snapshot, SnapshotIndex};
+    use crate::{day, template::Part};
+    use tinyjson::JsonValue;
+
+    fn snapshot(answer: &str, input: &str, date: &str) -> Snapshot {
+        Snapshot {
+            event: "2025".into(),
+            day: day!(7),
+            part: Part::One,
+            answer: answer.into(),
+            input: input.into(),
+            date: date.into(),
+        }
+    }
+
+    #[test]
+    fn finds_the_answer_accepted_by_a_date() {
+        let index = SnapshotIndex {
+            data: vec![
+                snapshot("42", "aaa", "2025-11-13"),
+                snapshot("43", "bbb", "2025-11-20"),
+            ],
+        };
+        let as_of = |date: &str| {
+            let date = date.parse().unwrap();
+            index
+                .as_of("2025", day!(7), Part::One, date)
+                .and_then(|s| s.answer())
+        };
+
+        assert_eq!(as_of("2025-11-12"), None);
+        assert_eq!(as_of("2025-11-13").as_deref(), Some("42"));
+        assert_eq!(as_of("2025-11-19").as_deref(), Some("42"));
+        assert_eq!(as_of("2026-01-01").as_deref(), Some("43"));
+        assert!(index
+            .as_of("story1", day!(7), Part::One, "2026-01-01".parse().unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let index = SnapshotIndex {
+            data: vec![snapshot("\"quoted\"", "abc", "2025-11-13")],
+        };
+        let json = JsonValue::from(&index).stringify().unwrap();
+        assert_eq!(SnapshotIndex::try_from(json), Ok(index));
+    }
+}
@@ -0,0 +1,317 @@
+/// Solutions as plain types implementing [`Solution`], an alternative to the `solution!` macro.
+///
+/// A trait-based solution can live anywhere, e.g. in a library module, so integration tests can
+/// call its parts directly and a [`Registry`] can run many quests from a single binary:
+///
+/// ```
+/// use everybody_codes::{day, template::{Day, Part, Solution}};
+///
+/// struct Quest07;
+///
+/// impl Solution for Quest07 {
+///     const DAY: Day = day!(7);
+///     type Input<'a> = Vec<&'a str>;
+///     type Answer = usize;
+///
+///     fn parse(input: &str) -> Self::Input<'_> {
+///         input.lines().collect()
+///     }
+///
+///     fn part_one(lines: Self::Input<'_>) -> Option<usize> {
+///         Some(lines.len())
+///     }
+/// }
+///
+/// assert_eq!(Quest07::part_one(Quest07::parse("a\nb")), Some(2));
+/// assert_eq!(Quest07::part(Part::Two, Quest07::parse("a\nb")), None);
+/// ```
+///
+/// A binary for the quest only needs a `main`:
+///
+/// ```ignore
+/// fn main() {
+///     everybody_codes::template::solution::run::<Quest07>(env!("CARGO_CRATE_NAME"));
+/// }
+/// ```
+use std::collections::BTreeMap;
+
+use crate::template::{
+    complexity, json_output, panic_report,
+    runner::{
+        check_sample, print_build_info, print_generated_input, read_input, run_part, sample_runs,
+        select_sample, should_run_part,
+    },
+    trace::runner_span,
+    Day, EcAnswer, Part, ANSI_BOLD, ANSI_RESET,
+};
+
+pub trait Solution {
+    /// The day of the quest.
+    const DAY: Day;
+
+    /// The parts the solution runs, e.g. only the first one while the others are locked.
+    const PARTS: &'static [Part] = &Part::ALL;
+
+    /// Whether `--chrome-trace` records [`Solution::parse`] as a span of its own. The
+    /// `solution!` macro turns it off, as its parts parse their input themselves.
+    #[doc(hidden)]
+    const TRACE_PARSE: bool = true;
+
+    /// The input of a part after parsing. It may borrow from the raw input.
+    type Input<'a>;
+
+    /// The answer type shared by all parts, e.g. [`String`] or
+    /// [`Answer`](crate::template::Answer) if the parts return different kinds of values.
+    type Answer: EcAnswer + Send;
+
+    /// Parse the raw input of a part. Each part has its own input file.
+    fn parse(input: &str) -> Self::Input<'_>;
+
+    /// Parse an input file that is not valid UTF-8, which panics unless overridden.
+    fn parse_bytes(input: &[u8]) -> Self::Input<'_> {
+        Self::parse(std::str::from_utf8(input).expect("input is not valid UTF-8"))
+    }
+
+    fn part_one(_input: Self::Input<'_>) -> Option<Self::Answer> {
+        None
+    }
+
+    fn part_two(_input: Self::Input<'_>) -> Option<Self::Answer> {
+        None
+    }
+
+    fn part_three(_input: Self::Input<'_>) -> Option<Self::Answer> {
+        None
+    }
+
+    /// Run a part by its number.
+    fn part(part: Part, input: Self::Input<'_>) -> Option<Self::Answer> {
+        match part {
+            Part::One => Self::part_one(input),
+            Part::Two => Self::part_two(input),
+            Part::Three => Self::part_three(input),
+        }
+    }
+}
+
+/// Parse the raw input and solve a part, with the answer as it would be submitted.
+pub fn answer<S: Solution>(part: Part, input: &str) -> Option<String> {
+    S::part(part, S::parse(input)).map(|answer| answer.to_answer())
+}
+
+/// The `main` of a solution binary: run, time and possibly submit every part of `S` like
+/// `cargo solve` expects. `crate_name` is `env!("CARGO_CRATE_NAME")` of the binary.
+pub fn run<S: Solution>(crate_name: &'static str) {
+    panic_report::install(S::DAY, crate_name);
+    if print_generated_input(S::DAY) || complexity::run_scale_bench::<S>() {
+        return;
+    }
+    print_build_info();
+    run_parts::<S>();
+}
+
+fn run_parts<S: Solution>() {
+    for &part in S::PARTS {
+        if !should_run_part(part) {
+            continue;
+        }
+        check_sample(S::DAY, part, |input| match std::str::from_utf8(input) {
+            Ok(text) => answer::<S>(part, text),
+            Err(_) => S::part(part, S::parse_bytes(input)).map(|answer| answer.to_answer()),
+        });
+
+        // `--sample` runs every example of the part, other runs just its input.
+        for sample in sample_runs(S::DAY, part) {
+            select_sample(sample);
+            let input = runner_span("read input", || read_input(S::DAY, part));
+            // check the encoding once up front, so it doesn't count towards the part's time.
+            match std::str::from_utf8(&input) {
+                Ok(text) => run_part(
+                    |text| S::part(part, traced_parse::<S, str>(text, S::parse)),
+                    text,
+                    S::DAY,
+                    part,
+                ),
+                Err(_) => run_part(
+                    |bytes| S::part(part, traced_parse::<S, [u8]>(bytes, S::parse_bytes)),
+                    &input[..],
+                    S::DAY,
+                    part,
+                ),
+            }
+        }
+        select_sample(None);
+    }
+}
+
+fn traced_parse<'a, S: Solution, R: ?Sized>(
+    raw: &'a R,
+    parse: fn(&'a R) -> S::Input<'a>,
+) -> S::Input<'a> {
+    if S::TRACE_PARSE {
+        runner_span("parse", || parse(raw))
+    } else {
+        parse(raw)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy)]
+struct Entry {
+    parts: &'static [Part],
+    run: fn(),
+    answer: fn(Part, &str) -> Option<String>,
+}
+
+/// Trait-based solutions collected by day, to run or query them without one binary per quest.
+///
+/// ```ignore
+/// fn main() {
+///     Registry::new()
+///         .register::<Quest01>()
+///         .register::<Quest02>()
+///         .run();
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Registry {
+    entries: BTreeMap<Day, Entry>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a solution. A later solution for the same day replaces the earlier one.
+    #[must_use]
+    pub fn register<S: Solution>(mut self) -> Self {
+        self.entries.insert(
+            S::DAY,
+            Entry {
+                parts: S::PARTS,
+                run: run_parts::<S>,
+                answer: answer::<S>,
+            },
+        );
+        self
+    }
+
+    /// The registered days in order.
+    pub fn days(&self) -> impl Iterator<Item = Day> + '_ {
+        self.entries.keys().copied()
+    }
+
+    /// The parts the solution of `day` runs, empty if there is none.
+    pub fn parts(&self, day: Day) -> &'static [Part] {
+        self.entries.get(&day).map_or(&[], |entry| entry.parts)
+    }
+
+    /// Solve a part on the given input, `None` if the day is not registered or the part has
+    /// no answer.
+    pub fn answer(&self, day: Day, part: Part, input: &str) -> Option<String> {
+        (self.entries.get(&day)?.answer)(part, input)
+    }
+
+    /// Run a registered quest on its input like its own binary does, `false` if it isn't
+    /// registered. `crate_name` is `env!("CARGO_CRATE_NAME")` of the binary calling it.
+    pub fn run_day(&self, day: Day, crate_name: &'static str) -> bool {
+        let Some(entry) = self.entries.get(&day) else {
+            return false;
+        };
+        panic_report::install(day, crate_name);
+        print_build_info();
+        (entry.run)();
+        true
+    }
+
+    /// Run every registered quest on its input, like `cargo all` does with quest binaries.
+    pub fn run(&self) {
+        print_build_info();
+        for (i, (day, entry)) in self.entries.iter().enumerate() {
+            if json_output::is_enabled() {
+                (entry.run)();
+                continue;
+            }
+            if i > 0 {
+                println!();
+            }
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
+            (entry.run)();
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer, Registry, Solution};
+    use crate::{
+        day,
+        template::{Day, Part},
+    };
+
+    struct Counting;
+
+    impl Solution for Counting {
+        const DAY: Day = day!(7);
+        type Input<'a> = Vec<&'a str>;
+        type Answer = usize;
+
+        fn parse(input: &str) -> Self::Input<'_> {
+            input.lines().collect()
+        }
+
+        fn part_one(lines: Self::Input<'_>) -> Option<usize> {
+            Some(lines.len())
+        }
+
+        fn part_three(lines: Self::Input<'_>) -> Option<usize> {
+            lines.iter().map(|line| line.len()).max()
+        }
+    }
+
+    struct FirstPartOnly;
+
+    impl Solution for FirstPartOnly {
+        const DAY: Day = day!(2);
+        const PARTS: &'static [Part] = &[Part::One];
+        type Input<'a> = &'a str;
+        type Answer = String;
+
+        fn parse(input: &str) -> Self::Input<'_> {
+            input
+        }
+
+        fn part_one(input: Self::Input<'_>) -> Option<String> {
+            Some(input.to_uppercase())
+        }
+    }
+
+    #[test]
+    fn answers_parts() {
+        assert_eq!(answer::<Counting>(Part::One, "a\nbcd"), Some("2".into()));
+        assert_eq!(answer::<Counting>(Part::Two, "a\nbcd"), None);
+        assert_eq!(answer::<Counting>(Part::Three, "a\nbcd"), Some("3".into()));
+    }
+
+    #[test]
+    fn registers_solutions() {
+        let registry = Registry::new()
+            .register::<Counting>()
+            .register::<FirstPartOnly>();
+
+        assert_eq!(registry.days().collect::<Vec<_>>(), vec![day!(2), day!(7)]);
+        assert_eq!(registry.parts(day!(2)), &[Part::One]);
+        assert!(registry.parts(day!(3)).is_empty());
+        assert_eq!(
+            registry.answer(day!(2), Part::One, "abc"),
+            Some("ABC".into())
+        );
+        assert_eq!(registry.answer(day!(7), Part::One, "x"), Some("1".into()));
+        assert_eq!(registry.answer(day!(3), Part::One, "x"), None);
+    }
+}
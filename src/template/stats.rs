@@ -0,0 +1,170 @@
+/// Progress of the scaffolded quests for `cargo stats`, read from the solution sources, the
+/// data folder and runs of the implemented parts on their samples.
+use crate::template::{all_parts, answers::normalize, Part};
+
+/// The names of the part functions of a solution, by part.
+const PART_FNS: [&str; 3] = ["part_one", "part_two", "part_three"];
+
+/// Whether each part of a solution has an implementation, by part index.
+///
+/// A part counts as implemented when its function returns anything other than a bare `None`, as
+/// scaffolded, or when `solution!` maps it to another function, e.g. `part_two = solve::<2>`.
+pub fn implemented_parts(source: &str) -> [bool; 3] {
+    let mut implemented = [false; 3];
+    for part in all_parts() {
+        implemented[part.index()] = is_implemented(source, part);
+    }
+    implemented
+}
+
+fn is_implemented(source: &str, part: Part) -> bool {
+    let name = PART_FNS[part.index()];
+    match fn_body(source, name) {
+        Some(body) => strip_comments(body).trim() != "None",
+        None => source.contains(&format!("{name} =")),
+    }
+}
+
+/// The text between the braces of the first function called `name`.
+fn fn_body<'a>(source: &'a str, name: &str) -> Option<&'a str> {
+    let signature = source.find(&format!("fn {name}("))?;
+    let open = signature + source[signature..].find('{')?;
+
+    let mut depth = 0;
+    for (i, c) in source[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&source[open + 1..open + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn strip_comments(body: &str) -> String {
+    body.lines()
+        .map(|line| line.split("//").next().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The sample test status of a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SampleStatus {
+    /// No example, or only the empty file created by `cargo scaffold`.
+    Missing,
+    /// Some examples have no expected answer yet.
+    Unanswered,
+    /// How many of the examples the part returned the expected answer for.
+    Checked { passed: usize, total: usize },
+}
+
+impl SampleStatus {
+    /// The status of the examples of a part, given their contents, their expected answers and
+    /// the answers the part returned for them, all in the same order. Examples after a panic
+    /// have no answer.
+    pub fn new(samples: &[String], expected: &[String], answers: &[Option<String>]) -> Self {
+        let checks: Vec<(&str, Option<&str>)> = samples
+            .iter()
+            .enumerate()
+            .filter(|(_, sample)| !sample.trim().is_empty())
+            .map(|(i, _)| {
+                let expected = expected.get(i).map_or("", |e| normalize(e));
+                let answer = answers.get(i).and_then(Option::as_deref).map(normalize);
+                (expected, answer)
+            })
+            .collect();
+
+        if checks.is_empty() {
+            SampleStatus::Missing
+        } else if checks.iter().any(|(expected, _)| expected.is_empty()) {
+            SampleStatus::Unanswered
+        } else {
+            SampleStatus::Checked {
+                passed: checks
+                    .iter()
+                    .filter(|(expected, answer)| Some(*expected) == *answer)
+                    .count(),
+                total: checks.len(),
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{implemented_parts, SampleStatus};
+
+    #[test]
+    fn finds_implemented_parts() {
+        let source = r"
+everybody_codes::solution!(7);
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let total = input.lines().count();
+    if total > 0 { Some(total as u64) } else { None }
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    // TODO: same as part one, but backwards
+    None
+}
+";
+        assert_eq!(implemented_parts(source), [true, false, false]);
+
+        let generic =
+            "everybody_codes::solution!(7, part_one = solve::<1>, part_three = solve::<3>);
+fn solve<const N: usize>(input: &str) -> Option<u64> { None }";
+        assert_eq!(implemented_parts(generic), [true, false, true]);
+    }
+
+    #[test]
+    fn checks_samples() {
+        let strings = |values: &[&str]| values.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let answers = |values: &[Option<&str>]| {
+            values
+                .iter()
+                .map(|v| v.map(ToString::to_string))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            SampleStatus::new(&strings(&[""]), &strings(&[""]), &answers(&[Some("0")])),
+            SampleStatus::Missing
+        );
+        assert_eq!(
+            SampleStatus::new(&strings(&["1 2", "3 4"]), &strings(&["3"]), &[]),
+            SampleStatus::Unanswered
+        );
+        assert_eq!(
+            SampleStatus::new(
+                &strings(&["1 2", "3 4"]),
+                &strings(&["3", "7\n"]),
+                &answers(&[Some("3"), Some("7")])
+            ),
+            SampleStatus::Checked {
+                passed: 2,
+                total: 2
+            }
+        );
+        // the second sample returned a wrong answer, the third none as the part panicked.
+        assert_eq!(
+            SampleStatus::new(
+                &strings(&["1 2", "3 4", "5 6"]),
+                &strings(&["3", "7", "11"]),
+                &answers(&[Some("3"), Some("8")])
+            ),
+            SampleStatus::Checked {
+                passed: 1,
+                total: 3
+            }
+        );
+    }
+}
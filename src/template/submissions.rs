@@ -0,0 +1,221 @@
+/// Log of answer submissions across all quests, used to respect the site's per-account
+/// cooldown between submissions.
+///
+/// The log is stored in the global cache directory, so checkouts sharing an account share the
+/// cooldown. Without a home directory, it falls back to `data/submissions.json`.
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{config::config, contest::now, dirs::cache_dir, Day, Part};
+
+fn get_submissions_path() -> PathBuf {
+    cache_dir().map_or_else(
+        || PathBuf::from("data").join("submissions.json"),
+        |dir| dir.join("submissions.json"),
+    )
+}
+
+/// Default minimum number of seconds between two submissions, overridable with
+/// `submit.cooldown_secs` in `ec.toml`.
+pub const DEFAULT_COOLDOWN_SECS: u64 = 30;
+
+/// Only the most recent submissions are kept.
+const MAX_ENTRIES: usize = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: Part,
+    /// Unix timestamp of the submission.
+    pub at: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SubmissionLog {
+    pub data: Vec<Submission>,
+}
+
+/// The configured cooldown between submissions.
+pub fn cooldown_secs() -> u64 {
+    config()
+        .get("submit.cooldown_secs")
+        .and_then(toml::Value::as_integer)
+        .and_then(|secs| u64::try_from(secs).ok())
+        .unwrap_or(DEFAULT_COOLDOWN_SECS)
+}
+
+impl SubmissionLog {
+    /// Rehydrate the log from disk. If not present, returns an empty log.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(get_submissions_path())
+            .ok()
+            .and_then(|s| SubmissionLog::try_from(s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let path = get_submissions_path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn last(&self) -> Option<&Submission> {
+        self.data.iter().max_by_key(|s| s.at)
+    }
+
+    /// Seconds left until the next submission is allowed at time `now`, if any.
+    pub fn remaining_cooldown(&self, now: u64, cooldown_secs: u64) -> Option<u64> {
+        let ready_at = self.last()?.at + cooldown_secs;
+        (ready_at > now).then(|| ready_at - now)
+    }
+
+    pub fn record(&mut self, day: Day, part: Part, at: u64) {
+        self.data.push(Submission { day, part, at });
+        if self.data.len() > MAX_ENTRIES {
+            self.data.drain(..self.data.len() - MAX_ENTRIES);
+        }
+    }
+}
+
+/// Check the global cooldown, returning the seconds to wait if a submission now would be
+/// too soon after the previous one.
+pub fn check_cooldown() -> Option<u64> {
+    SubmissionLog::read_from_file().remaining_cooldown(now(), cooldown_secs())
+}
+
+/// Record a submission that reached the site.
+pub fn record_submission(day: Day, part: Part) {
+    let mut log = SubmissionLog::read_from_file();
+    log.record(day, part, now());
+    if let Err(e) = log.store_file() {
+        eprintln!("Failed to record submission time: {e}");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&SubmissionLog> for JsonValue {
+    fn from(value: &SubmissionLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for SubmissionLog {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        Ok(SubmissionLog {
+            data: json
+                .get("data")
+                .and_then(|v| v.get::<Vec<JsonValue>>())
+                .ok_or("expected `json.data` to be an array.")?
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part".into(),
+            JsonValue::Number(f64::from(value.part.into_inner())),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("at".into(), JsonValue::Number(value.at as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .and_then(|v| Part::new(*v as u8))
+            .ok_or("Expected submission.part to be a part number.")?;
+
+        let at = json
+            .get("at")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected submission.at to be a number.")?;
+
+        Ok(Submission { day, part, at })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{SubmissionLog, MAX_ENTRIES};
+    use crate::{day, template::Part};
+
+    fn get_mock_log() -> SubmissionLog {
+        let mut log = SubmissionLog::default();
+        log.record(day!(3), Part::One, 1_000);
+        log.record(day!(7), Part::Two, 1_100);
+        log
+    }
+
+    #[test]
+    fn computes_cooldown_across_days() {
+        let log = get_mock_log();
+        assert_eq!(log.remaining_cooldown(1_110, 30), Some(20));
+        assert_eq!(log.remaining_cooldown(1_130, 30), None);
+        assert_eq!(SubmissionLog::default().remaining_cooldown(0, 30), None);
+    }
+
+    #[test]
+    fn keeps_recent_entries() {
+        let mut log = SubmissionLog::default();
+        for at in 0..(MAX_ENTRIES as u64 + 5) {
+            log.record(day!(1), Part::One, at);
+        }
+        assert_eq!(log.data.len(), MAX_ENTRIES);
+        assert_eq!(log.data[0].at, 5);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let log = get_mock_log();
+        let json = tinyjson::JsonValue::from(&log).stringify().unwrap();
+        assert_eq!(SubmissionLog::try_from(json).unwrap(), log);
+    }
+}
@@ -0,0 +1,239 @@
+/// The machine timings were measured on, stored next to them in `timings.json`.
+///
+/// With the `sysinfo` feature, `cargo time --store` records the CPU, core count, frequency
+/// governor, memory, OS and rustc version with every timing it stores, and points out what
+/// changed when a quest was last benchmarked on a different machine:
+///
+/// ```text
+/// Day 03, 05 were last stored on a different machine:
+///   cpu: Intel(R) Core(TM) i7-8700 → AMD Ryzen 7 5800X
+///   rustc: rustc 1.80.0 → rustc 1.82.0
+/// ```
+///
+/// Without the feature, nothing is captured and stored machine info is kept as is.
+use std::collections::HashMap;
+
+use tinyjson::JsonValue;
+
+use crate::template::mem_profile::format_bytes;
+
+/// A field that differs between two machines, with both of its values.
+pub type Change = (&'static str, String, String);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MachineInfo {
+    pub cpu: Option<String>,
+    pub cores: Option<usize>,
+    /// The CPU frequency governor on Linux, e.g. `powersave`.
+    pub governor: Option<String>,
+    pub memory_bytes: Option<u64>,
+    /// The OS and its version, e.g. `linux 6.8.0`.
+    pub os: Option<String>,
+    pub rustc: Option<String>,
+}
+
+impl MachineInfo {
+    /// The fields that differ from `other`, as `(field, ours, theirs)`.
+    pub fn diff(&self, other: &MachineInfo) -> Vec<Change> {
+        let unknown = || "unknown".to_string();
+        let memory = |info: &MachineInfo| {
+            info.memory_bytes
+                .map(|bytes| format_bytes(usize::try_from(bytes).unwrap_or(usize::MAX)))
+        };
+
+        [
+            ("cpu", self.cpu.clone(), other.cpu.clone()),
+            (
+                "cores",
+                self.cores.map(|cores| cores.to_string()),
+                other.cores.map(|cores| cores.to_string()),
+            ),
+            ("governor", self.governor.clone(), other.governor.clone()),
+            ("memory", memory(self), memory(other)),
+            ("os", self.os.clone(), other.os.clone()),
+            ("rustc", self.rustc.clone(), other.rustc.clone()),
+        ]
+        .into_iter()
+        .filter(|(_, ours, theirs)| ours != theirs)
+        .map(|(field, ours, theirs)| {
+            (
+                field,
+                ours.unwrap_or_else(unknown),
+                theirs.unwrap_or_else(unknown),
+            )
+        })
+        .collect()
+    }
+}
+
+/// Information about this machine, or `None` without the `sysinfo` feature.
+pub fn capture() -> Option<MachineInfo> {
+    #[cfg(feature = "sysinfo")]
+    return Some(probe::machine());
+
+    #[cfg(not(feature = "sysinfo"))]
+    None
+}
+
+#[cfg(feature = "sysinfo")]
+mod probe {
+    use std::{env, fs, process::Command, thread};
+
+    use super::MachineInfo;
+
+    pub fn machine() -> MachineInfo {
+        MachineInfo {
+            cpu: cpu(),
+            cores: thread::available_parallelism().ok().map(Into::into),
+            governor: read("/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor"),
+            memory_bytes: memory_bytes(),
+            os: Some(match os_version() {
+                Some(version) => format!("{} {version}", env::consts::OS),
+                None => env::consts::OS.to_string(),
+            }),
+            rustc: output("rustc", &["--version"]),
+        }
+    }
+
+    fn cpu() -> Option<String> {
+        fs::read_to_string("/proc/cpuinfo")
+            .ok()
+            .and_then(|info| {
+                info.lines()
+                    .find_map(|line| line.strip_prefix("model name"))
+                    .and_then(|line| line.split_once(':'))
+                    .map(|(_, model)| model.trim().to_string())
+            })
+            .or_else(|| output("sysctl", &["-n", "machdep.cpu.brand_string"]))
+    }
+
+    fn memory_bytes() -> Option<u64> {
+        fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|info| {
+                let kib = info
+                    .lines()
+                    .find_map(|line| line.strip_prefix("MemTotal:"))?;
+                let kib: u64 = kib.trim().strip_suffix("kB")?.trim().parse().ok()?;
+                Some(kib * 1024)
+            })
+            .or_else(|| output("sysctl", &["-n", "hw.memsize"])?.parse().ok())
+    }
+
+    fn os_version() -> Option<String> {
+        read("/proc/sys/kernel/osrelease").or_else(|| output("sw_vers", &["-productVersion"]))
+    }
+
+    fn read(path: &str) -> Option<String> {
+        let contents = fs::read_to_string(path).ok()?;
+        Some(contents.trim().to_string()).filter(|s| !s.is_empty())
+    }
+
+    fn output(program: &str, args: &[&str]) -> Option<String> {
+        let output = Command::new(program).args(args).output().ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !stdout.is_empty()).then_some(stdout)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MachineInfo> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &MachineInfo) -> Self {
+        let string = |s: &Option<String>| s.clone().map_or(JsonValue::Null, JsonValue::String);
+        let number = |n: Option<f64>| n.map_or(JsonValue::Null, JsonValue::Number);
+
+        let map: HashMap<String, JsonValue> = HashMap::from([
+            ("cpu".into(), string(&value.cpu)),
+            (
+                "cores".into(),
+                number(value.cores.map(|cores| cores as f64)),
+            ),
+            ("governor".into(), string(&value.governor)),
+            (
+                "memory_bytes".into(),
+                number(value.memory_bytes.map(|bytes| bytes as f64)),
+            ),
+            ("os".into(), string(&value.os)),
+            ("rustc".into(), string(&value.rustc)),
+        ]);
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MachineInfo {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing.machine to be a JSON object.")?;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+        let number = |key: &str| json.get(key).and_then(|v| v.get::<f64>()).copied();
+
+        Ok(MachineInfo {
+            cpu: string("cpu"),
+            cores: number("cores").map(|cores| cores as usize),
+            governor: string("governor"),
+            memory_bytes: number("memory_bytes").map(|bytes| bytes as u64),
+            os: string("os"),
+            rustc: string("rustc"),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::MachineInfo;
+
+    fn machine() -> MachineInfo {
+        MachineInfo {
+            cpu: Some("AMD Ryzen 7 5800X".into()),
+            cores: Some(16),
+            governor: Some("performance".into()),
+            memory_bytes: Some(32 << 30),
+            os: Some("linux 6.8.0".into()),
+            rustc: Some("rustc 1.82.0".into()),
+        }
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let json = JsonValue::from(&machine()).stringify().unwrap();
+        let parsed = MachineInfo::try_from(&json.parse::<JsonValue>().unwrap()).unwrap();
+        assert_eq!(parsed, machine());
+
+        let empty = MachineInfo::try_from(&"{}".parse::<JsonValue>().unwrap()).unwrap();
+        assert_eq!(empty, MachineInfo::default());
+    }
+
+    #[test]
+    fn diffs_machines() {
+        let other = MachineInfo {
+            cpu: Some("Intel(R) Core(TM) i7-8700".into()),
+            governor: None,
+            memory_bytes: Some(16 << 30),
+            ..machine()
+        };
+        assert_eq!(
+            other.diff(&machine()),
+            vec![
+                (
+                    "cpu",
+                    "Intel(R) Core(TM) i7-8700".to_string(),
+                    "AMD Ryzen 7 5800X".to_string()
+                ),
+                ("governor", "unknown".to_string(), "performance".to_string()),
+                ("memory", "16.0 GiB".to_string(), "32.0 GiB".to_string()),
+            ]
+        );
+        assert!(machine().diff(&machine()).is_empty());
+    }
+}
@@ -1,9 +1,16 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, process::Command, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    data_dir,
+    run_multi::child_commands::parse_duration,
+    sysinfo::{Change, MachineInfo},
+    Day, Part,
+};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+fn timings_path() -> PathBuf {
+    data_dir().join("timings.json")
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -13,32 +20,96 @@ pub struct Timing {
     pub part_2: Option<String>,
     pub part_3: Option<String>,
     pub total_nanos: f64,
+    /// Build profile the timing was measured with, e.g. `release (opt-level=3, ...)`.
+    pub build: Option<String>,
+    /// The machine the timing was measured on, with the `sysinfo` feature.
+    pub machine: Option<MachineInfo>,
+}
+
+impl Timing {
+    /// The timing of `part` as printed by the runner, e.g. `74.13ms`.
+    pub fn part(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_1.as_deref(),
+            Part::Two => self.part_2.as_deref(),
+            Part::Three => self.part_3.as_deref(),
+        }
+    }
+}
+
+/// A part that got slower than its stored timing allows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression {
+    pub day: Day,
+    pub part: Part,
+    pub stored: String,
+    pub current: String,
+    /// How much slower the part got, in percent.
+    pub slowdown: f64,
+}
+
+/// The timings of one run of `cargo time`, kept to see how solutions got faster or slower.
+#[derive(Clone, Debug)]
+pub struct TimingRun {
+    /// Unix timestamp of the run.
+    pub at: u64,
+    /// The checked out commit, `None` outside a git repository.
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes to tracked files.
+    pub dirty: bool,
+    pub data: Vec<Timing>,
+}
+
+impl TimingRun {
+    /// A run of `data` measured now on the checked out commit.
+    pub fn new(data: Vec<Timing>, at: u64) -> Self {
+        let commit = git(&["rev-parse", "HEAD"])
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+        let dirty = commit.is_some()
+            && git(&["diff", "--quiet", "HEAD"]).is_some_and(|output| !output.status.success());
+
+        TimingRun {
+            at,
+            commit,
+            dirty,
+            data,
+        }
+    }
+}
+
+fn git(args: &[&str]) -> Option<std::process::Output> {
+    Command::new("git").args(args).output().ok()
 }
 
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
+    /// The latest stored timing of each day, as shown in the README.
     pub data: Vec<Timing>,
+    /// Every run of `cargo time`, oldest first.
+    pub history: Vec<TimingRun>,
 }
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(timings_path())?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        fs::read_to_string(timings_path())
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Merge two sets of timings, overwriting `self` with `other` if present. The history of
+    /// `other` is appended to the one of `self`.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -52,8 +123,9 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|a| a.day);
+        let history = [self.history.as_slice(), new.history.as_slice()].concat();
+        Timings { data, history }
     }
 
     /// Sum up total duration of timings as millis.
@@ -61,6 +133,73 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// The stored `days` that were measured on another machine than `machine`, grouped by that
+    /// machine, with the fields that changed since as `(field, stored, current)`.
+    pub fn machine_changes(
+        &self,
+        machine: &MachineInfo,
+        days: &[Day],
+    ) -> Vec<(Vec<Day>, Vec<Change>)> {
+        let mut changes: Vec<(&MachineInfo, Vec<Day>)> = vec![];
+        for timing in self.data.iter().filter(|t| days.contains(&t.day)) {
+            let Some(stored) = timing.machine.as_ref().filter(|stored| *stored != machine) else {
+                continue;
+            };
+            match changes.iter_mut().find(|(other, _)| *other == stored) {
+                Some((_, days)) => days.push(timing.day),
+                None => changes.push((stored, vec![timing.day])),
+            }
+        }
+
+        changes
+            .into_iter()
+            .map(|(stored, days)| (days, stored.diff(machine)))
+            .collect()
+    }
+
+    /// The timing of `day` from the most recent run that included it, falling back to the stored
+    /// one for stores that predate the history.
+    pub fn latest(&self, day: Day) -> Option<&Timing> {
+        self.history
+            .iter()
+            .rev()
+            .flat_map(|run| &run.data)
+            .chain(&self.data)
+            .find(|timing| timing.day == day)
+    }
+
+    /// The parts of `current` that are more than `threshold` percent slower than their timing
+    /// stored in `self`. Parts without a stored timing are skipped.
+    pub fn regressions(&self, current: &Timings, threshold: f64) -> Vec<Regression> {
+        let mut regressions = vec![];
+        for timing in &current.data {
+            let Some(stored) = self.data.iter().find(|t| t.day == timing.day) else {
+                continue;
+            };
+            for part in Part::ALL {
+                let (Some(before), Some(after)) = (stored.part(part), timing.part(part)) else {
+                    continue;
+                };
+                let (Some(before_nanos), Some(after_nanos)) =
+                    (parse_duration(before), parse_duration(after))
+                else {
+                    continue;
+                };
+                let slowdown = (after_nanos / before_nanos - 1.0) * 100.0;
+                if before_nanos > 0.0 && slowdown > threshold {
+                    regressions.push(Regression {
+                        day: timing.day,
+                        part,
+                        stored: before.to_string(),
+                        current: after.to_string(),
+                        slowdown,
+                    });
+                }
+            }
+        }
+        regressions
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -78,6 +217,10 @@ impl From<Timings> for JsonValue {
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
 
         JsonValue::Object(map)
     }
@@ -89,19 +232,97 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        let history = match json.get("history") {
+            Some(history) => history
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?
+                .iter()
+                .map(TimingRun::try_from)
+                .collect::<Result<_, _>>()?,
+            None => vec![], // Optional for backwards compatibility
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            history,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&TimingRun> for JsonValue {
+    fn from(value: &TimingRun) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("at".into(), JsonValue::Number(value.at as f64));
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("dirty".into(), JsonValue::Boolean(value.dirty));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for TimingRun {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing run to be a JSON object.")?;
+
+        let at = json
+            .get("at")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected run.at to be a number.")?;
+
+        let commit = json.get("commit").and_then(|v| v.get::<String>()).cloned();
+
+        let dirty = json
+            .get("dirty")
+            .and_then(|v| v.get::<bool>())
+            .copied()
+            .unwrap_or_default();
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected run.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(TimingRun {
+            at,
+            commit,
+            dirty,
+            data,
         })
     }
 }
@@ -114,6 +335,20 @@ impl From<&Timing> for JsonValue {
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+        map.insert(
+            "build".into(),
+            match value.build.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "machine".into(),
+            value
+                .machine
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
 
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
@@ -181,12 +416,22 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let build = json.get("build").and_then(|v| v.get::<String>()).cloned(); // Optional for backwards compatibility
+
+        let machine = json
+            .get("machine")
+            .filter(|v| !v.is_null())
+            .map(MachineInfo::try_from)
+            .transpose()?; // Optional for backwards compatibility
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             part_3: part_3.cloned(),
             total_nanos,
+            build,
+            machine,
         })
     }
 }
@@ -208,6 +453,8 @@ mod tests {
                     part_2: Some("20ms".into()),
                     part_3: Some("30ms".into()),
                     total_nanos: 6e+10,
+                    build: None,
+                    machine: None,
                 },
                 Timing {
                     day: day!(2),
@@ -215,6 +462,8 @@ mod tests {
                     part_2: Some("40ms".into()),
                     part_3: Some("50ms".into()),
                     total_nanos: 12e+10,
+                    build: None,
+                    machine: None,
                 },
                 Timing {
                     day: day!(4),
@@ -222,8 +471,11 @@ mod tests {
                     part_2: None,
                     part_3: None,
                     total_nanos: 4e+10,
+                    build: None,
+                    machine: None,
                 },
             ],
+            history: vec![],
         }
     }
 
@@ -242,11 +494,43 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_build_info() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "build": "release" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].build, Some("release".to_string()));
+        }
+
+        #[test]
+        fn handles_machine_info() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "machine": { "cpu": "AMD Ryzen 7 5800X", "cores": 16 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let machine = timings.data[0].machine.clone().unwrap();
+            assert_eq!(machine.cpu, Some("AMD Ryzen 7 5800X".to_string()));
+            assert_eq!(machine.cores, Some(16));
+            assert_eq!(machine.rustc, None);
+        }
+
+        #[test]
+        fn handles_history() {
+            let json = r#"{ "data": [], "history": [{ "at": 1700000000, "commit": "2d78b0b", "dirty": true, "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000 }] }, { "at": 1700000100, "commit": null, "data": [] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.history.len(), 2);
+            let run = &timings.history[0];
+            assert_eq!(run.at, 1_700_000_000);
+            assert_eq!(run.commit, Some("2d78b0b".to_string()));
+            assert!(run.dirty);
+            assert_eq!(run.data[0].day, day!(1));
+            assert_eq!(timings.history[1].commit, None);
+            assert!(!timings.history[1].dirty);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 0);
+            assert_eq!(timings.history.len(), 0);
         }
 
         #[test]
@@ -302,10 +586,13 @@ mod tests {
                     part_2: Some("2ms".into()),
                     part_3: Some("3ms".into()),
                     total_nanos: 6_000_000_000_f64,
+                    build: None,
+                    machine: None,
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -317,10 +604,13 @@ mod tests {
                     part_2: None,
                     part_3: None,
                     total_nanos: 1_000_000_000_f64,
+                    build: None,
+                    machine: None,
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -332,17 +622,146 @@ mod tests {
                     part_2: None,
                     part_3: None,
                     total_nanos: 0.0,
+                    build: None,
+                    machine: None,
                 }],
+                history: vec![],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod machine_changes {
+        use crate::{day, template::sysinfo::MachineInfo};
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn groups_days_by_machine() {
+            let laptop = MachineInfo {
+                cpu: Some("laptop".into()),
+                ..MachineInfo::default()
+            };
+            let desktop = MachineInfo {
+                cpu: Some("desktop".into()),
+                ..MachineInfo::default()
+            };
+
+            let mut timings = get_mock_timings();
+            timings.data[0].machine = Some(laptop.clone());
+            timings.data[1].machine = Some(laptop.clone());
+            timings.data[2].machine = Some(desktop.clone());
+
+            let changes = timings.machine_changes(&desktop, &[day!(1), day!(2), day!(4)]);
+            assert_eq!(
+                changes,
+                vec![(
+                    vec![day!(1), day!(2)],
+                    vec![("cpu", "laptop".to_string(), "desktop".to_string())]
+                )]
+            );
+            assert_eq!(timings.machine_changes(&laptop, &[day!(1)]), vec![]);
+        }
+    }
+
+    mod regressions {
+        use crate::{
+            day,
+            template::{
+                timings::{Timing, Timings},
+                Part,
+            },
+        };
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn finds_slower_parts() {
+            let stored = get_mock_timings();
+            let current = Timings {
+                data: vec![
+                    Timing {
+                        day: day!(1),
+                        part_1: Some("10.9ms".into()),
+                        part_2: Some("25ms".into()),
+                        part_3: Some("15ms".into()),
+                        total_nanos: 5.09e7,
+                        build: None,
+                        machine: None,
+                    },
+                    Timing {
+                        day: day!(3),
+                        part_1: Some("1s".into()),
+                        part_2: None,
+                        part_3: None,
+                        total_nanos: 1e9,
+                        build: None,
+                        machine: None,
+                    },
+                ],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            let regressions = stored.regressions(&current, 10.0);
+            assert_eq!(regressions.len(), 1);
+            assert_eq!(regressions[0].day, day!(1));
+            assert_eq!(regressions[0].part, Part::Two);
+            assert_eq!(regressions[0].stored, "20ms");
+            assert_eq!(regressions[0].current, "25ms");
+            assert!((regressions[0].slowdown - 25.0).abs() < 1e-9);
+
+            assert_eq!(stored.regressions(&current, 5.0).len(), 2);
+            assert!(stored.regressions(&current, 50.0).is_empty());
+        }
+    }
+
+    mod latest {
+        use crate::{
+            day,
+            template::timings::{TimingRun, Timings},
+        };
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn prefers_the_most_recent_run() {
+            let stored = get_mock_timings();
+            let mut rerun = stored.data[1].clone();
+            rerun.part_1 = Some("25ms".into());
+            let timings = Timings {
+                history: vec![
+                    TimingRun {
+                        at: 1,
+                        commit: None,
+                        dirty: false,
+                        data: stored.data.clone(),
+                    },
+                    TimingRun {
+                        at: 2,
+                        commit: None,
+                        dirty: false,
+                        data: vec![rerun],
+                    },
+                ],
+                ..stored
+            };
+
+            let latest = |day| timings.latest(day).and_then(|t| t.part_1.clone());
+            assert_eq!(latest(day!(2)), Some("25ms".into()));
+            assert_eq!(latest(day!(1)), Some("10ms".into()));
+            assert_eq!(latest(day!(3)), None);
+            assert_eq!(
+                get_mock_timings().latest(day!(4)).map(|t| t.day),
+                Some(day!(4))
+            );
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{Timing, TimingRun, Timings},
         };
 
         use super::get_mock_timings;
@@ -357,7 +776,10 @@ mod tests {
                     part_2: None,
                     part_3: None,
                     total_nanos: 0_f64,
+                    build: None,
+                    machine: None,
                 }],
+                history: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -378,7 +800,10 @@ mod tests {
                     part_2: None,
                     part_3: None,
                     total_nanos: 0_f64,
+                    build: None,
+                    machine: None,
                 }],
+                history: vec![],
             };
             let merged = timings.merge(&other);
 
@@ -397,6 +822,24 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
 
+        #[test]
+        fn appends_history() {
+            let run = |at| TimingRun {
+                at,
+                commit: None,
+                dirty: false,
+                data: vec![],
+            };
+            let mut timings = get_mock_timings();
+            timings.history.push(run(1));
+            let mut other = Timings::default();
+            other.history.push(run(2));
+
+            let merged = timings.merge(&other);
+            let at: Vec<u64> = merged.history.iter().map(|run| run.at).collect();
+            assert_eq!(at, vec![1, 2]);
+        }
+
         #[test]
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
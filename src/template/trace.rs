@@ -0,0 +1,241 @@
+/// Timelines of a run in the Chrome tracing format, for multi-phase or parallel solutions where
+/// a flamegraph hides when things happen.
+///
+/// Running a quest with `--chrome-trace <path>` records a span for reading each input, for the
+/// first run of each part and for parsing the input of trait-based solutions. Solutions mark
+/// their own phases with [`span`], from any thread:
+///
+/// ```ignore
+/// let graph = span("build graph", || build_graph(input));
+/// let paths = span("search", || search(&graph));
+/// ```
+///
+/// The trace is written after every part and can be opened in <https://ui.perfetto.dev> or
+/// `chrome://tracing`. Benchmark samples are not recorded, and without `--chrome-trace`, spans
+/// only run their closure.
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    env, fs,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::Part;
+
+static PATH: OnceLock<Option<String>> = OnceLock::new();
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+static PAUSED: AtomicBool = AtomicBool::new(false);
+static PART_STARTED: Mutex<Option<(Part, Instant)>> = Mutex::new(None);
+static SPANS: Mutex<Vec<Span>> = Mutex::new(vec![]);
+/// The names of the threads spans were recorded on, by thread number.
+static THREADS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+thread_local! {
+    static THREAD: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// A finished span, with its start relative to the first recorded span.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span {
+    pub name: String,
+    /// `runner` for spans of the template, `solution` for the ones of [`span`].
+    pub category: &'static str,
+    pub start: Duration,
+    pub duration: Duration,
+    pub thread: usize,
+}
+
+fn path() -> Option<&'static str> {
+    PATH.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        args.iter()
+            .position(|x| x == "--chrome-trace")
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+    })
+    .as_deref()
+}
+
+/// Whether the binary was invoked with `--chrome-trace <path>`, so spans are recorded.
+pub fn is_enabled() -> bool {
+    path().is_some()
+}
+
+/// Run `f` as a phase of the solution, recorded as a span named `name`.
+pub fn span<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    traced(name, "solution", f)
+}
+
+/// Like [`span`], for the phases of the template itself.
+pub(crate) fn runner_span<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    traced(name, "runner", f)
+}
+
+fn traced<T>(name: &str, category: &'static str, f: impl FnOnce() -> T) -> T {
+    if !is_enabled() || PAUSED.load(Ordering::Relaxed) {
+        return f();
+    }
+    let started = Instant::now();
+    let result = f();
+    record(name, category, started);
+    result
+}
+
+fn record(name: &str, category: &'static str, started: Instant) {
+    let epoch = *EPOCH.get_or_init(|| started);
+    SPANS.lock().unwrap().push(Span {
+        name: name.to_string(),
+        category,
+        start: started.saturating_duration_since(epoch),
+        duration: started.elapsed(),
+        thread: current_thread(),
+    });
+}
+
+/// The number of the current thread, in the order threads first recorded a span.
+fn current_thread() -> usize {
+    THREAD.with(|thread| {
+        if let Some(number) = thread.get() {
+            return number;
+        }
+        let mut threads = THREADS.lock().unwrap();
+        let number = threads.len();
+        let name = thread::current().name().map(ToString::to_string);
+        threads.push(name.unwrap_or_else(|| format!("thread {number}")));
+        thread.set(Some(number));
+        number
+    })
+}
+
+pub(crate) fn begin(part: Part) {
+    if is_enabled() {
+        EPOCH.get_or_init(Instant::now);
+        *PART_STARTED.lock().unwrap() = Some((part, Instant::now()));
+        PAUSED.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Record the span of the part and pause, e.g. before the part is benchmarked.
+pub(crate) fn end() {
+    if let Some((part, started)) = PART_STARTED.lock().unwrap().take() {
+        record(&format!("Part {part}"), "runner", started);
+    }
+    PAUSED.store(true, Ordering::Relaxed);
+}
+
+/// Write the spans recorded so far and resume recording, e.g. for reading the next input.
+pub(crate) fn write() {
+    let Some(path) = path() else {
+        return;
+    };
+    let json = to_json(&SPANS.lock().unwrap(), &THREADS.lock().unwrap());
+    if let Err(e) = json
+        .stringify()
+        .map_err(|e| e.to_string())
+        .and_then(|json| fs::write(path, json).map_err(|e| e.to_string()))
+    {
+        eprintln!("Failed to write the trace to \"{path}\": {e}");
+    }
+    PAUSED.store(false, Ordering::Relaxed);
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The spans as a trace in the Chrome tracing format, with timestamps in microseconds.
+pub fn to_json(spans: &[Span], threads: &[String]) -> JsonValue {
+    let event = |fields: Vec<(&str, JsonValue)>| {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("pid".into(), JsonValue::Number(1.0));
+        for (key, value) in fields {
+            map.insert(key.into(), value);
+        }
+        JsonValue::Object(map)
+    };
+    #[allow(clippy::cast_precision_loss)]
+    let thread_id = |thread: usize| JsonValue::Number(thread as f64);
+    let micros = |duration: Duration| JsonValue::Number(duration.as_secs_f64() * 1e6);
+
+    let names = threads.iter().enumerate().map(|(thread, name)| {
+        event(vec![
+            ("name", JsonValue::String("thread_name".into())),
+            ("ph", JsonValue::String("M".into())),
+            ("tid", thread_id(thread)),
+            (
+                "args",
+                JsonValue::Object(HashMap::from([(
+                    "name".to_string(),
+                    JsonValue::String(name.clone()),
+                )])),
+            ),
+        ])
+    });
+    let spans = spans.iter().map(|span| {
+        event(vec![
+            ("name", JsonValue::String(span.name.clone())),
+            ("cat", JsonValue::String(span.category.into())),
+            ("ph", JsonValue::String("X".into())),
+            ("ts", micros(span.start)),
+            ("dur", micros(span.duration)),
+            ("tid", thread_id(span.thread)),
+        ])
+    });
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert(
+        "traceEvents".into(),
+        JsonValue::Array(names.chain(spans).collect()),
+    );
+    map.insert("displayTimeUnit".into(), JsonValue::String("ms".into()));
+    JsonValue::Object(map)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use tinyjson::JsonValue;
+
+    use super::{to_json, Span};
+
+    #[test]
+    fn writes_chrome_trace_events() {
+        let spans = [Span {
+            name: "search".into(),
+            category: "solution",
+            start: Duration::from_micros(1500),
+            duration: Duration::from_millis(2),
+            thread: 1,
+        }];
+        let json = to_json(&spans, &["main".into(), "worker".into()]);
+
+        let events: &Vec<JsonValue> = json["traceEvents"].get().unwrap();
+        assert_eq!(events.len(), 3);
+
+        let field = |event: &JsonValue, key: &str| event[key].clone();
+        let names: Vec<JsonValue> = events[..2].iter().map(|e| field(e, "args")).collect();
+        assert_eq!(
+            names[1],
+            JsonValue::Object(HashMap::from([(
+                "name".to_string(),
+                JsonValue::String("worker".into())
+            )]))
+        );
+
+        let span = &events[2];
+        assert_eq!(field(span, "ph"), JsonValue::String("X".into()));
+        assert_eq!(field(span, "name"), JsonValue::String("search".into()));
+        assert_eq!(field(span, "cat"), JsonValue::String("solution".into()));
+        assert_eq!(field(span, "ts"), JsonValue::Number(1500.0));
+        assert_eq!(field(span, "dur"), JsonValue::Number(2000.0));
+        assert_eq!(field(span, "tid"), JsonValue::Number(1.0));
+    }
+}
@@ -0,0 +1,59 @@
+//! Truncated inputs behind `cargo head-input`, which cuts an input to its first lines in
+//! growing steps to find the scale at which a part starts to misbehave.
+//!
+//! With a step of 50 lines, an input of 170 lines yields variants of 50, 100 and 150 lines.
+//! The full input is the real one, so it is not repeated.
+
+/// The step used without `--lines`: a tenth of the input, so about ten variants are written.
+pub fn default_step(total_lines: usize) -> usize {
+    total_lines.div_ceil(10).max(1)
+}
+
+/// Line counts of the variants, every multiple of `step` below the line count of the input.
+pub fn head_sizes(total_lines: usize, step: usize) -> Vec<usize> {
+    (1..)
+        .map(|i| i * step.max(1))
+        .take_while(|&lines| lines < total_lines)
+        .collect()
+}
+
+/// The first `lines` lines of an input, keeping their line endings.
+pub fn head(input: &str, lines: usize) -> &str {
+    let Some(last) = lines.checked_sub(1) else {
+        return "";
+    };
+    let end = input
+        .match_indices('\n')
+        .nth(last)
+        .map_or(input.len(), |(i, _)| i + 1);
+    &input[..end]
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{default_step, head, head_sizes};
+
+    #[test]
+    fn steps_through_the_input() {
+        assert_eq!(head_sizes(170, 50), vec![50, 100, 150]);
+        assert_eq!(head_sizes(150, 50), vec![50, 100]);
+        assert_eq!(head_sizes(30, 50), Vec::<usize>::new());
+        assert_eq!(head_sizes(3, 0), vec![1, 2]);
+
+        assert_eq!(default_step(95), 10);
+        assert_eq!(default_step(3), 1);
+        assert_eq!(default_step(0), 1);
+    }
+
+    #[test]
+    fn keeps_the_first_lines() {
+        let input = "a\nb\r\nc\n";
+        assert_eq!(head(input, 0), "");
+        assert_eq!(head(input, 1), "a\n");
+        assert_eq!(head(input, 2), "a\nb\r\n");
+        assert_eq!(head(input, 5), input);
+        assert_eq!(head("a\nb", 2), "a\nb");
+    }
+}
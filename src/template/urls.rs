@@ -0,0 +1,110 @@
+/// Links to the pages of the Everybody Codes site and the endpoints of its API, built from a
+/// typed event, quest and part so they never have to be typed by hand. Events are given as an
+/// [`Event`] or a [`Year`](crate::template::Year).
+///
+/// Quests are numbered without padding in URLs, e.g. `https://everybody.codes/event/2025/quests/7`.
+use crate::template::{Day, Event, Part};
+
+pub const SITE_URL: &str = "https://everybody.codes";
+const API_URL: &str = "https://everybody.codes/api";
+const CDN_URL: &str = "https://everybody-codes.b-cdn.net/assets";
+
+/// The overview of an event.
+pub fn event(event: impl Into<Event>) -> String {
+    match event.into() {
+        Event::MainEvent { year } => format!("{SITE_URL}/event/{year}"),
+        Event::Story { id } => format!("{SITE_URL}/story/{id}"),
+    }
+}
+
+/// The page of a quest, showing the description of all unlocked parts.
+pub fn quest(event: impl Into<Event>, day: Day) -> String {
+    format!("{}/quests/{}", self::event(event), day.into_inner())
+}
+
+/// The standings of an event.
+pub fn leaderboard(event: impl Into<Event>) -> String {
+    format!("{}/leaderboard", self::event(event))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The signed in user, including the seed of their inputs.
+pub fn api_me() -> String {
+    format!("{API_URL}/user/me")
+}
+
+/// The state of a quest: the keys of unlocked parts and the accepted answers.
+pub fn api_quest(event: impl Into<Event>, day: Day) -> String {
+    format!(
+        "{API_URL}/event/{}/quest/{}",
+        event.into().id(),
+        day.into_inner()
+    )
+}
+
+/// The endpoint answers of a part are posted to.
+pub fn api_answer(event: impl Into<Event>, day: Day, part: Part) -> String {
+    format!("{}/part/{part}/answer", api_quest(event, day))
+}
+
+/// The encrypted inputs generated for `seed`.
+pub fn cdn_inputs(event: impl Into<Event>, day: Day, seed: &str) -> String {
+    let event = event.into().id();
+    format!("{CDN_URL}/{event}/{}/input/{seed}.json", day.into_inner())
+}
+
+/// The encrypted descriptions of all parts.
+pub fn cdn_descriptions(event: impl Into<Event>, day: Day) -> String {
+    let event = event.into().id();
+    format!("{CDN_URL}/{event}/{}/description.json", day.into_inner())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{api_answer, cdn_inputs, leaderboard, quest};
+    use crate::{
+        day,
+        template::{Event, Part},
+        year,
+    };
+
+    #[test]
+    fn builds_urls() {
+        assert_eq!(
+            quest(year!(2025), day!(7)),
+            "https://everybody.codes/event/2025/quests/7"
+        );
+        assert_eq!(
+            leaderboard(year!(2024)),
+            "https://everybody.codes/event/2024/leaderboard"
+        );
+        assert_eq!(
+            api_answer(year!(2025), day!(12), Part::Three),
+            "https://everybody.codes/api/event/2025/quest/12/part/3/answer"
+        );
+        assert_eq!(
+            cdn_inputs(year!(2025), day!(1), "42"),
+            "https://everybody-codes.b-cdn.net/assets/2025/1/input/42.json"
+        );
+    }
+
+    #[test]
+    fn builds_story_urls() {
+        let story = Event::Story { id: 1 };
+        assert_eq!(
+            quest(story, day!(2)),
+            "https://everybody.codes/story/1/quests/2"
+        );
+        assert_eq!(
+            api_answer(story, day!(3), Part::One),
+            "https://everybody.codes/api/event/1/quest/3/part/1/answer"
+        );
+        assert_eq!(
+            cdn_inputs(story, day!(1), "42"),
+            "https://everybody-codes.b-cdn.net/assets/1/1/input/42.json"
+        );
+    }
+}
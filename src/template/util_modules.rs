@@ -0,0 +1,236 @@
+//! Utility modules that `cargo scaffold-utils` generates into `src/utils/`, grouped in presets.
+//!
+//! Each module is a copy of the module with the same name in the template's own `src/utils/`,
+//! tests included. Generating one declares it in `src/utils/mod.rs` and re-exports its main
+//! items from `src/utils/prelude.rs`, so a solution gets all of them with a single `use`.
+
+#[derive(Debug)]
+pub struct UtilModule {
+    pub name: &'static str,
+    /// Other modules this one or its tests use, generated along with it.
+    pub requires: &'static [&'static str],
+    /// Items re-exported by the prelude.
+    pub exports: &'static [&'static str],
+    pub source: &'static str,
+}
+
+macro_rules! util_template {
+    ($name:literal) => {
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/utils/",
+            $name,
+            ".rs"
+        ))
+    };
+}
+
+/// All modules, each after the ones it requires.
+pub static MODULES: &[UtilModule] = &[
+    UtilModule {
+        name: "point",
+        requires: &[],
+        exports: &["Point"],
+        source: util_template!("point"),
+    },
+    UtilModule {
+        name: "grid",
+        requires: &["point"],
+        exports: &["Grid"],
+        source: util_template!("grid"),
+    },
+    UtilModule {
+        name: "parse",
+        requires: &[],
+        exports: &["blocks", "ints", "lines_of", "numbers"],
+        source: util_template!("parse"),
+    },
+    UtilModule {
+        name: "numbers",
+        requires: &[],
+        exports: &["crt", "gcd", "lcm", "lcm_all"],
+        source: util_template!("numbers"),
+    },
+    UtilModule {
+        name: "hashing",
+        requires: &[],
+        exports: &["FastMap", "FastSet"],
+        source: util_template!("hashing"),
+    },
+    UtilModule {
+        name: "heap",
+        requires: &[],
+        exports: &["IndexedHeap"],
+        source: util_template!("heap"),
+    },
+    UtilModule {
+        name: "pathfinding",
+        requires: &["hashing", "heap", "grid"],
+        exports: &["a_star", "bfs", "bfs_path", "dijkstra", "dijkstra_path"],
+        source: util_template!("pathfinding"),
+    },
+];
+
+pub static PRESETS: &[(&str, &[&str])] = &[
+    (
+        "standard",
+        &["grid", "point", "parse", "numbers", "pathfinding"],
+    ),
+    ("minimal", &["grid", "parse"]),
+];
+
+/// The modules of a preset with the modules they require, in generation order.
+pub fn preset(name: &str) -> Result<Vec<&'static UtilModule>, String> {
+    let Some((_, names)) = PRESETS.iter().find(|(preset, _)| *preset == name) else {
+        let presets: Vec<&str> = PRESETS.iter().map(|(preset, _)| *preset).collect();
+        return Err(format!(
+            "Unknown preset `{name}`, expected one of: {}.",
+            presets.join(", ")
+        ));
+    };
+
+    let mut selected: Vec<&str> = names.to_vec();
+    let mut i = 0;
+    while i < selected.len() {
+        for required in module(selected[i]).map_or(&[][..], |m| m.requires) {
+            if !selected.contains(required) {
+                selected.push(required);
+            }
+        }
+        i += 1;
+    }
+
+    Ok(MODULES
+        .iter()
+        .filter(|m| selected.contains(&m.name))
+        .collect())
+}
+
+fn module(name: &str) -> Option<&'static UtilModule> {
+    MODULES.iter().find(|m| m.name == name)
+}
+
+/// `source` of a `mod.rs` or `lib.rs` with `pub mod <name>;` added among the other public
+/// modules, in alphabetical order. `None` if it is declared already.
+pub fn declare_module(source: &str, name: &str) -> Option<String> {
+    let declaration = format!("pub mod {name};");
+    let mut lines: Vec<&str> = source.lines().collect();
+    if lines.iter().any(|line| line.trim() == declaration) {
+        return None;
+    }
+
+    fn declared(line: &str) -> Option<&str> {
+        line.strip_prefix("pub mod ")?.strip_suffix(';')
+    }
+
+    let at = lines
+        .iter()
+        .position(|line| declared(line).is_some_and(|other| other > name))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|line| declared(line).is_some())
+                .map(|i| i + 1)
+        })
+        .unwrap_or(lines.len());
+    lines.insert(at, &declaration);
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// The first lines of a generated `src/utils/prelude.rs`.
+pub const PRELUDE_HEADER: &str = "//! The main items of the generated utility modules, for \
+    `use everybody_codes::utils::prelude::*;`.\n\n";
+
+/// `prelude` with a re-export added for every item of `module` that it lacks. The re-exports are
+/// kept sorted after the other lines, like rustfmt orders them.
+pub fn add_exports(prelude: &str, module: &UtilModule) -> String {
+    let (exports, other): (Vec<&str>, Vec<&str>) = prelude
+        .lines()
+        .partition(|line| line.starts_with("pub use "));
+
+    let mut exports: Vec<String> = exports
+        .into_iter()
+        .map(str::to_string)
+        .chain(
+            module
+                .exports
+                .iter()
+                .map(|item| format!("pub use crate::utils::{}::{item};", module.name)),
+        )
+        .collect();
+    exports.sort();
+    exports.dedup();
+
+    let mut lines: Vec<String> = other.into_iter().map(str::to_string).collect();
+    lines.extend(exports);
+    lines.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{add_exports, declare_module, preset, MODULES};
+
+    #[test]
+    fn adds_required_modules() {
+        let names = |name| {
+            preset(name)
+                .unwrap()
+                .iter()
+                .map(|m| m.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names("standard"),
+            vec![
+                "point",
+                "grid",
+                "parse",
+                "numbers",
+                "hashing",
+                "heap",
+                "pathfinding"
+            ]
+        );
+        assert_eq!(names("minimal"), vec!["point", "grid", "parse"]);
+        assert!(preset("everything")
+            .unwrap_err()
+            .contains("standard, minimal"));
+    }
+
+    #[test]
+    fn declares_modules_in_order() {
+        let source = "//! Helpers.\n\npub mod arena;\npub mod heap;\n";
+
+        assert_eq!(
+            declare_module(source, "grid").as_deref(),
+            Some("//! Helpers.\n\npub mod arena;\npub mod grid;\npub mod heap;\n")
+        );
+        assert_eq!(
+            declare_module(source, "point").as_deref(),
+            Some("//! Helpers.\n\npub mod arena;\npub mod heap;\npub mod point;\n")
+        );
+        assert_eq!(declare_module(source, "heap"), None);
+        assert_eq!(
+            declare_module("//! Helpers.\n", "grid").as_deref(),
+            Some("//! Helpers.\npub mod grid;\n")
+        );
+    }
+
+    #[test]
+    fn exports_items_once() {
+        let grid = MODULES.iter().find(|m| m.name == "grid").unwrap();
+        let point = MODULES.iter().find(|m| m.name == "point").unwrap();
+        let prelude = add_exports("//! Prelude.\n\n", point);
+        let prelude = add_exports(&prelude, grid);
+
+        assert_eq!(
+            prelude,
+            "//! Prelude.\n\npub use crate::utils::grid::Grid;\npub use crate::utils::point::Point;\n"
+        );
+        assert_eq!(add_exports(&prelude, grid), prelude);
+    }
+}
@@ -0,0 +1,208 @@
+/// Change detection and sample reports for `cargo ec watch`, which reruns a quest on its
+/// samples whenever its solution, the shared utilities or its data change.
+///
+/// Files are polled rather than watched through OS events, so there is nothing to install and
+/// editors that save by replacing files are picked up just the same.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{
+    authors::get_path_for_author_bin, data_dir, Day, Part, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Data folders holding files of a quest, named like `07-1.txt`.
+const DATA_FOLDERS: [&str; 3] = ["samples", "answers", "inputs"];
+
+/// The files a quest depends on: its solution, the utilities in `src/utils` and its data.
+pub fn watched_files(day: Day, author: Option<&str>) -> Vec<PathBuf> {
+    let mut files = vec![PathBuf::from(get_path_for_author_bin(day, author))];
+    collect_files(Path::new("src/utils"), &mut files);
+
+    for folder in DATA_FOLDERS {
+        let Ok(entries) = fs::read_dir(data_dir().join(folder)) else {
+            continue;
+        };
+        files.extend(
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| is_quest_file(&entry.file_name().to_string_lossy(), day))
+                .map(|entry| entry.path()),
+        );
+    }
+
+    files.sort();
+    files
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Whether a data file belongs to `day`, e.g. `07-1.txt` for day 7.
+pub fn is_quest_file(file_name: &str, day: Day) -> bool {
+    file_name
+        .strip_prefix(&format!("{day}-"))
+        .is_some_and(|rest| rest.ends_with(".txt"))
+}
+
+/// The modification times of a set of files. Any difference between two snapshots, including
+/// files that appeared or vanished, is a change.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(Vec<(PathBuf, Option<SystemTime>)>);
+
+impl Snapshot {
+    pub fn take(files: Vec<PathBuf>) -> Self {
+        Snapshot(
+            files
+                .into_iter()
+                .map(|path| {
+                    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                    (path, modified)
+                })
+                .collect(),
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of running a part on its sample.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SampleRun {
+    /// There is no sample for the part yet.
+    NoSample,
+    Panicked(String),
+    Ran {
+        answer: Option<String>,
+        expected: Option<String>,
+        duration: Option<Duration>,
+    },
+}
+
+impl SampleRun {
+    /// Whether the part returned the expected answer. Parts without a sample or expected answer
+    /// neither pass nor fail.
+    pub fn passed(&self) -> Option<bool> {
+        match self {
+            SampleRun::NoSample => None,
+            SampleRun::Panicked(_) => Some(false),
+            SampleRun::Ran {
+                answer, expected, ..
+            } => expected
+                .as_ref()
+                .map(|expected| answer.as_ref() == Some(expected)),
+        }
+    }
+}
+
+/// Shorten multi-line answers to their first line.
+pub(crate) fn compact(answer: &str) -> String {
+    match answer.split_once('\n') {
+        Some((first, _)) => format!("{first}…"),
+        None => answer.to_string(),
+    }
+}
+
+/// A single line summarizing the run of a part, e.g. `✅ Part 1: 42 (1.2ms)`.
+pub fn render(part: Part, run: &SampleRun) -> String {
+    let (answer, expected, duration) = match run {
+        SampleRun::NoSample => return format!("➖ Part {part}: no sample"),
+        SampleRun::Panicked(location) => return format!("💥 Part {part}: panicked at {location}"),
+        SampleRun::Ran {
+            answer,
+            expected,
+            duration,
+        } => (answer, expected, duration),
+    };
+
+    let icon = match run.passed() {
+        Some(true) => "✅",
+        Some(false) => "❌",
+        None => "❔",
+    };
+    let answer = answer.as_deref().map_or("no answer".into(), compact);
+    let expected = match (run.passed(), expected) {
+        (Some(false), Some(expected)) => format!(", expected {}", compact(expected)),
+        (None, _) => ", no expected answer".into(),
+        _ => String::new(),
+    };
+    let duration = duration
+        .map(|duration| format!(" {ANSI_ITALIC}({duration:.1?}){ANSI_RESET}"))
+        .unwrap_or_default();
+
+    format!("{icon} Part {part}: {answer}{expected}{duration}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{is_quest_file, render, SampleRun};
+    use crate::{
+        day,
+        template::{Part, ANSI_ITALIC, ANSI_RESET},
+    };
+
+    fn ran(answer: Option<&str>, expected: Option<&str>) -> SampleRun {
+        SampleRun::Ran {
+            answer: answer.map(Into::into),
+            expected: expected.map(Into::into),
+            duration: None,
+        }
+    }
+
+    #[test]
+    fn matches_quest_files() {
+        assert!(is_quest_file("07-1.txt", day!(7)));
+        assert!(!is_quest_file("17-1.txt", day!(7)));
+        assert!(!is_quest_file("07-1.txt.tmp", day!(7)));
+    }
+
+    #[test]
+    fn renders_sample_runs() {
+        let passed = SampleRun::Ran {
+            answer: Some("42".into()),
+            expected: Some("42".into()),
+            duration: Some(Duration::from_micros(1200)),
+        };
+        assert_eq!(
+            render(Part::One, &passed),
+            format!("✅ Part 1: 42 {ANSI_ITALIC}(1.2ms){ANSI_RESET}")
+        );
+
+        assert_eq!(
+            render(Part::Two, &ran(Some("41"), Some("42"))),
+            "❌ Part 2: 41, expected 42"
+        );
+        assert_eq!(
+            render(Part::Two, &ran(None, Some("A\nB"))),
+            "❌ Part 2: no answer, expected A…"
+        );
+        assert_eq!(
+            render(Part::Three, &ran(Some("7"), None)),
+            "❔ Part 3: 7, no expected answer"
+        );
+        assert_eq!(
+            render(Part::Three, &SampleRun::NoSample),
+            "➖ Part 3: no sample"
+        );
+        assert_eq!(
+            render(Part::One, &SampleRun::Panicked("src/bin/07.rs:3:5".into())),
+            "💥 Part 1: panicked at src/bin/07.rs:3:5"
+        );
+    }
+}
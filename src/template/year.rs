@@ -0,0 +1,255 @@
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+    time::SystemTime,
+};
+
+use crate::template::Event;
+
+/// An Everybody Codes event year, from 2024 onwards.
+///
+/// One repository can hold several events. Each keeps its quest data in `data/{year}` and
+/// its solutions in `src/bin/{year}_{day}.rs`, except for a repository's `EC_YEAR` event in a
+/// [single year layout](is_single_year_layout).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+const FIRST_YEAR: u16 = 2024;
+
+static YEAR_ENV: &str = "EC_YEAR";
+static YEAR: OnceLock<Year> = OnceLock::new();
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a possible event year,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        (FIRST_YEAR..=9999).contains(&year).then_some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The year commands and solutions work on. This is, in order of precedence:
+    ///  1. the year passed to `solution!`, or with `--year` to the template commands.
+    ///  2. the year in the name of the running solution binary, e.g. `2024_07`.
+    ///  3. the repository's [home](Year::home) year.
+    pub fn current() -> Self {
+        YEAR.get()
+            .copied()
+            .or_else(|| {
+                let exe = env::current_exe().ok()?;
+                from_bin_name(exe.file_stem()?.to_str()?)
+            })
+            .unwrap_or_else(Year::home)
+    }
+
+    /// The event the repository is set up for with `EC_YEAR`, or the most recent event.
+    pub fn home() -> Self {
+        env::var(YEAR_ENV)
+            .ok()
+            .and_then(|year| year.parse().ok())
+            .unwrap_or_else(latest_event)
+    }
+}
+
+/// Fix the current year of this process, see `solution!`.
+pub fn set_year(year: Year) {
+    let _ = YEAR.set(year);
+}
+
+/// The year of a solution binary or its test harness, e.g. `2024_07` or `2024_07-1a2b3c`.
+fn from_bin_name(name: &str) -> Option<Year> {
+    let (year, _) = name.split_once('_')?;
+    if year.len() != 4 {
+        return None;
+    }
+    year.parse().ok()
+}
+
+/// The main event takes place in November, so earlier months belong to the previous year.
+fn latest_event() -> Year {
+    let (year, month, _) = date_from_days(days_since_epoch());
+    let year = if month >= 11 { year } else { year - 1 };
+    Year::new(year).unwrap_or(Year(FIRST_YEAR))
+}
+
+/// Today's date in UTC, e.g. `2025-11-03`.
+pub(crate) fn today() -> String {
+    let (year, month, day) = date_from_days(days_since_epoch());
+    format!("{year}-{month:02}-{day:02}")
+}
+
+fn days_since_epoch() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+        / 86_400
+}
+
+/// The civil year, month and day of a day count since 1970-01-01.
+fn date_from_days(days: u64) -> (u16, u8, u8) {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    #[allow(clippy::cast_possible_truncation)]
+    (year as u16, month as u8, day as u8)
+}
+
+/// Whether the current year uses the layout from before multi-year support: the data of the
+/// home event lives directly in `data/` and its solutions in `src/bin/{day}.rs`. This layout is
+/// kept as long as `data/inputs` exists and `data/{year}` does not.
+pub fn is_single_year_layout() -> bool {
+    uses_single_year_layout(Event::current())
+}
+
+fn uses_single_year_layout(event: Event) -> bool {
+    let Event::MainEvent { year } = event else {
+        return false;
+    };
+    let data = Path::new("data");
+    year == Year::home() && data.join("inputs").exists() && !data.join(year.to_string()).exists()
+}
+
+/// The data folder of the current event, `data/{year}` or `data/story{id}`, or `data` in a
+/// single year layout.
+pub fn data_dir() -> PathBuf {
+    data_dir_of(Event::current())
+}
+
+/// The data folder of `event`, see [`data_dir`].
+pub fn data_dir_of(event: Event) -> PathBuf {
+    if uses_single_year_layout(event) {
+        PathBuf::from("data")
+    } else {
+        Path::new("data").join(event.slug())
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting an event year from {FIRST_YEAR}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2024 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting an event year from 2024"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{data_dir_of, date_from_days, from_bin_name, Year};
+    use crate::{template::Event, year};
+
+    /// A solution tied to 2024 whose binary name doesn't say so.
+    #[allow(dead_code)]
+    mod solution_2024 {
+        crate::solution!(7, part_one = |input: &str| Some(input.len()), year = 2024);
+    }
+
+    /// A solution tied to a story whose binary name doesn't say so.
+    #[allow(dead_code)]
+    mod solution_story {
+        crate::solution!(2, part_one = |input: &str| Some(input.len()), story = 1);
+    }
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2025".parse::<Year>().unwrap().into_inner(), 2025);
+        assert!("2023".parse::<Year>().is_err());
+        assert!("25".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_year_from_bin_name() {
+        assert_eq!(from_bin_name("2024_07"), Year::new(2024));
+        assert_eq!(from_bin_name("2024_07_alice"), Year::new(2024));
+        assert_eq!(from_bin_name("2024_07-1a2b3c4d"), Year::new(2024));
+        assert_eq!(from_bin_name("07"), None);
+        assert_eq!(from_bin_name("07_alice"), None);
+        assert_eq!(from_bin_name("everybody_codes"), None);
+    }
+
+    #[test]
+    fn converts_days_to_dates() {
+        assert_eq!(date_from_days(0), (1970, 1, 1));
+        assert_eq!(date_from_days(20_395), (2025, 11, 3));
+        assert_eq!(date_from_days(19_782), (2024, 2, 29));
+    }
+
+    #[test]
+    fn reads_data_of_the_solution_event() {
+        assert_eq!(
+            solution_2024::__EVENT,
+            Some(Event::MainEvent { year: year!(2024) })
+        );
+        assert_eq!(
+            data_dir_of(solution_2024::__EVENT.unwrap()),
+            Path::new("data/2024")
+        );
+        assert_eq!(
+            data_dir_of(solution_story::__EVENT.unwrap()),
+            Path::new("data/story1")
+        );
+    }
+}
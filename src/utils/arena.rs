@@ -0,0 +1,300 @@
+/// A bump arena: values are pushed into a single `Vec` and referred to by typed [`Id`] handles.
+///
+/// Trees and graphs whose nodes point at each other, at their parent or in cycles don't need
+/// `Rc<RefCell<...>>` this way. Nodes store the `Id`s of their neighbors, and the arena hands
+/// out the nodes themselves. Values are never freed individually, they live as long as the arena.
+///
+/// ```
+/// use everybody_codes::utils::arena::{Arena, Id};
+///
+/// struct Node {
+///     name: &'static str,
+///     parent: Option<Id<Node>>,
+///     children: Vec<Id<Node>>,
+/// }
+///
+/// let mut tree = Arena::new();
+/// let root = tree.alloc(Node { name: "root", parent: None, children: vec![] });
+/// for name in ["a", "b"] {
+///     let child = tree.alloc(Node { name, parent: Some(root), children: vec![] });
+///     tree[root].children.push(child);
+/// }
+///
+/// let b = tree[root].children[1];
+/// assert_eq!(tree[tree[b].parent.unwrap()].name, "root");
+/// ```
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+/// A handle to a value in an [`Arena`]. It is `Copy` and as small as a `u32`, so nodes can
+/// store as many as they like and use them as map keys.
+pub struct Id<T> {
+    index: u32,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Id<T> {
+    /// The position of the value in the arena, in allocation order.
+    pub fn index(self) -> usize {
+        self.index as usize
+    }
+
+    fn new(index: usize) -> Self {
+        Id {
+            index: u32::try_from(index).expect("arena holds more than u32::MAX values"),
+            marker: PhantomData,
+        }
+    }
+}
+
+// implemented by hand, as deriving would require `T` to implement the traits as well.
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> PartialOrd for Id<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Id<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+impl<T> Hash for Id<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Id({})", self.index)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug)]
+pub struct Arena<T> {
+    values: Vec<T>,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Arena::new()
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Arena { values: vec![] }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Arena {
+            values: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Moves `value` into the arena and returns its handle.
+    pub fn alloc(&mut self, value: T) -> Id<T> {
+        let id = Id::new(self.values.len());
+        self.values.push(value);
+        id
+    }
+
+    /// Allocates a value that needs to know its own handle, e.g. a node of a cyclic list that
+    /// starts out pointing at itself.
+    pub fn alloc_with(&mut self, value: impl FnOnce(Id<T>) -> T) -> Id<T> {
+        let id = Id::new(self.values.len());
+        self.values.push(value(id));
+        id
+    }
+
+    pub fn get(&self, id: Id<T>) -> Option<&T> {
+        self.values.get(id.index())
+    }
+
+    pub fn get_mut(&mut self, id: Id<T>) -> Option<&mut T> {
+        self.values.get_mut(id.index())
+    }
+
+    /// Mutable references to two different values at once, e.g. to link two nodes.
+    ///
+    /// # Panics
+    /// If `a` and `b` are the same handle.
+    pub fn pair_mut(&mut self, a: Id<T>, b: Id<T>) -> (&mut T, &mut T) {
+        assert_ne!(a, b, "pair_mut needs two different values");
+        if a < b {
+            let (left, right) = self.values.split_at_mut(b.index());
+            (&mut left[a.index()], &mut right[0])
+        } else {
+            let (left, right) = self.values.split_at_mut(a.index());
+            (&mut right[0], &mut left[b.index()])
+        }
+    }
+
+    /// The handles of all values, in allocation order.
+    pub fn ids(&self) -> impl Iterator<Item = Id<T>> {
+        (0..self.values.len()).map(Id::new)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Id<T>, &T)> {
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (Id::new(i), value))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Id<T>, &mut T)> {
+        self.values
+            .iter_mut()
+            .enumerate()
+            .map(|(i, value)| (Id::new(i), value))
+    }
+
+    /// The values in allocation order, e.g. to index a parallel `Vec` by [`Id::index`].
+    pub fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+}
+
+impl<T> Index<Id<T>> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, id: Id<T>) -> &T {
+        &self.values[id.index()]
+    }
+}
+
+impl<T> IndexMut<Id<T>> for Arena<T> {
+    fn index_mut(&mut self, id: Id<T>) -> &mut T {
+        &mut self.values[id.index()]
+    }
+}
+
+impl<T> FromIterator<T> for Arena<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Arena {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Arena, Id};
+
+    struct Node {
+        value: u32,
+        children: Vec<Id<Node>>,
+    }
+
+    fn sum(tree: &Arena<Node>, id: Id<Node>) -> u32 {
+        tree[id].value + tree[id].children.iter().map(|&c| sum(tree, c)).sum::<u32>()
+    }
+
+    #[test]
+    fn builds_trees() {
+        let mut tree = Arena::new();
+        let leaf = |value| Node {
+            value,
+            children: vec![],
+        };
+        let root = tree.alloc(leaf(1));
+        let a = tree.alloc(leaf(2));
+        let b = tree.alloc(leaf(3));
+        let c = tree.alloc(leaf(4));
+        tree[root].children.extend([a, b]);
+        tree[a].children.push(c);
+
+        assert_eq!(tree.len(), 4);
+        assert_eq!(sum(&tree, root), 10);
+        assert_eq!(sum(&tree, a), 6);
+        assert_eq!(c.index(), 3);
+        assert_eq!(tree.ids().collect::<Vec<_>>(), vec![root, a, b, c]);
+    }
+
+    #[test]
+    fn links_cyclic_lists() {
+        struct Link {
+            value: char,
+            next: Id<Link>,
+        }
+
+        let mut ring = Arena::new();
+        let first = ring.alloc_with(|id| Link {
+            value: 'a',
+            next: id,
+        });
+        assert_eq!(ring[first].next, first);
+
+        let mut last = first;
+        for value in ['b', 'c'] {
+            let link = ring.alloc(Link { value, next: first });
+            ring[last].next = link;
+            last = link;
+        }
+
+        let mut id = first;
+        let walked: String = (0..6)
+            .map(|_| {
+                let value = ring[id].value;
+                id = ring[id].next;
+                value
+            })
+            .collect();
+        assert_eq!(walked, "abcabc");
+    }
+
+    #[test]
+    fn borrows_two_values() {
+        let mut arena: Arena<Vec<u32>> = [vec![1], vec![2], vec![3]].into_iter().collect();
+        let ids: Vec<Id<Vec<u32>>> = arena.ids().collect();
+
+        let (a, c) = arena.pair_mut(ids[2], ids[0]);
+        a.append(c);
+        assert_eq!(arena[ids[2]], vec![3, 1]);
+        assert!(arena[ids[0]].is_empty());
+        assert_eq!(arena.get(ids[1]), Some(&vec![2]));
+    }
+
+    #[test]
+    #[should_panic(expected = "pair_mut needs two different values")]
+    fn rejects_borrowing_one_value_twice() {
+        let mut arena = Arena::new();
+        let id = arena.alloc(1);
+        arena.pair_mut(id, id);
+    }
+}
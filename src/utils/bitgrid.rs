@@ -0,0 +1,468 @@
+/// A dense grid of booleans packed into `u64` words, one row after another.
+///
+/// Whole-grid operations (shifts, boolean operations, neighbor counting) work on 64 cells
+/// at a time, which makes cellular automaton style simulations much faster than
+/// `Vec<Vec<bool>>`.
+use std::{
+    fmt::Display,
+    ops::{BitAnd, BitOr, BitXor, Not},
+};
+
+use crate::utils::grid::Grid;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    data: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Creates an empty grid.
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        BitGrid {
+            width,
+            height,
+            words_per_row,
+            data: vec![0; words_per_row * height],
+        }
+    }
+
+    /// Creates a grid from rows of booleans. Shorter rows are padded with `false`.
+    pub fn from_rows<R: AsRef<[bool]>>(rows: &[R]) -> Self {
+        let width = rows.iter().map(|r| r.as_ref().len()).max().unwrap_or(0);
+        let mut grid = BitGrid::new(width, rows.len());
+
+        for (row, cells) in rows.iter().enumerate() {
+            for (col, cell) in cells.as_ref().iter().enumerate() {
+                grid.set(row, col, *cell);
+            }
+        }
+
+        grid
+    }
+
+    /// Parses a grid from lines of text, where `on` marks set cells.
+    pub fn parse(input: &str, on: char) -> Self {
+        let rows: Vec<Vec<bool>> = input
+            .lines()
+            .map(|line| line.chars().map(|c| c == on).collect())
+            .collect();
+        BitGrid::from_rows(&rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, row: usize, col: usize) -> (usize, u64) {
+        (row * self.words_per_row + col / 64, 1 << (col % 64))
+    }
+
+    /// Returns the cell at `(row, col)`, or `false` if out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        if row >= self.height || col >= self.width {
+            return false;
+        }
+        let (i, mask) = self.index(row, col);
+        self.data[i] & mask != 0
+    }
+
+    /// Sets the cell at `(row, col)`.
+    ///
+    /// # Panics
+    /// Panics if `(row, col)` is out of bounds.
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        assert!(
+            row < self.height && col < self.width,
+            "({row}, {col}) out of bounds"
+        );
+        let (i, mask) = self.index(row, col);
+        if value {
+            self.data[i] |= mask;
+        } else {
+            self.data[i] &= !mask;
+        }
+    }
+
+    /// Flips the cell at `(row, col)`.
+    pub fn toggle(&mut self, row: usize, col: usize) {
+        let value = self.get(row, col);
+        self.set(row, col, !value);
+    }
+
+    /// The packed words of a single row, lowest column in the lowest bit.
+    pub fn row_words(&self, row: usize) -> &[u64] {
+        &self.data[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    /// Number of set cells.
+    pub fn count_ones(&self) -> usize {
+        self.data.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Iterates over the `(row, col)` of every set cell.
+    pub fn iter_ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.height).flat_map(move |row| {
+            self.row_words(row)
+                .iter()
+                .enumerate()
+                .flat_map(move |(w, word)| {
+                    let mut word = *word;
+                    std::iter::from_fn(move || {
+                        (word != 0).then(|| {
+                            let bit = word.trailing_zeros() as usize;
+                            word &= word - 1;
+                            (row, w * 64 + bit)
+                        })
+                    })
+                })
+        })
+    }
+
+    /// Clears padding bits beyond the grid width in the last word of every row.
+    fn mask_padding(&mut self) {
+        let rem = self.width % 64;
+        if rem == 0 || self.words_per_row == 0 {
+            return;
+        }
+        let mask = (1_u64 << rem) - 1;
+        for row in 0..self.height {
+            self.data[(row + 1) * self.words_per_row - 1] &= mask;
+        }
+    }
+
+    fn map_words(&self, f: impl Fn(u64) -> u64) -> Self {
+        let mut grid = BitGrid {
+            data: self.data.iter().map(|w| f(*w)).collect(),
+            ..self.clone()
+        };
+        grid.mask_padding();
+        grid
+    }
+
+    fn zip_words(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grid dimensions differ"
+        );
+        BitGrid {
+            data: self
+                .data
+                .iter()
+                .zip(&other.data)
+                .map(|(a, b)| f(*a, *b))
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Moves every cell one column to the right, dropping the last column.
+    #[must_use]
+    pub fn shift_east(&self) -> Self {
+        let mut grid = self.clone();
+        for row in grid.data.chunks_mut(self.words_per_row) {
+            let mut carry = 0;
+            for word in row.iter_mut() {
+                let next_carry = *word >> 63;
+                *word = (*word << 1) | carry;
+                carry = next_carry;
+            }
+        }
+        grid.mask_padding();
+        grid
+    }
+
+    /// Moves every cell one column to the left, dropping the first column.
+    #[must_use]
+    pub fn shift_west(&self) -> Self {
+        let mut grid = self.clone();
+        for row in grid.data.chunks_mut(self.words_per_row) {
+            let mut carry = 0;
+            for word in row.iter_mut().rev() {
+                let next_carry = *word << 63;
+                *word = (*word >> 1) | carry;
+                carry = next_carry;
+            }
+        }
+        grid
+    }
+
+    /// Moves every cell one row down, dropping the last row.
+    #[must_use]
+    pub fn shift_south(&self) -> Self {
+        let mut grid = BitGrid::new(self.width, self.height);
+        if self.height > 0 {
+            let n = self.data.len() - self.words_per_row;
+            grid.data[self.words_per_row..].copy_from_slice(&self.data[..n]);
+        }
+        grid
+    }
+
+    /// Moves every cell one row up, dropping the first row.
+    #[must_use]
+    pub fn shift_north(&self) -> Self {
+        let mut grid = BitGrid::new(self.width, self.height);
+        if self.height > 0 {
+            let n = self.data.len() - self.words_per_row;
+            grid.data[..n].copy_from_slice(&self.data[self.words_per_row..]);
+        }
+        grid
+    }
+
+    /// The grids of the 4 orthogonal neighbors of every cell.
+    fn shifted4(&self) -> [BitGrid; 4] {
+        [
+            self.shift_north(),
+            self.shift_south(),
+            self.shift_east(),
+            self.shift_west(),
+        ]
+    }
+
+    /// Counts the set orthogonal neighbors of every cell in parallel.
+    pub fn neighbors4(&self) -> NeighborCounts {
+        NeighborCounts::sum(self.width, self.height, &self.shifted4())
+    }
+
+    /// Counts the set neighbors (including diagonals) of every cell in parallel.
+    pub fn neighbors8(&self) -> NeighborCounts {
+        let north = self.shift_north();
+        let south = self.shift_south();
+        NeighborCounts::sum(
+            self.width,
+            self.height,
+            &[
+                north.shift_east(),
+                north.shift_west(),
+                south.shift_east(),
+                south.shift_west(),
+                north,
+                south,
+                self.shift_east(),
+                self.shift_west(),
+            ],
+        )
+    }
+
+    /// Counts the set neighbors (including diagonals) of a single cell.
+    pub fn count_neighbors8(&self, row: usize, col: usize) -> u8 {
+        let mut count = 0;
+        for r in row.saturating_sub(1)..=row + 1 {
+            for c in col.saturating_sub(1)..=col + 1 {
+                if (r, c) != (row, col) && self.get(r, c) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.zip_words(rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.zip_words(rhs, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.zip_words(rhs, |a, b| a ^ b)
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> Self::Output {
+        self.map_words(|w| !w)
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                write!(f, "{}", if self.get(row, col) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bits = BitGrid::new(grid.width, grid.height);
+        for row in 0..grid.height {
+            for col in 0..grid.width {
+                bits.set(row, col, grid[(row, col)]);
+            }
+        }
+        bits
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(bits: &BitGrid) -> Self {
+        Grid::from_fn(bits.width, bits.height, |row, col| bits.get(row, col))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Per-cell neighbor counts stored as bit planes (a bit-sliced binary counter).
+#[derive(Clone, Debug)]
+pub struct NeighborCounts {
+    planes: [BitGrid; 4],
+}
+
+impl NeighborCounts {
+    fn sum(width: usize, height: usize, grids: &[BitGrid]) -> Self {
+        let mut planes: [BitGrid; 4] = std::array::from_fn(|_| BitGrid::new(width, height));
+
+        for grid in grids {
+            let mut carry = grid.clone();
+            for plane in &mut planes {
+                let next_carry = &*plane & &carry;
+                *plane = &*plane ^ &carry;
+                carry = next_carry;
+            }
+        }
+
+        NeighborCounts { planes }
+    }
+
+    /// Mask of the cells with exactly `n` set neighbors.
+    pub fn exactly(&self, n: u8) -> BitGrid {
+        self.planes.iter().enumerate().fold(
+            !&BitGrid::new(self.planes[0].width, self.planes[0].height),
+            |acc, (bit, plane)| {
+                if n & (1 << bit) != 0 {
+                    &acc & plane
+                } else {
+                    &acc & &!plane
+                }
+            },
+        )
+    }
+
+    /// Mask of the cells whose neighbor count is one of `ns`.
+    pub fn any_of(&self, ns: &[u8]) -> BitGrid {
+        let empty = BitGrid::new(self.planes[0].width, self.planes[0].height);
+        ns.iter().fold(empty, |acc, n| &acc | &self.exactly(*n))
+    }
+
+    /// The neighbor count of a single cell.
+    pub fn get(&self, row: usize, col: usize) -> u8 {
+        self.planes
+            .iter()
+            .enumerate()
+            .map(|(bit, plane)| u8::from(plane.get(row, col)) << bit)
+            .sum()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BitGrid;
+    use crate::utils::grid::Grid;
+
+    fn get_mock_grid() -> BitGrid {
+        BitGrid::parse(".#.\n.#.\n.#.", '#')
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.count_ones(), 3);
+        assert_eq!(grid.to_string(), ".#.\n.#.\n.#.\n");
+    }
+
+    #[test]
+    fn shifts_across_word_boundaries() {
+        let mut grid = BitGrid::new(130, 2);
+        grid.set(0, 63, true);
+        grid.set(1, 129, true);
+
+        let east = grid.shift_east();
+        assert_eq!(east.get(0, 64), true);
+        assert_eq!(east.count_ones(), 1);
+
+        let west = grid.shift_west();
+        assert_eq!(west.get(0, 62), true);
+        assert_eq!(west.get(1, 128), true);
+
+        let south = grid.shift_south();
+        assert_eq!(south.get(1, 63), true);
+        assert_eq!(south.count_ones(), 1);
+
+        assert_eq!(grid.shift_north().get(0, 129), true);
+    }
+
+    #[test]
+    fn negation_keeps_padding_clear() {
+        let grid = !&BitGrid::new(70, 2);
+        assert_eq!(grid.count_ones(), 140);
+    }
+
+    #[test]
+    fn counts_neighbors_in_parallel() {
+        let grid = get_mock_grid();
+        let counts = grid.neighbors8();
+
+        for row in 0..3 {
+            for col in 0..3 {
+                assert_eq!(counts.get(row, col), grid.count_neighbors8(row, col));
+            }
+        }
+
+        // one step of Conway's game of life turns the blinker sideways.
+        let next = &counts.exactly(3) | &(&counts.exactly(2) & &grid);
+        assert_eq!(next.to_string(), "...\n###\n...\n");
+        assert_eq!(grid.neighbors4().get(1, 0), 1);
+    }
+
+    #[test]
+    fn iterates_set_cells() {
+        let mut grid = BitGrid::new(100, 2);
+        grid.set(0, 5, true);
+        grid.set(1, 70, true);
+        grid.toggle(1, 99);
+        assert_eq!(
+            grid.iter_ones().collect::<Vec<_>>(),
+            vec![(0, 5), (1, 70), (1, 99)]
+        );
+    }
+
+    #[test]
+    fn converts_to_and_from_grids() {
+        let grid = Grid::parse_with(".#.\n##.", |c| c == b'#');
+        let bits = BitGrid::from(&grid);
+        assert_eq!(bits.to_string(), ".#.\n##.\n");
+        assert_eq!(Grid::from(&bits), grid);
+    }
+}
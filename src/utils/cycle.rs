@@ -0,0 +1,200 @@
+/// Cycle detection for simulations that eventually repeat, so "the state after 10^12 steps" can
+/// be found without simulating every step.
+///
+/// Both detectors only compare states for equality and keep a couple of them around, so states
+/// don't need to be hashable. Brent's algorithm evaluates `step` fewer times than Floyd's and is
+/// the one [`simulate_with_cycle_skip`] uses:
+///
+/// ```ignore
+/// use everybody_codes::utils::cycle::simulate_with_cycle_skip;
+///
+/// let last = simulate_with_cycle_skip(grid, |grid| tilt(grid), 1_000_000_000_000);
+/// ```
+///
+/// A detected cycle: the sequence `x0, step(x0), step(step(x0)), ...` enters a cycle of `length`
+/// states at the state with index `start`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The index of the first state that is equal to the state with index `n`.
+    pub fn earliest_equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// The cycle that repeatedly applying `step` to `initial` runs into, with Brent's algorithm.
+/// Never returns if the states never repeat.
+pub fn brent<S: PartialEq + Clone>(initial: S, step: impl FnMut(&S) -> S) -> Cycle {
+    brent_within(initial, step, usize::MAX)
+        .expect("cycle detection should not exceed usize::MAX steps")
+        .0
+}
+
+/// The cycle that repeatedly applying `step` to `initial` runs into, with Floyd's tortoise and
+/// hare. Never returns if the states never repeat.
+pub fn floyd<S: PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    // the distance between them is now a multiple of the length, so they meet at the start.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// The cycle and the state with index `cycle.start`, `None` if finding them takes more than
+/// `limit` steps.
+fn brent_within<S: PartialEq + Clone>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    limit: usize,
+) -> Option<(Cycle, S)> {
+    let mut steps = 0;
+    let mut step = |state: &S| {
+        steps += 1;
+        (steps <= limit).then(|| step(state))
+    };
+
+    // the hare runs ahead in windows of doubling size until it meets the tortoise.
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    // with the hare `length` states ahead, both meet at the start of the cycle.
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some((Cycle { start, length }, tortoise))
+}
+
+/// The state after applying `step` to `initial` `n` times. Once the states repeat, the remaining
+/// whole cycles are skipped; if they don't repeat within about `n` steps, all `n` steps are
+/// simulated instead.
+pub fn simulate_with_cycle_skip<S: PartialEq + Clone>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    n: usize,
+) -> S {
+    let (mut state, remaining) = match brent_within(initial.clone(), &mut step, n) {
+        Some((cycle, state)) if n >= cycle.start => (state, (n - cycle.start) % cycle.length),
+        _ => (initial, n),
+    };
+    for _ in 0..remaining {
+        state = step(&state);
+    }
+    state
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, floyd, simulate_with_cycle_skip, Cycle};
+
+    // 0, 1, 2, 3, 4, 5, 3, 4, 5, ...
+    fn tail_and_loop(&x: &u64) -> u64 {
+        if x == 5 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let cycle = Cycle {
+            start: 3,
+            length: 3,
+        };
+        assert_eq!(brent(0, tail_and_loop), cycle);
+        assert_eq!(floyd(0, tail_and_loop), cycle);
+        assert_eq!(
+            brent(7, |&x| x),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+        assert_eq!(
+            floyd(7, |&x| x),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+
+        assert_eq!(cycle.earliest_equivalent(2), 2);
+        assert_eq!(cycle.earliest_equivalent(10), 4);
+    }
+
+    #[test]
+    fn skips_whole_cycles() {
+        assert_eq!(simulate_with_cycle_skip(0, tail_and_loop, 2), 2);
+        assert_eq!(simulate_with_cycle_skip(0, tail_and_loop, 10), 4);
+        assert_eq!(
+            simulate_with_cycle_skip(0, tail_and_loop, 1_000_000_000_000),
+            3 + (1_000_000_000_000 - 3) % 3
+        );
+
+        let mut calls = 0;
+        let state = simulate_with_cycle_skip(
+            vec![1_u64, 2],
+            |v| {
+                calls += 1;
+                vec![v[1], (v[0] + v[1]) % 7]
+            },
+            usize::MAX,
+        );
+        assert!(calls < 100);
+        assert_eq!(state.len(), 2);
+    }
+
+    #[test]
+    fn simulates_sequences_that_never_repeat_within_n() {
+        assert_eq!(simulate_with_cycle_skip(0_u64, |&x| x + 1, 1_000), 1_000);
+    }
+}
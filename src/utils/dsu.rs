@@ -0,0 +1,161 @@
+/// A disjoint set union over the elements `0..len`, for quests asking which things end up
+/// connected.
+///
+/// Finding a representative compresses the path to it and unions attach the shallower tree to
+/// the deeper one, so any sequence of operations runs in nearly constant time per operation.
+/// Elements that aren't numbered yet can be numbered as they are first seen, e.g. with a
+/// `FastMap<&str, usize>`:
+///
+/// ```ignore
+/// let mut sets = UnionFind::new(grid.width * grid.height);
+/// for p in grid.points().filter(|&p| grid[p] == b'#') {
+///     for n in grid.neighbors4(p).filter(|&n| grid[n] == b'#') {
+///         sets.union(index(p), index(n));
+///     }
+/// }
+/// let largest = sets.sizes().into_iter().max();
+/// ```
+use std::cell::Cell;
+
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    /// The parent of every element, in cells so `find` can compress paths through `&self`.
+    parents: Vec<Cell<usize>>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    /// `len` elements, each in a set of its own.
+    pub fn new(len: usize) -> Self {
+        UnionFind {
+            parents: (0..len).map(Cell::new).collect(),
+            ranks: vec![0; len],
+            sizes: vec![1; len],
+            components: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The representative of the set containing `x`.
+    ///
+    /// # Panics
+    /// If `x` is not an element.
+    pub fn find(&self, x: usize) -> usize {
+        let mut root = x;
+        while self.parents[root].get() != root {
+            root = self.parents[root].get();
+        }
+
+        let mut x = x;
+        while x != root {
+            x = self.parents[x].replace(root);
+        }
+        root
+    }
+
+    /// Merge the sets containing `a` and `b`. Returns `false` if they were the same set already.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let (root, child) = if self.ranks[a] < self.ranks[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parents[child].set(root);
+        self.sizes[root] += self.sizes[child];
+        if self.ranks[root] == self.ranks[child] {
+            self.ranks[root] += 1;
+        }
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(&self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the set containing `x`.
+    pub fn size(&self, x: usize) -> usize {
+        self.sizes[self.find(x)]
+    }
+
+    /// The number of disjoint sets.
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// The size of every set, in the order of their representatives.
+    pub fn sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&x| self.find(x) == x)
+            .map(|root| self.sizes[root])
+            .collect()
+    }
+
+    /// The elements of every set, each sorted, in the order of their smallest elements.
+    pub fn groups(&self) -> Vec<Vec<usize>> {
+        let mut index = vec![usize::MAX; self.len()];
+        let mut groups: Vec<Vec<usize>> = vec![];
+        for x in 0..self.len() {
+            let root = self.find(x);
+            if index[root] == usize::MAX {
+                index[root] = groups.len();
+                groups.push(vec![]);
+            }
+            groups[index[root]].push(x);
+        }
+        groups
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::UnionFind;
+
+    #[test]
+    fn merges_sets() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(sets.components(), 6);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 1));
+        assert!(sets.union(4, 5));
+        assert!(!sets.union(0, 2));
+
+        assert!(sets.connected(0, 2));
+        assert!(!sets.connected(0, 3));
+        assert_eq!(sets.components(), 3);
+        assert_eq!((sets.size(2), sets.size(3), sets.size(5)), (3, 1, 2));
+        assert_eq!(sets.groups(), vec![vec![0, 1, 2], vec![3], vec![4, 5]]);
+
+        let mut sizes = sets.sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn compresses_long_chains() {
+        let mut sets = UnionFind::new(10_000);
+        for x in 1..sets.len() {
+            sets.union(x - 1, x);
+        }
+        assert_eq!(sets.components(), 1);
+        assert_eq!(sets.size(0), 10_000);
+        assert_eq!(sets.find(9_999), sets.find(0));
+    }
+}
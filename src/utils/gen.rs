@@ -0,0 +1,149 @@
+/// Building blocks for synthetic inputs, used by a quest's `gen_input(size, seed)` generator.
+///
+/// [`Rng`] is a small seeded generator (SplitMix64), so the same size and seed always produce
+/// the same input on every machine.
+use std::ops::Range;
+
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed number in `0..n`.
+    ///
+    /// # Panics
+    /// If `n` is zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "cannot pick a number below 0");
+        // rejection sampling avoids the modulo bias.
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+
+    /// A uniformly distributed number in the half-open `range`.
+    ///
+    /// # Panics
+    /// If the range is empty.
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(!range.is_empty(), "cannot pick from an empty range");
+        let span = range.end.wrapping_sub(range.start) as u64;
+        range.start.wrapping_add(self.below(span) as i64)
+    }
+
+    /// `true` with probability `p`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// A random element of `items`.
+    ///
+    /// # Panics
+    /// If `items` is empty.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    /// Shuffle `items` in place (Fisher-Yates).
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// A string of `len` characters from `alphabet`.
+pub fn word(rng: &mut Rng, len: usize, alphabet: &[char]) -> String {
+    (0..len).map(|_| *rng.pick(alphabet)).collect()
+}
+
+/// A grid of `height` lines of `width` characters from `alphabet`.
+pub fn grid(rng: &mut Rng, width: usize, height: usize, alphabet: &[char]) -> String {
+    (0..height)
+        .map(|_| word(rng, width, alphabet))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `count` numbers in `range`, joined by `separator`.
+pub fn numbers(rng: &mut Rng, count: usize, range: Range<i64>, separator: &str) -> String {
+    (0..count)
+        .map(|_| rng.range(range.clone()).to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{grid, numbers, Rng};
+
+    #[test]
+    fn is_deterministic() {
+        let a: Vec<u64> = (0..5)
+            .scan(Rng::new(42), |r, _| Some(r.next_u64()))
+            .collect();
+        let b: Vec<u64> = (0..5)
+            .scan(Rng::new(42), |r, _| Some(r.next_u64()))
+            .collect();
+        let c: Vec<u64> = (0..5)
+            .scan(Rng::new(43), |r, _| Some(r.next_u64()))
+            .collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((-5..5).contains(&rng.range(-5..5)));
+            assert!(rng.below(3) < 3);
+        }
+        assert_eq!(rng.range(i64::MIN..i64::MIN + 1), i64::MIN);
+    }
+
+    #[test]
+    fn shuffles_all_elements() {
+        let mut rng = Rng::new(1);
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn builds_inputs() {
+        let mut rng = Rng::new(3);
+        let g = grid(&mut rng, 4, 3, &['.', '#']);
+        assert_eq!(g.lines().count(), 3);
+        assert!(g.lines().all(|l| l.len() == 4));
+
+        let n = numbers(&mut rng, 5, 1..10, ",");
+        assert_eq!(n.split(',').count(), 5);
+    }
+}
@@ -0,0 +1,144 @@
+/// Points in two and three dimensions and the four directions of a grid walk.
+///
+/// `Point2` is [`point::Point`](crate::utils::point::Point) and `Point3` is
+/// [`grid3::Point3`](crate::utils::grid3::Point3), so both work with [`Grid`](crate::utils::grid::Grid)
+/// and [`Grid3`](crate::utils::grid3::Grid3) as they are. Like the rows of the input, `y` grows
+/// downwards:
+///
+/// ```ignore
+/// let mut at = grid.find(|&c| c == b'S').unwrap();
+/// for step in Direction::parse_all("^^>v").unwrap() {
+///     at = step.step(at);
+/// }
+/// ```
+use std::ops::{Add, AddAssign};
+
+pub use crate::utils::{grid3::Point3, point::Point as Point2};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The direction of an arrow (`^`, `>`, `v`, `<`) or a letter (`U`, `R`, `D`, `L`, in
+    /// either case).
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '^' | 'U' | 'u' => Some(Direction::Up),
+            '>' | 'R' | 'r' => Some(Direction::Right),
+            'v' | 'D' | 'd' => Some(Direction::Down),
+            '<' | 'L' | 'l' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// Every direction in `s`, ignoring whitespace. `None` if any other character isn't one.
+    pub fn parse_all(s: &str) -> Option<Vec<Direction>> {
+        s.chars()
+            .filter(|c| !c.is_whitespace())
+            .map(Direction::from_char)
+            .collect()
+    }
+
+    /// The unit step in this direction.
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::Down => Point2::new(0, 1),
+            Direction::Left => Point2::new(-1, 0),
+        }
+    }
+
+    /// The point one step from `p` in this direction.
+    pub fn step(self, p: Point2) -> Point2 {
+        p + self.offset()
+    }
+
+    /// The direction a quarter turn clockwise.
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    /// The direction a quarter turn counterclockwise.
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl Add<Direction> for Point2 {
+    type Output = Point2;
+
+    fn add(self, direction: Direction) -> Point2 {
+        direction.step(self)
+    }
+}
+
+impl AddAssign<Direction> for Point2 {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = *self + direction;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Point2, Point3};
+    use crate::utils::{grid::Grid, point::UP};
+
+    #[test]
+    fn parses_arrows_and_letters() {
+        assert_eq!(
+            Direction::parse_all("^>v<\nUrDl"),
+            Some([Direction::ALL, Direction::ALL].concat())
+        );
+        assert_eq!(Direction::parse_all("^x"), None);
+        assert_eq!(Direction::from_char('v'), Some(Direction::Down));
+    }
+
+    #[test]
+    fn turns_and_steps() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+            assert_eq!(d.offset().turn_right(), d.turn_right().offset());
+        }
+        assert_eq!(Direction::Up.offset(), UP);
+        assert_eq!(Direction::Left.turn_left(), Direction::Down);
+
+        let mut p = Point2::new(2, 3);
+        p += Direction::Right;
+        assert_eq!(p + Direction::Up, Point2::new(3, 2));
+        assert_eq!(p * 2 - Point2::new(1, 1), Point2::new(5, 5));
+        assert_eq!(p.manhattan(Point2::default()), 6);
+        assert_eq!(Point3::new(1, 2, 3).manhattan(Point3::new(0, 0, 0)), 6);
+    }
+
+    #[test]
+    fn walks_grids() {
+        let grid = Grid::parse("S.\n.#\n");
+        let start = grid.find(|&c| c == b'S').unwrap();
+        let end = Direction::parse_all(">v")
+            .unwrap()
+            .into_iter()
+            .fold(start, |p, d| p + d);
+        assert_eq!(grid[end], b'#');
+    }
+}
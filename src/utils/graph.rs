@@ -0,0 +1,217 @@
+/// Orderings and components of directed graphs, for dependency style quests.
+///
+/// Graphs are given as their nodes and a closure returning the successors of a node. Nodes
+/// only reached as successors are part of the graph as well. An adjacency list over indices
+/// works the same way as a map of named nodes:
+///
+/// ```ignore
+/// use everybody_codes::utils::graph::{tarjan_scc, toposort};
+///
+/// let order = toposort(0..adjacency.len(), |&node| adjacency[node].iter().copied());
+/// let components = tarjan_scc(rules.keys().copied(), |name| rules[name].iter().copied());
+/// ```
+///
+/// Both run in `O(V + E)` without recursion, so long chains don't overflow the stack.
+use std::hash::Hash;
+
+use crate::utils::hashing::FastMap;
+
+/// The nodes numbered in the order they were first seen, with the successors of each by number.
+struct Indexed<N> {
+    nodes: Vec<N>,
+    successors: Vec<Vec<usize>>,
+}
+
+impl<N: Hash + Eq + Clone> Indexed<N> {
+    fn new<I: IntoIterator<Item = N>>(
+        nodes: impl IntoIterator<Item = N>,
+        mut neighbors: impl FnMut(&N) -> I,
+    ) -> Self {
+        let mut indexed = Indexed {
+            nodes: vec![],
+            successors: vec![],
+        };
+        let mut indices: FastMap<N, usize> = FastMap::default();
+        let mut index_of = |node: N, indexed: &mut Indexed<N>| {
+            *indices.entry(node.clone()).or_insert_with(|| {
+                indexed.nodes.push(node);
+                indexed.nodes.len() - 1
+            })
+        };
+
+        for node in nodes {
+            index_of(node, &mut indexed);
+        }
+
+        // successors may add nodes, which are visited in turn.
+        let mut i = 0;
+        while i < indexed.nodes.len() {
+            let node = indexed.nodes[i].clone();
+            let successors = neighbors(&node)
+                .into_iter()
+                .map(|next| index_of(next, &mut indexed))
+                .collect();
+            indexed.successors.push(successors);
+            i += 1;
+        }
+
+        indexed
+    }
+}
+
+/// Order the nodes so that every node comes before its successors, or `None` if the graph has
+/// a cycle. Nodes without a constraint between them keep the order they were first seen in.
+pub fn toposort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+) -> Option<Vec<N>>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let graph = Indexed::new(nodes, neighbors);
+
+    let mut in_degree = vec![0; graph.nodes.len()];
+    for &next in graph.successors.iter().flatten() {
+        in_degree[next] += 1;
+    }
+
+    let mut order: Vec<usize> = (0..graph.nodes.len())
+        .filter(|&node| in_degree[node] == 0)
+        .collect();
+    let mut i = 0;
+    while i < order.len() {
+        for &next in &graph.successors[order[i]] {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                order.push(next);
+            }
+        }
+        i += 1;
+    }
+
+    (order.len() == graph.nodes.len()).then(|| {
+        order
+            .into_iter()
+            .map(|node| graph.nodes[node].clone())
+            .collect()
+    })
+}
+
+/// The strongly connected components of the graph with Tarjan's algorithm: groups of nodes
+/// that can all reach each other. Components come in reverse topological order, so nothing in
+/// a component leads to a later one. A node on no cycle is a component of its own.
+pub fn tarjan_scc<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    const UNVISITED: usize = usize::MAX;
+
+    let graph = Indexed::new(nodes, neighbors);
+    let count = graph.nodes.len();
+
+    let mut index = vec![UNVISITED; count];
+    let mut low_link = vec![0; count];
+    let mut on_stack = vec![false; count];
+    let mut stack = vec![];
+    let mut components = vec![];
+    let mut next_index = 0;
+
+    for root in 0..count {
+        if index[root] != UNVISITED {
+            continue;
+        }
+
+        // the call stack of the recursive formulation: a node and its next successor to visit.
+        let mut calls = vec![(root, 0)];
+        index[root] = next_index;
+        low_link[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&mut (node, ref mut edge)) = calls.last_mut() {
+            if let Some(&next) = graph.successors[node].get(*edge) {
+                *edge += 1;
+                if index[next] == UNVISITED {
+                    index[next] = next_index;
+                    low_link[next] = next_index;
+                    next_index += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    calls.push((next, 0));
+                } else if on_stack[next] {
+                    low_link[node] = low_link[node].min(index[next]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low_link[parent] = low_link[parent].min(low_link[node]);
+            }
+
+            if low_link[node] == index[node] {
+                let mut component = vec![];
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(graph.nodes[member].clone());
+                    if member == node {
+                        break;
+                    }
+                }
+                component.reverse();
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{tarjan_scc, toposort};
+
+    #[test]
+    fn sorts_topologically() {
+        let adjacency = vec![vec![2], vec![2, 3], vec![4], vec![4], vec![]];
+        assert_eq!(
+            toposort(0..adjacency.len(), |&node| adjacency[node].clone()),
+            Some(vec![0, 1, 2, 3, 4])
+        );
+
+        let rules: HashMap<&str, Vec<&str>> =
+            HashMap::from([("shirt", vec!["tie"]), ("tie", vec!["jacket"])]);
+        let requires = |name: &&str| rules.get(name).cloned().unwrap_or_default();
+        assert_eq!(
+            toposort(["jacket", "shirt"], requires),
+            Some(vec!["shirt", "tie", "jacket"])
+        );
+
+        let cycle = vec![vec![1], vec![2], vec![0]];
+        assert_eq!(toposort(0..3, |&node| cycle[node].clone()), None);
+    }
+
+    #[test]
+    fn finds_strongly_connected_components() {
+        // 0 -> 1 -> 2 -> 0 is a cycle leading into 3 <-> 4, with 5 on its own.
+        let adjacency = vec![vec![1], vec![2], vec![0, 3], vec![4], vec![3], vec![0]];
+        let mut components = tarjan_scc(0..adjacency.len(), |&node| adjacency[node].clone());
+        components.iter_mut().for_each(|component| component.sort());
+        assert_eq!(components, vec![vec![3, 4], vec![0, 1, 2], vec![5]]);
+
+        assert_eq!(
+            tarjan_scc([1], |&node: &u32| (node < 100_000).then_some(node + 1)).len(),
+            100_000
+        );
+    }
+}
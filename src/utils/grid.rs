@@ -0,0 +1,303 @@
+/// A dense 2D grid indexed by [`Point`] or by `(row, col)`, for the character maps most quests
+/// start with.
+///
+/// ```ignore
+/// let grid = Grid::parse(input);
+/// let start = grid.find(|&c| c == b'S').unwrap();
+/// let open = grid.neighbors4(start).filter(|&p| grid[p] != b'#').count();
+/// let corner = grid.rotate_right()[(0, 0)];
+/// ```
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::utils::point::Point;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<T>,
+}
+
+impl Grid<u8> {
+    /// The bytes of the input, one row per line.
+    ///
+    /// # Panics
+    /// If the lines have different lengths.
+    pub fn parse(input: &str) -> Self {
+        Self::parse_with(input, |c| c)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// A grid with the value of every cell computed from its `(row, col)`.
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|row| (0..width).map(move |col| (row, col)))
+                .map(|(row, col)| cell(row, col))
+                .collect(),
+        }
+    }
+
+    /// The input, one row per line, with every byte converted by `cell`.
+    ///
+    /// # Panics
+    /// If the lines have different lengths.
+    pub fn parse_with(input: &str, mut cell: impl FnMut(u8) -> T) -> Self {
+        let mut grid = Grid {
+            width: 0,
+            height: 0,
+            cells: vec![],
+        };
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            if grid.height == 0 {
+                grid.width = line.len();
+            }
+            assert_eq!(line.len(), grid.width, "row {} is ragged", grid.height);
+            grid.cells.extend(line.bytes().map(&mut cell));
+            grid.height += 1;
+        }
+        grid
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (0..self.width as i64).contains(&p.x) && (0..self.height as i64).contains(&p.y)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.contains(p).then(|| &self.cells[self.offset(p)])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if !self.contains(p) {
+            return None;
+        }
+        let offset = self.offset(p);
+        Some(&mut self.cells[offset])
+    }
+
+    /// All points, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i64;
+        (0..self.height as i64).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// The first point, row by row, whose value matches.
+    pub fn find(&self, mut matches: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.points().find(|&p| matches(&self[p]))
+    }
+
+    /// The orthogonal neighbors of `p` inside the grid.
+    pub fn neighbors4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbors4().filter(|&n| self.contains(n))
+    }
+
+    /// The neighbors of `p` inside the grid, diagonals included.
+    pub fn neighbors8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbors8().filter(|&n| self.contains(n))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    /// The grid mirrored along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |row, col| self[(col, row)].clone())
+    }
+
+    /// The grid turned a quarter clockwise: the first column, read bottom to top, becomes the
+    /// first row.
+    pub fn rotate_right(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |row, col| {
+            self[(self.height - 1 - col, row)].clone()
+        })
+    }
+
+    /// The grid turned a quarter counterclockwise: the last column, read top to bottom, becomes
+    /// the first row.
+    pub fn rotate_left(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |row, col| {
+            self[(col, self.width - 1 - row)].clone()
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn offset(&self, p: Point) -> usize {
+        p.y as usize * self.width + p.x as usize
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        assert!(self.contains(p), "{p:?} is outside the grid");
+        &self.cells[self.offset(p)]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        assert!(self.contains(p), "{p:?} is outside the grid");
+        let offset = self.offset(p);
+        &mut self.cells[offset]
+    }
+}
+
+/// Cells by `(row, col)`, the order of the input.
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(
+            row < self.height && col < self.width,
+            "{:?} is outside the grid",
+            (row, col)
+        );
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(
+            row < self.height && col < self.width,
+            "{:?} is outside the grid",
+            (row, col)
+        );
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+impl fmt::Display for Grid<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+/// `#` for set cells and `.` for the others.
+impl fmt::Display for Grid<bool> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            let line: String = row.iter().map(|&set| if set { '#' } else { '.' }).collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+    use crate::utils::point::Point;
+
+    const INPUT: &str = "#.S\n..#\n";
+
+    #[test]
+    fn parses_inputs() {
+        let grid = Grid::parse(INPUT);
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid.find(|&c| c == b'S'), Some(Point::new(2, 0)));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.clone().get_mut(Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), INPUT);
+    }
+
+    #[test]
+    fn finds_neighbors_inside() {
+        let grid = Grid::parse(INPUT);
+        assert_eq!(grid.neighbors4(Point::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbors8(Point::new(1, 0)).count(), 5);
+
+        let walls = grid.map(|&c| c == b'#');
+        assert_eq!(walls.points().filter(|&p| walls[p]).count(), 2);
+    }
+
+    #[test]
+    fn indexes_by_row_and_column() {
+        let mut grid = Grid::parse(INPUT);
+        assert_eq!(grid[(0, 2)], b'S');
+        assert_eq!(grid[(0, 2)], grid[Point::new(2, 0)]);
+        grid[(1, 0)] = b'#';
+        assert_eq!(grid.row(1), b"#.#");
+        assert_eq!(grid.rows().count(), 2);
+    }
+
+    #[test]
+    fn rotates_and_transposes() {
+        let grid = Grid::parse(INPUT);
+        assert_eq!(grid.transpose().to_string(), "#.\n..\nS#\n");
+        assert_eq!(grid.rotate_right().to_string(), ".#\n..\n#S\n");
+        assert_eq!(grid.rotate_left().to_string(), "S#\n..\n#.\n");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(
+            grid.rotate_right().rotate_right().rotate_right(),
+            grid.rotate_left()
+        );
+    }
+
+    #[test]
+    fn displays_other_cells() {
+        let walls = Grid::parse(INPUT).map(|&c| c == b'#');
+        assert_eq!(walls.to_string(), "#..\n..#\n");
+        let chars = Grid::from_fn(2, 1, |_, col| char::from(b'a' + col as u8));
+        assert_eq!(chars.to_string(), "ab\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 is ragged")]
+    fn rejects_ragged_rows() {
+        Grid::parse("..\n.\n");
+    }
+}
@@ -0,0 +1,290 @@
+/// Points and dense voxel grids in three dimensions.
+///
+/// `Point3` uses signed coordinates, so neighbors of boundary cells can be computed freely and
+/// filtered with [`Grid3::contains`] or [`Bounds3::contains`].
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3::new(0, 0, 0);
+
+    /// Unit steps along each axis, in both directions.
+    pub const DIRECTIONS6: [Point3; 6] = [
+        Point3::new(1, 0, 0),
+        Point3::new(-1, 0, 0),
+        Point3::new(0, 1, 0),
+        Point3::new(0, -1, 0),
+        Point3::new(0, 0, 1),
+        Point3::new(0, 0, -1),
+    ];
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(&self, other: Point3) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// The 6 face neighbors.
+    pub fn neighbors6(self) -> impl Iterator<Item = Point3> {
+        Point3::DIRECTIONS6.into_iter().map(move |d| self + d)
+    }
+
+    /// The 26 face, edge and corner neighbors.
+    pub fn neighbors26(self) -> impl Iterator<Item = Point3> {
+        (-1..=1)
+            .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| Point3::new(x, y, z))))
+            .filter(|d| *d != Point3::ORIGIN)
+            .map(move |d| self + d)
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Self::Output {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An inclusive axis-aligned bounding box, e.g. of a sparse set of voxels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds3 {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Bounds3 {
+    /// The smallest box containing all `points`, or `None` if there are none.
+    pub fn from_points(points: impl IntoIterator<Item = Point3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(
+            Bounds3 {
+                min: first,
+                max: first,
+            },
+            |b, p| Bounds3 {
+                min: Point3::new(b.min.x.min(p.x), b.min.y.min(p.y), b.min.z.min(p.z)),
+                max: Point3::new(b.max.x.max(p.x), b.max.y.max(p.y), b.max.z.max(p.z)),
+            },
+        ))
+    }
+
+    pub fn contains(&self, p: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    /// The box grown by `amount` in every direction, e.g. to flood fill around a shape.
+    pub fn expand(&self, amount: i64) -> Self {
+        let delta = Point3::new(amount, amount, amount);
+        Bounds3 {
+            min: self.min - delta,
+            max: self.max + delta,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A dense grid of voxels covering `0..width`, `0..height` and `0..depth`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid3<T> {
+    width: usize,
+    height: usize,
+    depth: usize,
+    data: Vec<T>,
+}
+
+impl<T: Clone> Grid3<T> {
+    pub fn new(width: usize, height: usize, depth: usize, fill: T) -> Self {
+        Grid3 {
+            width,
+            height,
+            depth,
+            data: vec![fill; width * height * depth],
+        }
+    }
+}
+
+impl<T> Grid3<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn contains(&self, p: Point3) -> bool {
+        self.index_of(p).is_some()
+    }
+
+    fn index_of(&self, p: Point3) -> Option<usize> {
+        let x = usize::try_from(p.x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(p.y).ok().filter(|y| *y < self.height)?;
+        let z = usize::try_from(p.z).ok().filter(|z| *z < self.depth)?;
+        Some((z * self.height + y) * self.width + x)
+    }
+
+    fn point_of(&self, index: usize) -> Point3 {
+        let (x, rest) = (index % self.width, index / self.width);
+        let (y, z) = (rest % self.height, rest / self.height);
+        #[allow(clippy::cast_possible_wrap)]
+        Point3::new(x as i64, y as i64, z as i64)
+    }
+
+    pub fn get(&self, p: Point3) -> Option<&T> {
+        self.index_of(p).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point3) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.data[i])
+    }
+
+    /// Sets the voxel at `p`, returning `false` if it is out of bounds.
+    pub fn set(&mut self, p: Point3, value: T) -> bool {
+        self.get_mut(p).map(|v| *v = value).is_some()
+    }
+
+    /// The face neighbors of `p` that are inside the grid.
+    pub fn neighbors6(&self, p: Point3) -> impl Iterator<Item = Point3> + '_ {
+        p.neighbors6().filter(|n| self.contains(*n))
+    }
+
+    /// The face, edge and corner neighbors of `p` that are inside the grid.
+    pub fn neighbors26(&self, p: Point3) -> impl Iterator<Item = Point3> + '_ {
+        p.neighbors26().filter(|n| self.contains(*n))
+    }
+
+    /// All points of the grid, x varying fastest.
+    pub fn points(&self) -> impl Iterator<Item = Point3> + '_ {
+        (0..self.data.len()).map(|i| self.point_of(i))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point3, &T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (self.point_of(i), v))
+    }
+}
+
+impl<T> Index<Point3> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, p: Point3) -> &Self::Output {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside of the grid."))
+    }
+}
+
+impl<T> IndexMut<Point3> for Grid3<T> {
+    fn index_mut(&mut self, p: Point3) -> &mut Self::Output {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside of the grid."))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Bounds3, Grid3, Point3};
+
+    #[test]
+    fn iterates_neighbors() {
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(p.neighbors6().count(), 6);
+        assert_eq!(p.neighbors26().count(), 26);
+        assert_eq!(p.neighbors26().all(|n| n != p), true);
+        assert_eq!(p.manhattan(Point3::ORIGIN), 6);
+        assert_eq!(p + Point3::new(1, 1, 1) * 2, Point3::new(3, 4, 5));
+    }
+
+    #[test]
+    fn handles_grid_bounds() {
+        let mut grid = Grid3::new(2, 3, 4, 0u8);
+        assert_eq!(grid.set(Point3::new(1, 2, 3), 7), true);
+        assert_eq!(grid.set(Point3::new(2, 0, 0), 7), false);
+        assert_eq!(grid.get(Point3::new(-1, 0, 0)), None);
+        assert_eq!(grid[Point3::new(1, 2, 3)], 7);
+
+        assert_eq!(grid.neighbors6(Point3::ORIGIN).count(), 3);
+        assert_eq!(grid.neighbors26(Point3::ORIGIN).count(), 7);
+        assert_eq!(grid.points().count(), 24);
+        assert_eq!(
+            grid.iter().find(|(_, v)| **v == 7).map(|(p, _)| p),
+            Some(Point3::new(1, 2, 3))
+        );
+    }
+
+    #[test]
+    fn computes_bounds() {
+        let bounds = Bounds3::from_points([Point3::new(1, -2, 3), Point3::new(-1, 5, 0)]).unwrap();
+        assert_eq!(bounds.min, Point3::new(-1, -2, 0));
+        assert_eq!(bounds.max, Point3::new(1, 5, 3));
+        assert_eq!(bounds.contains(Point3::new(0, 0, 0)), true);
+        assert_eq!(bounds.contains(Point3::new(0, 0, 4)), false);
+        assert_eq!(bounds.expand(1).contains(Point3::new(0, 0, 4)), true);
+        assert_eq!(Bounds3::from_points([]), None);
+    }
+}
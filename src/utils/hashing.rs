@@ -0,0 +1,99 @@
+/// Fast hash containers for memoization and visited sets.
+///
+/// The standard library hashes with SipHash, which resists collision attacks but is slow for
+/// the small integer and tuple keys of search states. [`FastMap`] and [`FastSet`] use FxHash
+/// instead, so they are drop-in replacements created with `FastMap::default()`.
+///
+/// States made of a few small numbers hash even faster as a single `u64`, see
+/// [`compact_state!`](crate::compact_state).
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasher, Hash},
+};
+
+pub use rustc_hash::{FxBuildHasher, FxHasher};
+
+pub type FastMap<K, V> = HashMap<K, V, FxBuildHasher>;
+pub type FastSet<T> = HashSet<T, FxBuildHasher>;
+
+pub fn map_with_capacity<K, V>(capacity: usize) -> FastMap<K, V> {
+    FastMap::with_capacity_and_hasher(capacity, FxBuildHasher)
+}
+
+pub fn set_with_capacity<T>(capacity: usize) -> FastSet<T> {
+    FastSet::with_capacity_and_hasher(capacity, FxBuildHasher)
+}
+
+/// Hash a value with FxHash, e.g. to key a cache on a large state by its hash.
+pub fn hash_one<T: Hash>(value: &T) -> u64 {
+    FxBuildHasher.hash_one(value)
+}
+
+/// The lowest `bits` bits set, used by [`compact_state!`](crate::compact_state).
+#[doc(hidden)]
+pub const fn mask(bits: u32) -> u64 {
+    if bits >= 64 {
+        u64::MAX
+    } else {
+        (1 << bits) - 1
+    }
+}
+
+/// Pack small integers into one `u64` key, each given as `value => bits`. The first value ends
+/// up in the highest bits, so keys sort like the tuple of their values.
+///
+/// Values must fit in their bits. Negative values are packed as two's complement, so a field
+/// that holds `-8..8` needs 4 bits, but can't also hold `8..16`. The bits of all fields may add
+/// up to at most 64, which is checked at compile time.
+///
+/// ```ignore
+/// use everybody_codes::{compact_state, utils::hashing::FastMap};
+///
+/// let mut seen: FastMap<u64, usize> = FastMap::default();
+/// seen.insert(compact_state!(x => 16, y => 16, dir => 2, has_key => 1), steps);
+/// ```
+#[macro_export]
+macro_rules! compact_state {
+    ($($value:expr => $bits:expr),+ $(,)?) => {{
+        const _: () = assert!(0 $(+ $bits)+ <= 64, "compact_state! needs more than 64 bits");
+        let mut key: u64 = 0;
+        $(
+            key = key.checked_shl($bits).unwrap_or(0)
+                | ($value as u64 & $crate::utils::hashing::mask($bits));
+        )+
+        key
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{hash_one, FastMap, FastSet};
+
+    #[test]
+    fn packs_states() {
+        assert_eq!(compact_state!(0x12 => 8, 0x3 => 4, true => 1), 0x247);
+        assert_eq!(compact_state!(-1i32 => 4, 2u8 => 4), 0xf2);
+        assert_eq!(compact_state!(u64::MAX => 64), u64::MAX);
+
+        // keys sort like the tuple of their values.
+        let small = compact_state!(1 => 16, 900 => 16);
+        let large = compact_state!(2 => 16, 0 => 16);
+        assert!(small < large);
+    }
+
+    #[test]
+    fn uses_fast_containers() {
+        let mut seen: FastSet<(i32, i32)> = FastSet::default();
+        assert!(seen.insert((1, 2)));
+        assert!(!seen.insert((1, 2)));
+
+        let mut memo: FastMap<u64, usize> = super::map_with_capacity(4);
+        memo.insert(compact_state!(3 => 8, 4 => 8), 7);
+        assert_eq!(memo.get(&0x0304), Some(&7));
+
+        assert_eq!(hash_one(&(1, 2)), hash_one(&(1, 2)));
+        assert_ne!(hash_one(&(1, 2)), hash_one(&(2, 1)));
+    }
+}
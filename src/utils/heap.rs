@@ -0,0 +1,247 @@
+/// A binary min-heap keyed by item, supporting decrease-key.
+///
+/// Each key appears at most once, so shortest path searches can lower a node's distance in
+/// place instead of pushing duplicates and skipping stale entries when popping.
+/// Use [`std::cmp::Reverse`] priorities for a max-heap.
+use std::{collections::HashMap, hash::Hash};
+
+#[derive(Clone, Debug)]
+pub struct IndexedHeap<K, P> {
+    entries: Vec<(K, P)>,
+    positions: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Default for IndexedHeap<K, P> {
+    fn default() -> Self {
+        IndexedHeap::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedHeap<K, P> {
+    pub fn new() -> Self {
+        IndexedHeap {
+            entries: vec![],
+            positions: HashMap::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        IndexedHeap {
+            entries: Vec::with_capacity(capacity),
+            positions: HashMap::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    /// The current priority of `key`, if it is in the heap.
+    pub fn priority(&self, key: &K) -> Option<&P> {
+        self.positions.get(key).map(|i| &self.entries[*i].1)
+    }
+
+    /// The entry with the lowest priority.
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.entries.first().map(|(k, p)| (k, p))
+    }
+
+    /// Inserts `key`, or changes its priority if it is already present.
+    /// Returns the previous priority.
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if let Some(&index) = self.positions.get(&key) {
+            let previous = std::mem::replace(&mut self.entries[index].1, priority);
+            self.restore(index);
+            return Some(previous);
+        }
+
+        let index = self.entries.len();
+        self.positions.insert(key.clone(), index);
+        self.entries.push((key, priority));
+        self.sift_up(index);
+        None
+    }
+
+    /// Inserts `key`, or lowers its priority if `priority` is lower than the current one.
+    /// Returns `true` if the heap changed, the usual relaxation step of Dijkstra's algorithm.
+    pub fn decrease_key(&mut self, key: K, priority: P) -> bool {
+        match self.priority(&key) {
+            Some(current) if *current <= priority => false,
+            _ => {
+                self.push(key, priority);
+                true
+            }
+        }
+    }
+
+    /// Removes and returns the entry with the lowest priority.
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        self.remove_at(0)
+    }
+
+    /// Removes `key` from the heap, returning its priority.
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let index = *self.positions.get(key)?;
+        self.remove_at(index).map(|(_, p)| p)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.positions.clear();
+    }
+
+    fn remove_at(&mut self, index: usize) -> Option<(K, P)> {
+        if index >= self.entries.len() {
+            return None;
+        }
+
+        let last = self.entries.len() - 1;
+        self.swap(index, last);
+        let (key, priority) = self.entries.pop()?;
+        self.positions.remove(&key);
+
+        if index < self.entries.len() {
+            self.restore(index);
+        }
+
+        Some((key, priority))
+    }
+
+    /// Moves the entry at `index` up or down until the heap property holds again.
+    fn restore(&mut self, index: usize) {
+        let index = self.sift_up(index);
+        self.sift_down(index);
+    }
+
+    fn sift_up(&mut self, mut index: usize) -> usize {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.entries[index].1 >= self.entries[parent].1 {
+                break;
+            }
+            self.swap(index, parent);
+            index = parent;
+        }
+        index
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let mut smallest = index;
+
+            if left < self.entries.len() && self.entries[left].1 < self.entries[smallest].1 {
+                smallest = left;
+            }
+            if right < self.entries.len() && self.entries[right].1 < self.entries[smallest].1 {
+                smallest = right;
+            }
+            if smallest == index {
+                break;
+            }
+
+            self.swap(index, smallest);
+            index = smallest;
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.entries.swap(a, b);
+        for i in [a, b] {
+            if let Some(position) = self.positions.get_mut(&self.entries[i].0) {
+                *position = i;
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> FromIterator<(K, P)> for IndexedHeap<K, P> {
+    fn from_iter<T: IntoIterator<Item = (K, P)>>(iter: T) -> Self {
+        let mut heap = IndexedHeap::new();
+        for (key, priority) in iter {
+            heap.push(key, priority);
+        }
+        heap
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::IndexedHeap;
+    use std::cmp::Reverse;
+
+    #[test]
+    fn pops_in_priority_order() {
+        let mut heap: IndexedHeap<char, u32> = [('a', 5), ('b', 1), ('c', 3), ('d', 4), ('e', 2)]
+            .into_iter()
+            .collect();
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.peek(), Some((&'b', &1)));
+
+        let order: Vec<char> = std::iter::from_fn(|| heap.pop().map(|(k, _)| k)).collect();
+        assert_eq!(order, vec!['b', 'e', 'c', 'd', 'a']);
+        assert_eq!(heap.is_empty(), true);
+    }
+
+    #[test]
+    fn decreases_keys() {
+        let mut heap = IndexedHeap::new();
+        heap.push("far", 10);
+        heap.push("near", 5);
+
+        assert_eq!(heap.decrease_key("far", 20), false);
+        assert_eq!(heap.decrease_key("far", 1), true);
+        assert_eq!(heap.decrease_key("new", 7), true);
+        assert_eq!(heap.priority(&"far"), Some(&1));
+        assert_eq!(heap.pop(), Some(("far", 1)));
+        assert_eq!(heap.pop(), Some(("near", 5)));
+        assert_eq!(heap.pop(), Some(("new", 7)));
+    }
+
+    #[test]
+    fn updates_and_removes_keys() {
+        let mut heap = IndexedHeap::new();
+        for (key, priority) in [(1, 1), (2, 2), (3, 3), (4, 4)] {
+            heap.push(key, Reverse(priority));
+        }
+
+        assert_eq!(heap.push(4, Reverse(0)), Some(Reverse(4)));
+        assert_eq!(heap.remove(&3), Some(Reverse(3)));
+        assert_eq!(heap.remove(&3), None);
+        assert_eq!(heap.contains(&3), false);
+        assert_eq!(heap.pop(), Some((2, Reverse(2))));
+        assert_eq!(heap.pop(), Some((1, Reverse(1))));
+        assert_eq!(heap.pop(), Some((4, Reverse(0))));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn runs_dijkstra() {
+        let edges = [(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 5)];
+        let mut dist = [u32::MAX; 4];
+        let mut heap = IndexedHeap::new();
+        heap.push(0, 0);
+
+        while let Some((node, d)) = heap.pop() {
+            dist[node] = d;
+            for &(_, to, w) in edges.iter().filter(|(from, _, _)| *from == node) {
+                if dist[to] == u32::MAX {
+                    heap.decrease_key(to, d + w);
+                }
+            }
+        }
+
+        assert_eq!(dist, [0, 3, 1, 4]);
+    }
+}
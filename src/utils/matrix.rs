@@ -0,0 +1,345 @@
+/// Matrices over arbitrary semirings, with exponentiation by squaring.
+///
+/// Linear recurrences and "repeat this step 10^12 times" parts become matrix powers: use
+/// [`PlusTimes`] or [`PlusTimesMod`] to count paths, [`MinPlus`] or [`MaxPlus`] for shortest
+/// or longest paths of an exact length and [`OrAnd`] for reachability.
+use std::{
+    marker::PhantomData,
+    ops::{Add, Index, IndexMut, Mul},
+};
+
+/// A set with an associative addition and multiplication, where multiplication distributes
+/// over addition, `zero` is the additive identity and `one` the multiplicative identity.
+pub trait Semiring {
+    type Elem: Clone;
+
+    fn zero() -> Self::Elem;
+    fn one() -> Self::Elem;
+    fn add(a: &Self::Elem, b: &Self::Elem) -> Self::Elem;
+    fn mul(a: &Self::Elem, b: &Self::Elem) -> Self::Elem;
+}
+
+/// Ordinary arithmetic on a numeric type.
+#[derive(Clone, Copy, Debug)]
+pub struct PlusTimes<T>(PhantomData<T>);
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T> + From<u8>> Semiring for PlusTimes<T> {
+    type Elem = T;
+
+    fn zero() -> T {
+        T::from(0)
+    }
+
+    fn one() -> T {
+        T::from(1)
+    }
+
+    fn add(a: &T, b: &T) -> T {
+        *a + *b
+    }
+
+    fn mul(a: &T, b: &T) -> T {
+        *a * *b
+    }
+}
+
+/// Arithmetic modulo `M`.
+#[derive(Clone, Copy, Debug)]
+pub struct PlusTimesMod<const M: u64>;
+
+impl<const M: u64> Semiring for PlusTimesMod<M> {
+    type Elem = u64;
+
+    fn zero() -> u64 {
+        0
+    }
+
+    fn one() -> u64 {
+        1 % M
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn add(a: &u64, b: &u64) -> u64 {
+        ((u128::from(*a) + u128::from(*b)) % u128::from(M)) as u64
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn mul(a: &u64, b: &u64) -> u64 {
+        ((u128::from(*a) * u128::from(*b)) % u128::from(M)) as u64
+    }
+}
+
+/// The tropical semiring: addition is `min`, multiplication is `+` and `i64::MAX` is
+/// infinity (no path).
+#[derive(Clone, Copy, Debug)]
+pub struct MinPlus;
+
+impl Semiring for MinPlus {
+    type Elem = i64;
+
+    fn zero() -> i64 {
+        i64::MAX
+    }
+
+    fn one() -> i64 {
+        0
+    }
+
+    fn add(a: &i64, b: &i64) -> i64 {
+        *a.min(b)
+    }
+
+    fn mul(a: &i64, b: &i64) -> i64 {
+        if *a == i64::MAX || *b == i64::MAX {
+            i64::MAX
+        } else {
+            a + b
+        }
+    }
+}
+
+/// Addition is `max`, multiplication is `+` and `i64::MIN` is negative infinity (no path).
+#[derive(Clone, Copy, Debug)]
+pub struct MaxPlus;
+
+impl Semiring for MaxPlus {
+    type Elem = i64;
+
+    fn zero() -> i64 {
+        i64::MIN
+    }
+
+    fn one() -> i64 {
+        0
+    }
+
+    fn add(a: &i64, b: &i64) -> i64 {
+        *a.max(b)
+    }
+
+    fn mul(a: &i64, b: &i64) -> i64 {
+        if *a == i64::MIN || *b == i64::MIN {
+            i64::MIN
+        } else {
+            a + b
+        }
+    }
+}
+
+/// Boolean reachability: addition is `||`, multiplication is `&&`.
+#[derive(Clone, Copy, Debug)]
+pub struct OrAnd;
+
+impl Semiring for OrAnd {
+    type Elem = bool;
+
+    fn zero() -> bool {
+        false
+    }
+
+    fn one() -> bool {
+        true
+    }
+
+    fn add(a: &bool, b: &bool) -> bool {
+        *a || *b
+    }
+
+    fn mul(a: &bool, b: &bool) -> bool {
+        *a && *b
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A dense row-major matrix over the semiring `S`.
+pub struct Matrix<S: Semiring> {
+    rows: usize,
+    cols: usize,
+    data: Vec<S::Elem>,
+}
+
+impl<S: Semiring> Clone for Matrix<S> {
+    fn clone(&self) -> Self {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<S: Semiring> std::fmt::Debug for Matrix<S>
+where
+    S::Elem: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.data.chunks(self.cols)).finish()
+    }
+}
+
+impl<S: Semiring> PartialEq for Matrix<S>
+where
+    S::Elem: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows && self.cols == other.cols && self.data == other.data
+    }
+}
+
+impl<S: Semiring> Matrix<S> {
+    /// A matrix filled with the semiring's zero.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix {
+            rows,
+            cols,
+            data: vec![S::zero(); rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut matrix = Matrix::zeros(n, n);
+        for i in 0..n {
+            matrix[(i, i)] = S::one();
+        }
+        matrix
+    }
+
+    /// Creates a matrix from rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<S::Elem>>) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "all matrix rows must have the same length."
+        );
+
+        Matrix {
+            rows: rows.len(),
+            cols,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, row: usize) -> &[S::Elem] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// Raises a square matrix to the power `exp` in `O(n^3 log exp)`.
+    pub fn pow(&self, mut exp: u64) -> Self {
+        assert_eq!(self.rows, self.cols, "only square matrices have powers.");
+
+        let mut result = Matrix::identity(self.rows);
+        let mut base = self.clone();
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+
+        result
+    }
+
+    /// Multiplies the matrix by a column vector.
+    pub fn mul_vec(&self, vector: &[S::Elem]) -> Vec<S::Elem> {
+        assert_eq!(self.cols, vector.len(), "vector length must match columns.");
+
+        (0..self.rows)
+            .map(|row| {
+                self.row(row)
+                    .iter()
+                    .zip(vector)
+                    .fold(S::zero(), |acc, (a, b)| S::add(&acc, &S::mul(a, b)))
+            })
+            .collect()
+    }
+}
+
+impl<S: Semiring> Mul for &Matrix<S> {
+    type Output = Matrix<S>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert_eq!(self.cols, rhs.rows, "matrix dimensions do not match.");
+
+        let mut result = Matrix::zeros(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = &self[(i, k)];
+                for j in 0..rhs.cols {
+                    let product = S::mul(a, &rhs[(k, j)]);
+                    result[(i, j)] = S::add(&result[(i, j)], &product);
+                }
+            }
+        }
+        result
+    }
+}
+
+impl<S: Semiring> Index<(usize, usize)> for Matrix<S> {
+    type Output = S::Elem;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl<S: Semiring> IndexMut<(usize, usize)> for Matrix<S> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Matrix, MaxPlus, MinPlus, OrAnd, PlusTimes, PlusTimesMod};
+
+    #[test]
+    fn computes_fibonacci() {
+        let fib: Matrix<PlusTimes<u64>> = Matrix::from_rows(vec![vec![1, 1], vec![1, 0]]);
+        assert_eq!(fib.pow(10)[(0, 1)], 55);
+        assert_eq!(fib.pow(0), Matrix::identity(2));
+
+        let fib: Matrix<PlusTimesMod<1_000_000_007>> =
+            Matrix::from_rows(vec![vec![1, 1], vec![1, 0]]);
+        assert_eq!(fib.pow(1_000_000_000_000)[(0, 1)], 730_695_249);
+    }
+
+    #[test]
+    fn finds_paths_of_exact_length() {
+        const INF: i64 = i64::MAX;
+        let graph: Matrix<MinPlus> =
+            Matrix::from_rows(vec![vec![INF, 1, 5], vec![INF, INF, 1], vec![1, INF, INF]]);
+        // 0 -> 1 -> 2 -> 0 -> 1
+        assert_eq!(graph.pow(4)[(0, 1)], 4);
+        assert_eq!(graph.pow(2)[(0, 0)], 6);
+
+        let graph: Matrix<MaxPlus> = Matrix::from_rows(vec![vec![1, 3], vec![2, i64::MIN]]);
+        assert_eq!(graph.pow(3)[(0, 0)], 6);
+    }
+
+    #[test]
+    fn checks_reachability() {
+        let graph: Matrix<OrAnd> = Matrix::from_rows(vec![
+            vec![false, true, false],
+            vec![false, false, true],
+            vec![false, false, false],
+        ]);
+        assert_eq!(graph.pow(2).row(0), &[false, false, true]);
+        assert_eq!(graph.pow(3).row(0), &[false, false, false]);
+        assert_eq!(graph.mul_vec(&[false, true, true]), vec![true, true, false]);
+    }
+}
@@ -0,0 +1,21 @@
+//! Reusable helpers for solutions, available as `everybody_codes::utils`.
+
+pub mod arena;
+pub mod bitgrid;
+pub mod cycle;
+pub mod dsu;
+pub mod gen;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod grid3;
+pub mod hashing;
+pub mod heap;
+pub mod matrix;
+pub mod modint;
+pub mod numbers;
+pub mod parse;
+pub mod pathfinding;
+pub mod point;
+pub mod strings;
+pub mod sweep;
@@ -0,0 +1,229 @@
+/// Integers modulo `M`, for parts asking for a count "modulo 1000000007" or similar.
+///
+/// Values are always reduced, so sums and products can be written as usual. Operators also
+/// accept plain integers on the right, e.g. `x * 2 + 1`. Division multiplies by the
+/// [inverse](ModInt::inv), which exists when the divisor and `M` are coprime.
+use std::{
+    fmt::Display,
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(value: u64) -> Self {
+        ModInt(value % M)
+    }
+
+    /// The representative in `0..M`.
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    /// `self` to the power of `exp`, by squaring.
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut base = self;
+        let mut result = ModInt::new(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// The multiplicative inverse, `None` if `self` and `M` are not coprime.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    pub fn inv(self) -> Option<Self> {
+        // extended Euclid on (value, M), tracking the coefficient of value.
+        let (mut r0, mut r1) = (i128::from(M), i128::from(self.0));
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        (r0 == 1).then(|| ModInt(t0.rem_euclid(i128::from(M)) as u64))
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        ModInt::new(value)
+    }
+}
+
+impl<const M: u64> From<u32> for ModInt<M> {
+    fn from(value: u32) -> Self {
+        ModInt::new(u64::from(value))
+    }
+}
+
+impl<const M: u64> From<usize> for ModInt<M> {
+    fn from(value: usize) -> Self {
+        ModInt::new(value as u64)
+    }
+}
+
+impl<const M: u64> From<i64> for ModInt<M> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from(value: i64) -> Self {
+        ModInt(i128::from(value).rem_euclid(i128::from(M)) as u64)
+    }
+}
+
+impl<const M: u64> From<i32> for ModInt<M> {
+    fn from(value: i32) -> Self {
+        ModInt::from(i64::from(value))
+    }
+}
+
+impl<const M: u64> Display for ModInt<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl<const M: u64, T: Into<ModInt<M>>> Add<T> for ModInt<M> {
+    type Output = Self;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn add(self, rhs: T) -> Self {
+        let sum = u128::from(self.0) + u128::from(rhs.into().0);
+        ModInt((sum % u128::from(M)) as u64)
+    }
+}
+
+impl<const M: u64, T: Into<ModInt<M>>> Sub<T> for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: T) -> Self {
+        self + -rhs.into()
+    }
+}
+
+impl<const M: u64, T: Into<ModInt<M>>> Mul<T> for ModInt<M> {
+    type Output = Self;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn mul(self, rhs: T) -> Self {
+        let product = u128::from(self.0) * u128::from(rhs.into().0);
+        ModInt((product % u128::from(M)) as u64)
+    }
+}
+
+impl<const M: u64, T: Into<ModInt<M>>> Div<T> for ModInt<M> {
+    type Output = Self;
+
+    /// # Panics
+    /// If the divisor has no inverse modulo `M`.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: T) -> Self {
+        let inverse = rhs
+            .into()
+            .inv()
+            .expect("divisor is not invertible modulo M");
+        self * inverse
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        if self.0 == 0 {
+            self
+        } else {
+            ModInt(M - self.0)
+        }
+    }
+}
+
+impl<const M: u64, T: Into<ModInt<M>>> AddAssign<T> for ModInt<M> {
+    fn add_assign(&mut self, rhs: T) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const M: u64, T: Into<ModInt<M>>> SubAssign<T> for ModInt<M> {
+    fn sub_assign(&mut self, rhs: T) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const M: u64, T: Into<ModInt<M>>> MulAssign<T> for ModInt<M> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const M: u64, T: Into<ModInt<M>>> DivAssign<T> for ModInt<M> {
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ModInt(0), |acc, x| acc + x)
+    }
+}
+
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ModInt::new(1), |acc, x| acc * x)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ModInt;
+
+    type Mod = ModInt<1_000_000_007>;
+
+    #[test]
+    fn reduces_arithmetic() {
+        let a = Mod::new(1_000_000_006);
+        assert_eq!((a + 2).value(), 1);
+        assert_eq!((Mod::new(1) - 2).value(), 1_000_000_006);
+        assert_eq!((a * a).value(), 1);
+        assert_eq!(Mod::from(-3i64).value(), 1_000_000_004);
+        assert_eq!((-Mod::new(0)).value(), 0);
+
+        let mut x = Mod::new(5);
+        x *= 3;
+        x -= 1u32;
+        x += Mod::new(6);
+        assert_eq!(x.value(), 20);
+
+        assert_eq!(
+            (1..=20u64).map(Mod::new).product::<Mod>().value(),
+            146_326_063
+        );
+        assert_eq!([a, a, Mod::new(2)].into_iter().sum::<Mod>().value(), 0);
+    }
+
+    #[test]
+    fn computes_powers_and_inverses() {
+        assert_eq!(Mod::new(2).pow(0).value(), 1);
+        assert_eq!(Mod::new(2).pow(1_000_000_006).value(), 1);
+        assert_eq!(Mod::new(3).pow(200).value(), 136_318_165);
+
+        let inverse = Mod::new(123_456).inv().unwrap();
+        assert_eq!((inverse * 123_456u64).value(), 1);
+        assert_eq!((Mod::new(10) / 4u64 * 4u64).value(), 10);
+        assert_eq!(Mod::new(0).inv(), None);
+
+        // inverses modulo a composite only exist for coprime values.
+        assert_eq!(ModInt::<12>::new(5).inv(), Some(ModInt::new(5)));
+        assert_eq!(ModInt::<12>::new(4).inv(), None);
+    }
+}
@@ -0,0 +1,151 @@
+/// Number theory for quests about repeating cycles and gear ratios: common divisors and
+/// multiples of any integer type, powers and inverses modulo a number, and the Chinese remainder
+/// theorem to find when cycles with different lengths and offsets line up.
+///
+/// ```ignore
+/// use everybody_codes::utils::numbers::{crt, lcm_all};
+///
+/// let period = lcm_all(gears.iter().map(|gear| gear.teeth));
+/// let (first, every) = crt(&[(2, 3), (3, 5), (2, 7)])?;
+/// ```
+use std::ops::{Div, Mul, Rem, Sub};
+
+/// The integer types [`gcd`] and [`lcm`] work with.
+pub trait Integer:
+    Copy + Ord + Div<Output = Self> + Mul<Output = Self> + Rem<Output = Self> + Sub<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })*
+    };
+}
+
+integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+fn non_negative<T: Integer>(value: T) -> T {
+    if value < T::ZERO {
+        T::ZERO - value
+    } else {
+        value
+    }
+}
+
+/// The greatest common divisor, never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    non_negative(a)
+}
+
+/// The least common multiple, never negative. 0 if either number is.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    non_negative(a / gcd(a, b) * b)
+}
+
+/// The first step at which cycles of all the given lengths line up again.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ONE, lcm)
+}
+
+/// `base^exp mod modulus` by repeated squaring.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    (old_r, old_x, old_y)
+}
+
+/// The `x` in `0..modulus` with `a * x = 1 (mod modulus)`, `None` if `a` and `modulus` are not
+/// coprime.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = ext_gcd(a.rem_euclid(modulus), modulus);
+    (g.abs() == 1).then(|| (x * g).rem_euclid(modulus))
+}
+
+/// The smallest `x >= 0` with `x = r (mod m)` for every `(r, m)`, and the modulus it repeats
+/// with. The moduli don't need to be coprime. `None` if the congruences contradict each other.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0_i64, 1_i64), |(r1, m1), &(r2, m2)| {
+            let (g, p, _) = ext_gcd(m1, m2);
+            if (r2 - r1) % g != 0 {
+                return None;
+            }
+            let m = m1 / g * m2;
+            let step = i128::from((r2 - r1) / g) * i128::from(p) % i128::from(m2 / g);
+            let r = (i128::from(r1) + i128::from(m1) * step).rem_euclid(i128::from(m));
+            Some((r as i64, m))
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crt, ext_gcd, gcd, lcm, lcm_all, mod_inverse, mod_pow};
+
+    #[test]
+    fn lines_up_cycles() {
+        assert_eq!(gcd(12_u8, 18), 6);
+        assert_eq!(gcd(-12_i64, 18), 6);
+        assert_eq!(gcd(0_usize, 0), 0);
+        assert_eq!(lcm(-4_i32, 6), 12);
+        assert_eq!(lcm(0_u64, 6), 0);
+        assert_eq!(lcm_all([4_u128, 6, 10]), 60);
+        assert_eq!(lcm_all(Vec::<u32>::new()), 1);
+    }
+
+    #[test]
+    fn computes_remainders() {
+        assert_eq!(mod_pow(3, 200, 1_000_000_007), 136_318_165);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+
+        let (g, x, y) = ext_gcd(240, 46);
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 10), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+}
@@ -0,0 +1,125 @@
+/// Helpers for the parts of puzzle inputs that every quest parses the same way.
+use std::{fmt::Display, str::FromStr};
+
+/// All integers in `s`, e.g. `[3, -12]` for `x=3, y=-12`. A `-` only counts as a sign when it
+/// doesn't follow a digit, so `3-5` gives `[3, 5]`, and is dropped for unsigned `T`. Integers
+/// that don't fit `T` are skipped.
+pub fn ints<T: FromStr>(s: &str) -> Vec<T> {
+    let bytes = s.as_bytes();
+    let mut numbers = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let negative = bytes[i] == b'-'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_digit());
+        if !negative && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        // unsigned types don't parse the sign, so try again without it.
+        let number = s[start..i].parse().or_else(|e| match negative {
+            true => s[start + 1..i].parse(),
+            false => Err(e),
+        });
+        if let Ok(number) = number {
+            numbers.push(number);
+        }
+    }
+    numbers
+}
+
+/// Same as [`ints`].
+pub fn numbers<T: FromStr>(s: &str) -> Vec<T> {
+    ints(s)
+}
+
+/// The digits of `s`, skipping everything else.
+pub fn digits(s: &str) -> Vec<u32> {
+    s.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+
+/// The blocks of the input separated by blank lines.
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .map(|block| block.trim_matches('\n'))
+        .filter(|block| !block.is_empty())
+}
+
+/// Every non-empty line of the input parsed as `T`, or an error naming the first line that
+/// doesn't parse, e.g. ``line 3 `12x` is not a valid u32: invalid digit found in string``.
+pub fn lines_of<T: FromStr>(input: &str) -> Result<Vec<T>, String>
+where
+    T::Err: Display,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            line.parse().map_err(|e| {
+                format!(
+                    "line {} `{line}` is not a valid {}: {e}",
+                    i + 1,
+                    std::any::type_name::<T>()
+                )
+            })
+        })
+        .collect()
+}
+
+/// Like [`lines_of`], for inputs that are known to parse.
+///
+/// # Panics
+/// If a line doesn't parse, with the error of [`lines_of`].
+pub fn lines<T: FromStr>(input: &str) -> Vec<T>
+where
+    T::Err: Display,
+{
+    lines_of(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{blocks, digits, ints, lines, lines_of, numbers};
+
+    #[test]
+    fn finds_numbers() {
+        assert_eq!(ints::<i64>("x=3, y=-12"), vec![3, -12]);
+        assert_eq!(ints::<i64>("3-5 -7"), vec![3, 5, -7]);
+        assert_eq!(ints::<u32>("3-5 -7"), vec![3, 5, 7]);
+        assert_eq!(ints::<u8>("a 300 b 30"), vec![30]);
+        assert_eq!(numbers::<i32>("-1,-2"), vec![-1, -2]);
+        assert_eq!(digits("1a2b3"), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn splits_inputs() {
+        let input = "a\nb\n\nc\n";
+        assert_eq!(blocks(input).collect::<Vec<_>>(), vec!["a\nb", "c"]);
+        assert_eq!(lines::<u32>("1\n22\n\n333\n"), vec![1, 22, 333]);
+    }
+
+    #[test]
+    fn names_lines_that_dont_parse() {
+        assert_eq!(lines_of::<i8>("1\n\n-2\n"), Ok(vec![1, -2]));
+        assert_eq!(
+            lines_of::<u32>("1\n\n12x\n"),
+            Err("line 3 `12x` is not a valid u32: invalid digit found in string".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2 `b` is not a valid u8")]
+    fn panics_with_the_line() {
+        lines::<u8>("1\nb\n");
+    }
+}
@@ -0,0 +1,231 @@
+/// Shortest paths through implicit graphs, where the successors of a node are computed on the
+/// fly by a closure, e.g. the open cells next to a point of a grid.
+///
+/// Searches either return the distances to all reachable nodes, or the path to the first node
+/// that is a goal, from `start` to the goal inclusive:
+///
+/// ```ignore
+/// use everybody_codes::utils::pathfinding::{a_star, bfs_path, dijkstra};
+///
+/// let open = |&p: &Point| grid.neighbors4(p).filter(|&n| grid[n] != b'#').collect::<Vec<_>>();
+/// let path = bfs_path(start, open, |&p| p == end)?;
+/// let costs = dijkstra(start, |&p| grid.neighbors4(p).map(|n| (n, cost(n))));
+/// let (path, cost) = a_star(start, |&p| weighted(p), |&p| p.manhattan(end) as u64, |&p| p == end)?;
+/// ```
+///
+/// Edge costs are `u64`, so Dijkstra's algorithm never sees negative edges.
+use std::{collections::VecDeque, hash::Hash};
+
+use crate::utils::{hashing::FastMap, heap::IndexedHeap};
+
+/// The number of steps from `start` to every node it reaches.
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> FastMap<N, usize>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = FastMap::from_iter([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// The shortest path from `start` to a node that `is_goal`, `None` if there is none. Its number
+/// of steps is one less than its length.
+pub fn bfs_path<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: FastMap<N, Option<N>> = FastMap::from_iter([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(path(&parents, node));
+        }
+        for next in successors(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// The cost of the cheapest path from `start` to every node it reaches. `successors` returns the
+/// successors of a node with the cost of the edge to them.
+pub fn dijkstra<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> FastMap<N, u64>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut costs = FastMap::default();
+    let mut queue = IndexedHeap::new();
+    queue.push(start, 0);
+    while let Some((node, cost)) = queue.pop() {
+        // settled before relaxing, so edges back to the node itself don't requeue it.
+        costs.insert(node.clone(), cost);
+        for (next, edge) in successors(&node) {
+            if !costs.contains_key(&next) {
+                queue.decrease_key(next, cost + edge);
+            }
+        }
+    }
+    costs
+}
+
+/// The cheapest path from `start` to a node that `is_goal` with its cost, `None` if there is
+/// none. `successors` returns the successors of a node with the cost of the edge to them.
+pub fn dijkstra_path<N, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, u64)>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    a_star(start, successors, |_| 0, is_goal)
+}
+
+/// Like [`dijkstra_path`], exploring the nodes that `heuristic` estimates to be closest to a
+/// goal first. The path is the cheapest one as long as the heuristic never overestimates the
+/// remaining cost, e.g. the manhattan distance on a grid with steps costing at least 1.
+pub fn a_star<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> u64,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, u64)>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut costs = FastMap::from_iter([(start.clone(), 0)]);
+    let mut parents: FastMap<N, Option<N>> = FastMap::from_iter([(start.clone(), None)]);
+    let mut queue = IndexedHeap::new();
+    queue.push(start.clone(), heuristic(&start));
+    while let Some((node, _)) = queue.pop() {
+        let cost = costs[&node];
+        if is_goal(&node) {
+            return Some((path(&parents, node), cost));
+        }
+        for (next, edge) in successors(&node) {
+            let next_cost = cost + edge;
+            if costs.get(&next).is_some_and(|&best| best <= next_cost) {
+                continue;
+            }
+            costs.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), Some(node.clone()));
+            let estimate = next_cost + heuristic(&next);
+            queue.push(next, estimate);
+        }
+    }
+    None
+}
+
+/// The path from the start of a search to `end`, following the parent of every node.
+fn path<N: Hash + Eq + Clone>(parents: &FastMap<N, Option<N>>, end: N) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(Some(parent)) = parents.get(&path[path.len() - 1]) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{a_star, bfs, bfs_path, dijkstra, dijkstra_path};
+    use crate::utils::{grid::Grid, point::Point};
+
+    const MAZE: &str = "S.#.\n..#.\n.#..\n...E\n";
+
+    fn open(grid: &Grid<u8>, p: Point) -> Vec<Point> {
+        grid.neighbors4(p).filter(|&n| grid[n] != b'#').collect()
+    }
+
+    #[test]
+    fn counts_steps() {
+        let grid = Grid::parse(MAZE);
+        let start = grid.find(|&c| c == b'S').unwrap();
+        let end = grid.find(|&c| c == b'E').unwrap();
+
+        let distances = bfs(start, |&p| open(&grid, p));
+        assert_eq!(distances.len(), 13);
+        assert_eq!(distances[&end], 6);
+
+        let path = bfs_path(start, |&p| open(&grid, p), |&p| p == end).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!((path[0], path[6]), (start, end));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+        assert_eq!(bfs_path(start, |&p| open(&grid, p), |_| false), None);
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        // going up costs 10, other steps cost 1.
+        let line = |&n: &i64| {
+            [(n - 1, 1), (n + 1, 10)]
+                .into_iter()
+                .filter(|(m, _)| (0..5).contains(m))
+        };
+        let costs = dijkstra(4, line);
+        assert_eq!(costs[&0], 4);
+        assert_eq!(costs[&4], 0);
+
+        let (path, cost) = dijkstra_path(0, line, |&n| n == 2).unwrap();
+        assert_eq!((path, cost), (vec![0, 1, 2], 20));
+        assert_eq!(dijkstra_path(0, line, |&n| n == 7), None);
+    }
+
+    #[test]
+    fn waits_in_place() {
+        let wait_or_step = |&n: &u8| [(n, 1), (n + 1, 5)].into_iter().filter(|&(m, _)| m < 3);
+        let costs = dijkstra(0, wait_or_step);
+        assert_eq!((costs[&0], costs[&1], costs[&2]), (0, 5, 10));
+        assert_eq!(
+            dijkstra_path(0, wait_or_step, |&n| n == 2),
+            Some((vec![0, 1, 2], 10))
+        );
+    }
+
+    #[test]
+    fn a_star_agrees_with_dijkstra() {
+        let grid = Grid::parse(MAZE);
+        let start = grid.find(|&c| c == b'S').unwrap();
+        let end = grid.find(|&c| c == b'E').unwrap();
+        let weighted = |&p: &Point| {
+            open(&grid, p)
+                .into_iter()
+                .map(|n| (n, if n.y == 3 { 5 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+
+        let (path, cost) = a_star(
+            start,
+            weighted,
+            |&p| p.manhattan(end).unsigned_abs(),
+            |&p| p == end,
+        )
+        .unwrap();
+        assert_eq!(Some(cost), dijkstra(start, weighted).get(&end).copied());
+        assert_eq!(cost, 22);
+        assert_eq!(path.last(), Some(&end));
+    }
+}
@@ -0,0 +1,128 @@
+/// Integer points and directions on a 2D grid. `y` grows downwards, like the rows of the input,
+/// so `UP` is `(0, -1)`.
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+pub const UP: Point = Point::new(0, -1);
+pub const RIGHT: Point = Point::new(1, 0);
+pub const DOWN: Point = Point::new(0, 1);
+pub const LEFT: Point = Point::new(-1, 0);
+
+/// The four orthogonal directions, clockwise from `UP`.
+pub const ORTHOGONAL: [Point; 4] = [UP, RIGHT, DOWN, LEFT];
+
+/// All eight directions, clockwise from `UP`.
+pub const ALL: [Point; 8] = [
+    UP,
+    Point::new(1, -1),
+    RIGHT,
+    Point::new(1, 1),
+    DOWN,
+    Point::new(-1, 1),
+    LEFT,
+    Point::new(-1, -1),
+];
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The direction a quarter turn clockwise, e.g. `RIGHT` for `UP`.
+    pub fn turn_right(self) -> Point {
+        Point::new(-self.y, self.x)
+    }
+
+    /// The direction a quarter turn counterclockwise, e.g. `LEFT` for `UP`.
+    pub fn turn_left(self) -> Point {
+        Point::new(self.y, -self.x)
+    }
+
+    pub fn neighbors4(self) -> impl Iterator<Item = Point> {
+        ORTHOGONAL.into_iter().map(move |d| self + d)
+    }
+
+    pub fn neighbors8(self) -> impl Iterator<Item = Point> {
+        ALL.into_iter().map(move |d| self + d)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Point, DOWN, LEFT, ORTHOGONAL, RIGHT, UP};
+
+    #[test]
+    fn turns_clockwise() {
+        assert_eq!(UP.turn_right(), RIGHT);
+        assert_eq!(RIGHT.turn_right(), DOWN);
+        assert_eq!(UP.turn_left(), LEFT);
+        for d in ORTHOGONAL {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.turn_right().turn_right(), -d);
+        }
+    }
+
+    #[test]
+    fn measures_distances() {
+        let p = Point::new(2, 3);
+        assert_eq!(p + UP * 3, Point::new(2, 0));
+        assert_eq!(p.manhattan(Point::new(-1, 5)), 5);
+        assert_eq!(p.neighbors4().count(), 4);
+        assert!(p.neighbors8().all(|n| n.manhattan(p) <= 2));
+    }
+}
@@ -0,0 +1,205 @@
+/// String matching helpers for cipher and rune word style quests.
+///
+/// All functions work on slices, so they apply to `str::as_bytes`, `Vec<char>` or any other
+/// sequence of comparable symbols.
+use std::ops::Range;
+
+/// The Z-function of `s`: `z[i]` is the length of the longest common prefix of `s` and
+/// `s[i..]`. By convention `z[0]` is `s.len()`.
+pub fn z_function<T: Eq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![0; n];
+    if n == 0 {
+        return z;
+    }
+    z[0] = n;
+
+    let (mut left, mut right) = (0, 0);
+    for i in 1..n {
+        if i < right {
+            z[i] = z[i - left].min(right - i);
+        }
+        while i + z[i] < n && s[z[i]] == s[i + z[i]] {
+            z[i] += 1;
+        }
+        if i + z[i] > right {
+            (left, right) = (i, i + z[i]);
+        }
+    }
+
+    z
+}
+
+/// The KMP prefix function of `s`: `pi[i]` is the length of the longest proper prefix of
+/// `s[..=i]` that is also a suffix of it.
+pub fn prefix_function<T: Eq>(s: &[T]) -> Vec<usize> {
+    let mut pi = vec![0; s.len()];
+
+    for i in 1..s.len() {
+        let mut k = pi[i - 1];
+        while k > 0 && s[i] != s[k] {
+            k = pi[k - 1];
+        }
+        if s[i] == s[k] {
+            k += 1;
+        }
+        pi[i] = k;
+    }
+
+    pi
+}
+
+/// Start indices of all (possibly overlapping) occurrences of `pattern` in `text`, using KMP.
+pub fn find_all<T: Eq>(text: &[T], pattern: &[T]) -> Vec<usize> {
+    if pattern.is_empty() {
+        return (0..=text.len()).collect();
+    }
+
+    let pi = prefix_function(pattern);
+    let mut matches = vec![];
+    let mut k = 0;
+
+    for (i, symbol) in text.iter().enumerate() {
+        while k > 0 && *symbol != pattern[k] {
+            k = pi[k - 1];
+        }
+        if *symbol == pattern[k] {
+            k += 1;
+        }
+        if k == pattern.len() {
+            matches.push(i + 1 - k);
+            k = pi[k - 1];
+        }
+    }
+
+    matches
+}
+
+/// The shortest `p` such that `s` is a prefix of `s[..p]` repeated.
+pub fn period<T: Eq>(s: &[T]) -> usize {
+    s.len() - prefix_function(s).last().copied().unwrap_or(0)
+}
+
+/* -------------------------------------------------------------------------- */
+
+const MOD: u64 = (1 << 61) - 1;
+const BASE: u64 = 1_000_003;
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    #[allow(clippy::cast_possible_truncation)]
+    let product = ((u128::from(a) * u128::from(b)) % u128::from(MOD)) as u64;
+    product
+}
+
+/// Polynomial hashes of all prefixes of a sequence, modulo the Mersenne prime `2^61 - 1`,
+/// so the hash of any substring can be compared in constant time.
+#[derive(Clone, Debug)]
+pub struct RollingHash {
+    prefix: Vec<u64>,
+    powers: Vec<u64>,
+}
+
+impl RollingHash {
+    pub fn new<T: Copy + Into<u64>>(s: &[T]) -> Self {
+        let mut prefix = Vec::with_capacity(s.len() + 1);
+        let mut powers = Vec::with_capacity(s.len() + 1);
+        prefix.push(0);
+        powers.push(1);
+
+        for (i, symbol) in s.iter().enumerate() {
+            let value = ((*symbol).into() + 1) % MOD;
+            prefix.push((mul_mod(prefix[i], BASE) + value) % MOD);
+            powers.push(mul_mod(powers[i], BASE));
+        }
+
+        RollingHash { prefix, powers }
+    }
+
+    pub fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The hash of `s[range]`. Equal substrings (of equal or different sequences) have equal
+    /// hashes.
+    pub fn hash(&self, range: Range<usize>) -> u64 {
+        let shifted = mul_mod(self.prefix[range.start], self.powers[range.len()]);
+        (self.prefix[range.end] + MOD - shifted) % MOD
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The longest substring shared by `a` and `b`, as `(start in a, start in b, length)`.
+/// Ties are broken by the earliest position in `a`.
+pub fn longest_common_substring<T: Eq>(a: &[T], b: &[T]) -> (usize, usize, usize) {
+    let mut best = (0, 0, 0);
+    let mut previous = vec![0; b.len() + 1];
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            current[j] = if a[i - 1] == b[j - 1] {
+                previous[j - 1] + 1
+            } else {
+                0
+            };
+            if current[j] > best.2 {
+                best = (i - current[j], j - current[j], current[j]);
+            }
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    best
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        find_all, longest_common_substring, period, prefix_function, z_function, RollingHash,
+    };
+
+    #[test]
+    fn computes_z_function() {
+        assert_eq!(z_function(b"aabxaab"), vec![7, 1, 0, 0, 3, 1, 0]);
+        assert_eq!(z_function::<u8>(b""), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn computes_prefix_function() {
+        assert_eq!(prefix_function(b"abacaba"), vec![0, 0, 1, 0, 1, 2, 3]);
+        assert_eq!(period(b"abcabcab"), 3);
+        assert_eq!(period(b"abcd"), 4);
+    }
+
+    #[test]
+    fn finds_overlapping_matches() {
+        assert_eq!(find_all(b"THEHEHEART", b"HEHE"), vec![1, 3]);
+        assert_eq!(find_all(b"LOREM", b"X"), Vec::<usize>::new());
+
+        let text: Vec<char> = "ᚠᚢᚠᚢᚠ".chars().collect();
+        assert_eq!(find_all(&text, &['ᚠ', 'ᚢ']), vec![0, 2]);
+    }
+
+    #[test]
+    fn hashes_substrings() {
+        let a = RollingHash::new(b"POWERFUL POWER");
+        let b = RollingHash::new(b"WER");
+        assert_eq!(a.len(), 14);
+        assert_eq!(a.hash(0..5), a.hash(9..14));
+        assert_eq!(a.hash(2..5), b.hash(0..3));
+        assert_ne!(a.hash(0..5), a.hash(1..6));
+    }
+
+    #[test]
+    fn finds_longest_common_substring() {
+        assert_eq!(longest_common_substring(b"XABCDY", b"ZZBCDQ"), (2, 2, 3));
+        assert_eq!(longest_common_substring(b"AB", b"CD"), (0, 0, 0));
+    }
+}
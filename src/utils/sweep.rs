@@ -0,0 +1,175 @@
+/// Sweep line scaffolding and interval helpers for scheduling and segment coverage quests.
+///
+/// Intervals are half-open `Range<i64>`s, so `0..5` and `5..8` touch without overlapping.
+use std::ops::Range;
+
+/// Events at positions along a line, processed in order of position.
+#[derive(Clone, Debug)]
+pub struct Sweep<P, E> {
+    events: Vec<(P, E)>,
+}
+
+impl<P: Ord + Copy, E> Default for Sweep<P, E> {
+    fn default() -> Self {
+        Sweep::new()
+    }
+}
+
+impl<P: Ord + Copy, E> Sweep<P, E> {
+    pub fn new() -> Self {
+        Sweep { events: vec![] }
+    }
+
+    pub fn push(&mut self, position: P, event: E) {
+        self.events.push((position, event));
+    }
+
+    /// Calls `f` once per distinct position, in ascending order, with all events at that
+    /// position in insertion order.
+    pub fn run(mut self, mut f: impl FnMut(P, &[E])) {
+        self.events.sort_by_key(|(position, _)| *position);
+
+        let mut batch = vec![];
+        let mut events = self.events.into_iter().peekable();
+
+        while let Some((position, event)) = events.next() {
+            batch.push(event);
+            if events.peek().is_none_or(|(next, _)| *next != position) {
+                f(position, &batch);
+                batch.clear();
+            }
+        }
+    }
+}
+
+impl<P: Ord + Copy, E> FromIterator<(P, E)> for Sweep<P, E> {
+    fn from_iter<T: IntoIterator<Item = (P, E)>>(iter: T) -> Self {
+        Sweep {
+            events: iter.into_iter().collect(),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Splits the line into maximal segments with a constant, non-zero number of overlapping
+/// intervals, returned as `(segment, count)` in ascending order.
+pub fn coverage(intervals: &[Range<i64>]) -> Vec<(Range<i64>, usize)> {
+    let sweep: Sweep<i64, i64> = intervals
+        .iter()
+        .filter(|r| !r.is_empty())
+        .flat_map(|r| [(r.start, 1), (r.end, -1)])
+        .collect();
+
+    let mut segments: Vec<(Range<i64>, usize)> = vec![];
+    let mut active: i64 = 0;
+    let mut start = 0;
+
+    sweep.run(|position, deltas| {
+        let next: i64 = active + deltas.iter().sum::<i64>();
+        if next == active {
+            return;
+        }
+        if active > 0 {
+            segments.push((start..position, active.unsigned_abs() as usize));
+        }
+        (active, start) = (next, position);
+    });
+
+    segments
+}
+
+/// The maximum number of intervals overlapping at any point, e.g. the number of rooms
+/// needed to hold all meetings.
+pub fn max_overlap(intervals: &[Range<i64>]) -> usize {
+    coverage(intervals)
+        .into_iter()
+        .map(|(_, count)| count)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Merges overlapping or touching intervals, returned in ascending order.
+pub fn merge(intervals: &[Range<i64>]) -> Vec<Range<i64>> {
+    let mut merged: Vec<Range<i64>> = vec![];
+
+    for (segment, _) in coverage(intervals) {
+        match merged.last_mut() {
+            Some(last) if last.end == segment.start => last.end = segment.end,
+            _ => merged.push(segment),
+        }
+    }
+
+    merged
+}
+
+/// The total length covered by at least one interval.
+pub fn covered_length(intervals: &[Range<i64>]) -> i64 {
+    merge(intervals).iter().map(|r| r.end - r.start).sum()
+}
+
+/// A largest set of pairwise non-overlapping intervals, as indices into `intervals`, chosen
+/// greedily by earliest end.
+pub fn max_disjoint(intervals: &[Range<i64>]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    order.sort_by_key(|i| (intervals[*i].end, intervals[*i].start));
+
+    let mut chosen = vec![];
+    let mut end = i64::MIN;
+
+    for i in order {
+        if intervals[i].start >= end {
+            chosen.push(i);
+            end = intervals[i].end;
+        }
+    }
+
+    chosen
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{coverage, covered_length, max_disjoint, max_overlap, merge, Sweep};
+
+    #[test]
+    fn groups_events_by_position() {
+        let mut sweep = Sweep::new();
+        sweep.push(3, 'c');
+        sweep.push(1, 'a');
+        sweep.push(3, 'd');
+        sweep.push(2, 'b');
+
+        let mut seen = vec![];
+        sweep.run(|position, events| seen.push((position, events.to_vec())));
+        assert_eq!(
+            seen,
+            vec![(1, vec!['a']), (2, vec!['b']), (3, vec!['c', 'd'])]
+        );
+    }
+
+    #[test]
+    fn counts_coverage() {
+        let intervals = [0..5, 3..8, 5..6, 10..12, 4..4];
+        assert_eq!(
+            coverage(&intervals),
+            vec![(0..3, 1), (3..6, 2), (6..8, 1), (10..12, 1)]
+        );
+        assert_eq!(max_overlap(&intervals), 2);
+        assert_eq!(max_overlap(&[]), 0);
+    }
+
+    #[test]
+    fn merges_intervals() {
+        let intervals = [5..8, 0..5, 10..12, 11..15];
+        assert_eq!(merge(&intervals), vec![0..8, 10..15]);
+        assert_eq!(covered_length(&intervals), 13);
+    }
+
+    #[test]
+    fn schedules_disjoint_intervals() {
+        let intervals = [0..6, 1..3, 3..5, 4..7, 5..9];
+        assert_eq!(max_disjoint(&intervals), vec![1, 2, 4]);
+    }
+}